@@ -0,0 +1,88 @@
+use super::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics26_routing::context::Acknowledgement as AckTrait;
+use crate::prelude::*;
+use core::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use subtle_encoding::base64;
+
+/// A string constant included in error acknowledgements.
+/// NOTE: Changing this const is state machine breaking as acknowledgements are written into state
+pub const ACK_ERR_STR: &str = "error handling packet on host chain: see events for details";
+
+/// The JSON encoding of a channel acknowledgement, `{"result":"<base64>"}` on success
+/// and `{"error":"<reason>"}` on failure.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcknowledgementResponse {
+    Result(String),
+    Error(String),
+}
+
+/// The acknowledgement written by the host for an interchain account packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Acknowledgement {
+    response: AcknowledgementResponse,
+    encoded: Vec<u8>,
+}
+
+impl Acknowledgement {
+    /// A successful acknowledgement carrying the result of the executed transaction.
+    pub fn success(result: &[u8]) -> Self {
+        let result = String::from_utf8(base64::encode(result))
+            .expect("base64 encoded bytes are not valid UTF8");
+        Self::new(AcknowledgementResponse::Result(result))
+    }
+
+    pub fn from_error(err: Error) -> Self {
+        Self::new(AcknowledgementResponse::Error(format!(
+            "{}: {}",
+            ACK_ERR_STR, err
+        )))
+    }
+
+    fn new(response: AcknowledgementResponse) -> Self {
+        let encoded = serde_json::to_vec(&response)
+            .expect("AcknowledgementResponse's infallible Serialize impl failed");
+        Self { response, encoded }
+    }
+
+    pub fn response(&self) -> &AcknowledgementResponse {
+        &self.response
+    }
+
+    pub fn is_successful(&self) -> bool {
+        matches!(self.response, AcknowledgementResponse::Result(_))
+    }
+}
+
+impl AsRef<[u8]> for Acknowledgement {
+    fn as_ref(&self) -> &[u8] {
+        &self.encoded
+    }
+}
+
+impl TryFrom<&GenericAcknowledgement> for Acknowledgement {
+    type Error = Error;
+
+    fn try_from(ack: &GenericAcknowledgement) -> Result<Self, Self::Error> {
+        let response = serde_json::from_slice::<AcknowledgementResponse>(ack.as_ref())
+            .map_err(|_| Error::ack_deserialization())?;
+        Ok(Self {
+            response,
+            encoded: ack.as_ref().to_vec(),
+        })
+    }
+}
+
+impl Display for Acknowledgement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match &self.response {
+            AcknowledgementResponse::Result(result) => write!(f, "{}", result),
+            AcknowledgementResponse::Error(err_str) => write!(f, "{}", err_str),
+        }
+    }
+}
+
+impl AckTrait for Acknowledgement {}
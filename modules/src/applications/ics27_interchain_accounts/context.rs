@@ -0,0 +1,119 @@
+use ibc_proto::google::protobuf::Any;
+use sha2::{Digest, Sha256};
+use subtle_encoding::hex;
+
+use super::error::Error as Ics27Error;
+use crate::applications::ics27_interchain_accounts::MODULE_ACCOUNT_NAME;
+use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+pub trait Ics27Keeper:
+    ChannelKeeper + AccountKeeper<AccountId = <Self as Ics27Keeper>::AccountId>
+{
+    type AccountId;
+
+    /// Records `channel_id` as the active channel of the controller port `port_id` on the
+    /// connection `connection_id`.
+    fn store_active_channel_id(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(), Ics27Error>;
+
+    /// Records the address of the interchain account of the controller port `port_id` on the
+    /// connection `connection_id`.
+    fn store_interchain_account_address(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        address: Signer,
+    ) -> Result<(), Ics27Error>;
+}
+
+pub trait Ics27Reader: ChannelReader {
+    type AccountId: TryFrom<Signer>;
+
+    /// Returns true iff the controller submodule is enabled.
+    fn is_controller_enabled(&self) -> bool;
+
+    /// Returns true iff the host submodule is enabled.
+    fn is_host_enabled(&self) -> bool;
+
+    /// Returns true iff the host allows interchain accounts to execute messages of the given
+    /// type.
+    fn is_message_allowed(&self, type_url: &str) -> bool;
+
+    /// Returns the active channel of the controller port `port_id` on the connection
+    /// `connection_id`, if any.
+    fn get_active_channel_id(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<ChannelId>;
+
+    /// Returns the address of the interchain account of the controller port `port_id` on the
+    /// connection `connection_id`, if one has been registered.
+    fn get_interchain_account_address(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<Signer>;
+
+    /// Returns the address for a new interchain account of the controller port `port_id` on the
+    /// host connection `connection_id`.
+    fn generate_interchain_account_address(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Result<Signer, Ics27Error> {
+        let hash = cosmos_adr028_account_address(connection_id, port_id);
+        String::from_utf8(hex::encode_upper(hash))
+            .expect("hex encoded bytes are not valid UTF8")
+            .parse::<Signer>()
+            .map_err(Ics27Error::signer)
+    }
+}
+
+// https://github.com/cosmos/cosmos-sdk/blob/master/docs/architecture/adr-028-public-key-addresses.md
+fn cosmos_adr028_account_address(connection_id: &ConnectionId, port_id: &PortId) -> Vec<u8> {
+    // The address of the module account, from which interchain account addresses are derived.
+    let mut module_address = Sha256::digest(MODULE_ACCOUNT_NAME.as_bytes()).to_vec();
+    module_address.truncate(20);
+
+    let type_hash = Sha256::digest(&module_address);
+
+    let mut hasher = Sha256::new();
+    hasher.update(type_hash);
+    hasher.update(connection_id.as_bytes());
+    hasher.update(port_id.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+pub trait AccountKeeper {
+    type AccountId;
+
+    /// This function should create a new account, controlled by the interchain accounts module
+    fn new_interchain_account(&mut self, account: &Self::AccountId) -> Result<(), Ics27Error>;
+
+    /// This function should execute the messages on behalf of the interchain account and return
+    /// the result data of the execution, which is included in the acknowledgement of the packet.
+    /// The messages must only be executed if all of them are signed by `account`, and a failed
+    /// execution must not leave any of the state changes of the messages behind.
+    fn execute_tx(
+        &mut self,
+        account: &Self::AccountId,
+        messages: Vec<Any>,
+    ) -> Result<Vec<u8>, Ics27Error>;
+}
+
+/// Captures all the dependencies which the ICS27 modules require to be able to dispatch and
+/// process IBC messages.
+pub trait Ics27Context:
+    Ics27Keeper<AccountId = <Self as Ics27Context>::AccountId>
+    + Ics27Reader<AccountId = <Self as Ics27Context>::AccountId>
+{
+    type AccountId: TryFrom<Signer>;
+}
@@ -0,0 +1,381 @@
+//! The controller side of interchain accounts. The controller opens a channel from the port of an
+//! account owner to the host port, and sends transactions to be executed with the interchain
+//! account over that channel.
+use crate::applications::ics27_interchain_accounts::acknowledgement::Acknowledgement;
+use crate::applications::ics27_interchain_accounts::context::Ics27Context;
+use crate::applications::ics27_interchain_accounts::error::Error as Ics27Error;
+use crate::applications::ics27_interchain_accounts::events::{AckEvent, TimeoutEvent};
+use crate::applications::ics27_interchain_accounts::metadata::Metadata;
+use crate::applications::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+use crate::applications::ics27_interchain_accounts::{host_port_id, owner_from_port_id};
+use crate::core::ics04_channel::channel::{Counterparty, Order, State};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::handler::send_packet::send_packet;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+use crate::handler::{HandlerOutput, HandlerOutputBuilder};
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+fn validate_controller_port(port_id: &PortId) -> Result<(), Ics27Error> {
+    match owner_from_port_id(port_id) {
+        Some(_) => Ok(()),
+        None => Err(Ics27Error::invalid_controller_port(port_id.clone())),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_open_init(
+    ctx: &mut impl Ics27Context,
+    _output: &mut ModuleOutputBuilder,
+    order: Order,
+    connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    _channel_id: &ChannelId,
+    counterparty: &Counterparty,
+    version: &Version,
+) -> Result<(), Ics27Error> {
+    if !ctx.is_controller_enabled() {
+        return Err(Ics27Error::controller_disabled());
+    }
+
+    if order != Order::Ordered {
+        return Err(Ics27Error::channel_not_ordered(order));
+    }
+
+    validate_controller_port(port_id)?;
+
+    let host_port_id = host_port_id();
+    if counterparty.port_id() != &host_port_id {
+        return Err(Ics27Error::invalid_host_port(
+            counterparty.port_id().clone(),
+        ));
+    }
+
+    let metadata = Metadata::try_from(version)?;
+    metadata.validate()?;
+
+    let connection_id = &connection_hops[0];
+    let connection_end = ctx
+        .connection_end(connection_id)
+        .map_err(Ics27Error::ics04_channel)?;
+    let host_connection_id = connection_end
+        .counterparty()
+        .connection_id()
+        .ok_or_else(|| {
+            Ics27Error::connection_mismatch(
+                connection_id.clone(),
+                metadata.host_connection_id.to_string(),
+            )
+        })?;
+    metadata.validate_connections(connection_id, host_connection_id)?;
+
+    // Only one channel per account may be open at a time. A new channel can be opened after the
+    // active channel got closed, e.g. by a packet timeout.
+    if let Some(active_channel_id) = ctx.get_active_channel_id(connection_id, port_id) {
+        let active_channel = ctx
+            .channel_end(&(port_id.clone(), active_channel_id))
+            .map_err(Ics27Error::ics04_channel)?;
+        if active_channel.state_matches(&State::Open) {
+            return Err(Ics27Error::active_channel_exists(
+                port_id.clone(),
+                active_channel_id,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn on_chan_open_ack(
+    ctx: &mut impl Ics27Context,
+    _output: &mut ModuleOutputBuilder,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty_version: &Version,
+) -> Result<(), Ics27Error> {
+    validate_controller_port(port_id)?;
+
+    let channel_end = ctx
+        .channel_end(&(port_id.clone(), *channel_id))
+        .map_err(Ics27Error::ics04_channel)?;
+    let metadata = Metadata::try_from(channel_end.version())?;
+
+    let counterparty_metadata = Metadata::try_from(counterparty_version)?;
+    counterparty_metadata.validate()?;
+    if !counterparty_metadata.matches_except_address(&metadata) {
+        return Err(Ics27Error::metadata_changed());
+    }
+
+    let address = counterparty_metadata
+        .address
+        .parse::<Signer>()
+        .map_err(|_| Ics27Error::invalid_account_address(counterparty_metadata.address.clone()))?;
+
+    let connection_id = channel_end.connection_hops()[0].clone();
+    ctx.store_active_channel_id(connection_id.clone(), port_id.clone(), *channel_id)?;
+    ctx.store_interchain_account_address(connection_id, port_id.clone(), address)?;
+
+    Ok(())
+}
+
+pub fn on_acknowledgement_packet(
+    _ctx: &mut impl Ics27Context,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    acknowledgement: &GenericAcknowledgement,
+    _relayer: &Signer,
+) -> Result<(), Ics27Error> {
+    let acknowledgement = Acknowledgement::try_from(acknowledgement)?;
+
+    output.emit(
+        AckEvent {
+            controller_channel_id: packet.source_channel,
+            acknowledgement,
+        }
+        .into(),
+    );
+
+    Ok(())
+}
+
+pub fn on_timeout_packet(
+    _ctx: &mut impl Ics27Context,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    _relayer: &Signer,
+) -> Result<(), Ics27Error> {
+    // The ordered channel gets closed by the timeout, so there is nothing left to do here.
+    // The owner may register the interchain account again on a new channel.
+    output.emit(
+        TimeoutEvent {
+            controller_channel_id: packet.source_channel,
+            sequence: packet.sequence,
+        }
+        .into(),
+    );
+
+    Ok(())
+}
+
+/// This function handles the sending of a transaction to be executed by the interchain account of
+/// `owner` on the host chain reachable over `connection_id`. Returns the sequence of the packet
+/// that was sent.
+/// If this method returns an error, the runtime is expected to rollback all state modifications to
+/// the `Ctx` caused by all messages from the transaction that this call is a part of.
+pub fn send_tx<Ctx>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    connection_id: &ConnectionId,
+    owner: &str,
+    data: InterchainAccountPacketData,
+    timeout_height: Height,
+    timeout_timestamp: Timestamp,
+) -> Result<Sequence, Ics27Error>
+where
+    Ctx: Ics27Context,
+{
+    if !ctx.is_controller_enabled() {
+        return Err(Ics27Error::controller_disabled());
+    }
+
+    data.validate_basic()?;
+
+    let source_port = super::controller_port_id(owner)?;
+    let source_channel = ctx
+        .get_active_channel_id(connection_id, &source_port)
+        .ok_or_else(|| {
+            Ics27Error::active_channel_not_found(connection_id.clone(), source_port.clone())
+        })?;
+
+    let source_channel_end = ctx
+        .channel_end(&(source_port.clone(), source_channel))
+        .map_err(Ics27Error::ics04_channel)?;
+
+    let destination_port = source_channel_end.counterparty().port_id().clone();
+    let destination_channel = *source_channel_end
+        .counterparty()
+        .channel_id()
+        .ok_or_else(|| {
+            Ics27Error::destination_channel_not_found(source_port.clone(), source_channel)
+        })?;
+
+    let sequence = ctx
+        .get_next_sequence_send(&(source_port.clone(), source_channel))
+        .map_err(Ics27Error::ics04_channel)?;
+
+    let packet = Packet {
+        sequence,
+        source_port,
+        source_channel,
+        destination_port,
+        destination_channel,
+        data: serde_json::to_vec(&data)
+            .expect("InterchainAccountPacketData's infallible Serialize impl failed"),
+        timeout_height,
+        timeout_timestamp,
+    };
+
+    let HandlerOutput {
+        result,
+        log,
+        events,
+    } = send_packet(ctx, packet).map_err(Ics27Error::ics04_channel)?;
+
+    ctx.store_packet_result(result)
+        .map_err(Ics27Error::ics04_channel)?;
+
+    output.merge_output(
+        HandlerOutput::builder()
+            .with_log(log)
+            .with_events(events)
+            .with_result(()),
+    );
+
+    output.log(format!(
+        "interchain account transaction of {} sent over connection {}",
+        owner, connection_id
+    ));
+
+    Ok(sequence)
+}
+
+/// The ICS27 controller module, which handles the channel handshake and packet callbacks of the
+/// controller ports.
+pub struct IcaControllerModule<Ctx> {
+    ctx: Ctx,
+}
+
+impl<Ctx> IcaControllerModule<Ctx> {
+    pub fn new(ctx: Ctx) -> Self {
+        Self { ctx }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+}
+
+impl<Ctx> Module for IcaControllerModule<Ctx>
+where
+    Ctx: 'static + Ics27Context + Send + Sync,
+{
+    fn on_chan_open_init(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(), Error> {
+        on_chan_open_init(
+            &mut self.ctx,
+            output,
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        )
+        .map_err(|e: Ics27Error| Error::app_module(e.to_string()))
+    }
+
+    fn on_chan_open_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+        _counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        Err(Error::app_module(
+            Ics27Error::invalid_channel_flow().to_string(),
+        ))
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        on_chan_open_ack(
+            &mut self.ctx,
+            output,
+            port_id,
+            channel_id,
+            counterparty_version,
+        )
+        .map_err(|e: Ics27Error| Error::app_module(e.to_string()))
+    }
+
+    fn on_chan_open_confirm(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Err(Error::app_module(
+            Ics27Error::invalid_channel_flow().to_string(),
+        ))
+    }
+
+    fn on_chan_close_init(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Err(Error::app_module(
+            Ics27Error::cant_close_channel().to_string(),
+        ))
+    }
+
+    fn on_recv_packet(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(
+            Ics27Error::receive_not_supported(),
+        )))
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        on_acknowledgement_packet(&mut self.ctx, output, packet, acknowledgement, relayer)
+            .map_err(|e: Ics27Error| Error::app_module(e.to_string()))
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        on_timeout_packet(&mut self.ctx, output, packet, relayer)
+            .map_err(|e: Ics27Error| Error::app_module(e.to_string()))
+    }
+}
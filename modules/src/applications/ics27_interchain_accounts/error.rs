@@ -0,0 +1,141 @@
+use flex_error::{define_error, TraceError};
+
+use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::error as channel_error;
+use crate::core::ics24_host::error::ValidationError;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::prelude::*;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics04Channel
+            [ channel_error::Error ]
+            | _ | { "Ics04 channel error" },
+
+        InvalidOwner
+            [ ValidationError ]
+            | _ | { "the interchain account owner cannot be used in a port identifier" },
+
+        ControllerDisabled
+            | _ | { "the interchain accounts controller is not enabled" },
+
+        HostDisabled
+            | _ | { "the interchain accounts host is not enabled" },
+
+        ChannelNotOrdered
+            { order: Order }
+            | e | { format_args!("expected '{0}' channel, got '{1}'", Order::Ordered, e.order) },
+
+        InvalidControllerPort
+            { port_id: PortId }
+            | e | { format_args!("invalid controller port '{0}': expected a port with the prefix '{1}' followed by the account owner", e.port_id, super::CONTROLLER_PORT_PREFIX) },
+
+        InvalidHostPort
+            { port_id: PortId }
+            | e | { format_args!("invalid host port '{0}', expected '{1}'", e.port_id, super::HOST_PORT_ID_STR) },
+
+        InvalidChannelFlow
+            | _ | { "the channel handshake must be initiated by the controller chain" },
+
+        CantCloseChannel
+            | _ | { "interchain account channels cannot be closed by the application" },
+
+        MetadataDeserialization
+            | _ | { "failed to deserialize the interchain accounts version metadata" },
+
+        InvalidVersion
+            { version: String }
+            | e | { format_args!("expected version '{0}', got '{1}'", super::VERSION, e.version) },
+
+        InvalidEncoding
+            { encoding: String }
+            | e | { format_args!("unsupported encoding '{0}', expected '{1}'", e.encoding, super::ENCODING_PROTOBUF) },
+
+        InvalidTxType
+            { tx_type: String }
+            | e | { format_args!("unsupported transaction type '{0}', expected '{1}'", e.tx_type, super::TX_TYPE_SDK_MULTI_MSG) },
+
+        ConnectionMismatch
+            {
+                expected: ConnectionId,
+                actual: String,
+            }
+            | e | { format_args!("expected connection '{0}' in the version metadata, got '{1}'", e.expected, e.actual) },
+
+        MetadataChanged
+            | _ | { "the version metadata of the channel cannot change between handshakes" },
+
+        InvalidAccountAddress
+            { address: String }
+            | e | { format_args!("invalid interchain account address '{0}'", e.address) },
+
+        ActiveChannelExists
+            {
+                port_id: PortId,
+                channel_id: ChannelId,
+            }
+            | e | { format_args!("an active channel {0} is already open on port {1}", e.channel_id, e.port_id) },
+
+        ActiveChannelNotFound
+            {
+                connection_id: ConnectionId,
+                port_id: PortId,
+            }
+            | e | { format_args!("no active channel for port {0} on connection {1}", e.port_id, e.connection_id) },
+
+        AccountNotFound
+            {
+                connection_id: ConnectionId,
+                port_id: PortId,
+            }
+            | e | { format_args!("no interchain account registered for port {0} on connection {1}", e.port_id, e.connection_id) },
+
+        DestinationChannelNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            | e | { format_args!("destination channel not found in the counterparty of port_id {0} and channel_id {1} ", e.port_id, e.channel_id) },
+
+        PacketDataDeserialization
+            | _ | { "failed to deserialize packet data" },
+
+        UnsupportedPacketType
+            { packet_type: i32 }
+            | e | { format_args!("unsupported interchain account packet type {0}", e.packet_type) },
+
+        EmptyPacketData
+            | _ | { "packet data cannot be empty" },
+
+        MemoTooLong
+            { length: usize }
+            | e | { format_args!("memo of {0} bytes exceeds the maximum of {1} bytes", e.length, super::packet::MAX_MEMO_LENGTH) },
+
+        EmptyTx
+            | _ | { "the interchain account transaction contains no messages" },
+
+        MessageNotAllowed
+            { type_url: String }
+            | e | { format_args!("message type {0} is not allowed on the host", e.type_url) },
+
+        Decode
+            [ TraceError<prost::DecodeError> ]
+            | _ | { "error decoding the interchain account transaction" },
+
+        ExecutionFailed
+            { reason: String }
+            | e | { format_args!("failed to execute the interchain account transaction: {0}", e.reason) },
+
+        ReceiveNotSupported
+            | _ | { "the controller does not receive packets" },
+
+        AckDeserialization
+            | _ | { "failed to deserialize acknowledgement" },
+
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer" },
+
+        ParseAccountFailure
+            | _ | { "failed to parse as AccountId" },
+    }
+}
@@ -0,0 +1,98 @@
+use crate::applications::ics27_interchain_accounts::acknowledgement::Acknowledgement;
+use crate::applications::ics27_interchain_accounts::{
+    CONTROLLER_MODULE_ID_STR, HOST_MODULE_ID_STR,
+};
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::ChannelId;
+use crate::events::ModuleEvent;
+use crate::prelude::*;
+
+const EVENT_TYPE_PACKET: &str = "ics27_packet";
+const EVENT_TYPE_TIMEOUT: &str = "timeout";
+
+pub enum Event {
+    Recv(RecvEvent),
+    Ack(AckEvent),
+    Timeout(TimeoutEvent),
+}
+
+pub struct RecvEvent {
+    pub host_channel_id: ChannelId,
+    pub success: bool,
+}
+
+impl From<RecvEvent> for ModuleEvent {
+    fn from(ev: RecvEvent) -> Self {
+        let RecvEvent {
+            host_channel_id,
+            success,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_PACKET.to_string(),
+            module_name: HOST_MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("host_channel_id", host_channel_id).into(),
+                ("success", success).into(),
+            ],
+        }
+    }
+}
+
+pub struct AckEvent {
+    pub controller_channel_id: ChannelId,
+    pub acknowledgement: Acknowledgement,
+}
+
+impl From<AckEvent> for ModuleEvent {
+    fn from(ev: AckEvent) -> Self {
+        let AckEvent {
+            controller_channel_id,
+            acknowledgement,
+        } = ev;
+        let attr_label = if acknowledgement.is_successful() {
+            "success"
+        } else {
+            "error"
+        };
+        Self {
+            kind: EVENT_TYPE_PACKET.to_string(),
+            module_name: CONTROLLER_MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("controller_channel_id", controller_channel_id).into(),
+                (attr_label, acknowledgement).into(),
+            ],
+        }
+    }
+}
+
+pub struct TimeoutEvent {
+    pub controller_channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+impl From<TimeoutEvent> for ModuleEvent {
+    fn from(ev: TimeoutEvent) -> Self {
+        let TimeoutEvent {
+            controller_channel_id,
+            sequence,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_TIMEOUT.to_string(),
+            module_name: CONTROLLER_MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("controller_channel_id", controller_channel_id).into(),
+                ("sequence", sequence).into(),
+            ],
+        }
+    }
+}
+
+impl From<Event> for ModuleEvent {
+    fn from(ev: Event) -> Self {
+        match ev {
+            Event::Recv(ev) => ev.into(),
+            Event::Ack(ev) => ev.into(),
+            Event::Timeout(ev) => ev.into(),
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! The host side of interchain accounts. The host registers an interchain account when a controller
+//! opens a channel to the host port, and executes the transactions received over that channel with
+//! the account.
+use ibc_proto::google::protobuf::Any;
+
+use crate::applications::ics27_interchain_accounts::acknowledgement::Acknowledgement;
+use crate::applications::ics27_interchain_accounts::context::Ics27Context;
+use crate::applications::ics27_interchain_accounts::error::Error as Ics27Error;
+use crate::applications::ics27_interchain_accounts::events::RecvEvent;
+use crate::applications::ics27_interchain_accounts::metadata::Metadata;
+use crate::applications::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+use crate::applications::ics27_interchain_accounts::{host_port_id, owner_from_port_id};
+use crate::core::ics04_channel::channel::{Counterparty, Order, State};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_open_try(
+    ctx: &mut impl Ics27Context,
+    _output: &mut ModuleOutputBuilder,
+    order: Order,
+    connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    _channel_id: &ChannelId,
+    counterparty: &Counterparty,
+    _version: &Version,
+    counterparty_version: &Version,
+) -> Result<Version, Ics27Error> {
+    if !ctx.is_host_enabled() {
+        return Err(Ics27Error::host_disabled());
+    }
+
+    if order != Order::Ordered {
+        return Err(Ics27Error::channel_not_ordered(order));
+    }
+
+    if port_id != &host_port_id() {
+        return Err(Ics27Error::invalid_host_port(port_id.clone()));
+    }
+
+    let controller_port_id = counterparty.port_id();
+    if owner_from_port_id(controller_port_id).is_none() {
+        return Err(Ics27Error::invalid_controller_port(
+            controller_port_id.clone(),
+        ));
+    }
+
+    let mut metadata = Metadata::try_from(counterparty_version)?;
+    metadata.validate()?;
+
+    let connection_id = &connection_hops[0];
+    let connection_end = ctx
+        .connection_end(connection_id)
+        .map_err(Ics27Error::ics04_channel)?;
+    let controller_connection_id =
+        connection_end
+            .counterparty()
+            .connection_id()
+            .ok_or_else(|| {
+                Ics27Error::connection_mismatch(
+                    connection_id.clone(),
+                    metadata.controller_connection_id.to_string(),
+                )
+            })?;
+    metadata.validate_connections(controller_connection_id, connection_id)?;
+
+    if let Some(active_channel_id) = ctx.get_active_channel_id(connection_id, controller_port_id) {
+        let active_channel = ctx
+            .channel_end(&(port_id.clone(), active_channel_id))
+            .map_err(Ics27Error::ics04_channel)?;
+        if active_channel.state_matches(&State::Open) {
+            return Err(Ics27Error::active_channel_exists(
+                port_id.clone(),
+                active_channel_id,
+            ));
+        }
+    }
+
+    // An account registered over a previous channel, which got closed, is reused.
+    let address = match ctx.get_interchain_account_address(connection_id, controller_port_id) {
+        Some(address) => address,
+        None => {
+            let address =
+                ctx.generate_interchain_account_address(connection_id, controller_port_id)?;
+            let account = address
+                .clone()
+                .try_into()
+                .map_err(|_| Ics27Error::parse_account_failure())?;
+            ctx.new_interchain_account(&account)?;
+            ctx.store_interchain_account_address(
+                connection_id.clone(),
+                controller_port_id.clone(),
+                address.clone(),
+            )?;
+            address
+        }
+    };
+
+    metadata.address = address.to_string();
+    Ok(metadata.into())
+}
+
+pub fn on_chan_open_confirm(
+    ctx: &mut impl Ics27Context,
+    _output: &mut ModuleOutputBuilder,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<(), Ics27Error> {
+    let channel_end = ctx
+        .channel_end(&(port_id.clone(), *channel_id))
+        .map_err(Ics27Error::ics04_channel)?;
+
+    ctx.store_active_channel_id(
+        channel_end.connection_hops()[0].clone(),
+        channel_end.counterparty().port_id().clone(),
+        *channel_id,
+    )
+}
+
+/// Checks a received packet and returns the interchain account along with the messages it
+/// should execute.
+pub fn process_recv_packet<Ctx: Ics27Context>(
+    ctx: &Ctx,
+    packet: &Packet,
+) -> Result<(<Ctx as Ics27Context>::AccountId, Vec<Any>), Ics27Error> {
+    if !ctx.is_host_enabled() {
+        return Err(Ics27Error::host_disabled());
+    }
+
+    let data = serde_json::from_slice::<InterchainAccountPacketData>(&packet.data)
+        .map_err(|_| Ics27Error::packet_data_deserialization())?;
+    data.validate_basic()?;
+
+    let messages = data.messages()?;
+    if let Some(message) = messages
+        .iter()
+        .find(|message| !ctx.is_message_allowed(&message.type_url))
+    {
+        return Err(Ics27Error::message_not_allowed(message.type_url.clone()));
+    }
+
+    let channel_end = ctx
+        .channel_end(&(packet.destination_port.clone(), packet.destination_channel))
+        .map_err(Ics27Error::ics04_channel)?;
+    let connection_id = &channel_end.connection_hops()[0];
+
+    let account = ctx
+        .get_interchain_account_address(connection_id, &packet.source_port)
+        .ok_or_else(|| {
+            Ics27Error::account_not_found(connection_id.clone(), packet.source_port.clone())
+        })?
+        .try_into()
+        .map_err(|_| Ics27Error::parse_account_failure())?;
+
+    Ok((account, messages))
+}
+
+/// The ICS27 host module, which handles the channel handshake and packet callbacks of the host
+/// port.
+pub struct IcaHostModule<Ctx> {
+    ctx: Ctx,
+}
+
+impl<Ctx> IcaHostModule<Ctx> {
+    pub fn new(ctx: Ctx) -> Self {
+        Self { ctx }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+}
+
+impl<Ctx> Module for IcaHostModule<Ctx>
+where
+    Ctx: 'static + Ics27Context + Send + Sync,
+{
+    fn on_chan_open_init(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<(), Error> {
+        Err(Error::app_module(
+            Ics27Error::invalid_channel_flow().to_string(),
+        ))
+    }
+
+    fn on_chan_open_try(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        on_chan_open_try(
+            &mut self.ctx,
+            output,
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+            counterparty_version,
+        )
+        .map_err(|e: Ics27Error| Error::app_module(e.to_string()))
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        Err(Error::app_module(
+            Ics27Error::invalid_channel_flow().to_string(),
+        ))
+    }
+
+    fn on_chan_open_confirm(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        on_chan_open_confirm(&mut self.ctx, output, port_id, channel_id)
+            .map_err(|e: Ics27Error| Error::app_module(e.to_string()))
+    }
+
+    fn on_chan_close_init(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Err(Error::app_module(
+            Ics27Error::cant_close_channel().to_string(),
+        ))
+    }
+
+    /// The messages are executed before the acknowledgement is built, so that a failed execution
+    /// results in an error acknowledgement and a successful one in an acknowledgement carrying
+    /// the result data of the transaction, as in ibc-go.
+    fn on_recv_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        let result = process_recv_packet(&self.ctx, packet)
+            .and_then(|(account, messages)| self.ctx.execute_tx(&account, messages));
+        let ack = match result {
+            Ok(data) => OnRecvPacketAck::Successful(
+                Box::new(Acknowledgement::success(&data)),
+                Box::new(|_| Ok(())),
+            ),
+            Err(e) => OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
+        };
+
+        output.emit(
+            RecvEvent {
+                host_channel_id: packet.destination_channel,
+                success: ack.is_successful(),
+            }
+            .into(),
+        );
+
+        ack
+    }
+}
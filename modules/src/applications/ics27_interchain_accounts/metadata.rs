@@ -0,0 +1,130 @@
+//! The version metadata negotiated during the channel handshake of an interchain
+//! accounts channel. It is carried JSON-encoded in the version field of the channel end.
+use serde::{Deserialize, Serialize};
+
+use super::error::Error;
+use super::{ENCODING_PROTOBUF, TX_TYPE_SDK_MULTI_MSG, VERSION};
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// The ICS27 protocol version.
+    pub version: String,
+    /// The connection identifier associated with the controller chain.
+    pub controller_connection_id: ConnectionId,
+    /// The connection identifier associated with the host chain.
+    pub host_connection_id: ConnectionId,
+    /// The interchain account address, set by the host in the `ChanOpenTry` step.
+    #[serde(default)]
+    pub address: String,
+    /// The encoding of the messages carried in the packet data.
+    pub encoding: String,
+    /// The type of transactions supported by the host.
+    pub tx_type: String,
+}
+
+impl Metadata {
+    /// Returns the metadata of the current version, with no interchain account address set.
+    pub fn new(controller_connection_id: ConnectionId, host_connection_id: ConnectionId) -> Self {
+        Self {
+            version: VERSION.to_string(),
+            controller_connection_id,
+            host_connection_id,
+            address: String::new(),
+            encoding: ENCODING_PROTOBUF.to_string(),
+            tx_type: TX_TYPE_SDK_MULTI_MSG.to_string(),
+        }
+    }
+
+    /// Checks that the version, encoding and transaction type are supported.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.version != VERSION {
+            return Err(Error::invalid_version(self.version.clone()));
+        }
+        if self.encoding != ENCODING_PROTOBUF {
+            return Err(Error::invalid_encoding(self.encoding.clone()));
+        }
+        if self.tx_type != TX_TYPE_SDK_MULTI_MSG {
+            return Err(Error::invalid_tx_type(self.tx_type.clone()));
+        }
+        Ok(())
+    }
+
+    /// Checks that the connection identifiers match the connection the channel is opened on,
+    /// as seen from the controller and from the host.
+    pub fn validate_connections(
+        &self,
+        controller_connection_id: &ConnectionId,
+        host_connection_id: &ConnectionId,
+    ) -> Result<(), Error> {
+        if &self.controller_connection_id != controller_connection_id {
+            return Err(Error::connection_mismatch(
+                controller_connection_id.clone(),
+                self.controller_connection_id.to_string(),
+            ));
+        }
+        if &self.host_connection_id != host_connection_id {
+            return Err(Error::connection_mismatch(
+                host_connection_id.clone(),
+                self.host_connection_id.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the two metadata only differ in the interchain account address.
+    pub fn matches_except_address(&self, other: &Self) -> bool {
+        self.version == other.version
+            && self.controller_connection_id == other.controller_connection_id
+            && self.host_connection_id == other.host_connection_id
+            && self.encoding == other.encoding
+            && self.tx_type == other.tx_type
+    }
+}
+
+impl TryFrom<&Version> for Metadata {
+    type Error = Error;
+
+    fn try_from(version: &Version) -> Result<Self, Self::Error> {
+        serde_json::from_str(&version.to_string()).map_err(|_| Error::metadata_deserialization())
+    }
+}
+
+impl From<Metadata> for Version {
+    fn from(metadata: Metadata) -> Self {
+        serde_json::to_string(&metadata)
+            .expect("Metadata's infallible Serialize impl failed")
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_version_encoding() {
+        // The version string of a channel opened with ibc-go.
+        let version = Version::new(
+            r#"{"version":"ics27-1","controller_connection_id":"connection-0","host_connection_id":"connection-1","address":"","encoding":"proto3","tx_type":"sdk_multi_msg"}"#
+                .to_string(),
+        );
+
+        let metadata = Metadata::try_from(&version).unwrap();
+        assert_eq!(
+            metadata,
+            Metadata::new(ConnectionId::new(0), ConnectionId::new(1))
+        );
+        assert_eq!(Version::from(metadata.clone()), version);
+        assert!(metadata.validate().is_ok());
+
+        let wrong = Metadata {
+            encoding: "json".to_string(),
+            ..metadata
+        };
+        assert!(wrong.validate().is_err());
+        assert!(Metadata::try_from(&Version::new(VERSION.to_string())).is_err());
+    }
+}
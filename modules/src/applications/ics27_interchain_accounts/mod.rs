@@ -0,0 +1,394 @@
+//! ICS 27: Interchain Accounts allows a controller chain to register an account on a host chain
+//! and to execute transactions with it over an ordered IBC channel. The controller and host sides
+//! are implemented as separate modules, which bind to the controller ports and the host port
+//! respectively.
+pub mod acknowledgement;
+pub mod context;
+pub mod controller;
+pub mod error;
+pub mod events;
+pub mod host;
+pub mod metadata;
+pub mod packet;
+
+use crate::core::ics24_host::identifier::PortId;
+use crate::prelude::*;
+
+/// Module identifier for the ICS27 controller module.
+pub const CONTROLLER_MODULE_ID_STR: &str = "icacontroller";
+
+/// Module identifier for the ICS27 host module.
+pub const HOST_MODULE_ID_STR: &str = "icahost";
+
+/// The name of the module account on the host chain, from which the addresses
+/// of interchain accounts are derived.
+pub const MODULE_ACCOUNT_NAME: &str = "interchainaccounts";
+
+/// The prefix of the ports that the controller module binds to. Each interchain
+/// account owner gets its own controller port, `icacontroller-{owner}`.
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+
+/// The port identifier that the ICS27 host module binds with.
+pub const HOST_PORT_ID_STR: &str = "icahost";
+
+/// ICS27 application current version.
+pub const VERSION: &str = "ics27-1";
+
+/// The encoding of the messages carried in the packet data.
+pub const ENCODING_PROTOBUF: &str = "proto3";
+
+/// The type of transactions supported by the host.
+pub const TX_TYPE_SDK_MULTI_MSG: &str = "sdk_multi_msg";
+
+/// Returns the controller port identifier for the interchain account `owner`.
+pub fn controller_port_id(owner: &str) -> Result<PortId, error::Error> {
+    format!("{}{}", CONTROLLER_PORT_PREFIX, owner)
+        .parse()
+        .map_err(error::Error::invalid_owner)
+}
+
+/// Returns the owner of the interchain account bound to a controller port, or `None`
+/// if the port is not a controller port.
+pub fn owner_from_port_id(port_id: &PortId) -> Option<&str> {
+    port_id
+        .as_str()
+        .strip_prefix(CONTROLLER_PORT_PREFIX)
+        .filter(|owner| !owner.is_empty())
+}
+
+/// Returns the port identifier of the ICS27 host module.
+pub fn host_port_id() -> PortId {
+    HOST_PORT_ID_STR
+        .parse()
+        .expect("the ICS27 host port identifier is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use ibc_proto::google::protobuf::Any;
+    use test_log::test;
+
+    use super::acknowledgement::Acknowledgement;
+    use super::context::Ics27Reader;
+    use super::controller::{send_tx, IcaControllerModule};
+    use super::host::IcaHostModule;
+    use super::metadata::Metadata;
+    use super::packet::InterchainAccountPacketData;
+    use super::{controller_port_id, host_port_id, CONTROLLER_MODULE_ID_STR, HOST_MODULE_ID_STR};
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
+    use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+    use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+    use crate::core::ics04_channel::msgs::chan_open_ack::test_util::get_dummy_raw_msg_chan_open_ack;
+    use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::core::ics04_channel::msgs::chan_open_try::test_util::get_dummy_raw_msg_chan_open_try;
+    use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+    use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
+    use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{
+        Ics26Context, ModuleId, ModuleOutputBuilder, OnRecvPacketAck, Router, RouterBuilder,
+    };
+    use crate::core::ics26_routing::msgs::Ics26Envelope;
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::{MockContext, MockRouterBuilder};
+    use crate::test_utils::{get_dummy_account_id, DummyIcaContext};
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    const ALLOWED_MSG: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+    fn chain_with_module(
+        client_id: &ClientId,
+        client_height: Height,
+        connection_id: ConnectionId,
+        counterparty_connection_id: ConnectionId,
+        module_id: &ModuleId,
+        port_id: PortId,
+        controller: bool,
+    ) -> MockContext {
+        let mut ctx = MockContext::default()
+            .with_client(client_id, client_height)
+            .with_open_connection(client_id, connection_id, counterparty_connection_id);
+
+        let ica_ctx = DummyIcaContext::new(ctx.ibc_store_share(), vec![ALLOWED_MSG.to_string()]);
+        let builder = MockRouterBuilder::default();
+        let builder = if controller {
+            builder.add_route(module_id.clone(), IcaControllerModule::new(ica_ctx))
+        } else {
+            builder.add_route(module_id.clone(), IcaHostModule::new(ica_ctx))
+        };
+        ctx.scope_port_to_module(port_id, module_id.clone());
+        ctx.with_router(builder.unwrap().build())
+    }
+
+    fn controller_ctx(ctx: &mut MockContext) -> &mut DummyIcaContext {
+        let module_id: ModuleId = CONTROLLER_MODULE_ID_STR.parse().unwrap();
+        ctx.router_mut()
+            .get_route_mut(&module_id)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<IcaControllerModule<DummyIcaContext>>()
+            .unwrap()
+            .ctx_mut()
+    }
+
+    fn host_ctx(ctx: &mut MockContext) -> &mut DummyIcaContext {
+        let module_id: ModuleId = HOST_MODULE_ID_STR.parse().unwrap();
+        ctx.router_mut()
+            .get_route_mut(&module_id)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<IcaHostModule<DummyIcaContext>>()
+            .unwrap()
+            .ctx_mut()
+    }
+
+    fn send(ctx: &mut MockContext, connection_id: &ConnectionId, type_url: &str) -> Packet {
+        let data = InterchainAccountPacketData::execute_tx(
+            vec![Any {
+                type_url: type_url.to_string(),
+                value: vec![1, 2, 3],
+            }],
+            String::new(),
+        )
+        .unwrap();
+
+        let mut output = HandlerOutputBuilder::new();
+        send_tx(
+            controller_ctx(ctx),
+            &mut output,
+            connection_id,
+            "owner",
+            data,
+            Height::new(0, 100),
+            Timestamp::none(),
+        )
+        .unwrap();
+
+        output
+            .with_result(())
+            .events
+            .into_iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(event) => Some(event.packet),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    /// Calls the host module directly with a received `packet`, returning its acknowledgement.
+    fn host_recv(ctx: &mut MockContext, packet: &Packet) -> Acknowledgement {
+        let module_id: ModuleId = HOST_MODULE_ID_STR.parse().unwrap();
+        let ack = match ctx
+            .router_mut()
+            .get_route_mut(&module_id)
+            .unwrap()
+            .on_recv_packet(
+                &mut ModuleOutputBuilder::new(),
+                packet,
+                &get_dummy_account_id(),
+            ) {
+            OnRecvPacketAck::Successful(ack, _) | OnRecvPacketAck::Failed(ack) => ack,
+            OnRecvPacketAck::Nil(_) => panic!("the host must acknowledge packets"),
+        };
+        Acknowledgement::try_from(&GenericAcknowledgement::from((*ack).as_ref().to_vec())).unwrap()
+    }
+
+    fn recv_packet_msg(packet: Packet, proof_height: u64) -> Ics26Envelope {
+        let mut msg = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(proof_height)).unwrap();
+        msg.packet = packet;
+        Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg))
+    }
+
+    #[test]
+    fn ica_handshake_and_packet_flow() {
+        let proof_height = 10;
+        let client_height = Height::new(0, proof_height);
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let conn_a = ConnectionId::new(0);
+        let conn_b = ConnectionId::new(1);
+        let controller_port = controller_port_id("owner").unwrap();
+        let host_port = host_port_id();
+
+        let mut ctx_a = chain_with_module(
+            &client_id,
+            client_height,
+            conn_a.clone(),
+            conn_b.clone(),
+            &CONTROLLER_MODULE_ID_STR.parse().unwrap(),
+            controller_port.clone(),
+            true,
+        );
+        let mut ctx_b = chain_with_module(
+            &client_id,
+            client_height,
+            conn_b.clone(),
+            conn_a.clone(),
+            &HOST_MODULE_ID_STR.parse().unwrap(),
+            host_port.clone(),
+            false,
+        );
+
+        let version: Version = Metadata::new(conn_a.clone(), conn_b.clone()).into();
+        let init_channel = |order| {
+            ChannelEnd::new(
+                State::Init,
+                order,
+                Counterparty::new(host_port.clone(), None),
+                vec![conn_a.clone()],
+                version.clone(),
+            )
+        };
+        let init_msg = |channel| {
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(MsgChannelOpenInit::new(
+                controller_port.clone(),
+                channel,
+                get_dummy_account_id(),
+            )))
+        };
+
+        // Interchain account channels must be ordered.
+        assert!(ctx_a
+            .deliver(init_msg(init_channel(Order::Unordered)))
+            .is_err());
+
+        // ChanOpenInit on the controller.
+        ctx_a
+            .deliver(init_msg(init_channel(Order::Ordered)))
+            .unwrap();
+        let chan_a = ChannelId::new(0);
+
+        // ChanOpenTry on the host, which registers the interchain account.
+        let mut try_msg =
+            MsgChannelOpenTry::try_from(get_dummy_raw_msg_chan_open_try(proof_height)).unwrap();
+        try_msg.port_id = host_port.clone();
+        try_msg.previous_channel_id = None;
+        try_msg.channel = ChannelEnd::new(
+            State::TryOpen,
+            Order::Ordered,
+            Counterparty::new(controller_port.clone(), Some(chan_a)),
+            vec![conn_b.clone()],
+            Version::empty(),
+        );
+        try_msg.counterparty_version = version.clone();
+        ctx_b
+            .deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(
+                try_msg,
+            )))
+            .unwrap();
+        let chan_b = ChannelId::new(0);
+
+        let host_version = ctx_b
+            .channel_end(&(host_port.clone(), chan_b))
+            .unwrap()
+            .version()
+            .clone();
+        let host_metadata = Metadata::try_from(&host_version).unwrap();
+        assert!(host_metadata.matches_except_address(&Metadata::try_from(&version).unwrap()));
+        let address = host_metadata.address.parse().unwrap();
+        assert_eq!(host_ctx(&mut ctx_b).accounts, vec![address]);
+
+        // ChanOpenAck on the controller, which records the interchain account address.
+        let mut ack_msg =
+            MsgChannelOpenAck::try_from(get_dummy_raw_msg_chan_open_ack(proof_height)).unwrap();
+        ack_msg.port_id = controller_port.clone();
+        ack_msg.channel_id = chan_a;
+        ack_msg.counterparty_channel_id = chan_b;
+        ack_msg.counterparty_version = host_version;
+        ctx_a
+            .deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(
+                ack_msg,
+            )))
+            .unwrap();
+        assert_eq!(
+            controller_ctx(&mut ctx_a)
+                .get_interchain_account_address(&conn_a, &controller_port)
+                .map(|address| address.to_string()),
+            Some(host_metadata.address.clone())
+        );
+
+        // ChanOpenConfirm on the host.
+        let mut confirm_msg =
+            MsgChannelOpenConfirm::try_from(get_dummy_raw_msg_chan_open_confirm(proof_height))
+                .unwrap();
+        confirm_msg.port_id = host_port.clone();
+        confirm_msg.channel_id = chan_b;
+        ctx_b
+            .deliver(Ics26Envelope::Ics4ChannelMsg(
+                ChannelMsg::ChannelOpenConfirm(confirm_msg),
+            ))
+            .unwrap();
+        assert_eq!(
+            host_ctx(&mut ctx_b).get_active_channel_id(&conn_b, &controller_port),
+            Some(chan_b)
+        );
+
+        // A second channel cannot be opened for the same account.
+        assert!(ctx_a
+            .deliver(init_msg(init_channel(Order::Ordered)))
+            .is_err());
+
+        // The host executes an allowed message with the interchain account...
+        let packet = send(&mut ctx_a, &conn_a, ALLOWED_MSG);
+        ctx_b
+            .deliver(recv_packet_msg(packet.clone(), proof_height))
+            .unwrap();
+        let executed = &host_ctx(&mut ctx_b).executed_messages;
+        assert_eq!(executed.len(), 1);
+        assert_eq!(executed[0].0.to_string(), host_metadata.address);
+        assert_eq!(executed[0].1.type_url, ALLOWED_MSG);
+
+        // ...and the controller processes the acknowledgement.
+        let mut packet_ack_msg =
+            MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(proof_height)).unwrap();
+        packet_ack_msg.packet = packet;
+        packet_ack_msg.acknowledgement = Acknowledgement::success(&[]).as_ref().to_vec().into();
+        ctx_a
+            .deliver(Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(
+                packet_ack_msg,
+            )))
+            .unwrap();
+
+        // A message that is not allowed on the host gets an error acknowledgement.
+        let packet = send(&mut ctx_a, &conn_a, "/cosmos.staking.v1beta1.MsgDelegate");
+        ctx_b
+            .deliver(recv_packet_msg(packet, proof_height))
+            .unwrap();
+        assert_eq!(host_ctx(&mut ctx_b).executed_messages.len(), 1);
+
+        // A message that fails to execute on the host gets an error acknowledgement, rather than
+        // failing the receipt of the packet.
+        host_ctx(&mut ctx_b)
+            .failing_messages
+            .push(ALLOWED_MSG.to_string());
+        let packet = send(&mut ctx_a, &conn_a, ALLOWED_MSG);
+        ctx_b
+            .deliver(recv_packet_msg(packet.clone(), proof_height))
+            .unwrap();
+        assert_eq!(host_ctx(&mut ctx_b).executed_messages.len(), 1);
+
+        let ack = host_recv(&mut ctx_b, &packet);
+        assert!(!ack.is_successful());
+        assert_eq!(host_ctx(&mut ctx_b).executed_messages.len(), 1);
+
+        // The acknowledgement of a successful execution carries the result data of the
+        // transaction.
+        host_ctx(&mut ctx_b).failing_messages.clear();
+        let packet = send(&mut ctx_a, &conn_a, ALLOWED_MSG);
+        let ack = host_recv(&mut ctx_b, &packet);
+        assert_eq!(ack, Acknowledgement::success(ALLOWED_MSG.as_bytes()));
+        assert_eq!(host_ctx(&mut ctx_b).executed_messages.len(), 2);
+    }
+}
@@ -0,0 +1,180 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::interchain_accounts::v1::{
+    CosmosTx as RawCosmosTx, InterchainAccountPacketData as RawPacketData, Type,
+};
+use prost::Message;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use subtle_encoding::base64;
+
+use super::error::Error;
+use crate::prelude::*;
+
+/// The maximum length of the memo, in bytes.
+pub const MAX_MEMO_LENGTH: usize = 256;
+
+/// The packet data sent by the controller to the host. For packets of type
+/// [`Type::ExecuteTx`], `data` is the protobuf encoding of a `CosmosTx`
+/// holding the messages to execute with the interchain account.
+///
+/// The JSON encoding of the packet data, used on the wire, follows the
+/// protobuf JSON mapping: the type is given by its enum variant name and the
+/// data is base64-encoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterchainAccountPacketData {
+    pub packet_type: Type,
+    pub data: Vec<u8>,
+    pub memo: String,
+}
+
+impl InterchainAccountPacketData {
+    /// Creates the packet data for executing `messages` on the host chain.
+    pub fn execute_tx(messages: Vec<Any>, memo: String) -> Result<Self, Error> {
+        if messages.is_empty() {
+            return Err(Error::empty_tx());
+        }
+
+        let data = Self {
+            packet_type: Type::ExecuteTx,
+            data: RawCosmosTx { messages }.encode_to_vec(),
+            memo,
+        };
+        data.validate_basic()?;
+
+        Ok(data)
+    }
+
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.packet_type == Type::Unspecified {
+            return Err(Error::unsupported_packet_type(self.packet_type as i32));
+        }
+        if self.data.is_empty() {
+            return Err(Error::empty_packet_data());
+        }
+        if self.memo.len() > MAX_MEMO_LENGTH {
+            return Err(Error::memo_too_long(self.memo.len()));
+        }
+        Ok(())
+    }
+
+    /// Decodes the messages of an [`Type::ExecuteTx`] packet.
+    pub fn messages(&self) -> Result<Vec<Any>, Error> {
+        if self.packet_type != Type::ExecuteTx {
+            return Err(Error::unsupported_packet_type(self.packet_type as i32));
+        }
+
+        let tx = RawCosmosTx::decode(self.data.as_slice()).map_err(Error::decode)?;
+        if tx.messages.is_empty() {
+            return Err(Error::empty_tx());
+        }
+
+        Ok(tx.messages)
+    }
+}
+
+impl TryFrom<RawPacketData> for InterchainAccountPacketData {
+    type Error = Error;
+
+    fn try_from(raw: RawPacketData) -> Result<Self, Self::Error> {
+        let packet_type =
+            Type::from_i32(raw.r#type).ok_or_else(|| Error::unsupported_packet_type(raw.r#type))?;
+        let data = Self {
+            packet_type,
+            data: raw.data,
+            memo: raw.memo,
+        };
+        data.validate_basic()?;
+
+        Ok(data)
+    }
+}
+
+impl From<InterchainAccountPacketData> for RawPacketData {
+    fn from(data: InterchainAccountPacketData) -> Self {
+        Self {
+            r#type: data.packet_type as i32,
+            data: data.data,
+            memo: data.memo,
+        }
+    }
+}
+
+/// The JSON representation of the packet data.
+#[derive(Serialize, Deserialize)]
+struct JsonPacketData {
+    #[serde(rename = "type")]
+    packet_type: String,
+    data: String,
+    #[serde(default)]
+    memo: String,
+}
+
+fn type_as_str(packet_type: Type) -> &'static str {
+    match packet_type {
+        Type::Unspecified => "TYPE_UNSPECIFIED",
+        Type::ExecuteTx => "TYPE_EXECUTE_TX",
+    }
+}
+
+fn type_from_str(s: &str) -> Option<Type> {
+    match s {
+        "TYPE_UNSPECIFIED" => Some(Type::Unspecified),
+        "TYPE_EXECUTE_TX" => Some(Type::ExecuteTx),
+        _ => None,
+    }
+}
+
+impl Serialize for InterchainAccountPacketData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonPacketData {
+            packet_type: type_as_str(self.packet_type).to_string(),
+            data: String::from_utf8(base64::encode(&self.data))
+                .expect("base64 encoded bytes are not valid UTF8"),
+            memo: self.memo.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for InterchainAccountPacketData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = JsonPacketData::deserialize(deserializer)?;
+        let packet_type = type_from_str(&json.packet_type)
+            .ok_or_else(|| D::Error::custom(format!("unknown packet type {}", json.packet_type)))?;
+        let data = base64::decode(json.data.as_bytes()).map_err(D::Error::custom)?;
+
+        Ok(Self {
+            packet_type,
+            data,
+            memo: json.memo,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packet_data_json_round_trip() {
+        let messages = vec![Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![1, 2, 3],
+        }];
+        let data =
+            InterchainAccountPacketData::execute_tx(messages.clone(), "memo".into()).unwrap();
+
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(json.starts_with(r#"{"type":"TYPE_EXECUTE_TX","data":""#));
+
+        let decoded: InterchainAccountPacketData = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(decoded.messages().unwrap(), messages);
+
+        assert!(InterchainAccountPacketData::execute_tx(vec![], "".into()).is_err());
+        assert!(serde_json::from_str::<InterchainAccountPacketData>(
+            r#"{"type":"TYPE_SEND","data":"","memo":""}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use subtle_encoding::base64;
+
+use super::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics26_routing::context::Acknowledgement as AckTrait;
+use crate::prelude::*;
+
+/// The acknowledgement written for packets of fee-enabled channels. It wraps the acknowledgement
+/// of the underlying application with the address the receive fee is to be paid out to on the
+/// source chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncentivizedAcknowledgement {
+    #[serde(serialize_with = "ser_base64", deserialize_with = "de_base64")]
+    pub app_acknowledgement: Vec<u8>,
+    /// The counterparty payee of the relayer who relayed the packet, or the relayer itself.
+    pub forward_relayer_address: String,
+    pub underlying_app_success: bool,
+}
+
+impl IncentivizedAcknowledgement {
+    pub fn new(
+        app_acknowledgement: Vec<u8>,
+        forward_relayer_address: String,
+        underlying_app_success: bool,
+    ) -> Self {
+        Self {
+            app_acknowledgement,
+            forward_relayer_address,
+            underlying_app_success,
+        }
+    }
+
+    pub fn app_acknowledgement(&self) -> GenericAcknowledgement {
+        self.app_acknowledgement.clone().into()
+    }
+}
+
+fn ser_base64<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    let encoded =
+        String::from_utf8(base64::encode(bytes)).expect("base64 encoded bytes are not valid UTF8");
+    serializer.serialize_str(&encoded)
+}
+
+fn de_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    base64::decode(encoded).map_err(serde::de::Error::custom)
+}
+
+/// The JSON encoding of the acknowledgement, which is written into the state.
+pub struct Acknowledgement(Vec<u8>);
+
+impl From<IncentivizedAcknowledgement> for Acknowledgement {
+    fn from(ack: IncentivizedAcknowledgement) -> Self {
+        Self(
+            serde_json::to_vec(&ack)
+                .expect("IncentivizedAcknowledgement's infallible Serialize impl failed"),
+        )
+    }
+}
+
+impl AsRef<[u8]> for Acknowledgement {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AckTrait for Acknowledgement {}
+
+impl TryFrom<&GenericAcknowledgement> for IncentivizedAcknowledgement {
+    type Error = Error;
+
+    fn try_from(ack: &GenericAcknowledgement) -> Result<Self, Self::Error> {
+        serde_json::from_slice(ack.as_ref()).map_err(|_| Error::ack_deserialization())
+    }
+}
@@ -0,0 +1,116 @@
+use sha2::{Digest, Sha256};
+use subtle_encoding::hex;
+
+use super::error::Error as Ics29Error;
+use super::fee::PacketFee;
+use super::MODULE_ACCOUNT_NAME;
+use crate::applications::transfer::BaseCoin;
+use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::core::ics04_channel::packet::PacketId;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+pub trait Ics29Keeper:
+    ChannelKeeper + BankKeeper<AccountId = <Self as Ics29Keeper>::AccountId>
+{
+    type AccountId;
+
+    /// Marks the channel as fee-enabled, i.e. its version has been negotiated with the fee version
+    /// wrapping the version of the underlying application.
+    fn store_fee_enabled(
+        &mut self,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(), Ics29Error>;
+
+    /// Records `payee` as the account which gets paid the acknowledgement and timeout fees earned
+    /// by `relayer` on the channel `channel_id`.
+    fn store_payee(
+        &mut self,
+        relayer: Signer,
+        channel_id: ChannelId,
+        payee: Signer,
+    ) -> Result<(), Ics29Error>;
+
+    /// Records `counterparty_payee` as the account on the counterparty chain which gets paid the
+    /// receive fees earned by `relayer` on the channel `channel_id`.
+    fn store_counterparty_payee(
+        &mut self,
+        relayer: Signer,
+        channel_id: ChannelId,
+        counterparty_payee: Signer,
+    ) -> Result<(), Ics29Error>;
+
+    /// Replaces the fees escrowed for the packet `packet_id`.
+    fn store_fees_in_escrow(
+        &mut self,
+        packet_id: PacketId,
+        packet_fees: Vec<PacketFee>,
+    ) -> Result<(), Ics29Error>;
+
+    /// Removes the fees escrowed for the packet `packet_id`, once they have been distributed.
+    fn delete_fees_in_escrow(&mut self, packet_id: &PacketId) -> Result<(), Ics29Error>;
+}
+
+pub trait Ics29Reader: ChannelReader {
+    type AccountId: TryFrom<Signer>;
+
+    /// Returns true iff the channel is fee-enabled.
+    fn is_fee_enabled(&self, port_id: &PortId, channel_id: &ChannelId) -> bool;
+
+    /// Returns the payee registered by `relayer` on the channel `channel_id`, if any.
+    fn get_payee(&self, relayer: &Signer, channel_id: &ChannelId) -> Option<Signer>;
+
+    /// Returns the counterparty payee registered by `relayer` on the channel `channel_id`, if any.
+    fn get_counterparty_payee(&self, relayer: &Signer, channel_id: &ChannelId) -> Option<Signer>;
+
+    /// Returns the fees escrowed for the packet `packet_id`.
+    fn get_fees_in_escrow(&self, packet_id: &PacketId) -> Vec<PacketFee>;
+
+    /// Returns the fees escrowed for all the packets sent on the channel `channel_id`.
+    fn get_identified_packet_fees_for_channel(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Vec<(PacketId, Vec<PacketFee>)>;
+
+    /// Returns true iff the fee of a packet may be paid by the account `payer`.
+    /// Hosts may reject payments, e.g. from blocked addresses.
+    fn is_payment_allowed(&self, _payer: &Signer) -> bool {
+        true
+    }
+
+    /// Returns the module account which holds the escrowed fees.
+    fn get_fee_escrow_address(&self) -> Result<<Self as Ics29Reader>::AccountId, Ics29Error> {
+        let mut hash = Sha256::digest(MODULE_ACCOUNT_NAME.as_bytes()).to_vec();
+        hash.truncate(20);
+        String::from_utf8(hex::encode_upper(hash))
+            .expect("hex encoded bytes are not valid UTF8")
+            .parse::<Signer>()
+            .map_err(Ics29Error::signer)?
+            .try_into()
+            .map_err(|_| Ics29Error::parse_account_failure())
+    }
+}
+
+pub trait BankKeeper {
+    type AccountId;
+
+    /// This function should enable sending the coins of a fee from one account to another
+    fn send_coins(
+        &mut self,
+        from: &Self::AccountId,
+        to: &Self::AccountId,
+        amt: &BaseCoin,
+    ) -> Result<(), Ics29Error>;
+}
+
+/// Captures all the dependencies which the ICS29 middleware requires to be able to escrow and
+/// distribute packet fees.
+pub trait Ics29Context:
+    Ics29Keeper<AccountId = <Self as Ics29Context>::AccountId>
+    + Ics29Reader<AccountId = <Self as Ics29Context>::AccountId>
+{
+    type AccountId: TryFrom<Signer>;
+}
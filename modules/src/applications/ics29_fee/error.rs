@@ -0,0 +1,83 @@
+use flex_error::{define_error, TraceError};
+use tendermint_proto::Error as TendermintProtoError;
+
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::core::ics04_channel::error as channel_error;
+use crate::core::ics04_channel::packet::PacketId;
+use crate::core::ics24_host::error::ValidationError;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics04Channel
+            [ channel_error::Error ]
+            | _ | { "Ics04 channel error" },
+
+        InvalidIdentifier
+            [ ValidationError ]
+            | _ | { "invalid identifier" },
+
+        InvalidCoin
+            [ Ics20Error ]
+            | _ | { "invalid fee coin" },
+
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer" },
+
+        ParseAccountFailure
+            | _ | { "failed to parse as AccountId" },
+
+        MissingFee
+            | _ | { "missing fee" },
+
+        MissingPacketFee
+            | _ | { "missing packet fee" },
+
+        MissingPacketId
+            | _ | { "missing packet identifier" },
+
+        EmptyFee
+            | _ | { "at least one of the receive, acknowledgement and timeout fees must be set" },
+
+        RelayersNotSupported
+            | _ | { "a list of permitted relayers is not supported yet, the list must be empty" },
+
+        FeeNotEnabled
+            { port_id: PortId, channel_id: ChannelId }
+            | e | { format_args!("fee module is not enabled for port {0} and channel {1}", e.port_id, e.channel_id) },
+
+        PacketCommitmentNotFound
+            { packet_id: PacketId }
+            | e | { format_args!("no commitment found for packet {0}, the packet may have been relayed already", e.packet_id) },
+
+        FeePaymentRejected
+            { reason: String }
+            | e | { format_args!("fee payment rejected: {0}", e.reason) },
+
+        InsufficientFunds
+            { address: String, coin: String }
+            | e | { format_args!("account {0} has insufficient funds to send {1}", e.address, e.coin) },
+
+        MetadataDeserialization
+            | _ | { "failed to deserialize the fee version metadata" },
+
+        InvalidVersion
+            { version: String }
+            | e | { format_args!("invalid fee version {0}, expected {1}", e.version, super::VERSION) },
+
+        AckDeserialization
+            | _ | { "failed to deserialize the incentivized acknowledgement" },
+
+        UnknownMsgType
+            { msg_type: String }
+            | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+
+        DecodeRawMsg
+            [ TraceError<TendermintProtoError> ]
+            | _ | { "error decoding raw msg" },
+    }
+}
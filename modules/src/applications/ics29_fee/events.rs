@@ -0,0 +1,182 @@
+use tendermint::abci::Event as AbciEvent;
+
+use super::fee::Fee;
+use super::MODULE_ID_STR;
+use crate::applications::transfer::{Amount, BaseCoin, BaseDenom};
+use crate::core::ics04_channel::packet::PacketId;
+use crate::core::ics24_host::identifier::ChannelId;
+use crate::events::ModuleEvent;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+const EVENT_TYPE_INCENTIVIZED_PACKET: &str = "incentivized_ibc_packet";
+const EVENT_TYPE_REGISTER_PAYEE: &str = "register_payee";
+const EVENT_TYPE_REGISTER_COUNTERPARTY_PAYEE: &str = "register_counterparty_payee";
+const EVENT_TYPE_DISTRIBUTE_FEE: &str = "distribute_fee";
+
+/// Formats coins the way the Cosmos SDK does, e.g. `100stake,20uatom`.
+fn coins_to_string(coins: &[BaseCoin]) -> String {
+    coins
+        .iter()
+        .map(|coin| format!("{}{}", coin.amount, coin.denom))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses coins formatted the way the Cosmos SDK does, e.g. `100stake,20uatom`.
+fn coins_from_str(s: &str) -> Option<Vec<BaseCoin>> {
+    if s.is_empty() {
+        return Some(vec![]);
+    }
+
+    s.split(',')
+        .map(|coin| {
+            let denom_start = coin.find(|c: char| !c.is_ascii_digit())?;
+            let (amount, denom) = coin.split_at(denom_start);
+            Some(BaseCoin {
+                denom: denom.parse::<BaseDenom>().ok()?,
+                amount: amount.parse::<Amount>().ok()?,
+            })
+        })
+        .collect()
+}
+
+pub enum Event {
+    IncentivizedPacket(IncentivizedPacketEvent),
+    RegisterPayee(RegisterPayeeEvent),
+    RegisterCounterpartyPayee(RegisterCounterpartyPayeeEvent),
+    DistributeFee(DistributeFeeEvent),
+}
+
+/// Emitted when a fee is escrowed for a packet, with the total fees escrowed for it so far.
+pub struct IncentivizedPacketEvent {
+    pub packet_id: PacketId,
+    pub total_fee: Fee,
+}
+
+impl From<IncentivizedPacketEvent> for ModuleEvent {
+    fn from(ev: IncentivizedPacketEvent) -> Self {
+        let IncentivizedPacketEvent {
+            packet_id,
+            total_fee,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_INCENTIVIZED_PACKET.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("port_id", packet_id.port_id).into(),
+                ("channel_id", packet_id.channel_id).into(),
+                ("packet_sequence", packet_id.sequence).into(),
+                ("recv_fee", coins_to_string(&total_fee.recv_fee)).into(),
+                ("ack_fee", coins_to_string(&total_fee.ack_fee)).into(),
+                ("timeout_fee", coins_to_string(&total_fee.timeout_fee)).into(),
+            ],
+        }
+    }
+}
+
+pub struct RegisterPayeeEvent {
+    pub relayer: Signer,
+    pub payee: Signer,
+    pub channel_id: ChannelId,
+}
+
+impl From<RegisterPayeeEvent> for ModuleEvent {
+    fn from(ev: RegisterPayeeEvent) -> Self {
+        let RegisterPayeeEvent {
+            relayer,
+            payee,
+            channel_id,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_REGISTER_PAYEE.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("relayer", relayer).into(),
+                ("payee", payee).into(),
+                ("channel_id", channel_id).into(),
+            ],
+        }
+    }
+}
+
+pub struct RegisterCounterpartyPayeeEvent {
+    pub relayer: Signer,
+    pub counterparty_payee: Signer,
+    pub channel_id: ChannelId,
+}
+
+impl From<RegisterCounterpartyPayeeEvent> for ModuleEvent {
+    fn from(ev: RegisterCounterpartyPayeeEvent) -> Self {
+        let RegisterCounterpartyPayeeEvent {
+            relayer,
+            counterparty_payee,
+            channel_id,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_REGISTER_COUNTERPARTY_PAYEE.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("relayer", relayer).into(),
+                ("counterparty_payee", counterparty_payee).into(),
+                ("channel_id", channel_id).into(),
+            ],
+        }
+    }
+}
+
+/// Emitted for each payout of escrowed fees, to a relayer or as a refund.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistributeFeeEvent {
+    pub receiver: Signer,
+    pub fee: Vec<BaseCoin>,
+}
+
+impl DistributeFeeEvent {
+    /// Parses the event from the events of a transaction result.
+    pub fn try_from_tx(event: &AbciEvent) -> Option<Self> {
+        if event.type_str != EVENT_TYPE_DISTRIBUTE_FEE {
+            return None;
+        }
+
+        let mut receiver = None;
+        let mut fee = None;
+        for tag in &event.attributes {
+            match tag.key.as_ref() {
+                "receiver" => receiver = tag.value.as_ref().parse().ok(),
+                "fee" => fee = coins_from_str(tag.value.as_ref()),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            receiver: receiver?,
+            fee: fee?,
+        })
+    }
+}
+
+impl From<DistributeFeeEvent> for ModuleEvent {
+    fn from(ev: DistributeFeeEvent) -> Self {
+        let DistributeFeeEvent { receiver, fee } = ev;
+        Self {
+            kind: EVENT_TYPE_DISTRIBUTE_FEE.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("receiver", receiver).into(),
+                ("fee", coins_to_string(&fee)).into(),
+            ],
+        }
+    }
+}
+
+impl From<Event> for ModuleEvent {
+    fn from(ev: Event) -> Self {
+        match ev {
+            Event::IncentivizedPacket(ev) => ev.into(),
+            Event::RegisterPayee(ev) => ev.into(),
+            Event::RegisterCounterpartyPayee(ev) => ev.into(),
+            Event::DistributeFee(ev) => ev.into(),
+        }
+    }
+}
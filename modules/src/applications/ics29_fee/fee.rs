@@ -0,0 +1,127 @@
+//! The fees paid for relaying a packet.
+use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+use ibc_proto::ibc::applications::fee::v1::{Fee as RawFee, PacketFee as RawPacketFee};
+
+use super::error::Error;
+use crate::applications::transfer::BaseCoin;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+fn coins_from_raw(raw_coins: Vec<RawCoin>) -> Result<Vec<BaseCoin>, Error> {
+    raw_coins
+        .into_iter()
+        .map(|coin| coin.try_into().map_err(Error::invalid_coin))
+        .collect()
+}
+
+fn coins_into_raw(coins: Vec<BaseCoin>) -> Vec<RawCoin> {
+    coins.into_iter().map(Into::into).collect()
+}
+
+/// The fees for each of the steps of relaying a packet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fee {
+    /// Paid to the relayer who relays the packet to the destination chain.
+    pub recv_fee: Vec<BaseCoin>,
+    /// Paid to the relayer who relays the acknowledgement back to the source chain.
+    pub ack_fee: Vec<BaseCoin>,
+    /// Paid to the relayer who relays the timeout back to the source chain.
+    pub timeout_fee: Vec<BaseCoin>,
+}
+
+impl Fee {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.recv_fee.is_empty() && self.ack_fee.is_empty() && self.timeout_fee.is_empty() {
+            return Err(Error::empty_fee());
+        }
+        Ok(())
+    }
+
+    /// All the coins of the fee, which are escrowed when the fee is paid.
+    pub fn coins(&self) -> impl Iterator<Item = &BaseCoin> {
+        self.recv_fee
+            .iter()
+            .chain(self.ack_fee.iter())
+            .chain(self.timeout_fee.iter())
+    }
+}
+
+impl TryFrom<RawFee> for Fee {
+    type Error = Error;
+
+    fn try_from(raw: RawFee) -> Result<Self, Self::Error> {
+        Ok(Fee {
+            recv_fee: coins_from_raw(raw.recv_fee)?,
+            ack_fee: coins_from_raw(raw.ack_fee)?,
+            timeout_fee: coins_from_raw(raw.timeout_fee)?,
+        })
+    }
+}
+
+impl From<Fee> for RawFee {
+    fn from(fee: Fee) -> Self {
+        RawFee {
+            recv_fee: coins_into_raw(fee.recv_fee),
+            ack_fee: coins_into_raw(fee.ack_fee),
+            timeout_fee: coins_into_raw(fee.timeout_fee),
+        }
+    }
+}
+
+/// A fee escrowed for a packet, along with the account that gets refunded whatever is not paid
+/// out to relayers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PacketFee {
+    pub fee: Fee,
+    pub refund_address: Signer,
+    /// The relayers permitted to receive the fee. Not supported yet and must be empty.
+    pub relayers: Vec<Signer>,
+}
+
+impl PacketFee {
+    pub fn new(fee: Fee, refund_address: Signer) -> Self {
+        Self {
+            fee,
+            refund_address,
+            relayers: vec![],
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        self.fee.validate()?;
+        if !self.relayers.is_empty() {
+            return Err(Error::relayers_not_supported());
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<RawPacketFee> for PacketFee {
+    type Error = Error;
+
+    fn try_from(raw: RawPacketFee) -> Result<Self, Self::Error> {
+        Ok(PacketFee {
+            fee: raw.fee.ok_or_else(Error::missing_fee)?.try_into()?,
+            refund_address: raw.refund_address.parse().map_err(Error::signer)?,
+            relayers: raw
+                .relayers
+                .into_iter()
+                .map(|relayer| relayer.parse().map_err(Error::signer))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<PacketFee> for RawPacketFee {
+    fn from(packet_fee: PacketFee) -> Self {
+        RawPacketFee {
+            fee: Some(packet_fee.fee.into()),
+            refund_address: packet_fee.refund_address.to_string(),
+            relayers: packet_fee
+                .relayers
+                .into_iter()
+                .map(|relayer| relayer.to_string())
+                .collect(),
+        }
+    }
+}
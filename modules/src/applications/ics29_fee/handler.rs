@@ -0,0 +1,187 @@
+//! Handlers of the messages of the ICS29 middleware, which are submitted to the host chain
+//! alongside the IBC messages.
+use super::context::Ics29Context;
+use super::error::Error;
+use super::events::{IncentivizedPacketEvent, RegisterCounterpartyPayeeEvent, RegisterPayeeEvent};
+use super::fee::{Fee, PacketFee};
+use super::msgs::pay_packet_fee::MsgPayPacketFee;
+use super::msgs::pay_packet_fee_async::MsgPayPacketFeeAsync;
+use super::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+use super::msgs::register_payee::MsgRegisterPayee;
+use crate::core::ics04_channel::packet::PacketId;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::events::ModuleEvent;
+use crate::handler::HandlerOutputBuilder;
+use crate::prelude::*;
+
+fn ensure_fee_enabled<Ctx: Ics29Context>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<(), Error> {
+    if ctx.is_fee_enabled(port_id, channel_id) {
+        Ok(())
+    } else {
+        Err(Error::fee_not_enabled(port_id.clone(), *channel_id))
+    }
+}
+
+/// Transfers the fee from the payer to the escrow account and records it for the packet.
+fn escrow_packet_fee<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    packet_id: PacketId,
+    packet_fee: PacketFee,
+) -> Result<(), Error> {
+    packet_fee.validate()?;
+
+    if !ctx.is_payment_allowed(&packet_fee.refund_address) {
+        return Err(Error::fee_payment_rejected(format!(
+            "{} is not allowed to pay packet fees",
+            packet_fee.refund_address
+        )));
+    }
+
+    let payer = packet_fee
+        .refund_address
+        .clone()
+        .try_into()
+        .map_err(|_| Error::parse_account_failure())?;
+    let escrow_address = ctx.get_fee_escrow_address()?;
+    for coin in packet_fee.fee.coins() {
+        ctx.send_coins(&payer, &escrow_address, coin)?;
+    }
+
+    let mut packet_fees = ctx.get_fees_in_escrow(&packet_id);
+    packet_fees.push(packet_fee);
+
+    let total_fee = packet_fees
+        .iter()
+        .fold(Fee::default(), |mut total, packet_fee| {
+            total.recv_fee.extend_from_slice(&packet_fee.fee.recv_fee);
+            total.ack_fee.extend_from_slice(&packet_fee.fee.ack_fee);
+            total
+                .timeout_fee
+                .extend_from_slice(&packet_fee.fee.timeout_fee);
+            total
+        });
+
+    ctx.store_fees_in_escrow(packet_id.clone(), packet_fees)?;
+
+    output.log(format!("fee escrowed for packet {}", packet_id));
+    output.emit(
+        ModuleEvent::from(IncentivizedPacketEvent {
+            packet_id,
+            total_fee,
+        })
+        .into(),
+    );
+
+    Ok(())
+}
+
+/// Escrows the fee for the next packet to be sent on the channel.
+/// If this method returns an error, the runtime is expected to rollback all state modifications to
+/// the `Ctx` caused by all messages from the transaction that this `msg` is a part of.
+pub fn pay_packet_fee<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    msg: MsgPayPacketFee,
+) -> Result<(), Error> {
+    ensure_fee_enabled(ctx, &msg.source_port_id, &msg.source_channel_id)?;
+
+    let sequence = ctx
+        .get_next_sequence_send(&(msg.source_port_id.clone(), msg.source_channel_id))
+        .map_err(Error::ics04_channel)?;
+    let packet_id = PacketId::new(msg.source_port_id, msg.source_channel_id, sequence);
+
+    let packet_fee = PacketFee {
+        fee: msg.fee,
+        refund_address: msg.signer,
+        relayers: msg.relayers,
+    };
+
+    escrow_packet_fee(ctx, output, packet_id, packet_fee)
+}
+
+/// Escrows the fee for a packet which has been sent, but not acknowledged or timed out yet.
+pub fn pay_packet_fee_async<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    msg: MsgPayPacketFeeAsync,
+) -> Result<(), Error> {
+    let packet_id = msg.packet_id;
+    ensure_fee_enabled(ctx, &packet_id.port_id, &packet_id.channel_id)?;
+
+    let next_sequence_send = ctx
+        .get_next_sequence_send(&(packet_id.port_id.clone(), packet_id.channel_id))
+        .map_err(Error::ics04_channel)?;
+    let commitment_key = (
+        packet_id.port_id.clone(),
+        packet_id.channel_id,
+        packet_id.sequence,
+    );
+    if packet_id.sequence >= next_sequence_send
+        || ctx.get_packet_commitment(&commitment_key).is_err()
+    {
+        return Err(Error::packet_commitment_not_found(packet_id));
+    }
+
+    escrow_packet_fee(ctx, output, packet_id, msg.packet_fee)
+}
+
+/// Registers the payee of the acknowledgement and timeout fees earned by a relayer.
+pub fn register_payee<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    msg: MsgRegisterPayee,
+) -> Result<(), Error> {
+    ensure_fee_enabled(ctx, &msg.port_id, &msg.channel_id)?;
+
+    ctx.store_payee(msg.relayer.clone(), msg.channel_id, msg.payee.clone())?;
+
+    output.log(format!(
+        "registered payee {} for relayer {} on channel {}",
+        msg.payee, msg.relayer, msg.channel_id
+    ));
+    output.emit(
+        ModuleEvent::from(RegisterPayeeEvent {
+            relayer: msg.relayer,
+            payee: msg.payee,
+            channel_id: msg.channel_id,
+        })
+        .into(),
+    );
+
+    Ok(())
+}
+
+/// Registers the counterparty payee of the receive fees earned by a relayer.
+pub fn register_counterparty_payee<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    msg: MsgRegisterCounterpartyPayee,
+) -> Result<(), Error> {
+    ensure_fee_enabled(ctx, &msg.port_id, &msg.channel_id)?;
+
+    ctx.store_counterparty_payee(
+        msg.relayer.clone(),
+        msg.channel_id,
+        msg.counterparty_payee.clone(),
+    )?;
+
+    output.log(format!(
+        "registered counterparty payee {} for relayer {} on channel {}",
+        msg.counterparty_payee, msg.relayer, msg.channel_id
+    ));
+    output.emit(
+        ModuleEvent::from(RegisterCounterpartyPayeeEvent {
+            relayer: msg.relayer,
+            counterparty_payee: msg.counterparty_payee,
+            channel_id: msg.channel_id,
+        })
+        .into(),
+    );
+
+    Ok(())
+}
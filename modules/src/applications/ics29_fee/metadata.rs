@@ -0,0 +1,72 @@
+//! The version of a fee-enabled channel, which carries the version of the underlying application
+//! JSON-encoded alongside the fee version.
+use serde::{Deserialize, Serialize};
+
+use super::error::Error;
+use super::VERSION;
+use crate::core::ics04_channel::Version;
+use crate::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// The ICS29 fee version.
+    pub fee_version: String,
+    /// The version of the underlying application.
+    pub app_version: String,
+}
+
+impl Metadata {
+    /// Returns the metadata of the current fee version wrapping `app_version`.
+    pub fn new(app_version: &Version) -> Self {
+        Self {
+            fee_version: VERSION.to_string(),
+            app_version: app_version.to_string(),
+        }
+    }
+
+    pub fn app_version(&self) -> Version {
+        Version::new(self.app_version.clone())
+    }
+}
+
+/// Parses the metadata of a fee-enabled channel version. Fails if `version` is not a fee version,
+/// in which case the channel is not fee-enabled and the version belongs to the underlying
+/// application.
+impl TryFrom<&Version> for Metadata {
+    type Error = Error;
+
+    fn try_from(version: &Version) -> Result<Self, Self::Error> {
+        let metadata = serde_json::from_str::<Metadata>(&version.to_string())
+            .map_err(|_| Error::metadata_deserialization())?;
+        if metadata.fee_version != VERSION {
+            return Err(Error::invalid_version(metadata.fee_version));
+        }
+        Ok(metadata)
+    }
+}
+
+impl From<Metadata> for Version {
+    fn from(metadata: Metadata) -> Self {
+        Version::new(
+            serde_json::to_string(&metadata).expect("Metadata's infallible Serialize impl failed"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_version_encoding() {
+        let version = Version::new(r#"{"fee_version":"ics29-1","app_version":"ics20-1"}"#.into());
+        let metadata = Metadata::try_from(&version).unwrap();
+        assert_eq!(metadata.app_version(), Version::ics20());
+        assert_eq!(Version::from(Metadata::new(&Version::ics20())), version);
+
+        assert!(Metadata::try_from(&Version::ics20()).is_err());
+
+        let version = Version::new(r#"{"fee_version":"ics29-2","app_version":"ics20-1"}"#.into());
+        assert!(Metadata::try_from(&version).is_err());
+    }
+}
@@ -0,0 +1,460 @@
+//! The ICS29 fee middleware, which wraps the application module bound to a port. On channels
+//! whose version has been negotiated with the fee version, it wraps the acknowledgements of the
+//! application and pays out the fees escrowed for a packet to the relayers once the packet gets
+//! acknowledged or times out, or refunds them if the channel gets closed first. On all other
+//! channels it passes the callbacks through to the application unchanged.
+use super::acknowledgement::{Acknowledgement, IncentivizedAcknowledgement};
+use super::context::Ics29Context;
+use super::error::{Error as Ics29Error, ErrorDetail as Ics29ErrorDetail};
+use super::events::DistributeFeeEvent;
+use super::fee::PacketFee;
+use super::metadata::Metadata;
+use crate::applications::transfer::BaseCoin;
+use crate::core::ics04_channel::channel::{Counterparty, Order};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::{Packet, PacketId};
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+use crate::core::ics26_routing::middleware::{Middleware, MiddlewareStack};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+fn to_channel_error(e: Ics29Error) -> Error {
+    Error::app_module(e.to_string())
+}
+
+/// Returns the fee metadata wrapped in `version`, or `None` if `version` is not a fee version
+/// and belongs to the underlying application as is.
+fn fee_metadata(version: &Version) -> Result<Option<Metadata>, Ics29Error> {
+    match Metadata::try_from(version) {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(e) => match e.detail() {
+            Ics29ErrorDetail::MetadataDeserialization(_) => Ok(None),
+            _ => Err(e),
+        },
+    }
+}
+
+/// Pays `fee` out of the escrow account to `receiver`, or to `refund_address` if `receiver` is
+/// not a valid account.
+fn distribute_fee<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    receiver: Option<Signer>,
+    fee: &[BaseCoin],
+    refund_address: &Signer,
+) -> Result<(), Ics29Error> {
+    if fee.is_empty() {
+        return Ok(());
+    }
+
+    let (receiver, account) =
+        match receiver.and_then(|receiver| Some((receiver.clone(), receiver.try_into().ok()?))) {
+            Some(receiver) => receiver,
+            None => (
+                refund_address.clone(),
+                refund_address
+                    .clone()
+                    .try_into()
+                    .map_err(|_| Ics29Error::parse_account_failure())?,
+            ),
+        };
+
+    let escrow_address = ctx.get_fee_escrow_address()?;
+    for coin in fee {
+        ctx.send_coins(&escrow_address, &account, coin)?;
+    }
+
+    output.emit(
+        DistributeFeeEvent {
+            receiver,
+            fee: fee.to_vec(),
+        }
+        .into(),
+    );
+
+    Ok(())
+}
+
+/// Pays the receive fees to the forward relayer and the acknowledgement fees to the reverse
+/// relayer, and refunds the timeout fees.
+pub fn distribute_packet_fees_on_acknowledgement<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    forward_relayer: Option<Signer>,
+    reverse_relayer: &Signer,
+    packet_fees: &[PacketFee],
+) -> Result<(), Ics29Error> {
+    for PacketFee {
+        fee,
+        refund_address,
+        ..
+    } in packet_fees
+    {
+        distribute_fee(
+            ctx,
+            output,
+            forward_relayer.clone(),
+            &fee.recv_fee,
+            refund_address,
+        )?;
+        distribute_fee(
+            ctx,
+            output,
+            Some(reverse_relayer.clone()),
+            &fee.ack_fee,
+            refund_address,
+        )?;
+        distribute_fee(ctx, output, None, &fee.timeout_fee, refund_address)?;
+    }
+    Ok(())
+}
+
+/// Pays the timeout fees to the timeout relayer, and refunds the receive and acknowledgement
+/// fees.
+pub fn distribute_packet_fees_on_timeout<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    timeout_relayer: &Signer,
+    packet_fees: &[PacketFee],
+) -> Result<(), Ics29Error> {
+    for PacketFee {
+        fee,
+        refund_address,
+        ..
+    } in packet_fees
+    {
+        distribute_fee(ctx, output, None, &fee.recv_fee, refund_address)?;
+        distribute_fee(ctx, output, None, &fee.ack_fee, refund_address)?;
+        distribute_fee(
+            ctx,
+            output,
+            Some(timeout_relayer.clone()),
+            &fee.timeout_fee,
+            refund_address,
+        )?;
+    }
+    Ok(())
+}
+
+/// Refunds all the fees escrowed for the packets sent on the channel `channel_id`, which is
+/// being closed, so that no fees remain locked in escrow.
+pub fn refund_fees_on_channel_closure<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<(), Ics29Error> {
+    for (packet_id, packet_fees) in ctx.get_identified_packet_fees_for_channel(port_id, channel_id)
+    {
+        for PacketFee {
+            fee,
+            refund_address,
+            ..
+        } in &packet_fees
+        {
+            distribute_fee(ctx, output, None, &fee.recv_fee, refund_address)?;
+            distribute_fee(ctx, output, None, &fee.ack_fee, refund_address)?;
+            distribute_fee(ctx, output, None, &fee.timeout_fee, refund_address)?;
+        }
+        ctx.delete_fees_in_escrow(&packet_id)?;
+    }
+    Ok(())
+}
+
+/// The fee middleware, to be stacked over an application module with a
+/// [`ModuleStackBuilder`](crate::core::ics26_routing::middleware::ModuleStackBuilder).
+pub struct FeeMiddleware<Ctx> {
+    ctx: Ctx,
+}
+
+impl<Ctx> FeeMiddleware<Ctx> {
+    pub fn new(ctx: Ctx) -> Self {
+        Self { ctx }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+}
+
+impl<Ctx: Ics29Context> FeeMiddleware<Ctx> {
+    fn payee(&self, relayer: &Signer, channel_id: &ChannelId) -> Signer {
+        self.ctx
+            .get_payee(relayer, channel_id)
+            .unwrap_or_else(|| relayer.clone())
+    }
+}
+
+impl<Ctx> Middleware for FeeMiddleware<Ctx>
+where
+    Ctx: 'static + Ics29Context + Send + Sync,
+{
+    fn on_chan_open_init(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(), Error> {
+        match fee_metadata(version).map_err(to_channel_error)? {
+            Some(metadata) => {
+                next.on_chan_open_init(
+                    output,
+                    order,
+                    connection_hops,
+                    port_id,
+                    channel_id,
+                    counterparty,
+                    &metadata.app_version(),
+                )?;
+                self.ctx
+                    .store_fee_enabled(port_id.clone(), *channel_id)
+                    .map_err(to_channel_error)
+            }
+            None => next.on_chan_open_init(
+                output,
+                order,
+                connection_hops,
+                port_id,
+                channel_id,
+                counterparty,
+                version,
+            ),
+        }
+    }
+
+    fn on_chan_open_try(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        match fee_metadata(counterparty_version).map_err(to_channel_error)? {
+            Some(metadata) => {
+                let app_version = match fee_metadata(version).map_err(to_channel_error)? {
+                    Some(metadata) => metadata.app_version(),
+                    None => version.clone(),
+                };
+                let app_version = next.on_chan_open_try(
+                    output,
+                    order,
+                    connection_hops,
+                    port_id,
+                    channel_id,
+                    counterparty,
+                    &app_version,
+                    &metadata.app_version(),
+                )?;
+                self.ctx
+                    .store_fee_enabled(port_id.clone(), *channel_id)
+                    .map_err(to_channel_error)?;
+                Ok(Metadata::new(&app_version).into())
+            }
+            None => next.on_chan_open_try(
+                output,
+                order,
+                connection_hops,
+                port_id,
+                channel_id,
+                counterparty,
+                version,
+                counterparty_version,
+            ),
+        }
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        if !self.ctx.is_fee_enabled(port_id, channel_id) {
+            return next.on_chan_open_ack(output, port_id, channel_id, counterparty_version);
+        }
+
+        let metadata = fee_metadata(counterparty_version)
+            .map_err(to_channel_error)?
+            .ok_or_else(|| {
+                to_channel_error(Ics29Error::invalid_version(
+                    counterparty_version.to_string(),
+                ))
+            })?;
+        next.on_chan_open_ack(output, port_id, channel_id, &metadata.app_version())
+    }
+
+    fn on_chan_open_confirm(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        next.on_chan_open_confirm(output, port_id, channel_id)
+    }
+
+    fn on_chan_close_init(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        next.on_chan_close_init(output, port_id, channel_id)?;
+        if !self.ctx.is_fee_enabled(port_id, channel_id) {
+            return Ok(());
+        }
+
+        refund_fees_on_channel_closure(&mut self.ctx, output, port_id, channel_id)
+            .map_err(to_channel_error)
+    }
+
+    fn on_chan_close_confirm(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        next.on_chan_close_confirm(output, port_id, channel_id)?;
+        if !self.ctx.is_fee_enabled(port_id, channel_id) {
+            return Ok(());
+        }
+
+        refund_fees_on_channel_closure(&mut self.ctx, output, port_id, channel_id)
+            .map_err(to_channel_error)
+    }
+
+    fn on_recv_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        let app_ack = next.on_recv_packet(output, packet, relayer);
+
+        if !self
+            .ctx
+            .is_fee_enabled(&packet.destination_port, &packet.destination_channel)
+        {
+            return MiddlewareStack::<Self>::forward_ack(app_ack);
+        }
+
+        let forward_relayer = self
+            .ctx
+            .get_counterparty_payee(relayer, &packet.destination_channel)
+            .unwrap_or_else(|| relayer.clone())
+            .to_string();
+
+        match app_ack {
+            OnRecvPacketAck::Nil(write_fn) => {
+                OnRecvPacketAck::Nil(MiddlewareStack::<Self>::forward_write_fn(write_fn))
+            }
+            OnRecvPacketAck::Successful(ack, write_fn) => OnRecvPacketAck::Successful(
+                Box::new(Acknowledgement::from(IncentivizedAcknowledgement::new(
+                    AsRef::<[u8]>::as_ref(&*ack).to_vec(),
+                    forward_relayer,
+                    true,
+                ))),
+                MiddlewareStack::<Self>::forward_write_fn(write_fn),
+            ),
+            OnRecvPacketAck::Failed(ack) => OnRecvPacketAck::Failed(Box::new(
+                Acknowledgement::from(IncentivizedAcknowledgement::new(
+                    AsRef::<[u8]>::as_ref(&*ack).to_vec(),
+                    forward_relayer,
+                    false,
+                )),
+            )),
+        }
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        if !self
+            .ctx
+            .is_fee_enabled(&packet.source_port, &packet.source_channel)
+        {
+            return next.on_acknowledgement_packet(output, packet, acknowledgement, relayer);
+        }
+
+        let ack =
+            IncentivizedAcknowledgement::try_from(acknowledgement).map_err(to_channel_error)?;
+
+        let packet_id = PacketId::source(packet);
+        let packet_fees = self.ctx.get_fees_in_escrow(&packet_id);
+        if !packet_fees.is_empty() {
+            let forward_relayer = ack.forward_relayer_address.parse().ok();
+            let reverse_relayer = self.payee(relayer, &packet.source_channel);
+            distribute_packet_fees_on_acknowledgement(
+                &mut self.ctx,
+                output,
+                forward_relayer,
+                &reverse_relayer,
+                &packet_fees,
+            )
+            .map_err(to_channel_error)?;
+            self.ctx
+                .delete_fees_in_escrow(&packet_id)
+                .map_err(to_channel_error)?;
+        }
+
+        next.on_acknowledgement_packet(output, packet, &ack.app_acknowledgement(), relayer)
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        if self
+            .ctx
+            .is_fee_enabled(&packet.source_port, &packet.source_channel)
+        {
+            let packet_id = PacketId::source(packet);
+            let packet_fees = self.ctx.get_fees_in_escrow(&packet_id);
+            if !packet_fees.is_empty() {
+                let timeout_relayer = self.payee(relayer, &packet.source_channel);
+                distribute_packet_fees_on_timeout(
+                    &mut self.ctx,
+                    output,
+                    &timeout_relayer,
+                    &packet_fees,
+                )
+                .map_err(to_channel_error)?;
+                self.ctx
+                    .delete_fees_in_escrow(&packet_id)
+                    .map_err(to_channel_error)?;
+            }
+        }
+
+        next.on_timeout_packet(output, packet, relayer)
+    }
+}
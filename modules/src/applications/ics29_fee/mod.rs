@@ -0,0 +1,552 @@
+//! ICS 29: Fee middleware. Wraps the application bound to a port, so that relayers get paid
+//! for relaying the packets of the application's channels out of fees escrowed by the senders
+//! of those packets.
+pub mod acknowledgement;
+pub mod context;
+pub mod error;
+pub mod events;
+pub mod fee;
+pub mod handler;
+pub mod metadata;
+pub mod middleware;
+pub mod msgs;
+
+/// Module identifier for the ICS29 middleware.
+pub const MODULE_ID_STR: &str = "feeibc";
+
+/// Name of the module account which holds the escrowed fees.
+pub const MODULE_ACCOUNT_NAME: &str = "feeibc";
+
+/// ICS29 middleware current version.
+pub const VERSION: &str = "ics29-1";
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::acknowledgement::IncentivizedAcknowledgement;
+    use super::context::{Ics29Keeper, Ics29Reader};
+    use super::fee::{Fee, PacketFee};
+    use super::handler::{pay_packet_fee, register_counterparty_payee, register_payee};
+    use super::metadata::Metadata;
+    use super::middleware::FeeMiddleware;
+    use super::msgs::pay_packet_fee::MsgPayPacketFee;
+    use super::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+    use super::msgs::register_payee::MsgRegisterPayee;
+    use crate::applications::transfer::BaseCoin;
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+    use crate::core::ics04_channel::error::Error;
+    use crate::core::ics04_channel::handler::send_packet::send_packet;
+    use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
+    use crate::core::ics04_channel::msgs::acknowledgement::{
+        Acknowledgement as GenericAcknowledgement, MsgAcknowledgement,
+    };
+    use crate::core::ics04_channel::msgs::chan_open_ack::test_util::get_dummy_raw_msg_chan_open_ack;
+    use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::core::ics04_channel::msgs::chan_open_try::test_util::get_dummy_raw_msg_chan_open_try;
+    use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+    use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
+    use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+    use crate::core::ics04_channel::packet::{Packet, PacketId};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{
+        Acknowledgement, Ics26Context, Module, ModuleId, ModuleOutputBuilder, OnRecvPacketAck,
+        Router, RouterBuilder,
+    };
+    use crate::core::ics26_routing::middleware::{MiddlewareStack, ModuleStackBuilder};
+    use crate::core::ics26_routing::msgs::Ics26Envelope;
+    use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::{MockContext, MockRouterBuilder};
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_account_id, DummyFeeContext};
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    const APP_ACK: &[u8] = b"app ack";
+
+    struct MockAck;
+
+    impl AsRef<[u8]> for MockAck {
+        fn as_ref(&self) -> &[u8] {
+            APP_ACK
+        }
+    }
+
+    impl Acknowledgement for MockAck {}
+
+    /// An application which records what it gets to see from under the fee middleware.
+    #[derive(Debug, Default)]
+    struct MockApp {
+        versions: Vec<Version>,
+        acks: Vec<Vec<u8>>,
+        received: usize,
+    }
+
+    impl Module for MockApp {
+        fn on_chan_open_init(
+            &mut self,
+            _output: &mut ModuleOutputBuilder,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            version: &Version,
+        ) -> Result<(), Error> {
+            self.versions.push(version.clone());
+            Ok(())
+        }
+
+        fn on_chan_open_try(
+            &mut self,
+            _output: &mut ModuleOutputBuilder,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            _version: &Version,
+            counterparty_version: &Version,
+        ) -> Result<Version, Error> {
+            self.versions.push(counterparty_version.clone());
+            Ok(counterparty_version.clone())
+        }
+
+        fn on_chan_open_ack(
+            &mut self,
+            _output: &mut ModuleOutputBuilder,
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            counterparty_version: &Version,
+        ) -> Result<(), Error> {
+            self.versions.push(counterparty_version.clone());
+            Ok(())
+        }
+
+        fn on_recv_packet(
+            &mut self,
+            _output: &mut ModuleOutputBuilder,
+            _packet: &Packet,
+            _relayer: &Signer,
+        ) -> OnRecvPacketAck {
+            OnRecvPacketAck::Successful(
+                Box::new(MockAck),
+                Box::new(|module| {
+                    let module = module.downcast_mut::<MockApp>().unwrap();
+                    module.received += 1;
+                    Ok(())
+                }),
+            )
+        }
+
+        fn on_acknowledgement_packet(
+            &mut self,
+            _output: &mut ModuleOutputBuilder,
+            _packet: &Packet,
+            acknowledgement: &GenericAcknowledgement,
+            _relayer: &Signer,
+        ) -> Result<(), Error> {
+            self.acks.push(acknowledgement.as_ref().to_vec());
+            Ok(())
+        }
+    }
+
+    type MockFeeModule = MiddlewareStack<FeeMiddleware<DummyFeeContext>>;
+
+    fn chain_with_module(
+        client_id: &ClientId,
+        client_height: Height,
+        connection_id: ConnectionId,
+        counterparty_connection_id: ConnectionId,
+        port_id: PortId,
+    ) -> MockContext {
+        let mut ctx = MockContext::default()
+            .with_client(client_id, client_height)
+            .with_open_connection(client_id, connection_id, counterparty_connection_id);
+
+        let module_id: ModuleId = "feetransfer".parse().unwrap();
+        let module = ModuleStackBuilder::new(MockApp::default())
+            .push(FeeMiddleware::new(DummyFeeContext::new(
+                ctx.ibc_store_share(),
+            )))
+            .build();
+        let router = MockRouterBuilder::default()
+            .add_route(module_id.clone(), module)
+            .unwrap()
+            .build();
+        ctx.scope_port_to_module(port_id, module_id);
+        ctx.with_router(router)
+    }
+
+    fn fee_module(ctx: &mut MockContext) -> &mut MockFeeModule {
+        ctx.router_mut()
+            .get_route_mut(&"feetransfer".parse::<ModuleId>().unwrap())
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<MockFeeModule>()
+            .unwrap()
+    }
+
+    fn app(module: &mut MockFeeModule) -> &mut MockApp {
+        module
+            .next_mut()
+            .as_any_mut()
+            .downcast_mut::<MockApp>()
+            .unwrap()
+    }
+
+    fn coins(amount: u64) -> Vec<BaseCoin> {
+        vec![BaseCoin {
+            denom: "stake".parse().unwrap(),
+            amount: amount.into(),
+        }]
+    }
+
+    #[test]
+    fn fee_middleware_handshake_and_fee_distribution() {
+        let proof_height = 10;
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let conn_a = ConnectionId::new(0);
+        let conn_b = ConnectionId::new(1);
+        let port_id: PortId = "transfer".parse().unwrap();
+        let chan_a = ChannelId::new(0);
+        let chan_b = ChannelId::new(0);
+
+        let mut ctx_a = chain_with_module(
+            &client_id,
+            Height::new(0, proof_height),
+            conn_a.clone(),
+            conn_b.clone(),
+            port_id.clone(),
+        );
+        let mut ctx_b = chain_with_module(
+            &client_id,
+            Height::new(0, proof_height),
+            conn_b.clone(),
+            conn_a.clone(),
+            port_id.clone(),
+        );
+
+        // The channel is opened with the fee version wrapping the application version, which
+        // is all the application gets to see.
+        let fee_version: Version = Metadata::new(&Version::ics20()).into();
+        ctx_a
+            .deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(
+                MsgChannelOpenInit::new(
+                    port_id.clone(),
+                    ChannelEnd::new(
+                        State::Init,
+                        Order::Unordered,
+                        Counterparty::new(port_id.clone(), None),
+                        vec![conn_a.clone()],
+                        fee_version.clone(),
+                    ),
+                    get_dummy_account_id(),
+                ),
+            )))
+            .unwrap();
+
+        let mut try_msg =
+            MsgChannelOpenTry::try_from(get_dummy_raw_msg_chan_open_try(proof_height)).unwrap();
+        try_msg.port_id = port_id.clone();
+        try_msg.previous_channel_id = None;
+        try_msg.channel = ChannelEnd::new(
+            State::TryOpen,
+            Order::Unordered,
+            Counterparty::new(port_id.clone(), Some(chan_a)),
+            vec![conn_b.clone()],
+            Version::empty(),
+        );
+        try_msg.counterparty_version = fee_version.clone();
+        ctx_b
+            .deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(
+                try_msg,
+            )))
+            .unwrap();
+        assert_eq!(
+            ctx_b
+                .channel_end(&(port_id.clone(), chan_b))
+                .unwrap()
+                .version(),
+            &fee_version
+        );
+
+        let mut ack_msg =
+            MsgChannelOpenAck::try_from(get_dummy_raw_msg_chan_open_ack(proof_height)).unwrap();
+        ack_msg.port_id = port_id.clone();
+        ack_msg.channel_id = chan_a;
+        ack_msg.counterparty_channel_id = chan_b;
+        ack_msg.counterparty_version = fee_version.clone();
+        ctx_a
+            .deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(
+                ack_msg,
+            )))
+            .unwrap();
+
+        let mut confirm_msg =
+            MsgChannelOpenConfirm::try_from(get_dummy_raw_msg_chan_open_confirm(proof_height))
+                .unwrap();
+        confirm_msg.port_id = port_id.clone();
+        confirm_msg.channel_id = chan_b;
+        ctx_b
+            .deliver(Ics26Envelope::Ics4ChannelMsg(
+                ChannelMsg::ChannelOpenConfirm(confirm_msg),
+            ))
+            .unwrap();
+
+        // The application sees the version on init and ack on chain A, and on try on chain B.
+        for (ctx, channel_id, app_versions) in [
+            (&mut ctx_a, chan_a, vec![Version::ics20(), Version::ics20()]),
+            (&mut ctx_b, chan_b, vec![Version::ics20()]),
+        ] {
+            let module = fee_module(ctx);
+            assert!(module
+                .middleware()
+                .ctx()
+                .is_fee_enabled(&port_id, &channel_id));
+            assert_eq!(app(module).versions, app_versions);
+        }
+
+        // The relayer registers its payees, and a user pays the fee of the next packet.
+        let relayer: Signer = get_dummy_account_id();
+        let payee: Signer = "payee".parse().unwrap();
+        let counterparty_payee: Signer = "counterpartypayee".parse().unwrap();
+        let payer: Signer = "payer".parse().unwrap();
+
+        let fee_ctx_a = fee_module(&mut ctx_a).middleware_mut().ctx_mut();
+        let mut output = HandlerOutputBuilder::new();
+        register_payee(
+            fee_ctx_a,
+            &mut output,
+            MsgRegisterPayee {
+                port_id: port_id.clone(),
+                channel_id: chan_a,
+                relayer: relayer.clone(),
+                payee: payee.clone(),
+            },
+        )
+        .unwrap();
+        pay_packet_fee(
+            fee_ctx_a,
+            &mut output,
+            MsgPayPacketFee {
+                fee: Fee {
+                    recv_fee: coins(10),
+                    ack_fee: coins(5),
+                    timeout_fee: coins(3),
+                },
+                source_port_id: port_id.clone(),
+                source_channel_id: chan_a,
+                signer: payer.clone(),
+                relayers: vec![],
+            },
+        )
+        .unwrap();
+        let escrow = fee_ctx_a.get_fee_escrow_address().unwrap();
+        assert_eq!(fee_ctx_a.transfers.len(), 3);
+        assert!(fee_ctx_a
+            .transfers
+            .iter()
+            .all(|(from, to, _)| from == &payer && to == &escrow));
+        fee_ctx_a.transfers.clear();
+
+        let packet = Packet {
+            sequence: 1.into(),
+            source_port: port_id.clone(),
+            source_channel: chan_a,
+            destination_port: port_id.clone(),
+            destination_channel: chan_b,
+            data: b"data".to_vec(),
+            timeout_height: Height::new(0, 100),
+            timeout_timestamp: Timestamp::none(),
+        };
+        let result = send_packet(fee_ctx_a, packet.clone()).unwrap().result;
+        fee_ctx_a.store_packet_result(result).unwrap();
+        assert_eq!(
+            fee_ctx_a
+                .get_fees_in_escrow(&PacketId::source(&packet))
+                .len(),
+            1
+        );
+
+        // The destination wraps the application acknowledgement with the counterparty payee.
+        let mut output = HandlerOutputBuilder::new();
+        register_counterparty_payee(
+            fee_module(&mut ctx_b).middleware_mut().ctx_mut(),
+            &mut output,
+            MsgRegisterCounterpartyPayee {
+                port_id: port_id.clone(),
+                channel_id: chan_b,
+                relayer: relayer.clone(),
+                counterparty_payee: counterparty_payee.clone(),
+            },
+        )
+        .unwrap();
+
+        let ack = match fee_module(&mut ctx_b).on_recv_packet(
+            &mut ModuleOutputBuilder::new(),
+            &packet,
+            &relayer,
+        ) {
+            OnRecvPacketAck::Successful(ack, _) => AsRef::<[u8]>::as_ref(&*ack).to_vec(),
+            _ => panic!("expected a successful acknowledgement"),
+        };
+        let incentivized_ack =
+            IncentivizedAcknowledgement::try_from(&GenericAcknowledgement::from(ack.clone()))
+                .unwrap();
+        assert_eq!(
+            incentivized_ack,
+            IncentivizedAcknowledgement::new(
+                APP_ACK.to_vec(),
+                counterparty_payee.to_string(),
+                true
+            )
+        );
+
+        let mut recv_msg =
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(proof_height)).unwrap();
+        recv_msg.packet = packet.clone();
+        recv_msg.signer = relayer.clone();
+        ctx_b
+            .deliver(Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(
+                recv_msg,
+            )))
+            .unwrap();
+        assert_eq!(app(fee_module(&mut ctx_b)).received, 1);
+
+        // On acknowledgement, the escrowed fees are paid out and the application gets the
+        // acknowledgement it wrote.
+        let mut packet_ack_msg =
+            MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(proof_height)).unwrap();
+        packet_ack_msg.packet = packet.clone();
+        packet_ack_msg.acknowledgement = ack.into();
+        packet_ack_msg.signer = relayer;
+        ctx_a
+            .deliver(Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(
+                packet_ack_msg,
+            )))
+            .unwrap();
+
+        let module = fee_module(&mut ctx_a);
+        assert_eq!(app(module).acks, vec![APP_ACK.to_vec()]);
+        assert_eq!(
+            module.middleware().ctx().transfers,
+            vec![
+                (escrow.clone(), counterparty_payee, coins(10).remove(0)),
+                (escrow.clone(), payee, coins(5).remove(0)),
+                (escrow, payer, coins(3).remove(0)),
+            ]
+        );
+        assert!(module
+            .middleware()
+            .ctx()
+            .get_fees_in_escrow(&PacketId::source(&packet))
+            .is_empty());
+    }
+
+    #[test]
+    fn fees_are_refunded_on_channel_close() {
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let port_id: PortId = "transfer".parse().unwrap();
+        let mut ctx = chain_with_module(
+            &client_id,
+            Height::new(0, 10),
+            ConnectionId::new(0),
+            ConnectionId::new(1),
+            port_id.clone(),
+        );
+
+        let payer: Signer = "payer".parse().unwrap();
+        let other_payer: Signer = "otherpayer".parse().unwrap();
+        let fee = Fee {
+            recv_fee: coins(10),
+            ack_fee: coins(5),
+            timeout_fee: coins(3),
+        };
+        let packet_id =
+            |channel_id, sequence: u64| PacketId::new(port_id.clone(), channel_id, sequence.into());
+
+        // Two channels have fees in escrow: the first one for two packets, the second one for
+        // a single packet.
+        let fee_ctx = fee_module(&mut ctx).middleware_mut().ctx_mut();
+        for channel_id in [ChannelId::new(0), ChannelId::new(1)] {
+            fee_ctx
+                .store_fee_enabled(port_id.clone(), channel_id)
+                .unwrap();
+        }
+        fee_ctx
+            .store_fees_in_escrow(
+                packet_id(ChannelId::new(0), 1),
+                vec![PacketFee::new(fee.clone(), payer.clone())],
+            )
+            .unwrap();
+        fee_ctx
+            .store_fees_in_escrow(
+                packet_id(ChannelId::new(0), 2),
+                vec![PacketFee::new(fee.clone(), other_payer.clone())],
+            )
+            .unwrap();
+        fee_ctx
+            .store_fees_in_escrow(
+                packet_id(ChannelId::new(1), 1),
+                vec![PacketFee::new(fee, payer.clone())],
+            )
+            .unwrap();
+        let escrow = fee_ctx.get_fee_escrow_address().unwrap();
+
+        // Closing the first channel refunds all the fees of its packets, and leaves those of the
+        // second channel in escrow.
+        let module = fee_module(&mut ctx);
+        module
+            .on_chan_close_init(
+                &mut ModuleOutputBuilder::new(),
+                &port_id,
+                &ChannelId::new(0),
+            )
+            .unwrap();
+        let refunds = |payer: &Signer| {
+            [10, 5, 3]
+                .into_iter()
+                .map(|amount| (escrow.clone(), payer.clone(), coins(amount).remove(0)))
+                .collect::<Vec<_>>()
+        };
+        let fee_ctx = module.middleware_mut().ctx_mut();
+        assert_eq!(
+            fee_ctx.transfers,
+            [refunds(&payer), refunds(&other_payer)].concat()
+        );
+        assert!(fee_ctx
+            .get_identified_packet_fees_for_channel(&port_id, &ChannelId::new(0))
+            .is_empty());
+        assert_eq!(
+            fee_ctx
+                .get_fees_in_escrow(&packet_id(ChannelId::new(1), 1))
+                .len(),
+            1
+        );
+
+        // The same goes for the counterparty closing the second channel.
+        fee_ctx.transfers.clear();
+        module
+            .on_chan_close_confirm(
+                &mut ModuleOutputBuilder::new(),
+                &port_id,
+                &ChannelId::new(1),
+            )
+            .unwrap();
+        let fee_ctx = module.middleware().ctx();
+        assert_eq!(fee_ctx.transfers, refunds(&payer));
+        assert!(fee_ctx
+            .get_identified_packet_fees_for_channel(&port_id, &ChannelId::new(1))
+            .is_empty());
+    }
+}
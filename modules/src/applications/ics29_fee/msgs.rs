@@ -0,0 +1,4 @@
+pub mod pay_packet_fee;
+pub mod pay_packet_fee_async;
+pub mod register_counterparty_payee;
+pub mod register_payee;
@@ -0,0 +1,105 @@
+//! Message paying the fees for the next packet sent on a channel.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::fee::v1::MsgPayPacketFee as RawMsgPayPacketFee;
+use tendermint_proto::Protobuf;
+
+use crate::applications::ics29_fee::error::Error;
+use crate::applications::ics29_fee::fee::Fee;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgPayPacketFee";
+
+/// Escrows a fee for the next packet sent on the channel. It is meant to be submitted in the same
+/// transaction as, and right before, the message sending the packet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgPayPacketFee {
+    pub fee: Fee,
+    pub source_port_id: PortId,
+    pub source_channel_id: ChannelId,
+    /// The account paying the fee, which is also refunded the fee not paid out to relayers.
+    pub signer: Signer,
+    /// The relayers permitted to receive the fee. Not supported yet and must be empty.
+    pub relayers: Vec<Signer>,
+}
+
+impl Msg for MsgPayPacketFee {
+    type ValidationError = Error;
+    type Raw = RawMsgPayPacketFee;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgPayPacketFee> for MsgPayPacketFee {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgPayPacketFee) -> Result<Self, Self::Error> {
+        Ok(MsgPayPacketFee {
+            fee: raw_msg.fee.ok_or_else(Error::missing_fee)?.try_into()?,
+            source_port_id: raw_msg
+                .source_port_id
+                .parse()
+                .map_err(Error::invalid_identifier)?,
+            source_channel_id: raw_msg
+                .source_channel_id
+                .parse()
+                .map_err(Error::invalid_identifier)?,
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+            relayers: raw_msg
+                .relayers
+                .into_iter()
+                .map(|relayer| relayer.parse().map_err(Error::signer))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<MsgPayPacketFee> for RawMsgPayPacketFee {
+    fn from(domain_msg: MsgPayPacketFee) -> Self {
+        RawMsgPayPacketFee {
+            fee: Some(domain_msg.fee.into()),
+            source_port_id: domain_msg.source_port_id.to_string(),
+            source_channel_id: domain_msg.source_channel_id.to_string(),
+            signer: domain_msg.signer.to_string(),
+            relayers: domain_msg
+                .relayers
+                .into_iter()
+                .map(|relayer| relayer.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Protobuf<RawMsgPayPacketFee> for MsgPayPacketFee {}
+
+impl TryFrom<Any> for MsgPayPacketFee {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => MsgPayPacketFee::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgPayPacketFee> for Any {
+    fn from(msg: MsgPayPacketFee) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgPayPacketFee`"),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Message paying the fees for a packet which has already been sent.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::fee::v1::MsgPayPacketFeeAsync as RawMsgPayPacketFeeAsync;
+use tendermint_proto::Protobuf;
+
+use crate::applications::ics29_fee::error::Error;
+use crate::applications::ics29_fee::fee::PacketFee;
+use crate::core::ics04_channel::packet::PacketId;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgPayPacketFeeAsync";
+
+/// Escrows a fee for a packet that has been sent, but not relayed yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgPayPacketFeeAsync {
+    pub packet_id: PacketId,
+    pub packet_fee: PacketFee,
+}
+
+impl Msg for MsgPayPacketFeeAsync {
+    type ValidationError = Error;
+    type Raw = RawMsgPayPacketFeeAsync;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgPayPacketFeeAsync> for MsgPayPacketFeeAsync {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgPayPacketFeeAsync) -> Result<Self, Self::Error> {
+        Ok(MsgPayPacketFeeAsync {
+            packet_id: raw_msg
+                .packet_id
+                .ok_or_else(Error::missing_packet_id)?
+                .try_into()
+                .map_err(Error::ics04_channel)?,
+            packet_fee: raw_msg
+                .packet_fee
+                .ok_or_else(Error::missing_packet_fee)?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<MsgPayPacketFeeAsync> for RawMsgPayPacketFeeAsync {
+    fn from(domain_msg: MsgPayPacketFeeAsync) -> Self {
+        RawMsgPayPacketFeeAsync {
+            packet_id: Some(domain_msg.packet_id.into()),
+            packet_fee: Some(domain_msg.packet_fee.into()),
+        }
+    }
+}
+
+impl Protobuf<RawMsgPayPacketFeeAsync> for MsgPayPacketFeeAsync {}
+
+impl TryFrom<Any> for MsgPayPacketFeeAsync {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => MsgPayPacketFeeAsync::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgPayPacketFeeAsync> for Any {
+    fn from(msg: MsgPayPacketFeeAsync) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgPayPacketFeeAsync`"),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Message registering the payee of the receive fees earned by a relayer on the counterparty
+//! chain.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::fee::v1::MsgRegisterCounterpartyPayee as RawMsgRegisterCounterpartyPayee;
+use tendermint_proto::Protobuf;
+
+use crate::applications::ics29_fee::error::Error;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgRegisterCounterpartyPayee";
+
+/// Registers the account on the counterparty chain which gets paid the receive fees earned by the
+/// relayer on a channel. It is submitted on the destination chain of the packets, which writes
+/// the counterparty payee into the acknowledgement of each packet received from the relayer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgRegisterCounterpartyPayee {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub relayer: Signer,
+    pub counterparty_payee: Signer,
+}
+
+impl Msg for MsgRegisterCounterpartyPayee {
+    type ValidationError = Error;
+    type Raw = RawMsgRegisterCounterpartyPayee;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgRegisterCounterpartyPayee> for MsgRegisterCounterpartyPayee {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgRegisterCounterpartyPayee) -> Result<Self, Self::Error> {
+        Ok(MsgRegisterCounterpartyPayee {
+            port_id: raw_msg.port_id.parse().map_err(Error::invalid_identifier)?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(Error::invalid_identifier)?,
+            relayer: raw_msg.relayer.parse().map_err(Error::signer)?,
+            counterparty_payee: raw_msg.counterparty_payee.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgRegisterCounterpartyPayee> for RawMsgRegisterCounterpartyPayee {
+    fn from(domain_msg: MsgRegisterCounterpartyPayee) -> Self {
+        RawMsgRegisterCounterpartyPayee {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            relayer: domain_msg.relayer.to_string(),
+            counterparty_payee: domain_msg.counterparty_payee.to_string(),
+        }
+    }
+}
+
+impl Protobuf<RawMsgRegisterCounterpartyPayee> for MsgRegisterCounterpartyPayee {}
+
+impl TryFrom<Any> for MsgRegisterCounterpartyPayee {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => {
+                MsgRegisterCounterpartyPayee::decode_vec(&raw.value).map_err(Error::decode_raw_msg)
+            }
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgRegisterCounterpartyPayee> for Any {
+    fn from(msg: MsgRegisterCounterpartyPayee) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgRegisterCounterpartyPayee`"),
+        }
+    }
+}
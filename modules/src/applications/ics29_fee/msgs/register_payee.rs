@@ -0,0 +1,88 @@
+//! Message registering the payee of the fees earned by a relayer on the source chain.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::fee::v1::MsgRegisterPayee as RawMsgRegisterPayee;
+use tendermint_proto::Protobuf;
+
+use crate::applications::ics29_fee::error::Error;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgRegisterPayee";
+
+/// Registers the account which gets paid the acknowledgement and timeout fees earned by the
+/// relayer on a channel, instead of the relayer itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgRegisterPayee {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub relayer: Signer,
+    pub payee: Signer,
+}
+
+impl Msg for MsgRegisterPayee {
+    type ValidationError = Error;
+    type Raw = RawMsgRegisterPayee;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgRegisterPayee> for MsgRegisterPayee {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgRegisterPayee) -> Result<Self, Self::Error> {
+        Ok(MsgRegisterPayee {
+            port_id: raw_msg.port_id.parse().map_err(Error::invalid_identifier)?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(Error::invalid_identifier)?,
+            relayer: raw_msg.relayer.parse().map_err(Error::signer)?,
+            payee: raw_msg.payee.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgRegisterPayee> for RawMsgRegisterPayee {
+    fn from(domain_msg: MsgRegisterPayee) -> Self {
+        RawMsgRegisterPayee {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            relayer: domain_msg.relayer.to_string(),
+            payee: domain_msg.payee.to_string(),
+        }
+    }
+}
+
+impl Protobuf<RawMsgRegisterPayee> for MsgRegisterPayee {}
+
+impl TryFrom<Any> for MsgRegisterPayee {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => MsgRegisterPayee::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgRegisterPayee> for Any {
+    fn from(msg: MsgRegisterPayee) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgRegisterPayee`"),
+        }
+    }
+}
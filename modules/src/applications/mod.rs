@@ -1,3 +1,7 @@
 //! Various packet encoding semantics which underpin the various types of transactions.
 
+pub mod ics27_interchain_accounts;
+pub mod ics29_fee;
+pub mod packet_forward;
+pub mod rate_limit;
 pub mod transfer;
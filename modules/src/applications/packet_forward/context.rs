@@ -0,0 +1,52 @@
+use core::time::Duration;
+
+use super::error::Error;
+use crate::applications::transfer::context::{Ics20Context, Ics20Keeper, Ics20Reader};
+use crate::core::ics04_channel::packet::{Packet, PacketId};
+use crate::events::IbcEvent;
+use crate::signer::Signer;
+
+/// The timeout of forwarded packets, relative to the timestamp of the host when they are sent.
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// A packet received with forwarding instructions, whose acknowledgement is held until the
+/// packet it has been forwarded as gets acknowledged or times out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InFlightPacket {
+    /// The received packet, which gets acknowledged once the forwarded packet resolves.
+    pub original_packet: Packet,
+    /// The account on this chain which received the tokens before they got forwarded.
+    pub intermediate_receiver: Signer,
+}
+
+pub trait PacketForwardKeeper: Ics20Keeper {
+    /// Records the packet `in_flight` as forwarded as the packet `packet_id`.
+    fn store_in_flight_packet(
+        &mut self,
+        packet_id: PacketId,
+        in_flight: InFlightPacket,
+    ) -> Result<(), Error>;
+
+    /// Removes the in-flight packet forwarded as the packet `packet_id`, once the latter got
+    /// acknowledged or timed out.
+    fn delete_in_flight_packet(&mut self, packet_id: &PacketId) -> Result<(), Error>;
+
+    /// Emits an event of the IBC core. The forwarded packets get sent, and the held
+    /// acknowledgements get written, outside of the module callbacks, so their `SendPacket` and
+    /// `WriteAcknowledgement` events are handed over to the host instead.
+    fn emit_ibc_event(&mut self, event: IbcEvent);
+}
+
+pub trait PacketForwardReader: Ics20Reader {
+    /// Returns the in-flight packet forwarded as the packet `packet_id`, if any.
+    fn get_in_flight_packet(&self, packet_id: &PacketId) -> Option<InFlightPacket>;
+
+    /// Returns the timeout of forwarded packets, relative to the timestamp of the host.
+    fn forward_timeout(&self) -> Duration {
+        DEFAULT_FORWARD_TIMEOUT
+    }
+}
+
+/// Captures all the dependencies which the packet-forward middleware requires on top of the
+/// ones of the ICS20 module it is stacked over.
+pub trait PacketForwardContext: Ics20Context + PacketForwardKeeper + PacketForwardReader {}
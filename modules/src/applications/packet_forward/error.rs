@@ -0,0 +1,50 @@
+use flex_error::define_error;
+
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::core::ics04_channel::error as channel_error;
+use crate::core::ics04_channel::packet::PacketId;
+use crate::core::ics24_host::error::ValidationError;
+use crate::prelude::*;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics04Channel
+            [ channel_error::Error ]
+            | _ | { "Ics04 channel error" },
+
+        Ics20
+            [ Ics20Error ]
+            | _ | { "Ics20 transfer error" },
+
+        InvalidIdentifier
+            [ ValidationError ]
+            | _ | { "invalid identifier in forwarding instructions" },
+
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer in forwarding instructions" },
+
+        InvalidReceiver
+            { receiver: String }
+            | e | {
+                format_args!("receiver `{}` is not of the form `<intermediate>|<port>/<channel>:<receiver>`",
+                    e.receiver)
+            },
+
+        MemoDeserialization
+            { reason: String }
+            | e | { format_args!("failed to deserialize the forwarding instructions of the memo: {}", e.reason) },
+
+        ParseAccountFailure
+            | _ | { "failed to parse as AccountId" },
+
+        TimeoutOverflow
+            | _ | { "timeout of the forwarded packet overflows" },
+
+        ForwardTimeout
+            { packet_id: PacketId }
+            | e | { format_args!("forwarded packet {} timed out", e.packet_id) },
+    }
+}
@@ -0,0 +1,173 @@
+//! The forwarding instructions of an ICS20 packet, which are parsed either from the memo or from
+//! the receiver of the packet data.
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::Error;
+use crate::applications::transfer::packet::PacketDataV2;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// The JSON encoding of the forwarding instructions in the memo of a packet, e.g.
+/// `{"forward":{"receiver":"cosmos1...","port":"transfer","channel":"channel-1"}}`.
+///
+/// The `next` field, if any, becomes the memo of the forwarded packet, which allows the packet
+/// to be forwarded once more by the next chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct JsonForwardMetadata {
+    receiver: String,
+    port: String,
+    channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct JsonMemo {
+    forward: Option<JsonForwardMetadata>,
+}
+
+/// Instructions to forward the tokens of a received packet to `receiver` over the channel
+/// `channel` of the port `port`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForwardMetadata {
+    /// The account on this chain which receives the tokens before they are forwarded.
+    pub intermediate_receiver: Signer,
+    /// The receiver of the forwarded tokens on the next chain.
+    pub receiver: Signer,
+    pub port: PortId,
+    pub channel: ChannelId,
+    /// The memo of the forwarded packet.
+    pub next: String,
+}
+
+impl ForwardMetadata {
+    /// Parses the forwarding instructions of a packet, if it has any. The memo takes precedence
+    /// over the receiver, which may be of the form `<intermediate>|<port>/<channel>:<receiver>`.
+    pub fn from_packet_data(data: &PacketDataV2) -> Result<Option<Self>, Error> {
+        match Self::from_memo(&data.memo, &data.receiver)? {
+            Some(metadata) => Ok(Some(metadata)),
+            None => Self::from_receiver(data.receiver.as_ref()),
+        }
+    }
+
+    fn from_memo(memo: &str, receiver: &Signer) -> Result<Option<Self>, Error> {
+        if memo.trim().is_empty() {
+            return Ok(None);
+        }
+
+        // Memos which are not JSON objects are left to the other modules.
+        let memo = match serde_json::from_str::<JsonMemo>(memo) {
+            Ok(memo) => memo,
+            Err(_) => return Ok(None),
+        };
+
+        let forward = match memo.forward {
+            Some(forward) => forward,
+            None => return Ok(None),
+        };
+
+        let next = match forward.next {
+            Some(next) => serde_json::to_string(&next)
+                .map_err(|e| Error::memo_deserialization(e.to_string()))?,
+            None => String::new(),
+        };
+
+        Ok(Some(Self {
+            intermediate_receiver: receiver.clone(),
+            receiver: forward.receiver.parse().map_err(Error::signer)?,
+            port: PortId::from_str(&forward.port).map_err(Error::invalid_identifier)?,
+            channel: ChannelId::from_str(&forward.channel).map_err(Error::invalid_identifier)?,
+            next,
+        }))
+    }
+
+    fn from_receiver(receiver: &str) -> Result<Option<Self>, Error> {
+        let (intermediate_receiver, forward) = match receiver.split_once('|') {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+
+        let (path, receiver_on_next) = forward
+            .split_once(':')
+            .ok_or_else(|| Error::invalid_receiver(receiver.to_string()))?;
+        let (port, channel) = path
+            .split_once('/')
+            .ok_or_else(|| Error::invalid_receiver(receiver.to_string()))?;
+
+        Ok(Some(Self {
+            intermediate_receiver: intermediate_receiver.parse().map_err(Error::signer)?,
+            receiver: receiver_on_next.parse().map_err(Error::signer)?,
+            port: PortId::from_str(port).map_err(Error::invalid_identifier)?,
+            channel: ChannelId::from_str(channel).map_err(Error::invalid_identifier)?,
+            next: String::new(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::PrefixedCoin;
+    use crate::test_utils::get_dummy_account_id;
+
+    fn packet_data(receiver: &str, memo: &str) -> PacketDataV2 {
+        PacketData {
+            token: PrefixedCoin {
+                denom: "uatom".parse().unwrap(),
+                amount: 10u64.into(),
+            },
+            sender: get_dummy_account_id(),
+            receiver: receiver.parse().unwrap(),
+            memo: memo.to_string(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn forwarding_instructions() {
+        let metadata = ForwardMetadata::from_packet_data(&packet_data(
+            "intermediate|transfer/channel-1:receiver",
+            "",
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(metadata.intermediate_receiver.as_ref(), "intermediate");
+        assert_eq!(metadata.receiver.as_ref(), "receiver");
+        assert_eq!(metadata.port, PortId::transfer());
+        assert_eq!(metadata.channel, ChannelId::new(1));
+        assert!(metadata.next.is_empty());
+
+        let memo = r#"{"forward":{"receiver":"receiver","port":"transfer","channel":"channel-1","next":{"forward":{"receiver":"last","port":"transfer","channel":"channel-7"}}}}"#;
+        let metadata = ForwardMetadata::from_packet_data(&packet_data("intermediate", memo))
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.intermediate_receiver.as_ref(), "intermediate");
+        assert_eq!(metadata.receiver.as_ref(), "receiver");
+        assert_eq!(metadata.channel, ChannelId::new(1));
+        let next = ForwardMetadata::from_packet_data(&packet_data("receiver", &metadata.next))
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.receiver.as_ref(), "last");
+        assert_eq!(next.channel, ChannelId::new(7));
+
+        // Packets without forwarding instructions are left alone.
+        assert_eq!(
+            ForwardMetadata::from_packet_data(&packet_data("receiver", "")).unwrap(),
+            None
+        );
+        assert_eq!(
+            ForwardMetadata::from_packet_data(&packet_data("receiver", "some note")).unwrap(),
+            None
+        );
+
+        assert!(ForwardMetadata::from_packet_data(&packet_data(
+            "intermediate|transfer:receiver",
+            ""
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! The packet-forward middleware, which is stacked over the ICS20 module. It credits the tokens
+//! of a received packet with forwarding instructions to the intermediate receiver on this chain,
+//! then sends them on to the next chain as a new transfer. The acknowledgement of the received
+//! packet is held until the forwarded packet gets acknowledged or times out, and relays its
+//! outcome back.
+use super::context::{InFlightPacket, PacketForwardContext};
+use super::error::Error as PacketForwardError;
+use super::metadata::ForwardMetadata;
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::context::decode_packet_data;
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::applications::transfer::packet::PacketDataV2;
+use crate::applications::transfer::relay::send_transfer::send_transfer;
+use crate::applications::transfer::{is_receiver_chain_source, PrefixedCoin, TracePrefix};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::handler::write_acknowledgement;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::{Packet, PacketId};
+use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+use crate::core::ics26_routing::middleware::{Middleware, MiddlewareStack};
+use crate::handler::{HandlerOutput, HandlerOutputBuilder};
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::Height;
+
+fn to_channel_error(e: PacketForwardError) -> Error {
+    Error::app_module(e.to_string())
+}
+
+/// Returns the token `coin` of a packet as credited on this chain upon receiving `packet`, and
+/// whether it was unescrowed rather than minted as vouchers.
+fn received_coin(packet: &Packet, coin: &PrefixedCoin) -> (PrefixedCoin, bool) {
+    let mut coin = coin.clone();
+    if is_receiver_chain_source(
+        packet.source_port.clone(),
+        packet.source_channel,
+        &coin.denom,
+    ) {
+        let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
+        coin.denom.remove_trace_prefix(&prefix);
+        (coin, true)
+    } else {
+        let prefix = TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
+        coin.denom.add_trace_prefix(prefix);
+        (coin, false)
+    }
+}
+
+/// The packet-forward middleware, to be stacked over the ICS20 module with a
+/// [`ModuleStackBuilder`](crate::core::ics26_routing::middleware::ModuleStackBuilder).
+pub struct PacketForwardMiddleware<Ctx> {
+    ctx: Ctx,
+}
+
+impl<Ctx> PacketForwardMiddleware<Ctx> {
+    pub fn new(ctx: Ctx) -> Self {
+        Self { ctx }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+}
+
+impl<Ctx: PacketForwardContext> PacketForwardMiddleware<Ctx> {
+    /// Sends the tokens credited to the intermediate receiver upon receiving `packet` on to the
+    /// next chain, and holds the acknowledgement of `packet` until the forwarded packet resolves.
+    fn forward_packet(
+        &mut self,
+        packet: Packet,
+        data: &PacketDataV2,
+        metadata: ForwardMetadata,
+    ) -> Result<(), PacketForwardError> {
+        let tokens = data
+            .tokens
+            .iter()
+            .map(|coin| received_coin(&packet, coin).0)
+            .collect::<Vec<_>>();
+
+        let sequence = self
+            .ctx
+            .get_next_sequence_send(&(metadata.port.clone(), metadata.channel))
+            .map_err(PacketForwardError::ics04_channel)?;
+        let timeout_timestamp = (self.ctx.host_timestamp() + self.ctx.forward_timeout())
+            .map_err(|_| PacketForwardError::timeout_overflow())?;
+
+        let msg = MsgTransfer {
+            source_port: metadata.port.clone(),
+            source_channel: metadata.channel,
+            token: tokens,
+            sender: metadata.intermediate_receiver.clone(),
+            receiver: metadata.receiver,
+            timeout_height: Height::zero(),
+            timeout_timestamp,
+            memo: metadata.next,
+        };
+
+        let mut output = HandlerOutputBuilder::new();
+        send_transfer(&mut self.ctx, &mut output, msg).map_err(PacketForwardError::ics20)?;
+        for event in output.with_result(()).events {
+            self.ctx.emit_ibc_event(event);
+        }
+
+        self.ctx.store_in_flight_packet(
+            PacketId::new(metadata.port, metadata.channel, sequence),
+            InFlightPacket {
+                original_packet: packet,
+                intermediate_receiver: metadata.intermediate_receiver,
+            },
+        )
+    }
+
+    /// Takes back the tokens credited to the intermediate receiver upon receiving the original
+    /// packet of `in_flight`, so that they get refunded on the previous chain when it receives
+    /// the error acknowledgement of that packet. Each chain along the `TracePath` of the tokens
+    /// does the same with the packet it forwarded, until the refund reaches the sender.
+    fn revert_receive(&mut self, in_flight: &InFlightPacket) -> Result<(), Ics20Error> {
+        let packet = &in_flight.original_packet;
+        let data = decode_packet_data(
+            &self.ctx,
+            &packet.destination_port,
+            packet.destination_channel,
+            &packet.data,
+        )?;
+
+        let account = in_flight
+            .intermediate_receiver
+            .clone()
+            .try_into()
+            .map_err(|_| Ics20Error::parse_account_failure())?;
+
+        for coin in &data.tokens {
+            let (coin, unescrowed) = received_coin(packet, coin);
+            if unescrowed {
+                let escrow_address = self.ctx.get_channel_escrow_address(
+                    &packet.destination_port,
+                    packet.destination_channel,
+                )?;
+                self.ctx.send_coins(&account, &escrow_address, &coin)?;
+            } else {
+                self.ctx.burn_coins(&account, &coin)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the held acknowledgement of `packet`.
+    fn write_acknowledgement(
+        &mut self,
+        packet: Packet,
+        ack: &Acknowledgement,
+    ) -> Result<(), Error> {
+        let ack =
+            serde_json::to_vec(ack).expect("Acknowledgement's infallible Serialize impl failed");
+
+        let HandlerOutput { result, events, .. } =
+            write_acknowledgement::process(&self.ctx, packet, ack)?;
+        self.ctx.store_packet_result(result)?;
+        for event in events {
+            self.ctx.emit_ibc_event(event);
+        }
+
+        Ok(())
+    }
+
+    /// Relays the outcome `ack` of the forwarded packet `packet_id` back to the previous chain.
+    fn resolve_in_flight_packet(
+        &mut self,
+        packet_id: &PacketId,
+        ack: Acknowledgement,
+    ) -> Result<(), Error> {
+        let in_flight = match self.ctx.get_in_flight_packet(packet_id) {
+            Some(in_flight) => in_flight,
+            None => return Ok(()),
+        };
+        self.ctx
+            .delete_in_flight_packet(packet_id)
+            .map_err(to_channel_error)?;
+
+        if matches!(ack, Acknowledgement::Error(_)) {
+            self.revert_receive(&in_flight)
+                .map_err(|e| to_channel_error(PacketForwardError::ics20(e)))?;
+        }
+
+        self.write_acknowledgement(in_flight.original_packet, &ack)
+    }
+}
+
+impl<Ctx> Middleware for PacketForwardMiddleware<Ctx>
+where
+    Ctx: 'static + PacketForwardContext + Send + Sync,
+{
+    /// Packets without forwarding instructions are passed through to the ICS20 module. The
+    /// others are received by the intermediate receiver instead, and their acknowledgement is
+    /// held. If forwarding the tokens fails, the whole `MsgRecvPacket` fails.
+    fn on_recv_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        let version = self
+            .ctx
+            .channel_end(&(packet.destination_port.clone(), packet.destination_channel))
+            .map(|channel_end| channel_end.version().clone())
+            .ok();
+        let data = version
+            .as_ref()
+            .and_then(|version| PacketDataV2::decode(version, &packet.data).ok());
+        let (version, data) = match (version, data) {
+            (Some(version), Some(data)) => (version, data),
+            // Packets which aren't transfers are left to the ICS20 module to reject.
+            _ => {
+                return MiddlewareStack::<Self>::forward_ack(
+                    next.on_recv_packet(output, packet, relayer),
+                )
+            }
+        };
+
+        let metadata = match ForwardMetadata::from_packet_data(&data) {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => {
+                return MiddlewareStack::<Self>::forward_ack(
+                    next.on_recv_packet(output, packet, relayer),
+                )
+            }
+            Err(e) => return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
+        };
+
+        if let Err(e) = self
+            .ctx
+            .channel_end(&(metadata.port.clone(), metadata.channel))
+        {
+            return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(
+                PacketForwardError::ics04_channel(e),
+            )));
+        }
+
+        // The tokens are credited to the intermediate receiver, and the forwarding instructions
+        // are not passed on.
+        let data = PacketDataV2 {
+            receiver: metadata.intermediate_receiver.clone(),
+            memo: String::new(),
+            ..data
+        };
+        let received = match data.clone().encode(&version) {
+            Ok(data) => Packet {
+                data,
+                ..packet.clone()
+            },
+            Err(e) => {
+                return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(
+                    PacketForwardError::ics20(e),
+                )))
+            }
+        };
+
+        let credit = match next.on_recv_packet(output, &received, relayer) {
+            OnRecvPacketAck::Nil(write_fn) | OnRecvPacketAck::Successful(_, write_fn) => {
+                MiddlewareStack::<Self>::forward_write_fn(write_fn)
+            }
+            OnRecvPacketAck::Failed(ack) => return OnRecvPacketAck::Failed(ack),
+        };
+
+        let packet = packet.clone();
+        OnRecvPacketAck::Nil(Box::new(move |module| {
+            credit(module)?;
+            let stack = module
+                .downcast_mut::<MiddlewareStack<Self>>()
+                .ok_or_else(|| "write function called with a foreign module".to_string())?;
+            stack
+                .middleware_mut()
+                .forward_packet(packet, &data, metadata)
+                .map_err(|e| e.to_string())
+        }))
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        next.on_acknowledgement_packet(output, packet, acknowledgement, relayer)?;
+
+        let ack =
+            serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref()).map_err(|_| {
+                to_channel_error(PacketForwardError::ics20(Ics20Error::ack_deserialization()))
+            })?;
+        self.resolve_in_flight_packet(&PacketId::source(packet), ack)
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        next.on_timeout_packet(output, packet, relayer)?;
+
+        let packet_id = PacketId::source(packet);
+        let ack =
+            Acknowledgement::from_error(PacketForwardError::forward_timeout(packet_id.clone()));
+        self.resolve_in_flight_packet(&packet_id, ack)
+    }
+}
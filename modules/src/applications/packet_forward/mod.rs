@@ -0,0 +1,230 @@
+//! Packet-forward middleware. Stacked over the ICS20 module, it lets a single transfer hop over
+//! several chains: the tokens of a received packet with forwarding instructions, given either in
+//! its memo or in its receiver, are sent on to the next chain, and the acknowledgement of the
+//! received packet is held until the forwarded packet resolves.
+pub mod context;
+pub mod error;
+pub mod metadata;
+pub mod middleware;
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::context::{PacketForwardReader, DEFAULT_FORWARD_TIMEOUT};
+    use super::middleware::PacketForwardMiddleware;
+    use crate::applications::transfer::acknowledgement::Acknowledgement;
+    use crate::applications::transfer::context::Ics20Reader;
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::{PrefixedCoin, PrefixedDenom};
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+    use crate::core::ics04_channel::packet::{Packet, PacketId};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+    use crate::core::ics26_routing::middleware::{MiddlewareStack, ModuleStackBuilder};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_account_id, DummyBankOp, DummyForwardContext};
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    type ForwardStack = MiddlewareStack<PacketForwardMiddleware<DummyForwardContext>>;
+
+    fn incoming_packet(sequence: u64, memo: &str) -> Packet {
+        let data = PacketData {
+            token: PrefixedCoin {
+                denom: "uatom".parse().unwrap(),
+                amount: 10u64.into(),
+            },
+            sender: get_dummy_account_id(),
+            receiver: "intermediate".parse().unwrap(),
+            memo: memo.to_string(),
+        };
+        Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: serde_json::to_vec(&data).unwrap(),
+            timeout_height: Height::new(0, 100),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    fn receive(stack: &mut ForwardStack, packet: &Packet) {
+        let relayer = get_dummy_account_id();
+        match stack.on_recv_packet(&mut ModuleOutputBuilder::new(), packet, &relayer) {
+            OnRecvPacketAck::Nil(write_fn) => write_fn(stack).unwrap(),
+            _ => panic!("expected the acknowledgement to be held"),
+        }
+    }
+
+    fn sent_packet(events: &[IbcEvent]) -> Packet {
+        events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(event) => Some(event.packet.clone()),
+                _ => None,
+            })
+            .expect("expected the packet to be forwarded")
+    }
+
+    fn written_ack(events: &[IbcEvent]) -> (Packet, Acknowledgement) {
+        events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::WriteAcknowledgement(event) => Some((
+                    event.packet.clone(),
+                    serde_json::from_slice(&event.ack).unwrap(),
+                )),
+                _ => None,
+            })
+            .expect("expected an acknowledgement to be written")
+    }
+
+    /// Chain B of a transfer from A over `channel-0` forwarded to C over `channel-1`.
+    #[test]
+    fn forward_packet_and_relay_acknowledgements() {
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let conn_id = ConnectionId::new(0);
+        let from_a = ChannelId::new(0);
+        let to_c = ChannelId::new(1);
+
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 10))
+            .with_open_connection(&client_id, conn_id.clone(), ConnectionId::default())
+            .with_open_channel(
+                PortId::transfer(),
+                from_a,
+                conn_id.clone(),
+                ChannelId::new(0),
+                Version::ics20(),
+            )
+            .with_open_channel(
+                PortId::transfer(),
+                to_c,
+                conn_id,
+                ChannelId::new(0),
+                Version::ics20(),
+            );
+
+        let transfer = DummyForwardContext::new(ctx.ibc_store_share());
+        let mut stack = ModuleStackBuilder::new(transfer.clone())
+            .push(PacketForwardMiddleware::new(transfer.clone()))
+            .build();
+
+        let intermediate: Signer = "intermediate".parse().unwrap();
+        let escrow = transfer
+            .get_channel_escrow_address(&PortId::transfer(), to_c)
+            .unwrap();
+        let voucher = PrefixedCoin {
+            denom: "transfer/channel-0/uatom".parse::<PrefixedDenom>().unwrap(),
+            amount: 10u64.into(),
+        };
+        let memo = r#"{"forward":{"receiver":"receiver","port":"transfer","channel":"channel-1","next":"onward"}}"#;
+        let relayer = get_dummy_account_id();
+
+        // The vouchers minted to the intermediate receiver are escrowed for the next hop.
+        let original = incoming_packet(1, memo);
+        receive(&mut stack, &original);
+        assert_eq!(
+            transfer.take_bank_ops(),
+            vec![
+                DummyBankOp::Mint {
+                    account: intermediate.clone(),
+                    coin: voucher.clone(),
+                },
+                DummyBankOp::Send {
+                    from: intermediate.clone(),
+                    to: escrow.clone(),
+                    coin: voucher.clone(),
+                },
+            ]
+        );
+
+        let events = transfer.take_events();
+        let forwarded = sent_packet(&events);
+        assert_eq!(forwarded.source_channel, to_c);
+        assert_eq!(forwarded.sequence, 1.into());
+        assert!(forwarded.timeout_height.is_zero());
+        let data = serde_json::from_slice::<PacketData>(&forwarded.data).unwrap();
+        assert_eq!(data.token, voucher);
+        assert_eq!(data.sender, intermediate);
+        assert_eq!(data.receiver.as_ref(), "receiver");
+        assert_eq!(data.memo, "\"onward\"");
+        assert!(events
+            .iter()
+            .all(|event| !matches!(event, IbcEvent::WriteAcknowledgement(_))));
+
+        let forwarded_id = PacketId::source(&forwarded);
+        assert_eq!(
+            transfer
+                .get_in_flight_packet(&forwarded_id)
+                .unwrap()
+                .original_packet,
+            original
+        );
+        assert_eq!(transfer.forward_timeout(), DEFAULT_FORWARD_TIMEOUT);
+
+        // On timeout, the refund of the intermediate receiver is taken back, and the original
+        // packet fails so that A refunds the sender.
+        stack
+            .on_timeout_packet(&mut ModuleOutputBuilder::new(), &forwarded, &relayer)
+            .unwrap();
+        assert_eq!(
+            transfer.take_bank_ops(),
+            vec![
+                DummyBankOp::Send {
+                    from: escrow.clone(),
+                    to: intermediate.clone(),
+                    coin: voucher.clone(),
+                },
+                DummyBankOp::Burn {
+                    account: intermediate.clone(),
+                    coin: voucher.clone(),
+                },
+            ]
+        );
+        let (acked, ack) = written_ack(&transfer.take_events());
+        assert_eq!(acked, original);
+        assert!(matches!(ack, Acknowledgement::Error(_)));
+        assert!(transfer.get_in_flight_packet(&forwarded_id).is_none());
+
+        // A successful acknowledgement of the forwarded packet is relayed back as is.
+        let original = incoming_packet(2, memo);
+        receive(&mut stack, &original);
+        transfer.take_bank_ops();
+        let forwarded = sent_packet(&transfer.take_events());
+        assert_eq!(forwarded.sequence, 2.into());
+
+        let success = serde_json::to_vec(&Acknowledgement::success()).unwrap();
+        stack
+            .on_acknowledgement_packet(
+                &mut ModuleOutputBuilder::new(),
+                &forwarded,
+                &GenericAcknowledgement::from(success),
+                &relayer,
+            )
+            .unwrap();
+        assert!(transfer.take_bank_ops().is_empty());
+        let (acked, ack) = written_ack(&transfer.take_events());
+        assert_eq!(acked, original);
+        assert!(matches!(ack, Acknowledgement::Success(_)));
+
+        // Forwarding over a channel which doesn't exist fails the original packet right away.
+        let memo = r#"{"forward":{"receiver":"receiver","port":"transfer","channel":"channel-9"}}"#;
+        let ack = stack.on_recv_packet(
+            &mut ModuleOutputBuilder::new(),
+            &incoming_packet(3, memo),
+            &relayer,
+        );
+        assert!(matches!(ack, OnRecvPacketAck::Failed(_)));
+        assert!(transfer.take_bank_ops().is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use super::error::Error;
+use super::quota::RateLimit;
+use crate::applications::transfer::context::{Ics20Context, Ics20Keeper, Ics20Reader};
+use crate::applications::transfer::PrefixedDenom;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::ChannelId;
+use crate::timestamp::Timestamp;
+
+pub trait RateLimitKeeper: Ics20Keeper {
+    /// Replaces the rate limit of the denom `denom` on the channel `channel_id`, along with the
+    /// flow tracked against it.
+    fn store_rate_limit(
+        &mut self,
+        channel_id: ChannelId,
+        denom: PrefixedDenom,
+        rate_limit: RateLimit,
+    ) -> Result<(), Error>;
+
+    /// Removes the rate limit of the denom `denom` on the channel `channel_id`, which lifts any
+    /// bound on its flow.
+    fn delete_rate_limit(
+        &mut self,
+        channel_id: &ChannelId,
+        denom: &PrefixedDenom,
+    ) -> Result<(), Error>;
+
+    /// Stores the time at which the rate-limited packet with sequence `sequence` was sent over
+    /// the channel `channel_id`.
+    fn store_send_time(
+        &mut self,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        timestamp: Timestamp,
+    ) -> Result<(), Error>;
+
+    /// Removes the send time of the packet with sequence `sequence` on the channel `channel_id`,
+    /// once it was acknowledged or timed out.
+    fn delete_send_time(&mut self, channel_id: &ChannelId, sequence: Sequence)
+        -> Result<(), Error>;
+}
+
+pub trait RateLimitReader: Ics20Reader {
+    /// Returns the rate limit of the denom `denom` on the channel `channel_id`, if any. The flow
+    /// of denoms without a rate limit is unbounded.
+    fn get_rate_limit(&self, channel_id: &ChannelId, denom: &PrefixedDenom) -> Option<RateLimit>;
+
+    /// Returns the time at which the packet with sequence `sequence` was sent over the channel
+    /// `channel_id`, if it was rate limited.
+    fn get_send_time(&self, channel_id: &ChannelId, sequence: Sequence) -> Option<Timestamp>;
+}
+
+/// Captures all the dependencies which the rate limit middleware requires on top of the ones of
+/// the ICS20 module it is stacked over.
+pub trait RateLimitContext: Ics20Context + RateLimitKeeper + RateLimitReader {}
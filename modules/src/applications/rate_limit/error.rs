@@ -0,0 +1,33 @@
+use flex_error::define_error;
+
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::PrefixedDenom;
+use crate::core::ics24_host::identifier::ChannelId;
+use crate::prelude::*;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics20
+            [ Ics20Error ]
+            | _ | { "Ics20 transfer error" },
+
+        SendQuotaExceeded
+            { channel_id: ChannelId, denom: PrefixedDenom }
+            | e | {
+                format_args!("send quota of {} on channel {} exceeded",
+                    e.denom, e.channel_id)
+            },
+
+        AmountOverflow
+            { denom: PrefixedDenom }
+            | e | { format_args!("the total amount of {0} in the packet overflows", e.denom) },
+
+        RecvQuotaExceeded
+            { channel_id: ChannelId, denom: PrefixedDenom }
+            | e | {
+                format_args!("receive quota of {} on channel {} exceeded",
+                    e.denom, e.channel_id)
+            },
+    }
+}
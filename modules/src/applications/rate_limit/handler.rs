@@ -0,0 +1,52 @@
+//! Records ICS20 sends against the send quotas of their denoms.
+use alloc::collections::btree_map::{BTreeMap, Entry};
+
+use super::context::RateLimitContext;
+use super::error::Error;
+use crate::applications::transfer::PrefixedCoin;
+use crate::core::ics04_channel::packet::Packet;
+use crate::prelude::*;
+
+/// Records each of `tokens`, sent with `packet`, against the send quota of its denom on the
+/// source channel of the packet. Fails without recording anything if any quota would be exceeded.
+/// Hosts call this from their [`Ics20Keeper::on_send_transfer`] hook, which makes the ICS20
+/// `send_transfer` handler fail the transfer before any tokens are moved.
+///
+/// [`Ics20Keeper::on_send_transfer`]: crate::applications::transfer::context::Ics20Keeper::on_send_transfer
+pub fn record_send<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    tokens: &[PrefixedCoin],
+) -> Result<(), Error>
+where
+    Ctx: RateLimitContext,
+{
+    let channel_id = packet.source_channel;
+
+    // All the tokens are checked before any quota gets updated, so that a transfer exceeding
+    // the quota of one of its denoms is not recorded at all.
+    let now = ctx.host_timestamp();
+    let mut rate_limits = BTreeMap::new();
+    for token in tokens {
+        let rate_limit = match rate_limits.entry(token.denom.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match ctx.get_rate_limit(&channel_id, &token.denom) {
+                Some(rate_limit) => entry.insert(rate_limit),
+                None => continue,
+            },
+        };
+        if !rate_limit.record_send(token.amount, now) {
+            return Err(Error::send_quota_exceeded(channel_id, token.denom.clone()));
+        }
+    }
+    if rate_limits.is_empty() {
+        return Ok(());
+    }
+
+    for (denom, rate_limit) in rate_limits {
+        ctx.store_rate_limit(channel_id, denom, rate_limit)?;
+    }
+    // The send time is kept until the packet is acknowledged or times out, so that a refund is
+    // taken back from the outflow of the window the send was recorded in.
+    ctx.store_send_time(channel_id, packet.sequence, now)
+}
@@ -0,0 +1,243 @@
+//! The rate limit middleware, which is stacked over the ICS20 module. It fails the received
+//! packets which would exceed the receive quota of their denom on their channel, and takes
+//! back the sends which got refunded from the flow tracked against the send quota.
+use alloc::collections::btree_map::BTreeMap;
+
+use super::context::RateLimitContext;
+use super::error::Error as RateLimitError;
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::context::decode_packet_data;
+use crate::applications::transfer::packet::PacketDataV2;
+use crate::applications::transfer::{is_receiver_chain_source, Amount, PrefixedDenom, TracePrefix};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics24_host::identifier::ChannelId;
+use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck, WriteFn};
+use crate::core::ics26_routing::middleware::{Middleware, MiddlewareStack};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+fn to_channel_error(e: RateLimitError) -> Error {
+    Error::app_module(e.to_string())
+}
+
+/// Returns the denom `denom` of tokens on this chain, once received with `packet`.
+fn received_denom(packet: &Packet, denom: &PrefixedDenom) -> PrefixedDenom {
+    let mut denom = denom.clone();
+    if is_receiver_chain_source(packet.source_port.clone(), packet.source_channel, &denom) {
+        denom.remove_trace_prefix(&TracePrefix::new(
+            packet.source_port.clone(),
+            packet.source_channel,
+        ));
+    } else {
+        denom.add_trace_prefix(TracePrefix::new(
+            packet.destination_port.clone(),
+            packet.destination_channel,
+        ));
+    }
+    denom
+}
+
+/// Returns the total amounts of each denom received with `packet`.
+fn received_amounts(
+    packet: &Packet,
+    data: &PacketDataV2,
+) -> Result<BTreeMap<PrefixedDenom, Amount>, RateLimitError> {
+    let mut amounts = BTreeMap::new();
+    for token in &data.tokens {
+        let denom = received_denom(packet, &token.denom);
+        let amount = match amounts.get(&denom) {
+            Some(amount) => token
+                .amount
+                .checked_add(*amount)
+                .ok_or_else(|| RateLimitError::amount_overflow(denom.clone()))?,
+            None => token.amount,
+        };
+        amounts.insert(denom, amount);
+    }
+    Ok(amounts)
+}
+
+/// The rate limit middleware, to be stacked over the ICS20 module with a
+/// [`ModuleStackBuilder`](crate::core::ics26_routing::middleware::ModuleStackBuilder).
+/// For sends to be bounded as well, the host must record them with
+/// [`record_send`](super::handler::record_send) from its ICS20 `on_send_transfer` hook.
+pub struct RateLimitMiddleware<Ctx> {
+    ctx: Ctx,
+}
+
+impl<Ctx> RateLimitMiddleware<Ctx> {
+    pub fn new(ctx: Ctx) -> Self {
+        Self { ctx }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+}
+
+impl<Ctx: RateLimitContext> RateLimitMiddleware<Ctx> {
+    fn record_recv(
+        &mut self,
+        channel_id: ChannelId,
+        amounts: BTreeMap<PrefixedDenom, Amount>,
+    ) -> Result<(), RateLimitError> {
+        let now = self.ctx.host_timestamp();
+        let mut rate_limits = vec![];
+        for (denom, amount) in amounts {
+            if let Some(mut rate_limit) = self.ctx.get_rate_limit(&channel_id, &denom) {
+                if !rate_limit.record_recv(amount, now) {
+                    return Err(RateLimitError::recv_quota_exceeded(channel_id, denom));
+                }
+                rate_limits.push((denom, rate_limit));
+            }
+        }
+        for (denom, rate_limit) in rate_limits {
+            self.ctx.store_rate_limit(channel_id, denom, rate_limit)?;
+        }
+        Ok(())
+    }
+
+    /// Settles the rate-limited send of `packet` once it was acknowledged or timed out. If the
+    /// tokens got `refunded`, they are taken back from the outflow of the window the send was
+    /// recorded in.
+    fn settle_send(&mut self, packet: &Packet, refunded: bool) -> Result<(), RateLimitError> {
+        let sent_at = match self
+            .ctx
+            .get_send_time(&packet.source_channel, packet.sequence)
+        {
+            Some(sent_at) => sent_at,
+            None => return Ok(()),
+        };
+        self.ctx
+            .delete_send_time(&packet.source_channel, packet.sequence)?;
+        if !refunded {
+            return Ok(());
+        }
+
+        let data = match decode_packet_data(
+            &self.ctx,
+            &packet.source_port,
+            packet.source_channel,
+            &packet.data,
+        ) {
+            Ok(data) => data,
+            Err(_) => return Ok(()),
+        };
+
+        let now = self.ctx.host_timestamp();
+        for token in data.tokens {
+            if let Some(mut rate_limit) = self
+                .ctx
+                .get_rate_limit(&packet.source_channel, &token.denom)
+            {
+                rate_limit.revert_send(token.amount, sent_at, now);
+                self.ctx
+                    .store_rate_limit(packet.source_channel, token.denom, rate_limit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Ctx> Middleware for RateLimitMiddleware<Ctx>
+where
+    Ctx: 'static + RateLimitContext + Send + Sync,
+{
+    fn on_recv_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        let data = match decode_packet_data(
+            &self.ctx,
+            &packet.destination_port,
+            packet.destination_channel,
+            &packet.data,
+        ) {
+            Ok(data) => data,
+            // Packets which aren't transfers are left to the ICS20 module to reject.
+            Err(_) => {
+                return MiddlewareStack::<Self>::forward_ack(
+                    next.on_recv_packet(output, packet, relayer),
+                )
+            }
+        };
+        let amounts = match received_amounts(packet, &data) {
+            Ok(amounts) => amounts,
+            Err(e) => return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
+        };
+
+        let channel_id = packet.destination_channel;
+
+        // The quotas are checked here already, so that the packet fails with an acknowledgement
+        // rather than failing the whole `MsgRecvPacket`. All the tokens of a packet are received
+        // together, so the packet fails if any of them would exceed the quota of its denom.
+        for (denom, amount) in &amounts {
+            if let Some(mut rate_limit) = self.ctx.get_rate_limit(&channel_id, denom) {
+                if !rate_limit.record_recv(*amount, self.ctx.host_timestamp()) {
+                    return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(
+                        RateLimitError::recv_quota_exceeded(channel_id, denom.clone()),
+                    )));
+                }
+            }
+        }
+
+        let record = move |write_fn: Box<WriteFn>| -> Box<WriteFn> {
+            let write_fn = MiddlewareStack::<Self>::forward_write_fn(write_fn);
+            Box::new(move |module| {
+                write_fn(module)?;
+                let stack = module
+                    .downcast_mut::<MiddlewareStack<Self>>()
+                    .ok_or_else(|| "write function called with a foreign module".to_string())?;
+                stack
+                    .middleware_mut()
+                    .record_recv(channel_id, amounts)
+                    .map_err(|e| e.to_string())
+            })
+        };
+
+        match next.on_recv_packet(output, packet, relayer) {
+            OnRecvPacketAck::Nil(write_fn) => OnRecvPacketAck::Nil(record(write_fn)),
+            OnRecvPacketAck::Successful(ack, write_fn) => {
+                OnRecvPacketAck::Successful(ack, record(write_fn))
+            }
+            OnRecvPacketAck::Failed(ack) => OnRecvPacketAck::Failed(ack),
+        }
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        next.on_acknowledgement_packet(output, packet, acknowledgement, relayer)?;
+
+        let refunded = matches!(
+            serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref()),
+            Ok(Acknowledgement::Error(_))
+        );
+        self.settle_send(packet, refunded).map_err(to_channel_error)
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        next.on_timeout_packet(output, packet, relayer)?;
+        self.settle_send(packet, true).map_err(to_channel_error)
+    }
+}
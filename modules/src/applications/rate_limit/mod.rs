@@ -0,0 +1,209 @@
+//! Rate limiting of ICS20 transfers. The amounts of a denom sent and received over a channel are
+//! bounded by a [`Quota`](quota::Quota) over sliding windows of time, so that a compromised
+//! counterparty may not drain the escrow of the channel at once. Sends are bounded by
+//! [`record_send`](handler::record_send), which hosts call from the `on_send_transfer` hook of
+//! their ICS20 keeper, and receives by the [`RateLimitMiddleware`](middleware::RateLimitMiddleware)
+//! stacked over the ICS20 module.
+pub mod context;
+pub mod error;
+pub mod handler;
+pub mod middleware;
+pub mod quota;
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::context::{RateLimitKeeper, RateLimitReader};
+    use super::middleware::RateLimitMiddleware;
+    use super::quota::{Flow, Quota, RateLimit};
+    use crate::applications::transfer::error::ErrorDetail;
+    use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::relay::send_transfer::send_transfer;
+    use crate::applications::transfer::{PrefixedCoin, PrefixedDenom};
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+    use crate::core::ics26_routing::middleware::{MiddlewareStack, ModuleStackBuilder};
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::{get_dummy_account_id, DummyForwardContext};
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    type RateLimitStack = MiddlewareStack<RateLimitMiddleware<DummyForwardContext>>;
+
+    const WINDOW: Duration = Duration::from_secs(3600);
+
+    fn transfer_msg(amount: u64) -> MsgTransfer<PrefixedCoin> {
+        MsgTransfer {
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            token: PrefixedCoin {
+                denom: "uatom".parse().unwrap(),
+                amount: amount.into(),
+            },
+            sender: get_dummy_account_id(),
+            receiver: get_dummy_account_id(),
+            timeout_height: Height::new(0, 100),
+            timeout_timestamp: Timestamp::none(),
+            memo: String::new(),
+        }
+    }
+
+    /// Sends `amount` uatom with the ICS20 `send_transfer` handler, and returns the packet sent.
+    fn send(transfer: &mut DummyForwardContext, amount: u64) -> Packet {
+        let mut output = HandlerOutputBuilder::new();
+        send_transfer(transfer, &mut output, transfer_msg(amount)).unwrap();
+        output
+            .with_result(())
+            .events
+            .into_iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(event) => Some(event.packet),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    /// A packet returning `amount` uatom, which were sent from this chain, over `channel-0`.
+    fn incoming_packet(sequence: u64, amount: u64) -> Packet {
+        let data = PacketData {
+            token: PrefixedCoin {
+                denom: "transfer/channel-0/uatom".parse().unwrap(),
+                amount: amount.into(),
+            },
+            sender: get_dummy_account_id(),
+            receiver: get_dummy_account_id(),
+            memo: String::new(),
+        };
+        Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: serde_json::to_vec(&data).unwrap(),
+            timeout_height: Height::new(0, 100),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    fn receive(stack: &mut RateLimitStack, packet: &Packet) -> bool {
+        let relayer = get_dummy_account_id();
+        match stack.on_recv_packet(&mut ModuleOutputBuilder::new(), packet, &relayer) {
+            OnRecvPacketAck::Successful(_, write_fn) => {
+                write_fn(stack).unwrap();
+                true
+            }
+            OnRecvPacketAck::Failed(_) => false,
+            OnRecvPacketAck::Nil(_) => panic!("unexpected nil acknowledgement"),
+        }
+    }
+
+    fn time_out(stack: &mut RateLimitStack, packet: &Packet) {
+        stack
+            .on_timeout_packet(
+                &mut ModuleOutputBuilder::new(),
+                packet,
+                &get_dummy_account_id(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn rate_limited_transfers() {
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let channel_id = ChannelId::new(0);
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 10))
+            .with_open_connection(&client_id, ConnectionId::new(0), ConnectionId::new(0))
+            .with_open_channel(
+                PortId::transfer(),
+                channel_id,
+                ConnectionId::new(0),
+                channel_id,
+                Version::ics20(),
+            );
+
+        let mut transfer = DummyForwardContext::new(ctx.ibc_store_share());
+        let mut stack = ModuleStackBuilder::new(transfer.clone())
+            .push(RateLimitMiddleware::new(transfer.clone()))
+            .build();
+
+        let denom: PrefixedDenom = "uatom".parse().unwrap();
+        let at = |windows: u64, tenths: u64| {
+            let nanos = WINDOW.as_nanos() as u64;
+            Timestamp::from_nanoseconds((100 + windows) * nanos + tenths * nanos / 10).unwrap()
+        };
+        transfer.set_host_timestamp(at(0, 0));
+        transfer
+            .store_rate_limit(
+                channel_id,
+                denom.clone(),
+                RateLimit::new(Quota {
+                    max_send: 100u64.into(),
+                    max_recv: 50u64.into(),
+                    window: WINDOW,
+                }),
+            )
+            .unwrap();
+        let flow = |transfer: &DummyForwardContext| -> Flow {
+            transfer.get_rate_limit(&channel_id, &denom).unwrap().flow
+        };
+
+        // Sends beyond the quota fail without sending anything.
+        let sent = send(&mut transfer, 60);
+        let err = send_transfer(
+            &mut transfer,
+            &mut HandlerOutputBuilder::new(),
+            transfer_msg(50),
+        )
+        .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::TransferRejected(_)));
+        assert_eq!(flow(&transfer).outflow(), 60u64.into());
+        assert_eq!(transfer.take_bank_ops().len(), 1);
+
+        // The refund of a send which timed out within the window is taken back from the outflow.
+        transfer.set_host_timestamp(at(0, 5));
+        time_out(&mut stack, &sent);
+        assert_eq!(flow(&transfer).outflow(), 0u64.into());
+        transfer.take_bank_ops();
+
+        // Once the window of a send has passed, its refund is dropped rather than taken back
+        // from the sends of the current window.
+        let sent = send(&mut transfer, 60);
+        transfer.set_host_timestamp(at(1, 5));
+        send(&mut transfer, 80);
+        time_out(&mut stack, &sent);
+        assert_eq!(flow(&transfer).outflow(), 80u64.into());
+        transfer.take_bank_ops();
+
+        // Receives beyond the quota fail with an acknowledgement.
+        transfer.set_host_timestamp(at(3, 0));
+        assert!(receive(&mut stack, &incoming_packet(1, 40)));
+        transfer.set_host_timestamp(at(3, 5));
+        assert!(!receive(&mut stack, &incoming_packet(2, 20)));
+        assert!(receive(&mut stack, &incoming_packet(2, 10)));
+        assert_eq!(flow(&transfer).inflow(), 50u64.into());
+        assert_eq!(transfer.take_bank_ops().len(), 2);
+
+        // The window slides, so only the amounts received over the last window count.
+        transfer.set_host_timestamp(at(4, 0));
+        assert!(receive(&mut stack, &incoming_packet(3, 40)));
+        assert!(!receive(&mut stack, &incoming_packet(4, 10)));
+        assert_eq!(flow(&transfer).inflow(), 50u64.into());
+
+        // Without a rate limit, the flow is unbounded.
+        transfer.delete_rate_limit(&channel_id, &denom).unwrap();
+        assert!(receive(&mut stack, &incoming_packet(4, 1000)));
+    }
+}
@@ -0,0 +1,168 @@
+//! Quotas bounding the flow of a denom through a channel, and the flow tracked against them.
+use core::time::Duration;
+
+use crate::applications::transfer::Amount;
+use crate::bigint::U256;
+use crate::prelude::*;
+use crate::timestamp::Timestamp;
+
+/// The number of buckets the window of a quota is divided into. The flow is tracked per bucket,
+/// so that the window slides forward one bucket at a time instead of being reset at once.
+pub const BUCKETS_PER_WINDOW: u64 = 10;
+
+/// The maximum amounts of a denom which may be sent and received over a channel within any
+/// window of time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Quota {
+    pub max_send: Amount,
+    pub max_recv: Amount,
+    pub window: Duration,
+}
+
+/// The amounts of a denom sent and received over a channel within one bucket of a window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowBucket {
+    /// The index of the bucket, counted in bucket durations since the unix epoch.
+    pub index: u64,
+    pub inflow: Amount,
+    pub outflow: Amount,
+}
+
+impl FlowBucket {
+    fn new(index: u64) -> Self {
+        Self {
+            index,
+            inflow: 0u64.into(),
+            outflow: 0u64.into(),
+        }
+    }
+}
+
+/// The amounts of a denom sent and received over a channel within the window of its quota, as
+/// of the last transfer, in buckets ordered by their index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Flow {
+    pub buckets: Vec<FlowBucket>,
+}
+
+impl Flow {
+    fn total(&self, amount: impl Fn(&FlowBucket) -> Amount) -> Amount {
+        self.buckets.iter().fold(0u64.into(), |total, bucket| {
+            total
+                .checked_add(amount(bucket))
+                .unwrap_or_else(|| U256::MAX.into())
+        })
+    }
+
+    /// Returns the amount received within the window.
+    pub fn inflow(&self) -> Amount {
+        self.total(|bucket| bucket.inflow)
+    }
+
+    /// Returns the amount sent within the window.
+    pub fn outflow(&self) -> Amount {
+        self.total(|bucket| bucket.outflow)
+    }
+}
+
+/// The quota of a denom on a channel, along with the flow tracked against it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub quota: Quota,
+    pub flow: Flow,
+}
+
+impl RateLimit {
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            flow: Flow::default(),
+        }
+    }
+
+    /// Returns the index of the bucket which `at` falls into.
+    fn bucket_index(&self, at: Timestamp) -> u64 {
+        let bucket_nanos = self.quota.window.as_nanos() / u128::from(BUCKETS_PER_WINDOW);
+        let bucket_nanos = u64::try_from(bucket_nanos).unwrap_or(u64::MAX).max(1);
+        at.nanoseconds() / bucket_nanos
+    }
+
+    /// Drops the buckets which fell out of the window ending at `now`. Buckets ahead of `now`,
+    /// which the host clock going backwards leaves behind, are kept.
+    fn slide_window(&mut self, now: Timestamp) {
+        let current = self.bucket_index(now);
+        self.flow
+            .buckets
+            .retain(|bucket| bucket.index.saturating_add(BUCKETS_PER_WINDOW) > current);
+    }
+
+    /// Returns the bucket which `now` falls into, which gets added if it does not exist yet.
+    fn bucket_mut(&mut self, now: Timestamp) -> &mut FlowBucket {
+        let index = self.bucket_index(now);
+        let position = match self
+            .flow
+            .buckets
+            .binary_search_by_key(&index, |bucket| bucket.index)
+        {
+            Ok(position) => position,
+            Err(position) => {
+                self.flow.buckets.insert(position, FlowBucket::new(index));
+                position
+            }
+        };
+        &mut self.flow.buckets[position]
+    }
+
+    /// Records `amount` as sent at `now`. Returns `false`, leaving the flow unchanged, if the
+    /// send quota would be exceeded within the window ending at `now`.
+    pub fn record_send(&mut self, amount: Amount, now: Timestamp) -> bool {
+        self.slide_window(now);
+        match self.flow.outflow().checked_add(amount) {
+            Some(outflow) if outflow <= self.quota.max_send => {
+                let bucket = self.bucket_mut(now);
+                bucket.outflow = bucket
+                    .outflow
+                    .checked_add(amount)
+                    .expect("the outflow of a bucket is bounded by the total outflow");
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Records `amount` as received at `now`. Returns `false`, leaving the flow unchanged, if
+    /// the receive quota would be exceeded within the window ending at `now`.
+    pub fn record_recv(&mut self, amount: Amount, now: Timestamp) -> bool {
+        self.slide_window(now);
+        match self.flow.inflow().checked_add(amount) {
+            Some(inflow) if inflow <= self.quota.max_recv => {
+                let bucket = self.bucket_mut(now);
+                bucket.inflow = bucket
+                    .inflow
+                    .checked_add(amount)
+                    .expect("the inflow of a bucket is bounded by the total inflow");
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Takes back `amount`, which was sent at `sent_at` and got refunded at `now` as the send
+    /// failed or timed out, from the outflow of the bucket it was recorded in. Nothing is taken
+    /// back if that bucket has left the window already, since its outflow no longer counts.
+    pub fn revert_send(&mut self, amount: Amount, sent_at: Timestamp, now: Timestamp) {
+        self.slide_window(now);
+        let index = self.bucket_index(sent_at);
+        if let Ok(position) = self
+            .flow
+            .buckets
+            .binary_search_by_key(&index, |bucket| bucket.index)
+        {
+            let bucket = &mut self.flow.buckets[position];
+            bucket.outflow = bucket
+                .outflow
+                .checked_sub(amount)
+                .unwrap_or_else(|| 0u64.into());
+        }
+    }
+}
@@ -1,9 +1,8 @@
-use super::error::Error;
 use crate::core::ics26_routing::context::Acknowledgement as AckTrait;
 use crate::prelude::*;
 use core::fmt::{Display, Formatter};
 
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// A string constant included in error acknowledgements.
 /// NOTE: Changing this const is state machine breaking as acknowledgements are written into state
@@ -23,7 +22,7 @@ impl Acknowledgement {
         Self::Success(ACK_SUCCESS_B64.to_vec())
     }
 
-    pub fn from_error(err: Error) -> Self {
+    pub fn from_error(err: impl Display) -> Self {
         Self::Error(format!("{}: {}", ACK_ERR_STR, err))
     }
 }
@@ -49,6 +48,12 @@ impl<'de> Deserialize<'de> for Acknowledgement {
     }
 }
 
+impl Serialize for Acknowledgement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl Display for Acknowledgement {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -4,11 +4,11 @@ use subtle_encoding::hex;
 use super::error::Error as Ics20Error;
 use crate::applications::transfer::acknowledgement::Acknowledgement;
 use crate::applications::transfer::events::{AckEvent, AckStatusEvent, RecvEvent, TimeoutEvent};
-use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::packet::PacketDataV2;
 use crate::applications::transfer::relay::on_ack_packet::process_ack_packet;
 use crate::applications::transfer::relay::on_recv_packet::process_recv_packet;
 use crate::applications::transfer::relay::on_timeout_packet::process_timeout_packet;
-use crate::applications::transfer::{PrefixedCoin, PrefixedDenom, VERSION};
+use crate::applications::transfer::{Amount, PrefixedCoin, PrefixedDenom, VERSION};
 use crate::core::ics04_channel::channel::{Counterparty, Order};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
@@ -23,6 +23,18 @@ pub trait Ics20Keeper:
     ChannelKeeper + BankKeeper<AccountId = <Self as Ics20Keeper>::AccountId>
 {
     type AccountId;
+
+    /// Called by the [`send_transfer`](super::relay::send_transfer::send_transfer) handler with
+    /// the packet carrying `tokens`, before any of them are moved. The transfer fails if this
+    /// fails, which lets the host bound what gets sent, e.g. with the rate limits of
+    /// [`rate_limit`](crate::applications::rate_limit). Does nothing by default.
+    fn on_send_transfer(
+        &mut self,
+        _packet: &Packet,
+        _tokens: &[PrefixedCoin],
+    ) -> Result<(), Ics20Error> {
+        Ok(())
+    }
 }
 
 pub trait Ics20Reader: ChannelReader {
@@ -46,6 +58,13 @@ pub trait Ics20Reader: ChannelReader {
             .map_err(|_| Ics20Error::parse_account_failure())
     }
 
+    /// Returns the balance of `account` in `denom`.
+    fn get_balance(
+        &self,
+        account: &<Self as Ics20Reader>::AccountId,
+        denom: &PrefixedDenom,
+    ) -> Amount;
+
     /// Returns true iff send is enabled.
     fn is_send_enabled(&self) -> bool;
 
@@ -128,15 +147,19 @@ fn validate_transfer_channel_params(
         return Err(Ics20Error::invalid_port(port_id.clone(), bound_port));
     }
 
-    if version != &Version::ics20() {
+    if !is_supported_version(version) {
         return Err(Ics20Error::invalid_version(version.clone()));
     }
 
     Ok(())
 }
 
+fn is_supported_version(version: &Version) -> bool {
+    version == &Version::ics20() || version == &Version::ics20_v2()
+}
+
 fn validate_counterparty_version(counterparty_version: &Version) -> Result<(), Ics20Error> {
-    if counterparty_version == &Version::ics20() {
+    if is_supported_version(counterparty_version) {
         Ok(())
     } else {
         Err(Ics20Error::invalid_counterparty_version(
@@ -145,6 +168,20 @@ fn validate_counterparty_version(counterparty_version: &Version) -> Result<(), I
     }
 }
 
+/// Decodes the data of a packet sent or received over the channel end `channel_id` of the port
+/// `port_id`, according to the version negotiated for the channel.
+pub fn decode_packet_data(
+    ctx: &impl ChannelReader,
+    port_id: &PortId,
+    channel_id: ChannelId,
+    data: &[u8],
+) -> Result<PacketDataV2, Ics20Error> {
+    let channel_end = ctx
+        .channel_end(&(port_id.clone(), channel_id))
+        .map_err(Ics20Error::ics04_channel)?;
+    PacketDataV2::decode(channel_end.version(), data)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn on_chan_open_init(
     ctx: &mut impl Ics20Context,
@@ -173,7 +210,8 @@ pub fn on_chan_open_try(
 ) -> Result<Version, Ics20Error> {
     validate_transfer_channel_params(ctx, order, port_id, channel_id, version)?;
     validate_counterparty_version(counterparty_version)?;
-    Ok(Version::ics20())
+    // Both versions are supported, so the one proposed by the counterparty is agreed on.
+    Ok(counterparty_version.clone())
 }
 
 pub fn on_chan_open_ack(
@@ -220,13 +258,14 @@ pub fn on_recv_packet<Ctx: 'static + Ics20Context>(
     packet: &Packet,
     _relayer: &Signer,
 ) -> OnRecvPacketAck {
-    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+    let data = match decode_packet_data(
+        ctx,
+        &packet.destination_port,
+        packet.destination_channel,
+        &packet.data,
+    ) {
         Ok(data) => data,
-        Err(_) => {
-            return OnRecvPacketAck::Failed(Box::new(Acknowledgement::Error(
-                Ics20Error::packet_data_deserialization().to_string(),
-            )))
-        }
+        Err(e) => return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
     };
 
     let ack = match process_recv_packet(ctx, output, packet, data.clone()) {
@@ -234,13 +273,15 @@ pub fn on_recv_packet<Ctx: 'static + Ics20Context>(
         Err(e) => OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
     };
 
-    let recv_event = RecvEvent {
-        receiver: data.receiver,
-        denom: data.token.denom,
-        amount: data.token.amount,
-        success: ack.is_successful(),
-    };
-    output.emit(recv_event.into());
+    for token in data.tokens {
+        let recv_event = RecvEvent {
+            receiver: data.receiver.clone(),
+            denom: token.denom,
+            amount: token.amount,
+            success: ack.is_successful(),
+        };
+        output.emit(recv_event.into());
+    }
 
     ack
 }
@@ -252,21 +293,27 @@ pub fn on_acknowledgement_packet(
     acknowledgement: &GenericAcknowledgement,
     _relayer: &Signer,
 ) -> Result<(), Ics20Error> {
-    let data = serde_json::from_slice::<PacketData>(&packet.data)
-        .map_err(|_| Ics20Error::packet_data_deserialization())?;
+    let data = decode_packet_data(
+        &*ctx,
+        &packet.source_port,
+        packet.source_channel,
+        &packet.data,
+    )?;
 
     let acknowledgement = serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref())
         .map_err(|_| Ics20Error::ack_deserialization())?;
 
     process_ack_packet(ctx, packet, &data, &acknowledgement)?;
 
-    let ack_event = AckEvent {
-        receiver: data.receiver,
-        denom: data.token.denom,
-        amount: data.token.amount,
-        acknowledgement: acknowledgement.clone(),
-    };
-    output.emit(ack_event.into());
+    for token in data.tokens {
+        let ack_event = AckEvent {
+            receiver: data.receiver.clone(),
+            denom: token.denom,
+            amount: token.amount,
+            acknowledgement: acknowledgement.clone(),
+        };
+        output.emit(ack_event.into());
+    }
     output.emit(AckStatusEvent { acknowledgement }.into());
 
     Ok(())
@@ -278,17 +325,23 @@ pub fn on_timeout_packet(
     packet: &Packet,
     _relayer: &Signer,
 ) -> Result<(), Ics20Error> {
-    let data = serde_json::from_slice::<PacketData>(&packet.data)
-        .map_err(|_| Ics20Error::packet_data_deserialization())?;
+    let data = decode_packet_data(
+        &*ctx,
+        &packet.source_port,
+        packet.source_channel,
+        &packet.data,
+    )?;
 
     process_timeout_packet(ctx, packet, &data)?;
 
-    let timeout_event = TimeoutEvent {
-        refund_receiver: data.sender,
-        refund_denom: data.token.denom,
-        refund_amount: data.token.amount,
-    };
-    output.emit(timeout_event.into());
+    for token in data.tokens {
+        let timeout_event = TimeoutEvent {
+            refund_receiver: data.sender.clone(),
+            refund_denom: token.denom,
+            refund_amount: token.amount,
+        };
+        output.emit(timeout_event.into());
+    }
 
     Ok(())
 }
@@ -297,15 +350,29 @@ pub fn on_timeout_packet(
 pub(crate) mod test {
     use subtle_encoding::bech32;
 
-    use crate::applications::transfer::context::cosmos_adr028_escrow_address;
-    use crate::applications::transfer::error::Error as Ics20Error;
+    use crate::applications::transfer::context::Ics20Reader;
+    use crate::applications::transfer::context::{cosmos_adr028_escrow_address, on_chan_open_try};
+    use crate::applications::transfer::error::{Error as Ics20Error, ErrorDetail};
     use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::packet::PacketDataV2;
     use crate::applications::transfer::relay::send_transfer::send_transfer;
     use crate::applications::transfer::PrefixedCoin;
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics04_channel::channel::{Counterparty, Order};
     use crate::core::ics04_channel::error::Error;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+    use crate::events::IbcEvent;
     use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::MockContext;
     use crate::prelude::*;
-    use crate::test_utils::DummyTransferModule;
+    use crate::test_utils::{
+        get_dummy_account_id, DummyBankOp, DummyForwardContext, DummyTransferModule,
+    };
+    use crate::timestamp::Timestamp;
+    use crate::Height;
 
     pub(crate) fn deliver(
         ctx: &mut DummyTransferModule,
@@ -344,4 +411,161 @@ pub(crate) mod test {
             "cosmos177x69sver58mcfs74x6dg0tv6ls4s3xmmcaw53",
         );
     }
+
+    fn coin(denom: &str, amount: u64) -> PrefixedCoin {
+        PrefixedCoin {
+            denom: denom.parse().unwrap(),
+            amount: amount.into(),
+        }
+    }
+
+    #[test]
+    fn multi_token_transfers() {
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let conn_id = ConnectionId::new(0);
+        let (v1_channel, v2_channel) = (ChannelId::new(0), ChannelId::new(1));
+
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 10))
+            .with_open_connection(&client_id, conn_id.clone(), ConnectionId::default())
+            .with_open_channel(
+                PortId::transfer(),
+                v1_channel,
+                conn_id.clone(),
+                ChannelId::new(0),
+                Version::ics20(),
+            )
+            .with_open_channel(
+                PortId::transfer(),
+                v2_channel,
+                conn_id.clone(),
+                ChannelId::new(0),
+                Version::ics20_v2(),
+            );
+        let mut transfer = DummyForwardContext::new(ctx.ibc_store_share());
+
+        // The version proposed by the counterparty is agreed on.
+        let negotiated = on_chan_open_try(
+            &mut transfer,
+            &mut ModuleOutputBuilder::new(),
+            Order::Unordered,
+            &[conn_id],
+            &PortId::transfer(),
+            &v2_channel,
+            &Counterparty::new(PortId::transfer(), None),
+            &Version::ics20(),
+            &Version::ics20_v2(),
+        )
+        .unwrap();
+        assert_eq!(negotiated, Version::ics20_v2());
+
+        let sender = get_dummy_account_id();
+        let tokens = vec![coin("uatom", 10), coin("transfer/channel-1/uosmo", 20)];
+        let msg = |source_channel| MsgTransfer {
+            source_port: PortId::transfer(),
+            source_channel,
+            token: tokens.clone(),
+            sender: sender.clone(),
+            receiver: get_dummy_account_id(),
+            timeout_height: Height::new(0, 100),
+            timeout_timestamp: Timestamp::none(),
+            memo: String::new(),
+        };
+
+        // Several tokens can't be sent over an `ics20-1` channel.
+        let err = send_transfer(
+            &mut transfer,
+            &mut HandlerOutputBuilder::new(),
+            msg(v1_channel),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::MultiTokenUnsupported(_)
+        ));
+        assert!(transfer.take_bank_ops().is_empty());
+
+        // The native token is escrowed and the voucher returning to its source is burned.
+        let escrow = transfer
+            .get_channel_escrow_address(&PortId::transfer(), v2_channel)
+            .unwrap();
+        let mut output = HandlerOutputBuilder::new();
+        send_transfer(&mut transfer, &mut output, msg(v2_channel)).unwrap();
+        assert_eq!(
+            transfer.take_bank_ops(),
+            vec![
+                DummyBankOp::Send {
+                    from: sender.clone(),
+                    to: escrow.clone(),
+                    coin: tokens[0].clone(),
+                },
+                DummyBankOp::Burn {
+                    account: sender.clone(),
+                    coin: tokens[1].clone(),
+                },
+            ]
+        );
+        let sent = output
+            .with_result(())
+            .events
+            .into_iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(event) => Some(event.packet),
+                _ => None,
+            })
+            .unwrap();
+        let data = PacketDataV2::decode(&Version::ics20_v2(), &sent.data).unwrap();
+        assert_eq!(data.tokens, tokens);
+
+        // All the tokens are refunded on timeout.
+        transfer
+            .on_timeout_packet(&mut ModuleOutputBuilder::new(), &sent, &sender)
+            .unwrap();
+        assert_eq!(
+            transfer.take_bank_ops(),
+            vec![
+                DummyBankOp::Send {
+                    from: escrow.clone(),
+                    to: sender.clone(),
+                    coin: tokens[0].clone(),
+                },
+                DummyBankOp::Mint {
+                    account: sender.clone(),
+                    coin: tokens[1].clone(),
+                },
+            ]
+        );
+
+        // All the tokens of a received packet are credited together.
+        let received = Packet {
+            destination_channel: v2_channel,
+            ..sent
+        };
+        match transfer.on_recv_packet(&mut ModuleOutputBuilder::new(), &received, &sender) {
+            OnRecvPacketAck::Successful(_, write_fn) => write_fn(&mut transfer).unwrap(),
+            _ => panic!("expected the packet to be received"),
+        }
+        assert_eq!(
+            transfer.take_bank_ops(),
+            vec![
+                DummyBankOp::Send {
+                    from: escrow.clone(),
+                    to: get_dummy_account_id(),
+                    coin: coin("uosmo", 20),
+                },
+                DummyBankOp::Mint {
+                    account: get_dummy_account_id(),
+                    coin: coin("transfer/channel-1/uatom", 10),
+                },
+            ]
+        );
+
+        // None of the tokens are credited if the escrow account can't pay one of them out.
+        transfer.set_balance(escrow, "uosmo".parse().unwrap(), 19u64.into());
+        assert!(matches!(
+            transfer.on_recv_packet(&mut ModuleOutputBuilder::new(), &received, &sender),
+            OnRecvPacketAck::Failed(_)
+        ));
+        assert!(transfer.take_bank_ops().is_empty());
+    }
 }
@@ -4,6 +4,7 @@ use core::str::FromStr;
 use derive_more::{Display, From, Into};
 use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
 use ibc_proto::ibc::applications::transfer::v1::DenomTrace as RawDenomTrace;
+use ibc_proto::vendored::ibc::applications::transfer::v1::{Denom as RawDenom, Hop as RawHop};
 use serde::{Deserialize, Serialize};
 
 use super::error::Error;
@@ -258,6 +259,48 @@ impl From<PrefixedDenom> for RawDenomTrace {
     }
 }
 
+impl TryFrom<RawDenom> for PrefixedDenom {
+    type Error = Error;
+
+    fn try_from(value: RawDenom) -> Result<Self, Self::Error> {
+        let base_denom = BaseDenom::from_str(&value.base)?;
+        // The trace of a `RawDenom` starts with the most recent hop, unlike a `TracePath`.
+        let mut trace = vec![];
+        for (pos, hop) in value.trace.into_iter().rev().enumerate() {
+            let port_id =
+                PortId::from_str(&hop.port_id).map_err(|e| Error::invalid_trace_port_id(pos, e))?;
+            let channel_id = ChannelId::from_str(&hop.channel_id)
+                .map_err(|e| Error::invalid_trace_channel_id(pos, e))?;
+            trace.push(TracePrefix {
+                port_id,
+                channel_id,
+            });
+        }
+        Ok(Self {
+            trace_path: trace.into(),
+            base_denom,
+        })
+    }
+}
+
+impl From<PrefixedDenom> for RawDenom {
+    fn from(value: PrefixedDenom) -> Self {
+        Self {
+            base: value.base_denom.to_string(),
+            trace: value
+                .trace_path
+                .0
+                .into_iter()
+                .rev()
+                .map(|prefix| RawHop {
+                    port_id: prefix.port_id.to_string(),
+                    channel_id: prefix.channel_id.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<BaseDenom> for PrefixedDenom {
     fn from(denom: BaseDenom) -> Self {
         Self {
@@ -433,6 +476,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_raw_denom() -> Result<(), Error> {
+        let denom = PrefixedDenom::from_str("transfer/channel-0/transfer/channel-1/uatom")?;
+        let raw = RawDenom::from(denom.clone());
+        assert_eq!(raw.base, "uatom");
+        assert_eq!(
+            raw.trace[0],
+            RawHop {
+                port_id: "transfer".to_string(),
+                channel_id: "channel-0".to_string(),
+            },
+            "the most recent hop comes first"
+        );
+        assert_eq!(PrefixedDenom::try_from(raw)?, denom);
+
+        Ok(())
+    }
+
     #[test]
     fn test_trace_path() -> Result<(), Error> {
         assert!(TracePath::from_str("").is_ok(), "empty trace path");
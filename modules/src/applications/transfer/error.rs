@@ -5,6 +5,7 @@ use subtle_encoding::Error as EncodingError;
 use tendermint_proto::Error as TendermintProtoError;
 use uint::FromStrRadixErr;
 
+use super::denom::PrefixedDenom;
 use crate::core::ics04_channel::channel::Order;
 use crate::core::ics04_channel::error as channel_error;
 use crate::core::ics04_channel::Version;
@@ -98,11 +99,25 @@ define_error! {
 
         InvalidVersion
             { version: Version }
-            | e | { format_args!("expected version '{0}', got '{1}'", Version::ics20(), e.version) },
+            | e | { format_args!("expected version '{0}' or '{1}', got '{2}'", Version::ics20(), Version::ics20_v2(), e.version) },
 
         InvalidCounterpartyVersion
             { version: Version }
-            | e | { format_args!("expected counterparty version '{0}', got '{1}'", Version::ics20(), e.version) },
+            | e | { format_args!("expected counterparty version '{0}' or '{1}', got '{2}'", Version::ics20(), Version::ics20_v2(), e.version) },
+
+        MultiTokenUnsupported
+            { version: Version }
+            | e | { format_args!("channel version '{0}' does not support transferring several tokens in a packet", e.version) },
+
+        EmptyTokens
+            | _ | { "no tokens to transfer" },
+
+        InsufficientEscrow
+            { denom: PrefixedDenom }
+            | e | { format_args!("the escrow account holds too few '{0}' tokens", e.denom) },
+
+        ForwardingUnsupported
+            | _ | { "forwarding tokens along a path of hops is not supported" },
 
         CantCloseChannel
             | _ | { "channel cannot be closed" },
@@ -136,5 +151,9 @@ define_error! {
         UnknownMsgType
             { msg_type: String }
             | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+
+        TransferRejected
+            { reason: String }
+            | e | { format_args!("transfer rejected by the host: {0}", e.reason) },
     }
 }
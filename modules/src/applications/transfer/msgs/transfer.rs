@@ -4,10 +4,11 @@ use crate::prelude::*;
 
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::google::protobuf::Any;
-use ibc_proto::ibc::applications::transfer::v1::MsgTransfer as RawMsgTransfer;
+use ibc_proto::vendored::ibc::applications::transfer::v1::MsgTransfer as RawMsgTransfer;
 use tendermint_proto::Protobuf;
 
 use crate::applications::transfer::error::Error;
+use crate::applications::transfer::PrefixedCoin;
 use crate::core::ics02_client::height::Height;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::signer::Signer;
@@ -29,7 +30,8 @@ pub struct MsgTransfer<C = Coin> {
     pub source_port: PortId,
     /// the channel by which the packet will be sent
     pub source_channel: ChannelId,
-    /// the tokens to be transferred
+    /// the tokens to be transferred, either a single coin or, over channels of version
+    /// `ics20-2`, a `Vec` of coins sent in a single packet
     pub token: C,
     /// the sender address
     pub sender: Signer,
@@ -41,25 +43,46 @@ pub struct MsgTransfer<C = Coin> {
     /// Timeout timestamp relative to the current block timestamp.
     /// The timeout is disabled when set to 0.
     pub timeout_timestamp: Timestamp,
+    /// An optional memo, passed on in the packet data
+    pub memo: String,
 }
 
-impl Msg for MsgTransfer {
-    type ValidationError = Error;
-    type Raw = RawMsgTransfer;
+/// The tokens of a [`MsgTransfer`], which are all sent in a single packet.
+pub trait TransferTokens {
+    fn into_prefixed_coins(self) -> Result<Vec<PrefixedCoin>, Error>;
+}
 
-    fn route(&self) -> String {
-        crate::keys::ROUTER_KEY.to_string()
+impl TransferTokens for Coin {
+    fn into_prefixed_coins(self) -> Result<Vec<PrefixedCoin>, Error> {
+        Ok(vec![self.try_into().map_err(|_| Error::invalid_token())?])
     }
+}
 
-    fn type_url(&self) -> String {
-        TYPE_URL.to_string()
+impl TransferTokens for PrefixedCoin {
+    fn into_prefixed_coins(self) -> Result<Vec<PrefixedCoin>, Error> {
+        Ok(vec![self])
     }
 }
 
-impl TryFrom<RawMsgTransfer> for MsgTransfer {
-    type Error = Error;
+impl<C: TransferTokens> TransferTokens for Vec<C> {
+    fn into_prefixed_coins(self) -> Result<Vec<PrefixedCoin>, Error> {
+        let mut coins = vec![];
+        for token in self {
+            coins.extend(token.into_prefixed_coins()?);
+        }
+        Ok(coins)
+    }
+}
+
+impl<C> MsgTransfer<C> {
+    /// Builds the message transferring `token` with the other fields of `raw_msg`.
+    fn from_raw(raw_msg: RawMsgTransfer, token: C) -> Result<Self, Error> {
+        if let Some(forwarding) = raw_msg.forwarding {
+            if forwarding.unwind || !forwarding.hops.is_empty() {
+                return Err(Error::forwarding_unsupported());
+            }
+        }
 
-    fn try_from(raw_msg: RawMsgTransfer) -> Result<Self, Self::Error> {
         let timeout_timestamp = Timestamp::from_nanoseconds(raw_msg.timeout_timestamp)
             .map_err(|_| Error::invalid_packet_timeout_timestamp(raw_msg.timeout_timestamp))?;
 
@@ -79,25 +102,66 @@ impl TryFrom<RawMsgTransfer> for MsgTransfer {
                 .source_channel
                 .parse()
                 .map_err(|e| Error::invalid_channel_id(raw_msg.source_channel.clone(), e))?,
-            token: raw_msg.token.ok_or_else(Error::invalid_token)?,
+            token,
             sender: raw_msg.sender.parse().map_err(Error::signer)?,
             receiver: raw_msg.receiver.parse().map_err(Error::signer)?,
             timeout_height,
             timeout_timestamp,
+            memo: raw_msg.memo,
         })
     }
+
+    /// Splits the message into its token and the raw message with the other fields, which
+    /// transfers no tokens yet.
+    fn into_raw(self) -> (RawMsgTransfer, C) {
+        let raw_msg = RawMsgTransfer {
+            source_port: self.source_port.to_string(),
+            source_channel: self.source_channel.to_string(),
+            token: None,
+            sender: self.sender.to_string(),
+            receiver: self.receiver.to_string(),
+            timeout_height: Some(self.timeout_height.into()),
+            timeout_timestamp: self.timeout_timestamp.nanoseconds(),
+            memo: self.memo,
+            tokens: vec![],
+            forwarding: None,
+        };
+        (raw_msg, self.token)
+    }
+}
+
+impl Msg for MsgTransfer {
+    type ValidationError = Error;
+    type Raw = RawMsgTransfer;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgTransfer> for MsgTransfer {
+    type Error = Error;
+
+    fn try_from(mut raw_msg: RawMsgTransfer) -> Result<Self, Self::Error> {
+        // The tokens of a multi-token transfer don't fit in a single coin.
+        if !raw_msg.tokens.is_empty() {
+            return Err(Error::invalid_token());
+        }
+        let token = raw_msg.token.take().ok_or_else(Error::invalid_token)?;
+        MsgTransfer::from_raw(raw_msg, token)
+    }
 }
 
 impl From<MsgTransfer> for RawMsgTransfer {
     fn from(domain_msg: MsgTransfer) -> Self {
+        let (raw_msg, token) = domain_msg.into_raw();
         RawMsgTransfer {
-            source_port: domain_msg.source_port.to_string(),
-            source_channel: domain_msg.source_channel.to_string(),
-            token: Some(domain_msg.token),
-            sender: domain_msg.sender.to_string(),
-            receiver: domain_msg.receiver.to_string(),
-            timeout_height: Some(domain_msg.timeout_height.into()),
-            timeout_timestamp: domain_msg.timeout_timestamp.nanoseconds(),
+            token: Some(token),
+            ..raw_msg
         }
     }
 }
@@ -126,6 +190,66 @@ impl From<MsgTransfer> for Any {
     }
 }
 
+impl Msg for MsgTransfer<Vec<Coin>> {
+    type ValidationError = Error;
+    type Raw = RawMsgTransfer;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgTransfer> for MsgTransfer<Vec<Coin>> {
+    type Error = Error;
+
+    fn try_from(mut raw_msg: RawMsgTransfer) -> Result<Self, Self::Error> {
+        // Either `token` or `tokens` is set. Go chains always encode `token`, which is then left
+        // without a denom.
+        let token = raw_msg.token.take().filter(|token| !token.denom.is_empty());
+        let tokens = match (token, core::mem::take(&mut raw_msg.tokens)) {
+            (Some(token), tokens) if tokens.is_empty() => vec![token],
+            (None, tokens) if !tokens.is_empty() => tokens,
+            _ => return Err(Error::invalid_token()),
+        };
+        MsgTransfer::from_raw(raw_msg, tokens)
+    }
+}
+
+impl From<MsgTransfer<Vec<Coin>>> for RawMsgTransfer {
+    fn from(domain_msg: MsgTransfer<Vec<Coin>>) -> Self {
+        let (raw_msg, tokens) = domain_msg.into_raw();
+        RawMsgTransfer { tokens, ..raw_msg }
+    }
+}
+
+impl Protobuf<RawMsgTransfer> for MsgTransfer<Vec<Coin>> {}
+
+impl TryFrom<Any> for MsgTransfer<Vec<Coin>> {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => MsgTransfer::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgTransfer<Vec<Coin>>> for Any {
+    fn from(msg: MsgTransfer<Vec<Coin>>) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgTranfer`"),
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod test_util {
     use core::ops::Add;
@@ -133,6 +257,7 @@ pub mod test_util {
 
     use super::MsgTransfer;
     use crate::bigint::U256;
+    use crate::prelude::*;
     use crate::signer::Signer;
     use crate::{
         applications::transfer::{BaseCoin, PrefixedCoin},
@@ -160,6 +285,74 @@ pub mod test_util {
                 revision_number: 0,
                 revision_height: height,
             },
+            memo: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::google::protobuf::Any;
+    use tendermint_proto::Protobuf;
+
+    use super::test_util::get_dummy_msg_transfer;
+    use super::{MsgTransfer, RawMsgTransfer};
+    use crate::prelude::*;
+    use crate::timestamp::Timestamp;
+
+    fn coin(denom: &str, amount: u64) -> Coin {
+        Coin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn msg_transfer<C>(token: C) -> MsgTransfer<C> {
+        let msg = get_dummy_msg_transfer(10);
+        MsgTransfer {
+            source_port: msg.source_port,
+            source_channel: msg.source_channel,
+            token,
+            sender: msg.sender,
+            receiver: msg.receiver,
+            timeout_height: msg.timeout_height,
+            timeout_timestamp: Timestamp::from_nanoseconds(1).unwrap(),
+            memo: msg.memo,
         }
     }
+
+    #[test]
+    fn multi_token_msg_transfer_roundtrip() {
+        let msg = msg_transfer(vec![
+            coin("uatom", 10),
+            coin("transfer/channel-1/uosmo", 20),
+        ]);
+        let any = Any::from(msg.clone());
+        assert_eq!(
+            MsgTransfer::<Vec<Coin>>::try_from(any.clone()).unwrap(),
+            msg
+        );
+
+        // The tokens of a multi-token transfer don't fit in the single-token form.
+        assert!(MsgTransfer::<Coin>::try_from(any).is_err());
+
+        // Single-token transfers are read in both forms.
+        let single = msg_transfer(coin("uatom", 10));
+        let any = Any::from(single.clone());
+        assert_eq!(MsgTransfer::<Coin>::try_from(any.clone()).unwrap(), single);
+        assert_eq!(
+            MsgTransfer::<Vec<Coin>>::try_from(any).unwrap(),
+            msg_transfer(vec![coin("uatom", 10)])
+        );
+
+        // Transfers setting both `token` and `tokens` are rejected.
+        let raw = RawMsgTransfer {
+            token: Some(coin("uatom", 10)),
+            ..RawMsgTransfer::from(msg.clone())
+        };
+        assert!(
+            MsgTransfer::<Vec<Coin>>::decode_vec(&prost::Message::encode_to_vec(&raw)).is_err()
+        );
+    }
 }
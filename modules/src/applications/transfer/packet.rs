@@ -1,12 +1,17 @@
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use core::convert::TryFrom;
 use core::str::FromStr;
 
 use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use ibc_proto::vendored::ibc::applications::transfer::v1::Token as RawToken;
+use ibc_proto::vendored::ibc::applications::transfer::v2::FungibleTokenPacketDataV2 as RawPacketDataV2;
+use prost::Message;
 use serde::{Deserialize, Serialize};
 
 use super::error::Error;
 use super::{Amount, PrefixedCoin, PrefixedDenom};
+use crate::core::ics04_channel::Version;
+use crate::prelude::*;
 use crate::signer::Signer;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -14,6 +19,9 @@ pub struct PacketData {
     pub token: PrefixedCoin,
     pub sender: Signer,
     pub receiver: Signer,
+    /// An optional memo, which may carry instructions for middlewares on the receiving chain.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub memo: String,
 }
 
 impl TryFrom<RawPacketData> for PacketData {
@@ -27,6 +35,7 @@ impl TryFrom<RawPacketData> for PacketData {
             token: PrefixedCoin { denom, amount },
             sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
             receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo: raw_pkt_data.memo,
         })
     }
 }
@@ -38,6 +47,126 @@ impl From<PacketData> for RawPacketData {
             amount: pkt_data.token.amount.to_string(),
             sender: pkt_data.sender.to_string(),
             receiver: pkt_data.receiver.to_string(),
+            memo: pkt_data.memo,
+        }
+    }
+}
+
+/// The packet data of the `ics20-2` version of the transfer application, which carries several
+/// tokens. The tokens of a packet are transferred atomically: either all of them are received,
+/// or none are and all of them get refunded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PacketDataV2 {
+    pub tokens: Vec<PrefixedCoin>,
+    pub sender: Signer,
+    pub receiver: Signer,
+    /// An optional memo, which may carry instructions for middlewares on the receiving chain.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub memo: String,
+}
+
+impl PacketDataV2 {
+    /// Decodes the data of a packet sent over a channel of version `version`. Channels of version
+    /// `ics20-2` carry protobuf-encoded `FungibleTokenPacketDataV2`, while channels of any other
+    /// version carry JSON-encoded `ics20-1` packets, whose data is converted to carry a single
+    /// token.
+    pub fn decode(version: &Version, data: &[u8]) -> Result<Self, Error> {
+        let data = if version == &Version::ics20_v2() {
+            RawPacketDataV2::decode(data)
+                .map_err(|_| Error::packet_data_deserialization())?
+                .try_into()?
+        } else {
+            serde_json::from_slice::<PacketData>(data)
+                .map(Self::from)
+                .map_err(|_| Error::packet_data_deserialization())?
+        };
+        if data.tokens.is_empty() {
+            return Err(Error::empty_tokens());
+        }
+        Ok(data)
+    }
+
+    /// Encodes the data of a packet to be sent over a channel of version `version`. Only
+    /// `ics20-2` channels may carry more than one token in a packet.
+    pub fn encode(self, version: &Version) -> Result<Vec<u8>, Error> {
+        if version == &Version::ics20_v2() {
+            if self.tokens.is_empty() {
+                return Err(Error::empty_tokens());
+            }
+            Ok(RawPacketDataV2::from(self).encode_to_vec())
+        } else {
+            let mut tokens = self.tokens.into_iter();
+            let token = match (tokens.next(), tokens.next()) {
+                (Some(token), None) => token,
+                (None, _) => return Err(Error::empty_tokens()),
+                (Some(_), Some(_)) => return Err(Error::multi_token_unsupported(version.clone())),
+            };
+            let data = PacketData {
+                token,
+                sender: self.sender,
+                receiver: self.receiver,
+                memo: self.memo,
+            };
+            Ok(serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed"))
+        }
+    }
+}
+
+impl From<PacketData> for PacketDataV2 {
+    fn from(data: PacketData) -> Self {
+        Self {
+            tokens: vec![data.token],
+            sender: data.sender,
+            receiver: data.receiver,
+            memo: data.memo,
+        }
+    }
+}
+
+impl TryFrom<RawPacketDataV2> for PacketDataV2 {
+    type Error = Error;
+
+    fn try_from(raw_pkt_data: RawPacketDataV2) -> Result<Self, Self::Error> {
+        // Forwarding the tokens along a path of hops is not supported. IBC Go always encodes the
+        // forwarding information, so only non-empty forwarding information is rejected.
+        if let Some(forwarding) = raw_pkt_data.forwarding {
+            if !forwarding.hops.is_empty() || !forwarding.destination_memo.is_empty() {
+                return Err(Error::forwarding_unsupported());
+            }
+        }
+        let tokens = raw_pkt_data
+            .tokens
+            .into_iter()
+            .map(|token| {
+                let denom = token.denom.ok_or_else(Error::invalid_token)?.try_into()?;
+                let amount = Amount::from_str(&token.amount)?;
+                Ok(PrefixedCoin { denom, amount })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self {
+            tokens,
+            sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
+            receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo: raw_pkt_data.memo,
+        })
+    }
+}
+
+impl From<PacketDataV2> for RawPacketDataV2 {
+    fn from(pkt_data: PacketDataV2) -> Self {
+        Self {
+            tokens: pkt_data
+                .tokens
+                .into_iter()
+                .map(|token| RawToken {
+                    denom: Some(token.denom.into()),
+                    amount: token.amount.to_string(),
+                })
+                .collect(),
+            sender: pkt_data.sender.to_string(),
+            receiver: pkt_data.receiver.to_string(),
+            memo: pkt_data.memo,
+            forwarding: None,
         }
     }
 }
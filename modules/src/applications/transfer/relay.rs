@@ -2,7 +2,7 @@
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
 use crate::applications::transfer::is_sender_chain_source;
-use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::packet::PacketDataV2;
 use crate::core::ics04_channel::packet::Packet;
 use crate::prelude::*;
 
@@ -14,7 +14,7 @@ pub mod send_transfer;
 fn refund_packet_token(
     ctx: &mut impl Ics20Context,
     packet: &Packet,
-    data: &PacketData,
+    data: &PacketDataV2,
 ) -> Result<(), Ics20Error> {
     let sender = data
         .sender
@@ -22,19 +22,23 @@ fn refund_packet_token(
         .try_into()
         .map_err(|_| Ics20Error::parse_account_failure())?;
 
-    if is_sender_chain_source(
-        packet.source_port.clone(),
-        packet.source_channel,
-        &data.token.denom,
-    ) {
-        // unescrow tokens back to sender
-        let escrow_address =
-            ctx.get_channel_escrow_address(&packet.source_port, packet.source_channel)?;
+    for token in &data.tokens {
+        if is_sender_chain_source(
+            packet.source_port.clone(),
+            packet.source_channel,
+            &token.denom,
+        ) {
+            // unescrow tokens back to sender
+            let escrow_address =
+                ctx.get_channel_escrow_address(&packet.source_port, packet.source_channel)?;
 
-        ctx.send_coins(&escrow_address, &sender, &data.token)
-    }
-    // mint vouchers back to sender
-    else {
-        ctx.mint_coins(&sender, &data.token)
+            ctx.send_coins(&escrow_address, &sender, token)?;
+        }
+        // mint vouchers back to sender
+        else {
+            ctx.mint_coins(&sender, token)?;
+        }
     }
+
+    Ok(())
 }
@@ -1,14 +1,14 @@
 use crate::applications::transfer::acknowledgement::Acknowledgement;
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
-use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::packet::PacketDataV2;
 use crate::applications::transfer::relay::refund_packet_token;
 use crate::core::ics04_channel::packet::Packet;
 
 pub fn process_ack_packet(
     ctx: &mut impl Ics20Context,
     packet: &Packet,
-    data: &PacketData,
+    data: &PacketDataV2,
     ack: &Acknowledgement,
 ) -> Result<(), Ics20Error> {
     if matches!(ack, Acknowledgement::Error(_)) {
@@ -1,17 +1,23 @@
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
 use crate::applications::transfer::events::DenomTraceEvent;
-use crate::applications::transfer::packet::PacketData;
-use crate::applications::transfer::{is_receiver_chain_source, TracePrefix};
+use crate::applications::transfer::packet::PacketDataV2;
+use crate::applications::transfer::{
+    is_receiver_chain_source, Amount, PrefixedCoin, PrefixedDenom, TracePrefix,
+};
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics26_routing::context::{ModuleOutputBuilder, WriteFn};
 use crate::prelude::*;
+use alloc::collections::btree_map::BTreeMap;
 
+/// Processes the receipt of all the tokens of `data`. Every token is validated, and the escrow
+/// balance checked against the tokens to unescrow, before the returned write function credits any
+/// of them, so that the tokens of a packet are either all received or all rejected.
 pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
     ctx: &Ctx,
     output: &mut ModuleOutputBuilder,
     packet: &Packet,
-    data: PacketData,
+    data: PacketDataV2,
 ) -> Result<Box<WriteFn>, Ics20Error> {
     if !ctx.is_receive_enabled() {
         return Err(Ics20Error::receive_disabled());
@@ -23,46 +29,67 @@ pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
         .try_into()
         .map_err(|_| Ics20Error::parse_account_failure())?;
 
-    if is_receiver_chain_source(
-        packet.source_port.clone(),
-        packet.source_channel,
-        &data.token.denom,
-    ) {
-        // sender chain is not the source, unescrow tokens
-        let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
-        let coin = {
-            let mut c = data.token;
-            c.denom.remove_trace_prefix(&prefix);
-            c
-        };
+    let mut unescrowed: Vec<PrefixedCoin> = vec![];
+    let mut minted: Vec<PrefixedCoin> = vec![];
+    for mut coin in data.tokens {
+        if is_receiver_chain_source(
+            packet.source_port.clone(),
+            packet.source_channel,
+            &coin.denom,
+        ) {
+            // sender chain is not the source, unescrow tokens
+            let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
+            coin.denom.remove_trace_prefix(&prefix);
+            unescrowed.push(coin);
+        } else {
+            // sender chain is the source, mint vouchers
+            let prefix =
+                TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
+            coin.denom.add_trace_prefix(prefix);
+            minted.push(coin);
+        }
+    }
 
+    let escrow_address = if unescrowed.is_empty() {
+        None
+    } else {
         let escrow_address =
             ctx.get_channel_escrow_address(&packet.destination_port, packet.destination_channel)?;
+        let mut totals: BTreeMap<&PrefixedDenom, Amount> = BTreeMap::new();
+        for coin in &unescrowed {
+            let total = totals.entry(&coin.denom).or_insert_with(|| 0u64.into());
+            *total = total
+                .checked_add(coin.amount)
+                .ok_or_else(|| Ics20Error::insufficient_escrow(coin.denom.clone()))?;
+        }
+        for (denom, total) in totals {
+            if ctx.get_balance(&escrow_address, denom) < total {
+                return Err(Ics20Error::insufficient_escrow(denom.clone()));
+            }
+        }
+        Some(escrow_address)
+    };
 
-        Ok(Box::new(move |ctx| {
-            let ctx = ctx.downcast_mut::<Ctx>().unwrap();
-            ctx.send_coins(&escrow_address, &receiver_account, &coin)
-                .map_err(|e| e.to_string())
-        }))
-    } else {
-        // sender chain is the source, mint vouchers
-        let prefix = TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
-        let coin = {
-            let mut c = data.token;
-            c.denom.add_trace_prefix(prefix);
-            c
-        };
-
+    for coin in &minted {
         let denom_trace_event = DenomTraceEvent {
             trace_hash: ctx.denom_hash_string(&coin.denom),
             denom: coin.denom.clone(),
         };
         output.emit(denom_trace_event.into());
-
-        Ok(Box::new(move |ctx| {
-            let ctx = ctx.downcast_mut::<Ctx>().unwrap();
-            ctx.mint_coins(&receiver_account, &coin)
-                .map_err(|e| e.to_string())
-        }))
     }
+
+    Ok(Box::new(move |ctx| {
+        let ctx = ctx.downcast_mut::<Ctx>().unwrap();
+        if let Some(escrow_address) = escrow_address {
+            for coin in &unescrowed {
+                ctx.send_coins(&escrow_address, &receiver_account, coin)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        for coin in &minted {
+            ctx.mint_coins(&receiver_account, coin)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }))
 }
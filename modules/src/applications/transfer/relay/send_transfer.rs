@@ -1,16 +1,17 @@
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error;
 use crate::applications::transfer::events::TransferEvent;
-use crate::applications::transfer::msgs::transfer::MsgTransfer;
-use crate::applications::transfer::packet::PacketData;
-use crate::applications::transfer::{is_sender_chain_source, Coin, PrefixedCoin};
+use crate::applications::transfer::is_sender_chain_source;
+use crate::applications::transfer::msgs::transfer::{MsgTransfer, TransferTokens};
+use crate::applications::transfer::packet::PacketDataV2;
 use crate::core::ics04_channel::handler::send_packet::send_packet;
 use crate::core::ics04_channel::packet::Packet;
 use crate::events::ModuleEvent;
 use crate::handler::{HandlerOutput, HandlerOutputBuilder};
 use crate::prelude::*;
 
-/// This function handles the transfer sending logic.
+/// This function handles the transfer sending logic. All the tokens of `msg` are sent in a single
+/// packet, which requires an `ics20-2` channel if there are more than one.
 /// If this method returns an error, the runtime is expected to rollback all state modifications to
 /// the `Ctx` caused by all messages from the transaction that this `msg` is a part of.
 pub fn send_transfer<Ctx, C>(
@@ -20,7 +21,7 @@ pub fn send_transfer<Ctx, C>(
 ) -> Result<(), Error>
 where
     Ctx: Ics20Context,
-    C: TryInto<PrefixedCoin>,
+    C: TransferTokens,
 {
     if !ctx.is_send_enabled() {
         return Err(Error::send_disabled());
@@ -43,12 +44,17 @@ where
         .get_next_sequence_send(&(msg.source_port.clone(), msg.source_channel))
         .map_err(Error::ics04_channel)?;
 
-    let token = msg.token.try_into().map_err(|_| Error::invalid_token())?;
-    let denom = token.denom.clone();
-    let coin = Coin {
-        denom: denom.clone(),
-        amount: token.amount,
-    };
+    let tokens = msg.token.into_prefixed_coins()?;
+
+    // The packet is encoded before any tokens are moved, so that transfers which the channel
+    // version does not allow fail without side effects.
+    let data = PacketDataV2 {
+        tokens: tokens.clone(),
+        sender: msg.sender.clone(),
+        receiver: msg.receiver.clone(),
+        memo: msg.memo,
+    }
+    .encode(source_channel_end.version())?;
 
     let sender = msg
         .sender
@@ -56,26 +62,9 @@ where
         .try_into()
         .map_err(|_| Error::parse_account_failure())?;
 
-    if is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &denom) {
-        let escrow_address =
-            ctx.get_channel_escrow_address(&msg.source_port, msg.source_channel)?;
-        ctx.send_coins(&sender, &escrow_address, &coin)?;
-    } else {
-        ctx.burn_coins(&sender, &coin)?;
-    }
-
-    let data = {
-        let data = PacketData {
-            token: coin,
-            sender: msg.sender.clone(),
-            receiver: msg.receiver.clone(),
-        };
-        serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
-    };
-
     let packet = Packet {
         sequence,
-        source_port: msg.source_port,
+        source_port: msg.source_port.clone(),
         source_channel: msg.source_channel,
         destination_port,
         destination_channel,
@@ -84,6 +73,19 @@ where
         timeout_timestamp: msg.timeout_timestamp,
     };
 
+    // The host gets to reject the transfer before any tokens are moved.
+    ctx.on_send_transfer(&packet, &tokens)?;
+
+    for coin in &tokens {
+        if is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &coin.denom) {
+            let escrow_address =
+                ctx.get_channel_escrow_address(&msg.source_port, msg.source_channel)?;
+            ctx.send_coins(&sender, &escrow_address, coin)?;
+        } else {
+            ctx.burn_coins(&sender, coin)?;
+        }
+    }
+
     let HandlerOutput {
         result,
         log,
@@ -100,9 +102,14 @@ where
             .with_result(()),
     );
 
+    let tokens = tokens
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
     output.log(format!(
         "IBC fungible token transfer: {} --({})--> {}",
-        msg.sender, token, msg.receiver
+        msg.sender, tokens, msg.receiver
     ));
 
     let transfer_event = TransferEvent {
@@ -0,0 +1,842 @@
+use prost::Message;
+use tendermint::Signature;
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::Sum;
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as RawSignatureData;
+use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ibc::core::connection::v1::ConnectionEnd as RawConnectionEnd;
+use ibc_proto::ibc::lightclients::solomachine::v2::{
+    ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
+    HeaderData, NextSequenceRecvData, PacketAcknowledgementData, PacketCommitmentData,
+    PacketReceiptAbsenceData, SignBytes, TimestampedSignatureData,
+};
+
+use crate::clients::ics06_solomachine::client_state::ClientState;
+use crate::clients::ics06_solomachine::consensus_state::{encode_public_key, ConsensusState};
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::header::Header;
+use crate::clients::ics06_solomachine::misbehaviour::Misbehaviour;
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_def::ClientDef;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::ConnectionReader;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics23_commitment::merkle::apply_prefix;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
+    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+};
+use crate::core::ics24_host::Path;
+use crate::prelude::*;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// Solo machine clients verify signatures produced by the public key of the solo machine,
+/// rather than Merkle proofs. Each signature is over the sequence of the solo machine, the
+/// timestamp, the diversifier and the signed data, in the format of the ibc-go solo machine.
+///
+/// Every verified signature advances the sequence of the client, so that a signature cannot be
+/// replayed, and the `verify_*` functions return the client state with the advanced sequence.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoloMachineClient;
+
+impl ClientDef for SoloMachineClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Ics02Error> {
+        if header.sequence != client_state.sequence {
+            return Err(Error::sequence_mismatch(client_state.sequence, header.sequence).into());
+        }
+
+        let consensus_timestamp = client_state.consensus_state.timestamp.nanoseconds();
+        let header_timestamp = header.timestamp.nanoseconds();
+        if header_timestamp < consensus_timestamp {
+            return Err(Error::low_timestamp(consensus_timestamp, header_timestamp).into());
+        }
+
+        let data = HeaderData {
+            new_pub_key: Some(encode_public_key(&header.new_public_key)),
+            new_diversifier: header.new_diversifier.clone(),
+        }
+        .encode_to_vec();
+        verify_signature(
+            &client_state.consensus_state,
+            header.sequence,
+            header_timestamp,
+            DataType::Header,
+            data,
+            &header.signature,
+        )?;
+
+        let consensus_state = ConsensusState::new(
+            header.new_public_key,
+            header.new_diversifier,
+            header.timestamp,
+        );
+
+        Ok((
+            client_state.with_consensus_state(consensus_state.clone()),
+            consensus_state,
+        ))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Ics02Error> {
+        // The timestamps of the signatures are not checked against the consensus state, so
+        // that misbehaviour that happened in the past can still be processed.
+        for signature in [&misbehaviour.signature_one, &misbehaviour.signature_two] {
+            verify_signature(
+                &client_state.consensus_state,
+                misbehaviour.sequence,
+                signature.timestamp,
+                signature.data_type,
+                signature.data.clone(),
+                &signature.signature,
+            )?;
+        }
+
+        Ok(client_state.with_frozen())
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _client_state: &Self::ClientState,
+        _consensus_state: &Self::ConsensusState,
+        _upgraded_client_state: &Self::ClientState,
+        _upgraded_consensus_state: &Self::ConsensusState,
+        _proof_upgrade_client: RawMerkleProof,
+        _proof_upgrade_consensus_state: RawMerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Ics02Error> {
+        Err(Error::upgrade_not_supported().into())
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let path = ClientConsensusStatePath {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let data = ConsensusStateData {
+            path: prefixed_path(prefix, path),
+            consensus_state: Some(expected_consensus_state.clone().into()),
+        }
+        .encode_to_vec();
+        verify_signature_proof(client_state, height, proof, DataType::ConsensusState, data)
+            .map(Some)
+    }
+
+    fn verify_connection_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: &ConnectionId,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let path = ConnectionsPath(connection_id.clone());
+        let data = ConnectionStateData {
+            path: prefixed_path(prefix, path),
+            connection: Some(RawConnectionEnd::from(expected_connection_end.clone())),
+        }
+        .encode_to_vec();
+        verify_signature_proof(client_state, height, proof, DataType::ConnectionState, data)
+            .map(Some)
+    }
+
+    fn verify_channel_state(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let path = ChannelEndsPath(port_id.clone(), *channel_id);
+        let data = ChannelStateData {
+            path: prefixed_path(prefix, path),
+            channel: Some(RawChannel::from(expected_channel_end.clone())),
+        }
+        .encode_to_vec();
+        verify_signature_proof(client_state, height, proof, DataType::ChannelState, data).map(Some)
+    }
+
+    fn verify_client_full_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let path = ClientStatePath(client_id.clone());
+        let data = ClientStateData {
+            path: prefixed_path(prefix, path),
+            client_state: Some(expected_client_state.clone().into()),
+        }
+        .encode_to_vec();
+        verify_signature_proof(client_state, height, proof, DataType::ClientState, data).map(Some)
+    }
+
+    fn verify_packet_data(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        commitment: PacketCommitment,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let commitment_path = CommitmentsPath {
+            port_id: port_id.clone(),
+            channel_id: *channel_id,
+            sequence,
+        };
+        let data = PacketCommitmentData {
+            path: prefixed_path(connection_end.counterparty().prefix(), commitment_path),
+            commitment: commitment.into_vec(),
+        }
+        .encode_to_vec();
+        verify_signature_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketCommitment,
+            data,
+        )
+        .map(Some)
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let ack_path = AcksPath {
+            port_id: port_id.clone(),
+            channel_id: *channel_id,
+            sequence,
+        };
+        let data = PacketAcknowledgementData {
+            path: prefixed_path(connection_end.counterparty().prefix(), ack_path),
+            acknowledgement: ack_commitment.into_vec(),
+        }
+        .encode_to_vec();
+        verify_signature_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketAcknowledgement,
+            data,
+        )
+        .map(Some)
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let seq_path = SeqRecvsPath(port_id.clone(), *channel_id);
+        let data = NextSequenceRecvData {
+            path: prefixed_path(connection_end.counterparty().prefix(), seq_path),
+            next_seq_recv: sequence.into(),
+        }
+        .encode_to_vec();
+        verify_signature_proof(
+            client_state,
+            height,
+            proof,
+            DataType::NextSequenceRecv,
+            data,
+        )
+        .map(Some)
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        let receipt_path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: *channel_id,
+            sequence,
+        };
+        let data = PacketReceiptAbsenceData {
+            path: prefixed_path(connection_end.counterparty().prefix(), receipt_path),
+        }
+        .encode_to_vec();
+        verify_signature_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketReceiptAbsence,
+            data,
+        )
+        .map(Some)
+    }
+}
+
+/// Verifies `proof`, the `TimestampedSignatureData` produced by the solo machine over `data`
+/// at the current sequence of the client, against the current consensus state of the client.
+///
+/// Returns the client state with the sequence advanced and the consensus timestamp set to the
+/// timestamp of the proof, which must be stored for the signature not to be replayed.
+fn verify_signature_proof(
+    client_state: &ClientState,
+    height: Height,
+    proof: &CommitmentProofBytes,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Result<ClientState, Ics02Error> {
+    if client_state.is_frozen {
+        return Err(Error::client_frozen().into());
+    }
+
+    // The sequence of the solo machine is encoded as the revision height. Messages that carry
+    // several proofs verify them at successive sequences from the same proof height, so the
+    // proof height may be behind the sequence of the client, but never ahead of it.
+    if height.revision_number != 0 {
+        return Err(Error::invalid_proof_height(height).into());
+    }
+    if height.revision_height > client_state.sequence {
+        return Err(Error::sequence_mismatch(client_state.sequence, height.revision_height).into());
+    }
+
+    let proof_bytes: Vec<u8> = proof.clone().into();
+    let TimestampedSignatureData {
+        signature_data,
+        timestamp,
+    } = TimestampedSignatureData::decode(proof_bytes.as_slice()).map_err(Error::decode)?;
+
+    let consensus_timestamp = client_state.consensus_state.timestamp.nanoseconds();
+    if consensus_timestamp > timestamp {
+        return Err(Error::low_timestamp(consensus_timestamp, timestamp).into());
+    }
+
+    verify_signature(
+        &client_state.consensus_state,
+        client_state.sequence,
+        timestamp,
+        data_type,
+        data,
+        &signature_data,
+    )?;
+
+    let mut client_state = client_state.clone();
+    client_state.sequence += 1;
+    client_state.consensus_state.timestamp =
+        Timestamp::from_nanoseconds(timestamp).map_err(Error::invalid_timestamp)?;
+    Ok(client_state)
+}
+
+/// Verifies that `signature_data`, an encoded single signature, was produced by the public key
+/// of `consensus_state` over the sign bytes of `data`.
+fn verify_signature(
+    consensus_state: &ConsensusState,
+    sequence: u64,
+    timestamp: u64,
+    data_type: DataType,
+    data: Vec<u8>,
+    signature_data: &[u8],
+) -> Result<(), Error> {
+    if signature_data.is_empty() {
+        return Err(Error::empty_signature_data());
+    }
+
+    let signature = match RawSignatureData::decode(signature_data)
+        .map_err(Error::decode)?
+        .sum
+    {
+        Some(Sum::Single(single)) => single.signature,
+        Some(Sum::Multi(_)) => return Err(Error::multisig_not_supported()),
+        None => return Err(Error::missing_signature_data()),
+    };
+    let signature = Signature::try_from(signature).map_err(Error::invalid_signature)?;
+
+    let sign_bytes = SignBytes {
+        sequence,
+        timestamp,
+        diversifier: consensus_state.diversifier.clone(),
+        data_type: data_type as i32,
+        data,
+    }
+    .encode_to_vec();
+
+    consensus_state
+        .public_key
+        .verify(&sign_bytes, &signature)
+        .map_err(Error::invalid_signature)
+}
+
+/// Returns the path of a value under `prefix`, in the string representation of Merkle paths
+/// used by ibc-go, which is what solo machines sign over.
+fn prefixed_path(prefix: &CommitmentPrefix, path: impl Into<Path>) -> Vec<u8> {
+    apply_prefix(prefix, vec![path.into().to_string()])
+        .key_path
+        .iter()
+        .map(|key| format!("/{}", escape_path_segment(key)))
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// Escapes a path segment in the same way as `url.PathEscape` in Go.
+fn escape_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~'
+            | b'$'
+            | b'&'
+            | b'+'
+            | b':'
+            | b'='
+            | b'@' => char::from(b).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use ibc_proto::ibc::core::connection::v1::ConnectionEnd as RawConnectionEnd;
+    use ibc_proto::ibc::lightclients::solomachine::v2::{
+        ConnectionStateData, DataType, HeaderData,
+    };
+    use prost::Message;
+
+    use super::{escape_path_segment, prefixed_path, SoloMachineClient};
+    use crate::clients::ics06_solomachine::client_state::test_util::SoloMachine;
+    use crate::clients::ics06_solomachine::client_state::ClientState as SoloMachineClientState;
+    use crate::clients::ics06_solomachine::consensus_state::encode_public_key;
+    use crate::clients::ics06_solomachine::header::Header;
+    use crate::clients::ics06_solomachine::misbehaviour::{Misbehaviour, SignatureAndData};
+    use crate::core::ics02_client::client_def::ClientDef;
+    use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics02_client::context::ClientReader;
+    use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
+    use crate::core::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
+    use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+    use crate::core::ics03_connection::msgs::ConnectionMsg;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics23_commitment::commitment::{
+        CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+    };
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::core::ics24_host::path::ConnectionsPath;
+    use crate::core::ics26_routing::msgs::Ics26Envelope;
+    use crate::mock::context::MockContext;
+    use crate::proofs::Proofs;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::Height;
+
+    fn header(solo_machine: &SoloMachine, new_key: &SoloMachine) -> Header {
+        let data = HeaderData {
+            new_pub_key: Some(encode_public_key(&new_key.public_key())),
+            new_diversifier: new_key.diversifier.clone(),
+        }
+        .encode_to_vec();
+
+        Header {
+            sequence: solo_machine.sequence,
+            timestamp: Timestamp::from_nanoseconds(solo_machine.timestamp).unwrap(),
+            signature: solo_machine.sign(DataType::Header, data),
+            new_public_key: new_key.public_key(),
+            new_diversifier: new_key.diversifier.clone(),
+        }
+    }
+
+    fn connection_state_data(prefix: &CommitmentPrefix, connection_end: &ConnectionEnd) -> Vec<u8> {
+        ConnectionStateData {
+            path: prefixed_path(prefix, ConnectionsPath(ConnectionId::default())),
+            connection: Some(RawConnectionEnd::from(connection_end.clone())),
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn path_escaping() {
+        assert_eq!(
+            escape_path_segment("connections/connection-0"),
+            "connections%2Fconnection-0"
+        );
+        assert_eq!(escape_path_segment("a+b#c[d]"), "a+b%23c%5Bd%5D");
+
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        assert_eq!(
+            prefixed_path(&prefix, ConnectionsPath(ConnectionId::default())),
+            b"/ibc/connections%2Fconnection-0".to_vec()
+        );
+    }
+
+    #[test]
+    fn update_client_with_header() {
+        let ctx = MockContext::default();
+        let solo_machine = SoloMachine::new("solo", "diversifier");
+        let new_key = SoloMachine::new("solo-rotated", "new-diversifier");
+
+        let (client_state, consensus_state) = SoloMachineClient
+            .check_header_and_update_state(
+                &ctx,
+                ClientId::default(),
+                solo_machine.client_state(),
+                header(&solo_machine, &new_key),
+            )
+            .unwrap();
+
+        assert_eq!(client_state.latest_height(), Height::new(0, 2));
+        assert_eq!(consensus_state.public_key, new_key.public_key());
+        assert_eq!(consensus_state.diversifier, new_key.diversifier);
+        assert_eq!(client_state.consensus_state, consensus_state);
+    }
+
+    #[test]
+    fn update_client_with_invalid_header() {
+        let ctx = MockContext::default();
+        let solo_machine = SoloMachine::new("solo", "diversifier");
+        let impostor = SoloMachine::new("impostor", "diversifier");
+        let new_key = SoloMachine::new("solo-rotated", "new-diversifier");
+
+        // Signed by a key other than the one of the client.
+        let res = SoloMachineClient.check_header_and_update_state(
+            &ctx,
+            ClientId::default(),
+            solo_machine.client_state(),
+            header(&impostor, &new_key),
+        );
+        assert!(res.is_err());
+
+        // Signed over data other than the new public key.
+        let mut tampered = header(&solo_machine, &new_key);
+        tampered.new_public_key = impostor.public_key();
+        let res = SoloMachineClient.check_header_and_update_state(
+            &ctx,
+            ClientId::default(),
+            solo_machine.client_state(),
+            tampered,
+        );
+        assert!(res.is_err());
+
+        // At a sequence other than the one of the client.
+        let mut ahead = SoloMachine::new("solo", "diversifier");
+        ahead.sequence = 2;
+        let res = SoloMachineClient.check_header_and_update_state(
+            &ctx,
+            ClientId::default(),
+            solo_machine.client_state(),
+            header(&ahead, &new_key),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn update_client_from_mock_context() {
+        let client_id = ClientId::new(ClientType::SoloMachine, 0).unwrap();
+        let ctx = MockContext::default().with_client_parametrized(
+            &client_id,
+            Height::new(0, 5),
+            Some(ClientType::SoloMachine),
+            None,
+        );
+
+        let client_state = match ClientReader::client_state(&ctx, &client_id).unwrap() {
+            AnyClientState::SoloMachine(client_state) => client_state,
+            _ => panic!("expected a solo machine client state"),
+        };
+        assert_eq!(client_state.latest_height(), Height::new(0, 5));
+
+        // The mock context derives the solo machine fixture from the client height.
+        let mut solo_machine = SoloMachine::new("solomachine", "mock");
+        solo_machine.sequence = 5;
+        let new_key = SoloMachine::new("solo-rotated", "new-diversifier");
+
+        let (client_state, _) = SoloMachineClient
+            .check_header_and_update_state(
+                &ctx,
+                client_id,
+                client_state,
+                header(&solo_machine, &new_key),
+            )
+            .unwrap();
+        assert_eq!(client_state.latest_height(), Height::new(0, 6));
+    }
+
+    #[test]
+    fn verify_connection_state() {
+        let ctx = MockContext::default();
+        let solo_machine = SoloMachine::new("solo", "diversifier");
+        let client_state = solo_machine.client_state();
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let connection_end = ConnectionEnd::default();
+
+        let proof = solo_machine.proof(
+            DataType::ConnectionState,
+            connection_state_data(&prefix, &connection_end),
+        );
+        let verify = |height: Height, connection_end: &ConnectionEnd| {
+            SoloMachineClient.verify_connection_state(
+                &ctx,
+                &client_state,
+                height,
+                &prefix,
+                &proof,
+                &CommitmentRoot::empty(),
+                &ConnectionId::default(),
+                connection_end,
+            )
+        };
+
+        assert!(verify(Height::new(0, 1), &connection_end).is_ok());
+
+        // The proof is for a different connection end.
+        let mut other_connection_end = connection_end.clone();
+        other_connection_end.set_state(State::Open);
+        assert!(verify(Height::new(0, 1), &other_connection_end).is_err());
+
+        // The proof height is ahead of the sequence of the client, or not a solo machine height.
+        assert!(verify(Height::new(0, 2), &connection_end).is_err());
+        assert!(verify(Height::new(1, 1), &connection_end).is_err());
+    }
+
+    #[test]
+    fn verified_proof_cannot_be_replayed() {
+        let ctx = MockContext::default();
+        let mut solo_machine = SoloMachine::new("solo", "diversifier");
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let connection_end = ConnectionEnd::default();
+        let data = connection_state_data(&prefix, &connection_end);
+
+        let verify = |client_state: &SoloMachineClientState, proof: &CommitmentProofBytes| {
+            SoloMachineClient.verify_connection_state(
+                &ctx,
+                client_state,
+                Height::new(0, 1),
+                &prefix,
+                proof,
+                &CommitmentRoot::empty(),
+                &ConnectionId::default(),
+                &connection_end,
+            )
+        };
+
+        let proof = solo_machine.proof(DataType::ConnectionState, data.clone());
+        let client_state = verify(&solo_machine.client_state(), &proof)
+            .unwrap()
+            .expect("a verified proof must advance the sequence");
+        assert_eq!(client_state.sequence, 2);
+
+        // The same signature does not verify once the sequence has advanced.
+        assert!(verify(&client_state, &proof).is_err());
+
+        // A signature at the new sequence does, from the same proof height.
+        solo_machine.sequence = 2;
+        let proof = solo_machine.proof(DataType::ConnectionState, data);
+        let client_state = verify(&client_state, &proof).unwrap().unwrap();
+        assert_eq!(client_state.sequence, 3);
+    }
+
+    #[test]
+    fn handler_stores_advanced_sequence() {
+        let client_id = ClientId::new(ClientType::SoloMachine, 0).unwrap();
+        let connection_id = ConnectionId::new(0);
+        let counterparty_connection_id = ConnectionId::new(1);
+        let counterparty_prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+
+        let mut ctx = MockContext::default().with_client_parametrized(
+            &client_id,
+            Height::new(0, 5),
+            Some(ClientType::SoloMachine),
+            None,
+        );
+        let connection_end = ConnectionEnd::new(
+            State::TryOpen,
+            client_id.clone(),
+            Counterparty::new(
+                ClientId::default(),
+                Some(counterparty_connection_id.clone()),
+                counterparty_prefix.clone(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+        ctx.store_connection(connection_id.clone(), &connection_end)
+            .unwrap();
+
+        // The connection end the solo machine signs over, as expected by the handler.
+        let expected_connection_end = ConnectionEnd::new(
+            State::Open,
+            ClientId::default(),
+            Counterparty::new(
+                client_id.clone(),
+                Some(connection_id.clone()),
+                ctx.commitment_prefix(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+        let data = ConnectionStateData {
+            path: prefixed_path(
+                &counterparty_prefix,
+                ConnectionsPath(counterparty_connection_id),
+            ),
+            connection: Some(RawConnectionEnd::from(expected_connection_end)),
+        }
+        .encode_to_vec();
+
+        // The mock context derives the solo machine fixture from the client height.
+        let mut solo_machine = SoloMachine::new("solomachine", "mock");
+        solo_machine.sequence = 5;
+        let msg = MsgConnectionOpenConfirm {
+            connection_id: connection_id.clone(),
+            proofs: Proofs::new(
+                solo_machine.proof(DataType::ConnectionState, data),
+                None,
+                None,
+                None,
+                Height::new(0, 5),
+            )
+            .unwrap(),
+            signer: get_dummy_account_id(),
+        };
+        let envelope = Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenConfirm(msg));
+
+        ctx.deliver(envelope.clone()).unwrap();
+        let client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+        assert_eq!(client_state.latest_height(), Height::new(0, 6));
+
+        // Replaying the message fails, even with the connection back in its previous state.
+        ctx.store_connection(connection_id, &connection_end)
+            .unwrap();
+        assert!(ctx.deliver(envelope).is_err());
+    }
+
+    #[test]
+    fn misbehaviour_freezes_client() {
+        let ctx = MockContext::default();
+        let solo_machine = SoloMachine::new("solo", "diversifier");
+        let signature_and_data = |data: &[u8]| SignatureAndData {
+            signature: solo_machine.sign(DataType::ConnectionState, data.to_vec()),
+            data_type: DataType::ConnectionState,
+            data: data.to_vec(),
+            timestamp: solo_machine.timestamp,
+        };
+
+        let misbehaviour = Misbehaviour {
+            client_id: ClientId::default(),
+            sequence: solo_machine.sequence,
+            signature_one: signature_and_data(b"one"),
+            signature_two: signature_and_data(b"two"),
+        };
+
+        let client_state = SoloMachineClient
+            .check_misbehaviour_and_update_state(
+                &ctx,
+                ClientId::default(),
+                solo_machine.client_state(),
+                misbehaviour.clone(),
+            )
+            .unwrap();
+        assert!(client_state.is_frozen());
+
+        // A signature by another key is not evidence of misbehaviour.
+        let impostor = SoloMachine::new("impostor", "diversifier");
+        let forged = Misbehaviour {
+            signature_two: SignatureAndData {
+                signature: impostor.sign(DataType::ConnectionState, b"two".to_vec()),
+                ..misbehaviour.signature_two
+            },
+            ..misbehaviour
+        };
+        let res = SoloMachineClient.check_misbehaviour_and_update_state(
+            &ctx,
+            ClientId::default(),
+            solo_machine.client_state(),
+            forged,
+        );
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v2::ClientState as RawClientState;
+
+use crate::clients::ics06_solomachine::consensus_state::ConsensusState;
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The client state of a solo machine. The sequence of the solo machine is incremented with
+/// every header and is used as the revision height of the client, with a revision number of 0.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    pub sequence: u64,
+    pub is_frozen: bool,
+    pub consensus_state: ConsensusState,
+    pub allow_update_after_proposal: bool,
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl ClientState {
+    pub fn new(
+        sequence: u64,
+        consensus_state: ConsensusState,
+        allow_update_after_proposal: bool,
+    ) -> Result<ClientState, Error> {
+        if sequence == 0 {
+            return Err(Error::invalid_raw_client_state(
+                "sequence cannot be 0".into(),
+            ));
+        }
+
+        Ok(Self {
+            sequence,
+            is_frozen: false,
+            consensus_state,
+            allow_update_after_proposal,
+        })
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    /// Returns the client state as updated by a header, which increments the sequence and
+    /// replaces the consensus state.
+    pub fn with_consensus_state(self, consensus_state: ConsensusState) -> Self {
+        Self {
+            sequence: self.sequence + 1,
+            consensus_state,
+            ..self
+        }
+    }
+
+    pub fn with_frozen(self) -> Self {
+        Self {
+            is_frozen: true,
+            ..self
+        }
+    }
+
+    /// Solo machine clients do not expire, so there is no need to refresh them.
+    pub fn refresh_time(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Solo machine clients do not expire.
+    pub fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+}
+
+impl crate::core::ics02_client::client_state::ClientState for ClientState {
+    type UpgradeOptions = ();
+
+    /// Solo machines are not identified by a chain identifier; the diversifier, which
+    /// distinguishes the clients of the same solo machine, is used instead.
+    fn chain_id(&self) -> ChainId {
+        ChainId::from_string(&self.consensus_state.diversifier)
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.is_frozen.then(|| self.latest_height())
+    }
+
+    fn upgrade(self, upgrade_height: Height, _upgrade_options: (), _chain_id: ChainId) -> Self {
+        Self {
+            sequence: upgrade_height.revision_height,
+            is_frozen: false,
+            ..self
+        }
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::SoloMachine(self)
+    }
+}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Error::invalid_raw_client_state(
+                "sequence cannot be 0".into(),
+            ));
+        }
+
+        Ok(Self {
+            sequence: raw.sequence,
+            is_frozen: raw.is_frozen,
+            consensus_state: raw
+                .consensus_state
+                .ok_or_else(|| Error::invalid_raw_client_state("missing consensus state".into()))?
+                .try_into()?,
+            allow_update_after_proposal: raw.allow_update_after_proposal,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            sequence: value.sequence,
+            is_frozen: value.is_frozen,
+            consensus_state: Some(value.consensus_state.into()),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+pub mod test_util {
+    use crate::prelude::*;
+
+    use prost::Message;
+    use tendermint::signature::Signer;
+    use tendermint::PublicKey;
+    use tendermint_testgen::Validator;
+
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::{Single, Sum};
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as RawSignatureData;
+    use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+    use ibc_proto::ibc::lightclients::solomachine::v2::{
+        DataType, SignBytes, TimestampedSignatureData,
+    };
+
+    use crate::clients::ics06_solomachine::client_state::ClientState;
+    use crate::clients::ics06_solomachine::consensus_state::ConsensusState;
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::timestamp::Timestamp;
+
+    /// A solo machine holding an ed25519 key, which produces the signatures verified by the
+    /// solo machine client.
+    pub struct SoloMachine {
+        pub sequence: u64,
+        pub diversifier: String,
+        pub timestamp: u64,
+        key: tendermint::private_key::Ed25519,
+    }
+
+    impl SoloMachine {
+        pub fn new(key_id: &str, diversifier: &str) -> Self {
+            Self {
+                sequence: 1,
+                diversifier: diversifier.to_string(),
+                timestamp: 1_000_000_000,
+                key: Validator::new(key_id).get_private_key().unwrap(),
+            }
+        }
+
+        pub fn public_key(&self) -> PublicKey {
+            PublicKey::from_raw_ed25519(self.key.public.as_bytes()).unwrap()
+        }
+
+        pub fn consensus_state(&self) -> ConsensusState {
+            ConsensusState::new(
+                self.public_key(),
+                self.diversifier.clone(),
+                Timestamp::from_nanoseconds(self.timestamp).unwrap(),
+            )
+        }
+
+        pub fn client_state(&self) -> ClientState {
+            ClientState::new(self.sequence, self.consensus_state(), false).unwrap()
+        }
+
+        /// Signs `data` of type `data_type` at the current sequence and timestamp of the solo
+        /// machine, returning the encoded signature data.
+        pub fn sign(&self, data_type: DataType, data: Vec<u8>) -> Vec<u8> {
+            self.sign_with(self.sequence, self.timestamp, data_type, data)
+        }
+
+        pub fn sign_with(
+            &self,
+            sequence: u64,
+            timestamp: u64,
+            data_type: DataType,
+            data: Vec<u8>,
+        ) -> Vec<u8> {
+            let sign_bytes = SignBytes {
+                sequence,
+                timestamp,
+                diversifier: self.diversifier.clone(),
+                data_type: data_type as i32,
+                data,
+            }
+            .encode_to_vec();
+            let signature: tendermint::signature::Ed25519Signature = self.key.sign(&sign_bytes);
+
+            RawSignatureData {
+                sum: Some(Sum::Single(Single {
+                    mode: SignMode::Direct as i32,
+                    signature: signature.as_ref().to_vec(),
+                })),
+            }
+            .encode_to_vec()
+        }
+
+        /// Produces a proof of `data` of type `data_type` at the current sequence and
+        /// timestamp of the solo machine.
+        pub fn proof(&self, data_type: DataType, data: Vec<u8>) -> CommitmentProofBytes {
+            TimestampedSignatureData {
+                signature_data: self.sign(data_type, data),
+                timestamp: self.timestamp,
+            }
+            .encode_to_vec()
+            .try_into()
+            .unwrap()
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::prelude::*;
+
+use core::convert::Infallible;
+
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint::PublicKey;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v2::ConsensusState as RawConsensusState;
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+pub const SECP256K1_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+pub const ED25519_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+/// Solo machines do not commit to a root, proofs are verified against signatures instead.
+static EMPTY_ROOT: CommitmentRoot = CommitmentRoot::empty();
+
+/// The consensus state of a solo machine, consisting of the public key its signatures are
+/// verified with, and the diversifier and timestamp included in the signed data.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusState {
+    pub public_key: PublicKey,
+    pub diversifier: String,
+    pub timestamp: Timestamp,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: Timestamp) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+        }
+    }
+}
+
+impl crate::core::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    type Error = Infallible;
+
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &EMPTY_ROOT
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::SoloMachine(self)
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        if raw.diversifier.trim().is_empty() {
+            return Err(Error::invalid_raw_consensus_state(
+                "diversifier cannot be blank".into(),
+            ));
+        }
+        if raw.timestamp == 0 {
+            return Err(Error::invalid_raw_consensus_state(
+                "timestamp cannot be 0".into(),
+            ));
+        }
+
+        Ok(Self {
+            public_key: decode_public_key(
+                raw.public_key.ok_or_else(|| {
+                    Error::invalid_raw_consensus_state("missing public key".into())
+                })?,
+            )?,
+            diversifier: raw.diversifier,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(|e| Error::invalid_raw_consensus_state(e.to_string()))?,
+        })
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            public_key: Some(encode_public_key(&value.public_key)),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// The Cosmos SDK encoding of a public key, shared by the secp256k1 and ed25519 key types.
+#[derive(Clone, PartialEq, Message)]
+struct RawPublicKey {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+}
+
+/// Decodes a public key from its Cosmos SDK `Any` encoding.
+pub fn decode_public_key(any: Any) -> Result<PublicKey, Error> {
+    let RawPublicKey { key } = RawPublicKey::decode(any.value.as_slice()).map_err(Error::decode)?;
+
+    let public_key = match any.type_url.as_str() {
+        SECP256K1_PUBLIC_KEY_TYPE_URL => PublicKey::from_raw_secp256k1(&key),
+        ED25519_PUBLIC_KEY_TYPE_URL => PublicKey::from_raw_ed25519(&key),
+        _ => return Err(Error::unknown_public_key_type(any.type_url)),
+    };
+
+    public_key.ok_or_else(|| Error::invalid_public_key(any.type_url))
+}
+
+/// Encodes a public key into its Cosmos SDK `Any` encoding.
+pub fn encode_public_key(public_key: &PublicKey) -> Any {
+    let type_url = match public_key {
+        PublicKey::Secp256k1(_) => SECP256K1_PUBLIC_KEY_TYPE_URL,
+        _ => ED25519_PUBLIC_KEY_TYPE_URL,
+    };
+
+    Any {
+        type_url: type_url.to_string(),
+        value: RawPublicKey {
+            key: public_key.to_bytes(),
+        }
+        .encode_to_vec(),
+    }
+}
@@ -0,0 +1,95 @@
+use crate::prelude::*;
+
+use flex_error::{define_error, TraceError};
+use tendermint::Error as TendermintError;
+
+use crate::core::ics24_host::error::ValidationError;
+use crate::timestamp::ParseTimestampError;
+use crate::Height;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        InvalidRawClientState
+            { reason: String }
+            |e| { format_args!("invalid raw client state: {}", e.reason) },
+
+        InvalidRawConsensusState
+            { reason: String }
+            |e| { format_args!("invalid raw client consensus state: {}", e.reason) },
+
+        InvalidRawHeader
+            { reason: String }
+            |e| { format_args!("invalid raw header: {}", e.reason) },
+
+        InvalidRawMisbehaviour
+            { reason: String }
+            |e| { format_args!("invalid raw misbehaviour: {}", e.reason) },
+
+        InvalidClientId
+            [ ValidationError ]
+            |_| { "invalid client identifier" },
+
+        Decode
+            [ TraceError<prost::DecodeError> ]
+            |_| { "decode error" },
+
+        UnknownPublicKeyType
+            { type_url: String }
+            |e| { format_args!("unknown public key type: {}", e.type_url) },
+
+        InvalidPublicKey
+            { type_url: String }
+            |e| { format_args!("invalid public key of type {}", e.type_url) },
+
+        ClientFrozen
+            |_| { "the solo machine client is frozen" },
+
+        InvalidProofHeight
+            { height: Height }
+            |e| {
+                format_args!("invalid proof height {}: the revision number of a solo machine height must be 0",
+                    e.height)
+            },
+
+        SequenceMismatch
+            {
+                sequence: u64,
+                proof_sequence: u64,
+            }
+            |e| {
+                format_args!("client sequence {0} does not match the sequence {1} of the proof or header",
+                    e.sequence, e.proof_sequence)
+            },
+
+        LowTimestamp
+            {
+                consensus_timestamp: u64,
+                timestamp: u64,
+            }
+            |e| {
+                format_args!("the consensus state timestamp {0} is greater than the signature timestamp {1}",
+                    e.consensus_timestamp, e.timestamp)
+            },
+
+        InvalidTimestamp
+            [ ParseTimestampError ]
+            |_| { "invalid signature timestamp" },
+
+        EmptySignatureData
+            |_| { "signature data cannot be empty" },
+
+        MissingSignatureData
+            |_| { "signature data is missing its single or multi signature" },
+
+        MultisigNotSupported
+            |_| { "multi-signatures are not supported" },
+
+        InvalidSignature
+            [ TendermintError ]
+            |_| { "signature verification failed" },
+
+        UpgradeNotSupported
+            |_| { "solo machine clients cannot be upgraded" },
+    }
+}
@@ -0,0 +1,98 @@
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use tendermint::PublicKey;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v2::Header as RawHeader;
+
+use crate::clients::ics06_solomachine::consensus_state::{decode_public_key, encode_public_key};
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::header::AnyHeader;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// A solo machine header, signed by the current public key of the solo machine at the current
+/// sequence, which sets a new public key and diversifier.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Header {
+    pub sequence: u64,
+    pub timestamp: Timestamp,
+    pub signature: Vec<u8>,
+    pub new_public_key: PublicKey,
+    pub new_diversifier: String,
+}
+
+impl Header {
+    pub fn height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+}
+
+impl crate::core::ics02_client::header::Header for Header {
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn height(&self) -> Height {
+        self.height()
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    fn wrap_any(self) -> AnyHeader {
+        AnyHeader::SoloMachine(self)
+    }
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Error::invalid_raw_header("sequence cannot be 0".into()));
+        }
+        if raw.timestamp == 0 {
+            return Err(Error::invalid_raw_header("timestamp cannot be 0".into()));
+        }
+        if raw.signature.is_empty() {
+            return Err(Error::invalid_raw_header(
+                "signature cannot be empty".into(),
+            ));
+        }
+        if raw.new_diversifier.trim().is_empty() {
+            return Err(Error::invalid_raw_header(
+                "new diversifier cannot be blank".into(),
+            ));
+        }
+
+        Ok(Self {
+            sequence: raw.sequence,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(|e| Error::invalid_raw_header(e.to_string()))?,
+            signature: raw.signature,
+            new_public_key: decode_public_key(
+                raw.new_public_key
+                    .ok_or_else(|| Error::invalid_raw_header("missing new public key".into()))?,
+            )?,
+            new_diversifier: raw.new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawHeader {
+    fn from(value: Header) -> Self {
+        RawHeader {
+            sequence: value.sequence,
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature,
+            new_public_key: Some(encode_public_key(&value.new_public_key)),
+            new_diversifier: value.new_diversifier,
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::prelude::*;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v2::{
+    DataType, Misbehaviour as RawMisbehaviour, SignatureAndData as RawSignatureAndData,
+};
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics02_client::misbehaviour::AnyMisbehaviour;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::Height;
+
+/// Evidence of misbehaviour of a solo machine, consisting of two signatures over different data
+/// at the same sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misbehaviour {
+    pub client_id: ClientId,
+    pub sequence: u64,
+    pub signature_one: SignatureAndData,
+    pub signature_two: SignatureAndData,
+}
+
+/// A signature along with the data signed over, and the type and timestamp it was signed with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureAndData {
+    pub signature: Vec<u8>,
+    pub data_type: DataType,
+    pub data: Vec<u8>,
+    pub timestamp: u64,
+}
+
+impl crate::core::ics02_client::misbehaviour::Misbehaviour for Misbehaviour {
+    fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    fn height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    fn wrap_any(self) -> AnyMisbehaviour {
+        AnyMisbehaviour::SoloMachine(self)
+    }
+}
+
+impl Protobuf<RawMisbehaviour> for Misbehaviour {}
+
+impl TryFrom<RawMisbehaviour> for Misbehaviour {
+    type Error = Error;
+
+    fn try_from(raw: RawMisbehaviour) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Error::invalid_raw_misbehaviour(
+                "sequence cannot be 0".into(),
+            ));
+        }
+
+        let signature_one: SignatureAndData = raw
+            .signature_one
+            .ok_or_else(|| Error::invalid_raw_misbehaviour("missing signature one".into()))?
+            .try_into()?;
+        let signature_two: SignatureAndData = raw
+            .signature_two
+            .ok_or_else(|| Error::invalid_raw_misbehaviour("missing signature two".into()))?
+            .try_into()?;
+
+        if signature_one.signature == signature_two.signature {
+            return Err(Error::invalid_raw_misbehaviour(
+                "misbehaviour signatures cannot be equal".into(),
+            ));
+        }
+        if signature_one.data == signature_two.data {
+            return Err(Error::invalid_raw_misbehaviour(
+                "data signed cannot be equal".into(),
+            ));
+        }
+
+        Ok(Self {
+            client_id: raw.client_id.parse().map_err(Error::invalid_client_id)?,
+            sequence: raw.sequence,
+            signature_one,
+            signature_two,
+        })
+    }
+}
+
+impl From<Misbehaviour> for RawMisbehaviour {
+    fn from(value: Misbehaviour) -> Self {
+        RawMisbehaviour {
+            client_id: value.client_id.to_string(),
+            sequence: value.sequence,
+            signature_one: Some(value.signature_one.into()),
+            signature_two: Some(value.signature_two.into()),
+        }
+    }
+}
+
+impl TryFrom<RawSignatureAndData> for SignatureAndData {
+    type Error = Error;
+
+    fn try_from(raw: RawSignatureAndData) -> Result<Self, Self::Error> {
+        if raw.signature.is_empty() {
+            return Err(Error::invalid_raw_misbehaviour(
+                "signature cannot be empty".into(),
+            ));
+        }
+        if raw.data.is_empty() {
+            return Err(Error::invalid_raw_misbehaviour(
+                "data for signature cannot be empty".into(),
+            ));
+        }
+        if raw.timestamp == 0 {
+            return Err(Error::invalid_raw_misbehaviour(
+                "timestamp for signature cannot be 0".into(),
+            ));
+        }
+
+        let data_type = match DataType::from_i32(raw.data_type) {
+            Some(DataType::UninitializedUnspecified) | None => {
+                return Err(Error::invalid_raw_misbehaviour(format!(
+                    "invalid data type {}",
+                    raw.data_type
+                )))
+            }
+            Some(data_type) => data_type,
+        };
+
+        Ok(Self {
+            signature: raw.signature,
+            data_type,
+            data: raw.data,
+            timestamp: raw.timestamp,
+        })
+    }
+}
+
+impl From<SignatureAndData> for RawSignatureAndData {
+    fn from(value: SignatureAndData) -> Self {
+        RawSignatureAndData {
+            signature: value.signature,
+            data_type: value.data_type as i32,
+            data: value.data,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+impl core::fmt::Display for Misbehaviour {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{:?} sequence: {}", self.client_id, self.sequence)
+    }
+}
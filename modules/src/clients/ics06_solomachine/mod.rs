@@ -0,0 +1,10 @@
+//! ICS 06: Solo Machine Client implements a client verification algorithm for solo machines,
+//! such as phones or browsers, which authenticate their state with signatures of a public key
+//! rather than with a consensus algorithm.
+
+pub mod client_def;
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod misbehaviour;
@@ -1,15 +1,17 @@
 use core::convert::TryInto;
 
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleRoot};
 use prost::Message;
 use tendermint_light_client_verifier::types::{TrustedBlockState, UntrustedBlockState};
 use tendermint_light_client_verifier::{ProdVerifier, Verdict, Verifier};
 use tendermint_proto::Protobuf;
 
 use crate::clients::ics07_tendermint::client_state::ClientState;
-use crate::clients::ics07_tendermint::consensus_state::ConsensusState;
+use crate::clients::ics07_tendermint::consensus_state::{ConsensusState, SENTINEL_ROOT};
 use crate::clients::ics07_tendermint::error::Error;
 use crate::clients::ics07_tendermint::header::Header;
+use crate::clients::ics07_tendermint::misbehaviour::Misbehaviour;
 use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_def::ClientDef;
 use crate::core::ics02_client::client_state::AnyClientState;
@@ -17,6 +19,7 @@ use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::Error as Ics02Error;
 use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
@@ -28,8 +31,8 @@ use crate::core::ics23_commitment::merkle::{apply_prefix, MerkleProof};
 use crate::core::ics24_host::identifier::ConnectionId;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, ClientUpgradePath,
+    CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
 };
 use crate::core::ics24_host::Path;
 use crate::downcast;
@@ -45,6 +48,7 @@ impl ClientDef for TendermintClient {
     type Header = Header;
     type ClientState = ClientState;
     type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
@@ -81,57 +85,7 @@ impl ClientDef for TendermintClient {
                 None => None,
             };
 
-        let trusted_consensus_state =
-            downcast_consensus_state(ctx.consensus_state(&client_id, header.trusted_height)?)?;
-
-        let trusted_state = TrustedBlockState {
-            header_time: trusted_consensus_state.timestamp,
-            height: header
-                .trusted_height
-                .revision_height
-                .try_into()
-                .map_err(|_| {
-                    Ics02Error::tendermint_handler_error(Error::invalid_header_height(
-                        header.trusted_height,
-                    ))
-                })?,
-            next_validators: &header.trusted_validator_set,
-            next_validators_hash: trusted_consensus_state.next_validators_hash,
-        };
-
-        let untrusted_state = UntrustedBlockState {
-            signed_header: &header.signed_header,
-            validators: &header.validator_set,
-            // NB: This will skip the
-            // VerificationPredicates::next_validators_match check for the
-            // untrusted state.
-            next_validators: None,
-        };
-
-        let options = client_state.as_light_client_options()?;
-
-        let verdict = self.verifier.verify(
-            untrusted_state,
-            trusted_state,
-            &options,
-            ctx.host_timestamp().into_tm_time().unwrap(),
-        );
-
-        match verdict {
-            Verdict::Success => {}
-            Verdict::NotEnoughTrust(voting_power_tally) => {
-                return Err(Error::not_enough_trusted_vals_signed(format!(
-                    "voting power tally: {}",
-                    voting_power_tally
-                ))
-                .into())
-            }
-            Verdict::Invalid(detail) => {
-                return Err(Ics02Error::tendermint_handler_error(
-                    Error::verification_error(detail),
-                ))
-            }
-        }
+        self.verify_header(ctx, &client_id, &client_state, &header)?;
 
         // If the header has verified, but its corresponding consensus state
         // differs from the existing consensus state for that height, freeze the
@@ -190,8 +144,65 @@ impl ClientDef for TendermintClient {
         ))
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Ics02Error> {
+        let Misbehaviour {
+            header1, header2, ..
+        } = misbehaviour;
+
+        for header in [&header1, &header2] {
+            if header.signed_header.header.chain_id.as_str() != client_state.chain_id.as_str() {
+                return Err(Error::misbehaviour_headers_chain_id_mismatch(
+                    header.signed_header.header.chain_id.to_string(),
+                    client_state.chain_id.clone(),
+                )
+                .into());
+            }
+        }
+
+        // The headers must either be a fork at the same height, or violate
+        // the monotonicity of BFT time.
+        if header1.compatible_with(&header2) {
+            return Err(Error::misbehaviour_headers_not_conflicting(
+                header1.height(),
+                header2.height(),
+            )
+            .into());
+        }
+
+        // Both headers must be valid with respect to the consensus states
+        // this client trusts, otherwise the evidence cannot be attributed to
+        // the validators of the counterparty chain.
+        for header in [&header1, &header2] {
+            let trusted_consensus_state =
+                downcast_consensus_state(ctx.consensus_state(&client_id, header.trusted_height)?)?;
+
+            let trusted_validators_hash = header.trusted_validator_set.hash();
+            if trusted_validators_hash != trusted_consensus_state.next_validators_hash {
+                return Err(Error::invalid_validator_set(
+                    trusted_validators_hash,
+                    trusted_consensus_state.next_validators_hash,
+                )
+                .into());
+            }
+
+            self.verify_header(ctx, &client_id, &client_state, header)?;
+        }
+
+        let frozen_height = core::cmp::min(header1.height(), header2.height());
+        client_state
+            .with_frozen_height(frozen_height)
+            .map_err(Ics02Error::tendermint_handler_error)
+    }
+
     fn verify_client_consensus_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -200,7 +211,7 @@ impl ClientDef for TendermintClient {
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
 
         let path = ClientConsensusStatePath {
@@ -211,11 +222,13 @@ impl ClientDef for TendermintClient {
         let value = expected_consensus_state
             .encode_vec()
             .map_err(Ics02Error::invalid_any_consensus_state)?;
-        verify_membership(client_state, prefix, proof, root, path, value)
+        verify_membership(client_state, prefix, proof, root, path, value)?;
+        Ok(None)
     }
 
     fn verify_connection_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -223,18 +236,20 @@ impl ClientDef for TendermintClient {
         root: &CommitmentRoot,
         connection_id: &ConnectionId,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
 
         let path = ConnectionsPath(connection_id.clone());
         let value = expected_connection_end
             .encode_vec()
             .map_err(Ics02Error::invalid_connection_end)?;
-        verify_membership(client_state, prefix, proof, root, path, value)
+        verify_membership(client_state, prefix, proof, root, path, value)?;
+        Ok(None)
     }
 
     fn verify_channel_state(
         &self,
+        _ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -243,18 +258,20 @@ impl ClientDef for TendermintClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
 
         let path = ChannelEndsPath(port_id.clone(), *channel_id);
         let value = expected_channel_end
             .encode_vec()
             .map_err(Ics02Error::invalid_channel_end)?;
-        verify_membership(client_state, prefix, proof, root, path, value)
+        verify_membership(client_state, prefix, proof, root, path, value)?;
+        Ok(None)
     }
 
     fn verify_client_full_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -262,14 +279,15 @@ impl ClientDef for TendermintClient {
         root: &CommitmentRoot,
         client_id: &ClientId,
         expected_client_state: &AnyClientState,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
 
         let path = ClientStatePath(client_id.clone());
         let value = expected_client_state
             .encode_vec()
             .map_err(Ics02Error::invalid_any_client_state)?;
-        verify_membership(client_state, prefix, proof, root, path, value)
+        verify_membership(client_state, prefix, proof, root, path, value)?;
+        Ok(None)
     }
 
     fn verify_packet_data(
@@ -284,7 +302,7 @@ impl ClientDef for TendermintClient {
         channel_id: &ChannelId,
         sequence: Sequence,
         commitment: PacketCommitment,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
         verify_delay_passed(ctx, height, connection_end)?;
 
@@ -301,7 +319,8 @@ impl ClientDef for TendermintClient {
             root,
             commitment_path,
             commitment.into_vec(),
-        )
+        )?;
+        Ok(None)
     }
 
     fn verify_packet_acknowledgement(
@@ -316,7 +335,7 @@ impl ClientDef for TendermintClient {
         channel_id: &ChannelId,
         sequence: Sequence,
         ack_commitment: AcknowledgementCommitment,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
         verify_delay_passed(ctx, height, connection_end)?;
 
@@ -332,7 +351,8 @@ impl ClientDef for TendermintClient {
             root,
             ack_path,
             ack_commitment.into_vec(),
-        )
+        )?;
+        Ok(None)
     }
 
     fn verify_next_sequence_recv(
@@ -346,7 +366,7 @@ impl ClientDef for TendermintClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         sequence: Sequence,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
         verify_delay_passed(ctx, height, connection_end)?;
 
@@ -363,7 +383,8 @@ impl ClientDef for TendermintClient {
             root,
             seq_path,
             seq_bytes,
-        )
+        )?;
+        Ok(None)
     }
 
     fn verify_packet_receipt_absence(
@@ -377,7 +398,7 @@ impl ClientDef for TendermintClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         sequence: Sequence,
-    ) -> Result<(), Ics02Error> {
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
         client_state.verify_height(height)?;
         verify_delay_passed(ctx, height, connection_end)?;
 
@@ -392,17 +413,155 @@ impl ClientDef for TendermintClient {
             proof,
             root,
             receipt_path,
-        )
+        )?;
+        Ok(None)
     }
 
     fn verify_upgrade_and_update_state(
         &self,
-        _client_state: &Self::ClientState,
-        _consensus_state: &Self::ConsensusState,
-        _proof_upgrade_client: RawMerkleProof,
-        _proof_upgrade_consensus_state: RawMerkleProof,
+        client_state: &Self::ClientState,
+        consensus_state: &Self::ConsensusState,
+        upgraded_client_state: &Self::ClientState,
+        upgraded_consensus_state: &Self::ConsensusState,
+        proof_upgrade_client: RawMerkleProof,
+        proof_upgrade_consensus_state: RawMerkleProof,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Ics02Error> {
-        todo!()
+        // The upgrade path holds the key of the upgrade store, under which the chain commits
+        // the upgraded states, followed by the upgrade key they are stored under.
+        let (store_key, upgrade_key) = match client_state.upgrade_path.as_slice() {
+            [store_key, upgrade_key] => (store_key, upgrade_key),
+            [] => return Err(Error::empty_upgrade_path().into()),
+            upgrade_path => return Err(Error::invalid_upgrade_path(upgrade_path.to_vec()).into()),
+        };
+
+        // The upgraded states are committed by the counterparty chain under the height of the
+        // last block before the upgrade, which is the latest height of this client.
+        let last_height = client_state.latest_height.revision_height;
+        let root: MerkleRoot = consensus_state.root.clone().into();
+
+        let client_path = MerklePath {
+            key_path: vec![
+                store_key.clone(),
+                ClientUpgradePath::UpgradedClientState(last_height).key(upgrade_key),
+            ],
+        };
+        let client_value = AnyClientState::Tendermint(upgraded_client_state.clone())
+            .encode_vec()
+            .map_err(Ics02Error::invalid_any_client_state)?;
+        MerkleProof::from(proof_upgrade_client)
+            .verify_membership(
+                &client_state.proof_specs,
+                root.clone(),
+                client_path,
+                client_value,
+                0,
+            )
+            .map_err(Ics02Error::invalid_upgrade_client_proof)?;
+
+        let consensus_path = MerklePath {
+            key_path: vec![
+                store_key.clone(),
+                ClientUpgradePath::UpgradedClientConsensusState(last_height).key(upgrade_key),
+            ],
+        };
+        let consensus_value = AnyConsensusState::Tendermint(upgraded_consensus_state.clone())
+            .encode_vec()
+            .map_err(Ics02Error::invalid_any_consensus_state)?;
+        MerkleProof::from(proof_upgrade_consensus_state)
+            .verify_membership(
+                &client_state.proof_specs,
+                root,
+                consensus_path,
+                consensus_value,
+                0,
+            )
+            .map_err(Ics02Error::invalid_upgrade_consensus_state_proof)?;
+
+        // Fields chosen by the chain are taken from the upgraded client state, while the
+        // fields chosen by the client (zeroed out by the chain) are kept from the current one.
+        let new_client_state = ClientState::new(
+            upgraded_client_state.chain_id.clone(),
+            client_state.trust_level,
+            client_state.trusting_period,
+            upgraded_client_state.unbonding_period,
+            client_state.max_clock_drift,
+            upgraded_client_state.latest_height,
+            upgraded_client_state.proof_specs.clone(),
+            upgraded_client_state.upgrade_path.clone(),
+            client_state.allow_update,
+        )?;
+
+        // The commitment root of the upgraded consensus state cannot be used for verification,
+        // as the chain only commits to it in the next block; a sentinel value is stored instead.
+        let new_consensus_state = ConsensusState::new(
+            SENTINEL_ROOT.to_vec().into(),
+            upgraded_consensus_state.timestamp,
+            upgraded_consensus_state.next_validators_hash,
+        );
+
+        Ok((new_client_state, new_consensus_state))
+    }
+}
+
+impl TendermintClient {
+    /// Verifies `header` against the consensus state this client has stored at the header's
+    /// trusted height, using the light client verification algorithm.
+    fn verify_header(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: &ClientId,
+        client_state: &ClientState,
+        header: &Header,
+    ) -> Result<(), Ics02Error> {
+        let trusted_consensus_state =
+            downcast_consensus_state(ctx.consensus_state(client_id, header.trusted_height)?)?;
+
+        let trusted_state = TrustedBlockState {
+            header_time: trusted_consensus_state.timestamp,
+            height: header
+                .trusted_height
+                .revision_height
+                .try_into()
+                .map_err(|_| {
+                    Ics02Error::tendermint_handler_error(Error::invalid_header_height(
+                        header.trusted_height,
+                    ))
+                })?,
+            next_validators: &header.trusted_validator_set,
+            next_validators_hash: trusted_consensus_state.next_validators_hash,
+        };
+
+        let untrusted_state = UntrustedBlockState {
+            signed_header: &header.signed_header,
+            validators: &header.validator_set,
+            // NB: This will skip the
+            // VerificationPredicates::next_validators_match check for the
+            // untrusted state.
+            next_validators: None,
+        };
+
+        let options = client_state.as_light_client_options()?;
+
+        let verdict = self.verifier.verify(
+            untrusted_state,
+            trusted_state,
+            &options,
+            ctx.host_timestamp().into_tm_time().unwrap(),
+        );
+
+        match verdict {
+            Verdict::Success => Ok(()),
+            Verdict::NotEnoughTrust(voting_power_tally) => {
+                Err(Error::not_enough_trusted_vals_signed(format!(
+                    "voting power tally: {}",
+                    voting_power_tally
+                ))
+                .into())
+            }
+            Verdict::Invalid(detail) => Err(Ics02Error::tendermint_handler_error(
+                Error::verification_error(detail),
+            )),
+        }
     }
 }
 
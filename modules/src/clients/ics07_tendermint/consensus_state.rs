@@ -15,6 +15,11 @@ use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics23_commitment::commitment::CommitmentRoot;
 
+/// The commitment root of the consensus state stored by a client upgrade. The chain only
+/// commits to the root of the upgraded consensus state in the next block, so this placeholder
+/// is stored until the client is updated past the upgrade and never verifies any proof.
+pub const SENTINEL_ROOT: &[u8] = b"sentinel_root";
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct ConsensusState {
     pub timestamp: Time,
@@ -4,7 +4,7 @@ use flex_error::{define_error, TraceError};
 
 use crate::core::ics23_commitment::error::Error as Ics23Error;
 use crate::core::ics24_host::error::ValidationError;
-use crate::core::ics24_host::identifier::ClientId;
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
 use crate::timestamp::{Timestamp, TimestampOverflowError};
 
 use crate::Height;
@@ -105,6 +105,33 @@ define_error! {
             { reason: String }
             | e | { format_args!("invalid raw misbehaviour: {}", e.reason) },
 
+        MisbehaviourHeadersChainIdMismatch
+            {
+                header_chain_id: String,
+                chain_id: ChainId,
+            }
+            | e | {
+                format_args!("misbehaviour header chain id {0} does not match the client chain id {1}", e.header_chain_id, e.chain_id)
+            },
+
+        MisbehaviourHeadersNotConflicting
+            {
+                height1: Height,
+                height2: Height,
+            }
+            | e | {
+                format_args!("misbehaviour headers at heights {0} and {1} are not conflicting", e.height1, e.height2)
+            },
+
+        EmptyUpgradePath
+            |_| { "cannot upgrade client as no upgrade path has been set" },
+
+        InvalidUpgradePath
+            { upgrade_path: Vec<String> }
+            | e | {
+                format_args!("invalid upgrade path {:?}: expected the key of the upgrade store followed by the upgrade key", e.upgrade_path)
+            },
+
         Decode
             [ TraceError<prost::DecodeError> ]
             | _ | { "decode error" },
@@ -0,0 +1,505 @@
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
+use crate::clients::ics09_localhost::client_state::ClientState;
+use crate::clients::ics09_localhost::consensus_state::ConsensusState;
+use crate::clients::ics09_localhost::error::Error;
+use crate::clients::ics09_localhost::header::Header;
+use crate::clients::ics09_localhost::misbehaviour::Misbehaviour;
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_def::ClientDef;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::ConnectionReader;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::ErrorDetail as Ics04ErrorDetail;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
+    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+};
+use crate::prelude::*;
+use crate::Height;
+
+/// The localhost client verifies the state of the host chain by reading it from the host store,
+/// so the proofs, roots and prefixes passed to the `verify_*` functions are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocalhostClient;
+
+impl ClientDef for LocalhostClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Ics02Error> {
+        let host_height = ctx.host_height();
+        if header.height > host_height {
+            return Err(Error::header_height_too_high(header.height, host_height).into());
+        }
+
+        let host_timestamp = ctx.host_consensus_state(header.height)?.timestamp();
+        if header.timestamp != host_timestamp {
+            return Err(Error::header_timestamp_mismatch(header.height).into());
+        }
+
+        Ok((
+            client_state.with_height(header.height),
+            ConsensusState::new(host_timestamp),
+        ))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Ics02Error> {
+        match misbehaviour {}
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _client_state: &Self::ClientState,
+        _consensus_state: &Self::ConsensusState,
+        _upgraded_client_state: &Self::ClientState,
+        _upgraded_consensus_state: &Self::ConsensusState,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Ics02Error> {
+        Err(Error::upgrade_not_supported().into())
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = ClientConsensusStatePath {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let consensus_state = ctx
+            .client_consensus_state(client_id, consensus_height)
+            .map_err(|e| Error::connection_reader(path.to_string(), e))?;
+
+        check_value(path, &consensus_state, expected_consensus_state)?;
+        Ok(None)
+    }
+
+    fn verify_connection_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: &ConnectionId,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = ConnectionsPath(connection_id.clone());
+        let connection_end = ctx
+            .connection_end(connection_id)
+            .map_err(|e| Error::connection_reader(path.to_string(), e))?;
+
+        check_value(path, &connection_end, expected_connection_end)?;
+        Ok(None)
+    }
+
+    fn verify_channel_state(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = ChannelEndsPath(port_id.clone(), *channel_id);
+        let channel_end = ctx
+            .channel_end(&(port_id.clone(), *channel_id))
+            .map_err(|e| Error::channel_reader(path.to_string(), e))?;
+
+        check_value(path, &channel_end, expected_channel_end)?;
+        Ok(None)
+    }
+
+    fn verify_client_full_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = ClientStatePath(client_id.clone());
+        let stored_client_state = ctx
+            .client_state(client_id)
+            .map_err(|e| Error::connection_reader(path.to_string(), e))?;
+
+        check_value(path, &stored_client_state, expected_client_state)?;
+        Ok(None)
+    }
+
+    fn verify_packet_data(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        commitment: PacketCommitment,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = CommitmentsPath {
+            port_id: port_id.clone(),
+            channel_id: *channel_id,
+            sequence,
+        };
+        let stored_commitment = ctx
+            .get_packet_commitment(&(port_id.clone(), *channel_id, sequence))
+            .map_err(|e| Error::channel_reader(path.to_string(), e))?;
+
+        check_value(path, &stored_commitment, &commitment)?;
+        Ok(None)
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = AcksPath {
+            port_id: port_id.clone(),
+            channel_id: *channel_id,
+            sequence,
+        };
+        let stored_ack_commitment = ctx
+            .get_packet_acknowledgement(&(port_id.clone(), *channel_id, sequence))
+            .map_err(|e| Error::channel_reader(path.to_string(), e))?;
+
+        check_value(path, &stored_ack_commitment, &ack_commitment)?;
+        Ok(None)
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = SeqRecvsPath(port_id.clone(), *channel_id);
+        let next_sequence_recv = ctx
+            .get_next_sequence_recv(&(port_id.clone(), *channel_id))
+            .map_err(|e| Error::channel_reader(path.to_string(), e))?;
+
+        check_value(path, &next_sequence_recv, &sequence)?;
+        Ok(None)
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<Self::ClientState>, Ics02Error> {
+        check_proof_height(client_state, height)?;
+
+        let path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: *channel_id,
+            sequence,
+        };
+        match ctx.get_packet_receipt(&(port_id.clone(), *channel_id, sequence)) {
+            Ok(_) => Err(Error::packet_receipt_present(path.to_string()).into()),
+            Err(e) if matches!(e.detail(), Ics04ErrorDetail::PacketReceiptNotFound(_)) => Ok(None),
+            Err(e) => Err(Error::channel_reader(path.to_string(), e).into()),
+        }
+    }
+}
+
+/// The host store is read at its current height, which is only known to be consistent with
+/// proofs at heights the client has been updated to.
+fn check_proof_height(client_state: &ClientState, height: Height) -> Result<(), Error> {
+    if height > client_state.latest_height() {
+        return Err(Error::proof_height_too_high(
+            height,
+            client_state.latest_height(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_value<T: PartialEq>(
+    path: impl ToString,
+    value: &T,
+    expected: &T,
+) -> Result<(), Ics02Error> {
+    if value != expected {
+        return Err(Error::state_mismatch(path.to_string()).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::LocalhostClient;
+    use crate::clients::ics09_localhost::client_state::ClientState;
+    use crate::clients::ics09_localhost::header::Header;
+    use crate::core::ics02_client::client_consensus::AnyConsensusState;
+    use crate::core::ics02_client::client_def::ClientDef;
+    use crate::core::ics02_client::context::ClientReader;
+    use crate::core::ics03_connection::connection::{ConnectionEnd, State};
+    use crate::core::ics04_channel::context::ChannelKeeper;
+    use crate::core::ics04_channel::packet::{Receipt, Sequence};
+    use crate::core::ics23_commitment::commitment::{CommitmentProofBytes, CommitmentRoot};
+    use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+    use crate::mock::client_state::MockConsensusState;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::host::HostType;
+    use crate::Height;
+
+    fn localhost_fixture() -> (MockContext, ClientState) {
+        let chain_id = ChainId::new("mockgaia".to_string(), 1);
+        let ctx = MockContext::new(chain_id.clone(), HostType::Mock, 5, Height::new(1, 5));
+        let client_state = ClientState::new(chain_id, Height::new(1, 4)).unwrap();
+
+        (ctx, client_state)
+    }
+
+    fn proof() -> CommitmentProofBytes {
+        b"ignored".to_vec().try_into().unwrap()
+    }
+
+    #[test]
+    fn update_to_host_height() {
+        let (ctx, client_state) = localhost_fixture();
+        let host_height = ctx.host_height();
+        let timestamp = ctx.host_consensus_state(host_height).unwrap().timestamp();
+
+        let (client_state, consensus_state) = LocalhostClient
+            .check_header_and_update_state(
+                &ctx,
+                ClientId::default(),
+                client_state,
+                Header::new(host_height, timestamp),
+            )
+            .unwrap();
+        assert_eq!(client_state.latest_height(), host_height);
+        assert_eq!(consensus_state.timestamp, timestamp);
+    }
+
+    #[test]
+    fn update_beyond_host_height() {
+        let (ctx, client_state) = localhost_fixture();
+        let timestamp = ctx.host_timestamp();
+
+        let res = LocalhostClient.check_header_and_update_state(
+            &ctx,
+            ClientId::default(),
+            client_state.clone(),
+            Header::new(ctx.host_height().increment(), timestamp),
+        );
+        assert!(res.is_err());
+
+        // The timestamp must match that of the host at the given height.
+        let res = LocalhostClient.check_header_and_update_state(
+            &ctx,
+            ClientId::default(),
+            client_state,
+            Header::new(Height::new(1, 3), timestamp),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn verify_stored_connection_state() {
+        let (ctx, client_state) = localhost_fixture();
+        let connection_id = ConnectionId::new(0);
+        let connection_end = ConnectionEnd::default();
+        let ctx = ctx.with_connection(connection_id.clone(), connection_end.clone());
+
+        let verify = |height: Height, connection_id: &ConnectionId, expected: &ConnectionEnd| {
+            LocalhostClient.verify_connection_state(
+                &ctx,
+                &client_state,
+                height,
+                &Default::default(),
+                &proof(),
+                &CommitmentRoot::empty(),
+                connection_id,
+                expected,
+            )
+        };
+
+        assert!(verify(Height::new(1, 4), &connection_id, &connection_end).is_ok());
+
+        let mut other_connection_end = connection_end.clone();
+        other_connection_end.set_state(State::Open);
+        assert!(verify(Height::new(1, 4), &connection_id, &other_connection_end).is_err());
+
+        // The connection does not exist on the host.
+        assert!(verify(Height::new(1, 4), &ConnectionId::new(1), &connection_end).is_err());
+
+        // The client has not been updated to the proof height.
+        assert!(verify(Height::new(1, 5), &connection_id, &connection_end).is_err());
+    }
+
+    #[test]
+    fn verify_stored_consensus_state() {
+        let (ctx, client_state) = localhost_fixture();
+        let client_id = ClientId::default();
+        let consensus_height = Height::new(1, 2);
+        let ctx = ctx.with_client(&client_id, consensus_height);
+        let stored = ClientReader::consensus_state(&ctx, &client_id, consensus_height).unwrap();
+
+        let verify = |expected: &AnyConsensusState| {
+            LocalhostClient.verify_client_consensus_state(
+                &ctx,
+                &client_state,
+                Height::new(1, 4),
+                &Default::default(),
+                &proof(),
+                &CommitmentRoot::empty(),
+                &client_id,
+                consensus_height,
+                expected,
+            )
+        };
+
+        assert!(verify(&stored).is_ok());
+
+        // A consensus state with the same timestamp, but a different root, does not match.
+        let other = AnyConsensusState::Mock(MockConsensusState::new(
+            MockHeader::new(Height::new(1, 3)).with_timestamp(stored.timestamp()),
+        ));
+        assert!(verify(&other).is_err());
+    }
+
+    #[test]
+    fn verify_packet_receipt_absence() {
+        let (mut ctx, client_state) = localhost_fixture();
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::default();
+        ctx.store_packet_receipt(
+            (port_id.clone(), channel_id, Sequence::from(1)),
+            Receipt::Ok,
+        )
+        .unwrap();
+
+        let verify = |sequence: u64| {
+            LocalhostClient.verify_packet_receipt_absence(
+                &ctx,
+                &client_state,
+                Height::new(1, 4),
+                &ConnectionEnd::default(),
+                &proof(),
+                &CommitmentRoot::empty(),
+                &port_id,
+                &channel_id,
+                Sequence::from(sequence),
+            )
+        };
+
+        assert!(verify(1).is_err());
+        assert!(verify(2).is_ok());
+    }
+
+    #[test]
+    fn verify_next_sequence_recv() {
+        let (ctx, client_state) = localhost_fixture();
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::default();
+        let ctx = ctx.with_recv_sequence(port_id.clone(), channel_id, Sequence::from(3));
+
+        let verify = |sequence: u64| {
+            LocalhostClient.verify_next_sequence_recv(
+                &ctx,
+                &client_state,
+                Height::new(1, 4),
+                &ConnectionEnd::default(),
+                &proof(),
+                &CommitmentRoot::empty(),
+                &port_id,
+                &channel_id,
+                Sequence::from(sequence),
+            )
+        };
+
+        assert!(verify(3).is_ok());
+        assert!(verify(4).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::localhost::v1::ClientState as RawClientState;
+
+use crate::clients::ics09_localhost::error::Error;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The client state of a localhost client, which tracks the height of the host chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    pub chain_id: ChainId,
+    pub latest_height: Height,
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl ClientState {
+    pub fn new(chain_id: ChainId, latest_height: Height) -> Result<ClientState, Error> {
+        if latest_height.is_zero() {
+            return Err(Error::invalid_raw_client_state(
+                "height cannot be zero".into(),
+            ));
+        }
+
+        Ok(Self {
+            chain_id,
+            latest_height,
+        })
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    /// Returns the client state as updated to `height`. The latest height of the client
+    /// never decreases.
+    pub fn with_height(self, height: Height) -> Self {
+        Self {
+            latest_height: core::cmp::max(self.latest_height, height),
+            ..self
+        }
+    }
+
+    /// Localhost clients do not expire, so there is no need to refresh them.
+    pub fn refresh_time(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Localhost clients do not expire.
+    pub fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+}
+
+impl crate::core::ics02_client::client_state::ClientState for ClientState {
+    type UpgradeOptions = ();
+
+    fn chain_id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        None
+    }
+
+    fn upgrade(self, upgrade_height: Height, _upgrade_options: (), chain_id: ChainId) -> Self {
+        Self {
+            chain_id,
+            latest_height: upgrade_height,
+        }
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::Localhost(self)
+    }
+}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .height
+            .ok_or_else(|| Error::invalid_raw_client_state("missing height".into()))?
+            .into();
+
+        ClientState::new(ChainId::from_string(&raw.chain_id), latest_height)
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            chain_id: value.chain_id.to_string(),
+            height: Some(value.latest_height.into()),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::prelude::*;
+
+use core::convert::Infallible;
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use crate::clients::ics09_localhost::error::Error;
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+/// The values are read from the host store rather than verified against a root.
+static EMPTY_ROOT: CommitmentRoot = CommitmentRoot::empty();
+
+/// The consensus state of a localhost client at some height, which records the timestamp of the
+/// host chain at that height.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusState {
+    pub timestamp: Timestamp,
+}
+
+impl ConsensusState {
+    pub fn new(timestamp: Timestamp) -> Self {
+        Self { timestamp }
+    }
+}
+
+impl crate::core::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    type Error = Infallible;
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &EMPTY_ROOT
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::Localhost(self)
+    }
+}
+
+/// The encoding of the localhost consensus state. The `ibc.lightclients.localhost.v1` package
+/// does not define one, as ibc-go does not store consensus states for localhost clients.
+#[derive(Clone, PartialEq, Message)]
+pub struct RawConsensusState {
+    #[prost(uint64, tag = "1")]
+    pub timestamp: u64,
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(|e| Error::invalid_raw_consensus_state(e.to_string()))?,
+        })
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
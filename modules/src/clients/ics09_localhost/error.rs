@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+use flex_error::{define_error, DisplayOnly};
+
+use crate::core::ics03_connection::error::Error as Ics03Error;
+use crate::core::ics04_channel::error::Error as Ics04Error;
+use crate::Height;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        InvalidRawClientState
+            { reason: String }
+            |e| { format_args!("invalid raw client state: {}", e.reason) },
+
+        InvalidRawConsensusState
+            { reason: String }
+            |e| { format_args!("invalid raw client consensus state: {}", e.reason) },
+
+        InvalidRawHeader
+            { reason: String }
+            |e| { format_args!("invalid raw header: {}", e.reason) },
+
+        HeaderHeightTooHigh
+            {
+                height: Height,
+                host_height: Height,
+            }
+            |e| {
+                format_args!("header height {0} is greater than the host height {1}",
+                    e.height, e.host_height)
+            },
+
+        HeaderTimestampMismatch
+            { height: Height }
+            |e| {
+                format_args!("header timestamp does not match the timestamp of the host at height {}",
+                    e.height)
+            },
+
+        ProofHeightTooHigh
+            {
+                proof_height: Height,
+                latest_height: Height,
+            }
+            |e| {
+                format_args!("proof height {0} is greater than the latest height {1} of the client",
+                    e.proof_height, e.latest_height)
+            },
+
+        ConnectionReader
+            { path: String }
+            [ DisplayOnly<Ics03Error> ]
+            |e| { format_args!("failed to read {} from the host store", e.path) },
+
+        ChannelReader
+            { path: String }
+            [ DisplayOnly<Ics04Error> ]
+            |e| { format_args!("failed to read {} from the host store", e.path) },
+
+        StateMismatch
+            { path: String }
+            |e| { format_args!("the value stored at {} does not match the expected value", e.path) },
+
+        PacketReceiptPresent
+            { path: String }
+            |e| { format_args!("a packet receipt is stored at {}", e.path) },
+
+        MisbehaviourNotSupported
+            |_| { "localhost clients cannot misbehave" },
+
+        UpgradeNotSupported
+            |_| { "localhost clients cannot be upgraded" },
+    }
+}
@@ -0,0 +1,86 @@
+use crate::prelude::*;
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+use crate::clients::ics09_localhost::error::Error;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::header::AnyHeader;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// A localhost header, which updates the client to a height of the host chain. The header
+/// carries no evidence: its timestamp is checked against the host's own record for the height.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Header {
+    pub height: Height,
+    pub timestamp: Timestamp,
+}
+
+impl Header {
+    pub fn new(height: Height, timestamp: Timestamp) -> Self {
+        Self { height, timestamp }
+    }
+}
+
+impl crate::core::ics02_client::header::Header for Header {
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn height(&self) -> Height {
+        self.height
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    fn wrap_any(self) -> AnyHeader {
+        AnyHeader::Localhost(self)
+    }
+}
+
+/// The encoding of the localhost header, which the `ibc.lightclients.localhost.v1` package
+/// does not define, as ibc-go updates localhost clients at every block instead.
+#[derive(Clone, PartialEq, Message)]
+pub struct RawHeader {
+    #[prost(message, optional, tag = "1")]
+    pub height: Option<RawHeight>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+        let height: Height = raw
+            .height
+            .ok_or_else(|| Error::invalid_raw_header("missing height".into()))?
+            .into();
+        if height.is_zero() {
+            return Err(Error::invalid_raw_header("height cannot be zero".into()));
+        }
+
+        Ok(Self {
+            height,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(|e| Error::invalid_raw_header(e.to_string()))?,
+        })
+    }
+}
+
+impl From<Header> for RawHeader {
+    fn from(value: Header) -> Self {
+        RawHeader {
+            height: Some(value.height.into()),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::core::ics02_client::misbehaviour::AnyMisbehaviour;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::Height;
+
+/// Localhost clients read from the host store, so there is no evidence of misbehaviour that
+/// could be submitted for them. This type has no values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Misbehaviour {}
+
+impl crate::core::ics02_client::misbehaviour::Misbehaviour for Misbehaviour {
+    fn client_id(&self) -> &ClientId {
+        match *self {}
+    }
+
+    fn height(&self) -> Height {
+        match *self {}
+    }
+
+    fn wrap_any(self) -> AnyMisbehaviour {
+        match self {}
+    }
+}
@@ -0,0 +1,10 @@
+//! ICS 09: Loopback Client implements a client for the host chain itself, which allows IBC
+//! connections and channels to be opened between modules of the same chain. Instead of verifying
+//! proofs, the localhost client reads the expected values directly from the host store.
+
+pub mod client_def;
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod misbehaviour;
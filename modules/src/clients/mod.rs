@@ -1,3 +1,5 @@
 //! Implementations of client verification algorithms for specific types of chains.
 
+pub mod ics06_solomachine;
 pub mod ics07_tendermint;
+pub mod ics09_localhost;
@@ -1,13 +1,18 @@
 use ibc_proto::ibc::core::commitment::v1::MerkleProof;
 
+use crate::clients::ics06_solomachine::client_def::SoloMachineClient;
 use crate::clients::ics07_tendermint::client_def::TendermintClient;
+use crate::clients::ics09_localhost::client_def::LocalhostClient;
+use crate::clients::ics09_localhost::error::Error as Ics09Error;
 use crate::core::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
 use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::Error;
 use crate::core::ics02_client::header::{AnyHeader, Header};
+use crate::core::ics02_client::misbehaviour::{AnyMisbehaviour, Misbehaviour};
 use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
@@ -27,6 +32,7 @@ pub trait ClientDef: Clone {
     type Header: Header;
     type ClientState: ClientState;
     type ConsensusState: ConsensusState;
+    type Misbehaviour: Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
@@ -36,11 +42,27 @@ pub trait ClientDef: Clone {
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Error>;
 
-    /// TODO
+    /// Checks the evidence of misbehaviour submitted for this client. If the evidence is valid,
+    /// returns the client state with its `frozen_height` set, which prevents any further updates
+    /// or proof verifications against heights at or above the frozen height.
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Error>;
+
+    /// Verifies the proofs that the counterparty chain has committed `upgraded_client_state` and
+    /// `upgraded_consensus_state` under its upgrade path, against the commitment root of
+    /// `consensus_state`, the consensus state at the latest height of `client_state`.
+    /// On success, returns the client and consensus states the client is to be upgraded to.
     fn verify_upgrade_and_update_state(
         &self,
         client_state: &Self::ClientState,
         consensus_state: &Self::ConsensusState,
+        upgraded_client_state: &Self::ClientState,
+        upgraded_consensus_state: &Self::ConsensusState,
         proof_upgrade_client: MerkleProof,
         proof_upgrade_consensus_state: MerkleProof,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Error>;
@@ -48,6 +70,10 @@ pub trait ClientDef: Clone {
     /// Verification functions as specified in:
     /// <https://github.com/cosmos/ibc/tree/master/spec/core/ics-002-client-semantics>
     ///
+    /// Each of them returns the client state to store after a successful verification, or `None`
+    /// if the verification leaves the client state unchanged (e.g. a solo machine advances its
+    /// sequence with every verified signature, while a Tendermint client is left untouched).
+    ///
     /// Verify a `proof` that the consensus state of a given client (at height `consensus_height`)
     /// matches the input `consensus_state`. The parameter `counterparty_height` represent the
     /// height of the counterparty chain that this proof assumes (i.e., the height at which this
@@ -55,6 +81,7 @@ pub trait ClientDef: Clone {
     #[allow(clippy::too_many_arguments)]
     fn verify_client_consensus_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -63,12 +90,13 @@ pub trait ClientDef: Clone {
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify a `proof` that a connection state matches that of the input `connection_end`.
     #[allow(clippy::too_many_arguments)]
     fn verify_connection_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -76,12 +104,13 @@ pub trait ClientDef: Clone {
         root: &CommitmentRoot,
         connection_id: &ConnectionId,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify a `proof` that a channel state matches that of the input `channel_end`.
     #[allow(clippy::too_many_arguments)]
     fn verify_channel_state(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -90,12 +119,13 @@ pub trait ClientDef: Clone {
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify the client state for this chain that it is stored on the counterparty chain.
     #[allow(clippy::too_many_arguments)]
     fn verify_client_full_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -103,7 +133,7 @@ pub trait ClientDef: Clone {
         root: &CommitmentRoot,
         client_id: &ClientId,
         expected_client_state: &AnyClientState,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify a `proof` that a packet has been commited.
     #[allow(clippy::too_many_arguments)]
@@ -119,7 +149,7 @@ pub trait ClientDef: Clone {
         channel_id: &ChannelId,
         sequence: Sequence,
         commitment: PacketCommitment,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify a `proof` that a packet has been commited.
     #[allow(clippy::too_many_arguments)]
@@ -135,7 +165,7 @@ pub trait ClientDef: Clone {
         channel_id: &ChannelId,
         sequence: Sequence,
         ack: AcknowledgementCommitment,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify a `proof` that of the next_seq_received.
     #[allow(clippy::too_many_arguments)]
@@ -150,7 +180,7 @@ pub trait ClientDef: Clone {
         port_id: &PortId,
         channel_id: &ChannelId,
         sequence: Sequence,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 
     /// Verify a `proof` that a packet has not been received.
     #[allow(clippy::too_many_arguments)]
@@ -165,12 +195,14 @@ pub trait ClientDef: Clone {
         port_id: &PortId,
         channel_id: &ChannelId,
         sequence: Sequence,
-    ) -> Result<(), Error>;
+    ) -> Result<Option<Self::ClientState>, Error>;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AnyClient {
     Tendermint(TendermintClient),
+    SoloMachine(SoloMachineClient),
+    Localhost(LocalhostClient),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockClient),
@@ -180,6 +212,8 @@ impl AnyClient {
     pub fn from_client_type(client_type: ClientType) -> AnyClient {
         match client_type {
             ClientType::Tendermint => Self::Tendermint(TendermintClient::default()),
+            ClientType::SoloMachine => Self::SoloMachine(SoloMachineClient::default()),
+            ClientType::Localhost => Self::Localhost(LocalhostClient::default()),
 
             #[cfg(any(test, feature = "mocks"))]
             ClientType::Mock => Self::Mock(MockClient),
@@ -192,6 +226,7 @@ impl ClientDef for AnyClient {
     type Header = AnyHeader;
     type ClientState = AnyClientState;
     type ConsensusState = AnyConsensusState;
+    type Misbehaviour = AnyMisbehaviour;
 
     /// Validates an incoming `header` against the latest consensus state of this client.
     fn check_header_and_update_state(
@@ -218,6 +253,38 @@ impl ClientDef for AnyClient {
                 ))
             }
 
+            Self::SoloMachine(client) => {
+                let (client_state, header) = downcast!(
+                    client_state => AnyClientState::SoloMachine,
+                    header => AnyHeader::SoloMachine,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                let (new_state, new_consensus) =
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::SoloMachine(new_state),
+                    AnyConsensusState::SoloMachine(new_consensus),
+                ))
+            }
+
+            Self::Localhost(client) => {
+                let (client_state, header) = downcast!(
+                    client_state => AnyClientState::Localhost,
+                    header => AnyHeader::Localhost,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                let (new_state, new_consensus) =
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::Localhost(new_state),
+                    AnyConsensusState::Localhost(new_consensus),
+                ))
+            }
+
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(client) => {
                 let (client_state, header) = downcast!(
@@ -237,8 +304,73 @@ impl ClientDef for AnyClient {
         }
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: AnyClientState,
+        misbehaviour: AnyMisbehaviour,
+    ) -> Result<AnyClientState, Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Tendermint,
+                    misbehaviour => AnyMisbehaviour::Tendermint,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Tendermint(new_state))
+            }
+
+            Self::SoloMachine(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::SoloMachine,
+                    misbehaviour => AnyMisbehaviour::SoloMachine,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::SoloMachine(new_state))
+            }
+
+            Self::Localhost(_) => Err(Ics09Error::misbehaviour_not_supported().into()),
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Mock,
+                    misbehaviour => AnyMisbehaviour::Mock,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Mock(new_state))
+            }
+        }
+    }
+
     fn verify_client_consensus_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -247,7 +379,7 @@ impl ClientDef for AnyClient {
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -255,16 +387,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_client_consensus_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    client_id,
-                    consensus_height,
-                    expected_consensus_state,
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -274,22 +451,26 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_client_consensus_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    client_id,
-                    consensus_height,
-                    expected_consensus_state,
-                )
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
 
     fn verify_connection_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &AnyClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -297,21 +478,60 @@ impl ClientDef for AnyClient {
         root: &CommitmentRoot,
         connection_id: &ConnectionId,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(client_state => AnyClientState::Tendermint)
                     .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_connection_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    connection_id,
-                    expected_connection_end,
-                )
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::SoloMachine)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -319,21 +539,25 @@ impl ClientDef for AnyClient {
                 let client_state = downcast!(client_state => AnyClientState::Mock)
                     .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_connection_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    connection_id,
-                    expected_connection_end,
-                )
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
 
     fn verify_channel_state(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &AnyClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -342,22 +566,63 @@ impl ClientDef for AnyClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(client_state => AnyClientState::Tendermint)
                     .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_channel_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    expected_channel_end,
-                )
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::SoloMachine)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -365,22 +630,26 @@ impl ClientDef for AnyClient {
                 let client_state = downcast!(client_state => AnyClientState::Mock)
                     .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_channel_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    expected_channel_end,
-                )
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
 
     fn verify_client_full_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
@@ -388,7 +657,7 @@ impl ClientDef for AnyClient {
         root: &CommitmentRoot,
         client_id: &ClientId,
         client_state_on_counterparty: &AnyClientState,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -396,15 +665,58 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_client_full_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    client_id,
-                    client_state_on_counterparty,
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        client_state_on_counterparty,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        client_state_on_counterparty,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
                 )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        client_state_on_counterparty,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -414,15 +726,18 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_client_full_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    root,
-                    client_id,
-                    client_state_on_counterparty,
-                )
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        client_state_on_counterparty,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
@@ -438,7 +753,7 @@ impl ClientDef for AnyClient {
         channel_id: &ChannelId,
         sequence: Sequence,
         commitment: PacketCommitment,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -446,18 +761,64 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_data(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
-                    commitment,
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -467,18 +828,20 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_packet_data(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
-                    commitment,
-                )
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
@@ -495,7 +858,7 @@ impl ClientDef for AnyClient {
         channel_id: &ChannelId,
         sequence: Sequence,
         ack_commitment: AcknowledgementCommitment,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -503,18 +866,64 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_acknowledgement(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
-                    ack_commitment,
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        ack_commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        ack_commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        ack_commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -524,18 +933,20 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_packet_acknowledgement(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
-                    ack_commitment,
-                )
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                        ack_commitment,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
@@ -551,7 +962,7 @@ impl ClientDef for AnyClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         sequence: Sequence,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -559,17 +970,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_next_sequence_recv(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -579,17 +1034,19 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_next_sequence_recv(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
-                )
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
@@ -604,7 +1061,7 @@ impl ClientDef for AnyClient {
         port_id: &PortId,
         channel_id: &ChannelId,
         sequence: Sequence,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -612,17 +1069,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_receipt_absence(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::Tendermint))
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::SoloMachine))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
                 )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::Localhost))
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -632,17 +1133,19 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
-                client.verify_packet_receipt_absence(
-                    ctx,
-                    client_state,
-                    height,
-                    connection_end,
-                    proof,
-                    root,
-                    port_id,
-                    channel_id,
-                    sequence,
-                )
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        connection_end,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        sequence,
+                    )
+                    .map(|state| state.map(AnyClientState::Mock))
             }
         }
     }
@@ -651,6 +1154,8 @@ impl ClientDef for AnyClient {
         &self,
         client_state: &Self::ClientState,
         consensus_state: &Self::ConsensusState,
+        upgraded_client_state: &Self::ClientState,
+        upgraded_consensus_state: &Self::ConsensusState,
         proof_upgrade_client: MerkleProof,
         proof_upgrade_consensus_state: MerkleProof,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Error> {
@@ -662,9 +1167,17 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
 
+                let (upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    upgraded_client_state => AnyClientState::Tendermint,
+                    upgraded_consensus_state => AnyConsensusState::Tendermint,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
                 let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
                     client_state,
                     consensus_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
                     proof_upgrade_client,
                     proof_upgrade_consensus_state,
                 )?;
@@ -675,6 +1188,62 @@ impl ClientDef for AnyClient {
                 ))
             }
 
+            Self::SoloMachine(client) => {
+                let (client_state, consensus_state) = downcast!(
+                    client_state => AnyClientState::SoloMachine,
+                    consensus_state => AnyConsensusState::SoloMachine,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                let (upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    upgraded_client_state => AnyClientState::SoloMachine,
+                    upgraded_consensus_state => AnyConsensusState::SoloMachine,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::SoloMachine))?;
+
+                let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
+                    client_state,
+                    consensus_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                )?;
+
+                Ok((
+                    AnyClientState::SoloMachine(new_state),
+                    AnyConsensusState::SoloMachine(new_consensus),
+                ))
+            }
+
+            Self::Localhost(client) => {
+                let (client_state, consensus_state) = downcast!(
+                    client_state => AnyClientState::Localhost,
+                    consensus_state => AnyConsensusState::Localhost,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                let (upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    upgraded_client_state => AnyClientState::Localhost,
+                    upgraded_consensus_state => AnyConsensusState::Localhost,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Localhost))?;
+
+                let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
+                    client_state,
+                    consensus_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                )?;
+
+                Ok((
+                    AnyClientState::Localhost(new_state),
+                    AnyConsensusState::Localhost(new_consensus),
+                ))
+            }
+
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(client) => {
                 let (client_state, consensus_state) = downcast!(
@@ -683,9 +1252,17 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
 
+                let (upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    upgraded_client_state => AnyClientState::Mock,
+                    upgraded_consensus_state => AnyConsensusState::Mock,
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
                 let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
                     client_state,
                     consensus_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
                     proof_upgrade_client,
                     proof_upgrade_consensus_state,
                 )?;
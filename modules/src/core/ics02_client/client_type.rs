@@ -8,13 +8,17 @@ use super::error::Error;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ClientType {
     Tendermint = 1,
+    SoloMachine = 6,
+    Localhost = 9,
 
     #[cfg(any(test, feature = "mocks"))]
     Mock = 9999,
 }
 
 impl ClientType {
+    const SOLOMACHINE_STR: &'static str = "06-solomachine";
     const TENDERMINT_STR: &'static str = "07-tendermint";
+    const LOCALHOST_STR: &'static str = "09-localhost";
 
     #[cfg_attr(not(test), allow(dead_code))]
     const MOCK_STR: &'static str = "9999-mock";
@@ -22,7 +26,9 @@ impl ClientType {
     /// Yields the identifier of this client type as a string
     pub fn as_str(&self) -> &'static str {
         match self {
+            Self::SoloMachine => Self::SOLOMACHINE_STR,
             Self::Tendermint => Self::TENDERMINT_STR,
+            Self::Localhost => Self::LOCALHOST_STR,
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock => Self::MOCK_STR,
@@ -41,7 +47,9 @@ impl core::str::FromStr for ClientType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            Self::SOLOMACHINE_STR => Ok(Self::SoloMachine),
             Self::TENDERMINT_STR => Ok(Self::Tendermint),
+            Self::LOCALHOST_STR => Ok(Self::Localhost),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::MOCK_STR => Ok(Self::Mock),
@@ -69,6 +77,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_solomachine_client_type() {
+        let client_type = ClientType::from_str("06-solomachine");
+
+        match client_type {
+            Ok(ClientType::SoloMachine) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
     #[test]
     fn parse_mock_client_type() {
         let client_type = ClientType::from_str("9999-mock");
@@ -6,7 +6,9 @@ use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::error::{Error, ErrorDetail};
-use crate::core::ics02_client::handler::ClientResult::{self, Create, Update, Upgrade};
+use crate::core::ics02_client::handler::ClientResult::{
+    self, Create, Misbehaviour, Update, Upgrade,
+};
 use crate::core::ics24_host::identifier::ClientId;
 use crate::timestamp::Timestamp;
 use crate::Height;
@@ -133,6 +135,10 @@ pub trait ClientKeeper {
                 )?;
                 Ok(())
             }
+            Misbehaviour(res) => {
+                self.store_client_state(res.client_id, res.client_state)?;
+                Ok(())
+            }
         }
     }
 
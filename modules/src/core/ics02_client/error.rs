@@ -4,7 +4,9 @@ use flex_error::{define_error, TraceError};
 use tendermint::Error as TendermintError;
 use tendermint_proto::Error as TendermintProtoError;
 
+use crate::clients::ics06_solomachine::error::Error as Ics06Error;
 use crate::clients::ics07_tendermint::error::Error as Ics07Error;
+use crate::clients::ics09_localhost::error::Error as Ics09Error;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::height::HeightError;
 use crate::core::ics23_commitment::error::Error as Ics23Error;
@@ -55,6 +57,10 @@ define_error! {
             { reason: String }
             | e | { format_args!("header verification failed with reason: {}", e.reason) },
 
+        MisbehaviourHandlingFailure
+            { reason: String }
+            | e | { format_args!("misbehaviour handling failed with reason: {}", e.reason) },
+
         InvalidTrustThreshold
             { numerator: u64, denominator: u64 }
             | e | { format_args!("failed to build trust threshold from fraction: {}/{}", e.numerator, e.denominator) },
@@ -153,6 +159,10 @@ define_error! {
         MissingRawMisbehaviour
             | _ | { "missing raw misbehaviour" },
 
+        InvalidMisbehaviour
+            { reason: String }
+            | e | { format_args!("invalid misbehaviour: {}", e.reason) },
+
         InvalidStringAsHeight
             { value: String }
             [ HeightError ]
@@ -180,6 +190,14 @@ define_error! {
             [ Ics07Error ]
             | _ | { "tendermint error" },
 
+        SoloMachine
+            [ Ics06Error ]
+            | _ | { "solo machine error" },
+
+        Localhost
+            [ Ics09Error ]
+            | _ | { "localhost error" },
+
         InvalidPacketTimestamp
             [ crate::timestamp::ParseTimestampError ]
             | _ | { "invalid packet timeout timestamp value" },
@@ -280,3 +298,15 @@ impl From<Ics07Error> for Error {
         Error::tendermint_handler_error(e)
     }
 }
+
+impl From<Ics06Error> for Error {
+    fn from(e: Ics06Error) -> Error {
+        Error::solo_machine(e)
+    }
+}
+
+impl From<Ics09Error> for Error {
+    fn from(e: Ics09Error) -> Error {
+        Error::localhost(e)
+    }
+}
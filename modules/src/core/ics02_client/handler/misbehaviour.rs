@@ -0,0 +1,370 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgSubmitAnyMisbehaviour`.
+
+use crate::core::ics02_client::client_def::{AnyClient, ClientDef};
+use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::Error;
+use crate::core::ics02_client::events::Attributes;
+use crate::core::ics02_client::handler::ClientResult;
+use crate::core::ics02_client::misbehaviour::Misbehaviour;
+use crate::core::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+/// The result following the successful processing of a `MsgSubmitAnyMisbehaviour` message.
+/// This data type should be used with a qualified name `misbehaviour::Result` to avoid ambiguity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Result {
+    pub client_id: ClientId,
+    pub client_state: AnyClientState,
+}
+
+pub fn process(
+    ctx: &dyn ClientReader,
+    msg: MsgSubmitAnyMisbehaviour,
+) -> HandlerResult<ClientResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let MsgSubmitAnyMisbehaviour {
+        client_id,
+        misbehaviour,
+        signer: _,
+    } = msg;
+
+    // Read client type from the host chain store. The client should already exist.
+    let client_type = ctx.client_type(&client_id)?;
+
+    let client_def = AnyClient::from_client_type(client_type);
+
+    // Read client state from the host chain store.
+    let client_state = ctx.client_state(&client_id)?;
+
+    if client_state.is_frozen() {
+        return Err(Error::client_frozen(client_id));
+    }
+
+    let consensus_height = misbehaviour.height();
+
+    // Verify the evidence against the trusted consensus states of this client.
+    // On success, the returned client state is frozen.
+    let client_state = client_def
+        .check_misbehaviour_and_update_state(ctx, client_id.clone(), client_state, misbehaviour)
+        .map_err(|e| Error::misbehaviour_handling_failure(e.to_string()))?;
+
+    let result = ClientResult::Misbehaviour(Result {
+        client_id: client_id.clone(),
+        client_state,
+    });
+
+    let event_attributes = Attributes {
+        client_id,
+        client_type,
+        height: ctx.host_height(),
+        consensus_height,
+    };
+    output.emit(IbcEvent::ClientMisbehaviour(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Add;
+    use core::str::FromStr;
+    use core::time::Duration;
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::header::Header as TendermintHeader;
+    use crate::clients::ics07_tendermint::misbehaviour::Misbehaviour as TendermintMisbehaviour;
+    use crate::core::ics02_client::client_state::ClientState;
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
+    use crate::core::ics02_client::error::{Error, ErrorDetail};
+    use crate::core::ics02_client::handler::dispatch;
+    use crate::core::ics02_client::handler::ClientResult::Misbehaviour;
+    use crate::core::ics02_client::header::{AnyHeader, Header};
+    use crate::core::ics02_client::misbehaviour::AnyMisbehaviour;
+    use crate::core::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics24_host::identifier::{ChainId, ClientId};
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::host::{HostBlock, HostType};
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+    use crate::prelude::*;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    fn mock_misbehaviour(client_id: &ClientId, h1: MockHeader, h2: MockHeader) -> AnyMisbehaviour {
+        AnyMisbehaviour::Mock(MockMisbehaviour {
+            client_id: client_id.clone(),
+            header1: h1,
+            header2: h2,
+        })
+    }
+
+    #[test]
+    fn test_misbehaviour_mock_client_ok() {
+        let client_id = ClientId::default();
+        let signer = get_dummy_account_id();
+        let misbehaviour_height = Height::new(0, 46);
+
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let timestamp = Timestamp::now();
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: mock_misbehaviour(
+                &client_id,
+                MockHeader::new(misbehaviour_height).with_timestamp(timestamp),
+                MockHeader::new(misbehaviour_height)
+                    .with_timestamp(timestamp.add(Duration::from_secs(1)).unwrap()),
+            ),
+            signer,
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg.clone()));
+
+        match output {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log,
+            }) => {
+                assert_eq!(events.len(), 1);
+                let event = events.pop().unwrap();
+                assert!(
+                    matches!(event, IbcEvent::ClientMisbehaviour(ref e) if e.client_id() == &msg.client_id)
+                );
+                assert_eq!(event.height(), ctx.host_height());
+                assert!(log.is_empty());
+
+                // Check the result, then store it and check that the client is now frozen
+                match result {
+                    Misbehaviour(ref res) => {
+                        assert_eq!(res.client_id, client_id);
+                        assert_eq!(res.client_state.frozen_height(), Some(misbehaviour_height));
+                    }
+                    _ => panic!("misbehaviour handler result has incorrect type"),
+                }
+                ctx.store_client_result(result).unwrap();
+                assert!(ctx.latest_client_states(&client_id).is_frozen());
+            }
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+
+        // Any further evidence is rejected since the client is already frozen
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+        match output {
+            Err(Error(ErrorDetail::ClientFrozen(e), _)) => {
+                assert_eq!(e.client_id, client_id);
+            }
+            _ => panic!("expected ClientFrozen error, instead got {:?}", output),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_nonexisting_client() {
+        let client_id = ClientId::from_str("mockclient1").unwrap();
+        let signer = get_dummy_account_id();
+        let height = Height::new(0, 46);
+
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: ClientId::from_str("nonexistingclient").unwrap(),
+            misbehaviour: mock_misbehaviour(
+                &client_id,
+                MockHeader::new(height),
+                MockHeader::new(height),
+            ),
+            signer,
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg.clone()));
+
+        match output {
+            Err(Error(ErrorDetail::ClientNotFound(e), _)) => {
+                assert_eq!(e.client_id, msg.client_id);
+            }
+            _ => {
+                panic!("expected ClientNotFound error, instead got {:?}", output)
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_mock_client_not_conflicting() {
+        let client_id = ClientId::default();
+        let signer = get_dummy_account_id();
+
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let header = MockHeader::new(Height::new(0, 46));
+        let tests = vec![
+            ("identical headers", header, header),
+            (
+                "headers at different heights",
+                header,
+                MockHeader::new(Height::new(0, 47)),
+            ),
+        ];
+
+        for (name, h1, h2) in tests {
+            let msg = MsgSubmitAnyMisbehaviour {
+                client_id: client_id.clone(),
+                misbehaviour: mock_misbehaviour(&client_id, h1, h2),
+                signer: signer.clone(),
+            };
+
+            let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+
+            match output {
+                Err(Error(ErrorDetail::MisbehaviourHandlingFailure(_), _)) => {}
+                _ => panic!(
+                    "{}: expected MisbehaviourHandlingFailure error, instead got {:?}",
+                    name, output
+                ),
+            }
+        }
+    }
+
+    /// Returns a context with a Tendermint client at `client_height`, along with the identifier
+    /// of the chain tracked by this client and a header of that chain at `misbehaviour_height`.
+    fn tendermint_client_fixture(
+        client_id: &ClientId,
+        client_height: Height,
+        misbehaviour_height: Height,
+    ) -> (MockContext, ChainId, TendermintHeader) {
+        let ctx = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1),
+        )
+        .with_client_parametrized(
+            client_id,
+            client_height,
+            Some(ClientType::Tendermint), // The target host chain (B) is synthetic TM.
+            Some(client_height),
+        );
+
+        // The headers are checked against the chain identifier of the client state,
+        // so the synthetic chain must use the same one.
+        let chain_id_b = ctx.latest_client_states(client_id).chain_id();
+
+        let ctx_b = MockContext::new(
+            chain_id_b.clone(),
+            HostType::SyntheticTendermint,
+            5,
+            misbehaviour_height,
+        );
+
+        let block_ref = ctx_b.host_block(misbehaviour_height);
+        let header: AnyHeader = block_ref.cloned().map(Into::into).unwrap();
+        let header = match header {
+            AnyHeader::Tendermint(mut theader) => {
+                theader.trusted_height = client_height;
+                theader
+            }
+            AnyHeader::Mock(_) | AnyHeader::SoloMachine(_) | AnyHeader::Localhost(_) => {
+                panic!("expected a Tendermint header")
+            }
+        };
+
+        (ctx, chain_id_b, header)
+    }
+
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_fork_ok() {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+        let misbehaviour_height = Height::new(1, 21);
+
+        let (ctx, chain_id_b, header1) =
+            tendermint_client_fixture(&client_id, client_height, misbehaviour_height);
+
+        // A conflicting block at the same height, differing only by its timestamp.
+        let mut header2: TendermintHeader = HostBlock::generate_tm_block(
+            chain_id_b,
+            misbehaviour_height.revision_height,
+            header1.timestamp().add(Duration::from_secs(1)).unwrap(),
+        )
+        .into();
+        header2.trusted_height = client_height;
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Tendermint(TendermintMisbehaviour {
+                client_id: client_id.clone(),
+                header1,
+                header2,
+            }),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg.clone()));
+
+        match output {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log,
+            }) => {
+                assert_eq!(events.len(), 1);
+                let event = events.pop().unwrap();
+                assert!(
+                    matches!(event, IbcEvent::ClientMisbehaviour(ref e) if e.client_id() == &msg.client_id)
+                );
+                assert!(log.is_empty());
+                match result {
+                    Misbehaviour(res) => {
+                        assert_eq!(res.client_id, client_id);
+                        assert_eq!(res.client_state.frozen_height(), Some(misbehaviour_height));
+                    }
+                    _ => panic!("misbehaviour handler result has incorrect type"),
+                }
+            }
+            Err(err) => {
+                panic!("unexpected error: {:?}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_same_header() {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+        let misbehaviour_height = Height::new(1, 21);
+
+        let (ctx, _, header) =
+            tendermint_client_fixture(&client_id, client_height, misbehaviour_height);
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Tendermint(TendermintMisbehaviour {
+                client_id,
+                header1: header.clone(),
+                header2: header,
+            }),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+
+        match output {
+            Err(Error(ErrorDetail::MisbehaviourHandlingFailure(_), _)) => {}
+            _ => panic!(
+                "expected MisbehaviourHandlingFailure error, instead got {:?}",
+                output
+            ),
+        }
+    }
+}
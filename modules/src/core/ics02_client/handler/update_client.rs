@@ -288,6 +288,8 @@ mod tests {
                 AnyHeader::Tendermint(theader)
             }
             AnyHeader::Mock(m) => AnyHeader::Mock(m),
+            AnyHeader::SoloMachine(h) => AnyHeader::SoloMachine(h),
+            AnyHeader::Localhost(h) => AnyHeader::Localhost(h),
         };
 
         let msg = MsgUpdateAnyClient {
@@ -366,6 +368,8 @@ mod tests {
                 AnyHeader::Tendermint(theader)
             }
             AnyHeader::Mock(m) => AnyHeader::Mock(m),
+            AnyHeader::SoloMachine(h) => AnyHeader::SoloMachine(h),
+            AnyHeader::Localhost(h) => AnyHeader::Localhost(h),
         };
 
         let msg = MsgUpdateAnyClient {
@@ -445,6 +449,8 @@ mod tests {
                 AnyHeader::Tendermint(theader)
             }
             AnyHeader::Mock(header) => AnyHeader::Mock(header),
+            AnyHeader::SoloMachine(header) => AnyHeader::SoloMachine(header),
+            AnyHeader::Localhost(header) => AnyHeader::Localhost(header),
         };
 
         let msg = MsgUpdateAnyClient {
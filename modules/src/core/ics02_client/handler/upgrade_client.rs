@@ -45,20 +45,23 @@ pub fn process(
         ));
     }
 
+    // Read the consensus state at the latest height of the client, against whose root the
+    // upgrade proofs are verified.
+    let consensus_state = ctx.consensus_state(&client_id, client_state.latest_height())?;
+
     let client_type = ctx.client_type(&client_id)?;
 
     let client_def = AnyClient::from_client_type(client_type);
 
     let (new_client_state, new_consensus_state) = client_def.verify_upgrade_and_update_state(
+        &client_state,
+        &consensus_state,
         &upgrade_client_state,
         &msg.consensus_state,
         msg.proof_upgrade_client.clone(),
         msg.proof_upgrade_consensus_state,
     )?;
 
-    // Not implemented yet: https://github.com/informalsystems/ibc-rs/issues/722
-    // todo!()
-
     let result = ClientResult::Upgrade(Result {
         client_id: client_id.clone(),
         client_state: new_client_state,
@@ -79,19 +82,35 @@ mod tests {
     use crate::prelude::*;
 
     use core::str::FromStr;
+    use core::time::Duration;
 
-    use crate::core::ics02_client::context::ClientReader;
+    use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof};
+    use ics23::commitment_proof::Proof;
+    use ics23::{calculate_existence_root, CommitmentProof, ExistenceProof, HashOp, InnerOp};
+    use tendermint_proto::Protobuf;
+
+    use crate::clients::ics07_tendermint::client_state::UpgradeOptions;
+    use crate::clients::ics07_tendermint::consensus_state::SENTINEL_ROOT;
+    use crate::clients::ics07_tendermint::error::ErrorDetail as Ics07ErrorDetail;
+    use crate::core::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
+    use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
     use crate::core::ics02_client::error::{Error, ErrorDetail};
     use crate::core::ics02_client::handler::dispatch;
     use crate::core::ics02_client::handler::ClientResult::Upgrade;
     use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
     use crate::core::ics02_client::msgs::ClientMsg;
-    use crate::core::ics24_host::identifier::ClientId;
+    use crate::core::ics23_commitment::commitment::CommitmentRoot;
+    use crate::core::ics23_commitment::merkle::MerkleProof;
+    use crate::core::ics24_host::identifier::{ChainId, ClientId};
+    use crate::core::ics24_host::path::ClientUpgradePath;
     use crate::events::IbcEvent;
     use crate::handler::HandlerOutput;
     use crate::mock::client_state::{MockClientState, MockConsensusState};
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::mock::host::HostType;
     use crate::test_utils::get_dummy_account_id;
     use crate::Height;
 
@@ -197,4 +216,373 @@ mod tests {
             }
         }
     }
+
+    /// Proofs of the upgraded client and consensus states committed by the counterparty chain,
+    /// together with the commitment root they are verified against.
+    struct UpgradeProofs {
+        root: Vec<u8>,
+        proof_upgrade_client: RawMerkleProof,
+        proof_upgrade_consensus_state: RawMerkleProof,
+    }
+
+    /// Commits the upgraded states in the format of `ics23::tendermint_spec()` at both levels
+    /// of the proof: the upgraded client and consensus states are the only two leaves of the
+    /// upgrade store, keyed under `upgrade_key`, and the root of the upgrade store is committed
+    /// under `store_key`.
+    fn upgrade_proofs(
+        store_key: &str,
+        upgrade_key: &str,
+        last_height: u64,
+        upgraded_client_state: &AnyClientState,
+        upgraded_consensus_state: &AnyConsensusState,
+    ) -> UpgradeProofs {
+        let leaf = |key: String, value: Vec<u8>| ExistenceProof {
+            key: key.into_bytes(),
+            value,
+            leaf: ics23::tendermint_spec().leaf_spec,
+            path: vec![],
+        };
+        let inner = |prefix: Vec<u8>, suffix: Vec<u8>| InnerOp {
+            hash: HashOp::Sha256 as i32,
+            prefix,
+            suffix,
+        };
+
+        let mut client_leaf = leaf(
+            ClientUpgradePath::UpgradedClientState(last_height).key(upgrade_key),
+            upgraded_client_state.encode_vec().unwrap(),
+        );
+        let mut consensus_leaf = leaf(
+            ClientUpgradePath::UpgradedClientConsensusState(last_height).key(upgrade_key),
+            upgraded_consensus_state.encode_vec().unwrap(),
+        );
+        let client_hash = calculate_existence_root(&client_leaf).unwrap();
+        let consensus_hash = calculate_existence_root(&consensus_leaf).unwrap();
+
+        // The client state is the left child of the upgrade store root, the consensus state is
+        // the right one.
+        client_leaf.path = vec![inner(vec![1], consensus_hash)];
+        consensus_leaf.path = vec![inner([vec![1], client_hash].concat(), vec![])];
+
+        let store_root = calculate_existence_root(&client_leaf).unwrap();
+        assert_eq!(
+            calculate_existence_root(&consensus_leaf).unwrap(),
+            store_root
+        );
+
+        let store_leaf = leaf(store_key.to_string(), store_root);
+        let root = calculate_existence_root(&store_leaf).unwrap();
+
+        let merkle_proof = |existence_proof: ExistenceProof| -> RawMerkleProof {
+            MerkleProof {
+                proofs: vec![
+                    CommitmentProof {
+                        proof: Some(Proof::Exist(existence_proof)),
+                    },
+                    CommitmentProof {
+                        proof: Some(Proof::Exist(store_leaf.clone())),
+                    },
+                ],
+            }
+            .into()
+        };
+
+        UpgradeProofs {
+            root,
+            proof_upgrade_client: merkle_proof(client_leaf),
+            proof_upgrade_consensus_state: merkle_proof(consensus_leaf),
+        }
+    }
+
+    /// The upgrade path of the Cosmos SDK chains.
+    fn default_upgrade_path() -> Vec<String> {
+        vec!["upgrade".to_string(), "upgradedIBCState".to_string()]
+    }
+
+    /// Sets up a context with a Tendermint client at height 1-20, whose latest consensus state
+    /// commits to an upgrade of the counterparty chain to `mockgaiaA-2` under the default
+    /// upgrade path, and returns it along with the message that upgrades the client.
+    fn tendermint_upgrade_fixture(upgrade_path: Vec<String>) -> (MockContext, MsgUpgradeAnyClient) {
+        tendermint_upgrade_fixture_with_key(upgrade_path, "upgradedIBCState")
+    }
+
+    /// Same as [`tendermint_upgrade_fixture`], with the counterparty chain committing the
+    /// upgraded states under `upgrade_key` in its upgrade store.
+    fn tendermint_upgrade_fixture_with_key(
+        upgrade_path: Vec<String>,
+        upgrade_key: &str,
+    ) -> (MockContext, MsgUpgradeAnyClient) {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+
+        let mut ctx = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1),
+        )
+        .with_client_parametrized(
+            &client_id,
+            client_height,
+            Some(ClientType::Tendermint),
+            Some(client_height),
+        );
+
+        let mut client_state = match ctx.client_state(&client_id).unwrap() {
+            AnyClientState::Tendermint(client_state) => client_state,
+            _ => panic!("unexpected client state type"),
+        };
+        client_state.upgrade_path = upgrade_path;
+        client_state.proof_specs = vec![ics23::tendermint_spec(), ics23::tendermint_spec()].into();
+
+        let mut consensus_state = match ctx.consensus_state(&client_id, client_height).unwrap() {
+            AnyConsensusState::Tendermint(consensus_state) => consensus_state,
+            _ => panic!("unexpected consensus state type"),
+        };
+
+        let upgraded_client_state = AnyClientState::Tendermint(client_state.clone().upgrade(
+            Height::new(2, 1),
+            UpgradeOptions {
+                unbonding_period: Duration::from_secs(256000),
+            },
+            ChainId::new("mockgaiaA".to_string(), 2),
+        ));
+        let upgraded_consensus_state = AnyConsensusState::Tendermint(consensus_state.clone());
+
+        let proofs = upgrade_proofs(
+            "upgrade",
+            upgrade_key,
+            client_height.revision_height,
+            &upgraded_client_state,
+            &upgraded_consensus_state,
+        );
+        consensus_state.root = proofs.root.into();
+
+        ctx.store_client_state(client_id.clone(), AnyClientState::Tendermint(client_state))
+            .unwrap();
+        ctx.store_consensus_state(
+            client_id.clone(),
+            client_height,
+            AnyConsensusState::Tendermint(consensus_state),
+        )
+        .unwrap();
+
+        let msg = MsgUpgradeAnyClient {
+            client_id,
+            client_state: upgraded_client_state,
+            consensus_state: upgraded_consensus_state,
+            proof_upgrade_client: proofs.proof_upgrade_client,
+            proof_upgrade_consensus_state: proofs.proof_upgrade_consensus_state,
+            signer: get_dummy_account_id(),
+        };
+
+        (ctx, msg)
+    }
+
+    #[test]
+    fn test_upgrade_tendermint_client_ok() {
+        let (ctx, msg) = tendermint_upgrade_fixture(default_upgrade_path());
+
+        let client_state = match ctx.client_state(&msg.client_id).unwrap() {
+            AnyClientState::Tendermint(client_state) => client_state,
+            _ => panic!("unexpected client state type"),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg.clone()));
+
+        match output {
+            Ok(HandlerOutput {
+                result: Upgrade(upg_res),
+                ..
+            }) => {
+                assert_eq!(upg_res.client_id, msg.client_id);
+
+                let new_client_state = match upg_res.client_state {
+                    AnyClientState::Tendermint(client_state) => client_state,
+                    _ => panic!("unexpected client state type"),
+                };
+                // Fields chosen by the chain are upgraded.
+                assert_eq!(
+                    new_client_state.chain_id,
+                    ChainId::new("mockgaiaA".to_string(), 2)
+                );
+                assert_eq!(new_client_state.latest_height, Height::new(2, 1));
+                assert_eq!(
+                    new_client_state.unbonding_period,
+                    Duration::from_secs(256000)
+                );
+                // Fields chosen by the client are preserved.
+                assert_eq!(new_client_state.trust_level, client_state.trust_level);
+                assert_eq!(
+                    new_client_state.trusting_period,
+                    client_state.trusting_period
+                );
+                assert_eq!(
+                    new_client_state.max_clock_drift,
+                    client_state.max_clock_drift
+                );
+                assert_eq!(new_client_state.allow_update, client_state.allow_update);
+                assert_eq!(new_client_state.frozen_height, None);
+
+                match upg_res.consensus_state {
+                    AnyConsensusState::Tendermint(consensus_state) => {
+                        assert_eq!(consensus_state.root.as_bytes(), SENTINEL_ROOT)
+                    }
+                    _ => panic!("unexpected consensus state type"),
+                }
+            }
+            Ok(_) => panic!("upgrade handler result has incorrect type"),
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_tendermint_client_invalid_proofs() {
+        let (ctx, mut msg) = tendermint_upgrade_fixture(default_upgrade_path());
+
+        if let AnyClientState::Tendermint(client_state) = &mut msg.client_state {
+            client_state.unbonding_period = Duration::from_secs(512000);
+        }
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        match output {
+            Err(Error(ErrorDetail::InvalidUpgradeClientProof(_), _)) => {}
+            _ => panic!(
+                "expected InvalidUpgradeClientProof error, instead got {:?}",
+                output
+            ),
+        }
+
+        let (ctx, mut msg) = tendermint_upgrade_fixture(default_upgrade_path());
+
+        if let AnyConsensusState::Tendermint(consensus_state) = &mut msg.consensus_state {
+            consensus_state.root = b"tampered_root".to_vec().into();
+        }
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        match output {
+            Err(Error(ErrorDetail::InvalidUpgradeConsensusStateProof(_), _)) => {}
+            _ => panic!(
+                "expected InvalidUpgradeConsensusStateProof error, instead got {:?}",
+                output
+            ),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_tendermint_client_no_upgrade_path() {
+        let (ctx, msg) = tendermint_upgrade_fixture(vec![]);
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        match output {
+            Err(Error(ErrorDetail::TendermintHandlerError(e), _)) => {
+                assert!(matches!(e.source, Ics07ErrorDetail::EmptyUpgradePath(_)));
+            }
+            _ => panic!("expected EmptyUpgradePath error, instead got {:?}", output),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_tendermint_client_custom_upgrade_key() {
+        let upgrade_path = vec!["upgrade".to_string(), "customIBCState".to_string()];
+        let (ctx, msg) = tendermint_upgrade_fixture_with_key(upgrade_path, "customIBCState");
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        assert!(
+            matches!(
+                output,
+                Ok(HandlerOutput {
+                    result: Upgrade(_),
+                    ..
+                })
+            ),
+            "unexpected output: {:?}",
+            output
+        );
+
+        // The upgraded states are not found under another upgrade key than the chain's.
+        let (ctx, msg) =
+            tendermint_upgrade_fixture_with_key(default_upgrade_path(), "customIBCState");
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        match output {
+            Err(Error(ErrorDetail::InvalidUpgradeClientProof(_), _)) => {}
+            _ => panic!(
+                "expected InvalidUpgradeClientProof error, instead got {:?}",
+                output
+            ),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_tendermint_client_invalid_upgrade_path() {
+        let (ctx, msg) = tendermint_upgrade_fixture(vec!["upgrade".to_string()]);
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        match output {
+            Err(Error(ErrorDetail::TendermintHandlerError(e), _)) => {
+                assert!(matches!(e.source, Ics07ErrorDetail::InvalidUpgradePath(_)));
+            }
+            _ => panic!(
+                "expected InvalidUpgradePath error, instead got {:?}",
+                output
+            ),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_tendermint_client_sentinel_root() {
+        let (ctx, msg) = tendermint_upgrade_fixture(default_upgrade_path());
+
+        let client_state = match ctx.client_state(&msg.client_id).unwrap() {
+            AnyClientState::Tendermint(client_state) => client_state,
+            _ => panic!("unexpected client state type"),
+        };
+        let committed_root = ctx
+            .consensus_state(&msg.client_id, client_state.latest_height)
+            .unwrap()
+            .root()
+            .clone();
+
+        let upgrade_result = match dispatch(&ctx, ClientMsg::UpgradeClient(msg.clone())) {
+            Ok(HandlerOutput {
+                result: Upgrade(upgrade_result),
+                ..
+            }) => upgrade_result,
+            output => panic!("unexpected output: {:?}", output),
+        };
+        let upgraded_root = upgrade_result.consensus_state.root().clone();
+        assert_eq!(upgraded_root.as_bytes(), SENTINEL_ROOT);
+
+        // A proof of a value committed by the counterparty chain does not verify against the
+        // root stored by the upgrade, but against the root of a consensus state stored by a
+        // later update of the client.
+        let verify = |root: CommitmentRoot| {
+            let client_path = MerklePath {
+                key_path: vec![
+                    "upgrade".to_string(),
+                    ClientUpgradePath::UpgradedClientState(
+                        client_state.latest_height.revision_height,
+                    )
+                    .key("upgradedIBCState"),
+                ],
+            };
+            MerkleProof::from(msg.proof_upgrade_client.clone()).verify_membership(
+                &client_state.proof_specs,
+                root.into(),
+                client_path,
+                msg.client_state.encode_vec().unwrap(),
+                0,
+            )
+        };
+
+        assert!(verify(upgraded_root).is_err());
+        assert!(verify(committed_root).is_ok());
+    }
 }
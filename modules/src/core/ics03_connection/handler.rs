@@ -1,5 +1,6 @@
 //! This module implements the processing logic for ICS3 (connection open handshake) messages.
 
+use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics03_connection::error::Error;
@@ -37,6 +38,10 @@ pub struct ConnectionResult {
 
     /// The connection end, which the handler produced as a result of processing the message.
     pub connection_end: ConnectionEnd,
+
+    /// The state of the client of the connection, if the verification of the proofs bundled in
+    /// the message updated it (e.g. by advancing the sequence of a solo machine).
+    pub client_state: Option<AnyClientState>,
 }
 
 /// General entry point for processing any type of message related to the ICS3 connection open
@@ -65,7 +65,7 @@ pub(crate) fn process(
     };
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         msg.client_state.clone(),
         msg.proofs.height(),
@@ -80,6 +80,7 @@ pub(crate) fn process(
         connection_id: msg.connection_id,
         connection_id_state: ConnectionIdState::Reused,
         connection_end: conn_end,
+        client_state,
     };
 
     let event_attributes = Attributes {
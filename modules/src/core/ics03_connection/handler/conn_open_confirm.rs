@@ -40,7 +40,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         None,
         msg.proofs.height(),
@@ -58,6 +58,7 @@ pub(crate) fn process(
         connection_id: msg.connection_id,
         connection_id_state: ConnectionIdState::Reused,
         connection_end: conn_end,
+        client_state,
     };
 
     let event_attributes = Attributes {
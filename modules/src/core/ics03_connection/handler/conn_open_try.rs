@@ -78,7 +78,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         msg.client_state.clone(),
         msg.proofs.height(),
@@ -108,6 +108,7 @@ pub(crate) fn process(
             ConnectionIdState::Reused
         },
         connection_end: new_connection_end,
+        client_state,
     };
 
     let event_attributes = Attributes {
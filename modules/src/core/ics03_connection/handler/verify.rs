@@ -11,6 +11,10 @@ use crate::proofs::{ConsensusProof, Proofs};
 use crate::Height;
 
 /// Entry point for verifying all proofs bundled in any ICS3 message.
+///
+/// Returns the client state to store once all the proofs are verified, if any of the
+/// verifications updated it. Each proof is verified against the client state produced by the
+/// verification of the previous one.
 pub fn verify_proofs(
     ctx: &dyn ConnectionReader,
     client_state: Option<AnyClientState>,
@@ -18,11 +22,14 @@ pub fn verify_proofs(
     connection_end: &ConnectionEnd,
     expected_conn: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    verify_connection_proof(
+) -> Result<Option<AnyClientState>, Error> {
+    // Fetch the client state (IBC client on the local/host chain).
+    let local_client_state = ctx.client_state(connection_end.client_id())?;
+    let mut updated_client_state = verify_connection_proof(
         ctx,
         height,
         connection_end,
+        &local_client_state,
         expected_conn,
         proofs.height(),
         proofs.object_proof(),
@@ -30,10 +37,11 @@ pub fn verify_proofs(
 
     // If the message includes a client state, then verify the proof for that state.
     if let Some(expected_client_state) = client_state {
-        verify_client_proof(
+        let updated = verify_client_proof(
             ctx,
             height,
             connection_end,
+            updated_client_state.as_ref().unwrap_or(&local_client_state),
             expected_client_state,
             proofs.height(),
             proofs
@@ -41,14 +49,22 @@ pub fn verify_proofs(
                 .as_ref()
                 .ok_or_else(Error::null_client_proof)?,
         )?;
+        updated_client_state = updated.or(updated_client_state);
     }
 
     // If a consensus proof is attached to the message, then verify it.
     if let Some(proof) = proofs.consensus_proof() {
-        Ok(verify_consensus_proof(ctx, height, connection_end, &proof)?)
-    } else {
-        Ok(())
+        let updated = verify_consensus_proof(
+            ctx,
+            height,
+            connection_end,
+            updated_client_state.as_ref().unwrap_or(&local_client_state),
+            &proof,
+        )?;
+        updated_client_state = updated.or(updated_client_state);
     }
+
+    Ok(updated_client_state)
 }
 
 /// Verifies the authenticity and semantic correctness of a commitment `proof`. The commitment
@@ -58,13 +74,11 @@ pub fn verify_connection_proof(
     ctx: &dyn ConnectionReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     expected_conn: &ConnectionEnd,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local/host chain).
-    let client_state = ctx.client_state(connection_end.client_id())?;
-
+) -> Result<Option<AnyClientState>, Error> {
     // The client must not be frozen.
     if client_state.is_frozen() {
         return Err(Error::frozen_client(connection_end.client_id().clone()));
@@ -85,7 +99,8 @@ pub fn verify_connection_proof(
     // Verify the proof for the connection state against the expected connection end.
     client_def
         .verify_connection_state(
-            &client_state,
+            ctx,
+            client_state,
             height,
             connection_end.counterparty().prefix(),
             proof,
@@ -107,13 +122,11 @@ pub fn verify_client_proof(
     ctx: &dyn ConnectionReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     expected_client_state: AnyClientState,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the local client state (IBC client running on the host chain).
-    let client_state = ctx.client_state(connection_end.client_id())?;
-
+) -> Result<Option<AnyClientState>, Error> {
     if client_state.is_frozen() {
         return Err(Error::frozen_client(connection_end.client_id().clone()));
     }
@@ -124,7 +137,8 @@ pub fn verify_client_proof(
 
     client_def
         .verify_client_full_state(
-            &client_state,
+            ctx,
+            client_state,
             height,
             connection_end.counterparty().prefix(),
             proof,
@@ -141,11 +155,9 @@ pub fn verify_consensus_proof(
     ctx: &dyn ConnectionReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     proof: &ConsensusProof,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local chain).
-    let client_state = ctx.client_state(connection_end.client_id())?;
-
+) -> Result<Option<AnyClientState>, Error> {
     if client_state.is_frozen() {
         return Err(Error::frozen_client(connection_end.client_id().clone()));
     }
@@ -159,7 +171,8 @@ pub fn verify_consensus_proof(
 
     client
         .verify_client_consensus_state(
-            &client_state,
+            ctx,
+            client_state,
             height,
             connection_end.counterparty().prefix(),
             proof.proof(),
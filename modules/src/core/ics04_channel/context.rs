@@ -176,12 +176,14 @@ pub trait ChannelKeeper {
                     port_id,
                     channel_id,
                     next_seq_recv,
+                    ..
                 } => self.store_next_sequence_recv((port_id, channel_id), next_seq_recv)?,
                 RecvPacketResult::Unordered {
                     port_id,
                     channel_id,
                     sequence,
                     receipt,
+                    ..
                 } => self.store_packet_receipt((port_id, channel_id, sequence), receipt)?,
                 RecvPacketResult::NoOp => unreachable!(),
             },
@@ -1,11 +1,12 @@
 //! This module implements the processing logic for ICS4 (channel) messages.
 
+use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::msgs::ChannelMsg;
 use crate::core::ics04_channel::{msgs::PacketMsg, packet::PacketResult};
-use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::core::ics26_routing::context::{
     Ics26Context, ModuleId, ModuleOutputBuilder, OnRecvPacketAck, Router,
 };
@@ -42,6 +43,9 @@ pub struct ChannelResult {
     pub channel_id: ChannelId,
     pub channel_id_state: ChannelIdState,
     pub channel_end: ChannelEnd,
+    /// The client state updated by the verification of the proofs of the message, if any, with
+    /// the identifier of the client.
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
 pub fn channel_validate<Ctx>(ctx: &Ctx, msg: &ChannelMsg) -> Result<ModuleId, Error>
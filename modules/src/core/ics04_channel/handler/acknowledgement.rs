@@ -1,3 +1,4 @@
+use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{Counterparty, Order};
@@ -6,7 +7,7 @@ use crate::core::ics04_channel::handler::verify::verify_packet_acknowledgement_p
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use crate::core::ics04_channel::packet::{PacketResult, Sequence};
 use crate::core::ics04_channel::{context::ChannelReader, error::Error};
-use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
@@ -17,6 +18,9 @@ pub struct AckPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub seq_number: Option<Sequence>,
+    /// The client state updated by the verification of the proofs of the message, if any, with
+    /// the identifier of the client.
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
 pub fn process(
@@ -72,12 +76,15 @@ pub fn process(
     }
 
     // Verify the acknowledgement proof
-    verify_packet_acknowledgement_proofs(
+    let client_id = connection_end.client_id().clone();
+    let client_state = ctx.client_state(&client_id)?;
+    let client_state = verify_packet_acknowledgement_proofs(
         ctx,
         msg.proofs.height(),
         packet,
         msg.acknowledgement.clone(),
         &connection_end,
+        &client_state,
         &msg.proofs,
     )?;
 
@@ -97,6 +104,7 @@ pub fn process(
             channel_id: packet.source_channel,
             seq: packet.sequence,
             seq_number: Some(next_seq_ack.increment()),
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     } else {
         PacketResult::Ack(AckPacketResult {
@@ -104,6 +112,7 @@ pub fn process(
             channel_id: packet.source_channel,
             seq: packet.sequence,
             seq_number: None,
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     };
 
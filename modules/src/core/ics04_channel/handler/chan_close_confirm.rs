@@ -61,11 +61,14 @@ pub(crate) fn process(
         channel_end.version().clone(),
     );
 
-    verify_channel_proofs(
+    let client_id = conn.client_id().clone();
+    let client_state = ctx.client_state(&client_id)?;
+    let client_state = verify_channel_proofs(
         ctx,
         msg.proofs.height(),
         &channel_end,
         &conn,
+        &client_state,
         &expected_channel_end,
         &msg.proofs,
     )?;
@@ -80,6 +83,7 @@ pub(crate) fn process(
         channel_id: msg.channel_id,
         channel_id_state: ChannelIdState::Reused,
         channel_end,
+        client_state: client_state.map(|client_state| (client_id, client_state)),
     };
 
     let event_attributes = Attributes {
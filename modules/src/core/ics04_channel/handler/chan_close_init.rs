@@ -52,6 +52,7 @@ pub(crate) fn process(
         channel_id: msg.channel_id,
         channel_id_state: ChannelIdState::Reused,
         channel_end,
+        client_state: None,
     };
 
     let event_attributes = Attributes {
@@ -60,6 +60,7 @@ pub(crate) fn process(
         port_id: msg.port_id.clone(),
         channel_id: chan_id,
         channel_end: new_channel_end,
+        client_state: None,
         channel_id_state: ChannelIdState::Generated,
     };
 
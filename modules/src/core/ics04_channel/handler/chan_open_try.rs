@@ -108,11 +108,14 @@ pub(crate) fn process(
     );
 
     // 2. Actual proofs are verified now.
-    verify_channel_proofs(
+    let client_id = conn.client_id().clone();
+    let client_state = ctx.client_state(&client_id)?;
+    let client_state = verify_channel_proofs(
         ctx,
         msg.proofs.height(),
         &new_channel_end,
         &conn,
+        &client_state,
         &expected_channel_end,
         &msg.proofs,
     )?;
@@ -131,6 +134,7 @@ pub(crate) fn process(
         },
         channel_id,
         channel_end: new_channel_end,
+        client_state: client_state.map(|client_state| (client_id, client_state)),
     };
 
     let event_attributes = Attributes {
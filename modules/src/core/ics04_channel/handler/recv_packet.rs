@@ -1,3 +1,4 @@
+use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::{Counterparty, Order, State};
 use crate::core::ics04_channel::context::ChannelReader;
@@ -6,7 +7,7 @@ use crate::core::ics04_channel::events::ReceivePacket;
 use crate::core::ics04_channel::handler::verify::verify_packet_recv_proofs;
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
 use crate::core::ics04_channel::packet::{PacketResult, Receipt, Sequence};
-use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::timestamp::Expiry;
@@ -20,11 +21,13 @@ pub enum RecvPacketResult {
         channel_id: ChannelId,
         sequence: Sequence,
         receipt: Receipt,
+        client_state: Option<(ClientId, AnyClientState)>,
     },
     Ordered {
         port_id: PortId,
         channel_id: ChannelId,
         next_seq_recv: Sequence,
+        client_state: Option<(ClientId, AnyClientState)>,
     },
 }
 
@@ -73,11 +76,14 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
         return Err(Error::low_packet_timestamp());
     }
 
-    verify_packet_recv_proofs(
+    let client_id = connection_end.client_id().clone();
+    let client_state = ctx.client_state(&client_id)?;
+    let client_state = verify_packet_recv_proofs(
         ctx,
         msg.proofs.height(),
         packet,
         &connection_end,
+        &client_state,
         &msg.proofs,
     )?;
 
@@ -104,6 +110,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
             port_id: packet.destination_port.clone(),
             channel_id: packet.destination_channel,
             next_seq_recv: next_seq_recv.increment(),
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     } else {
         let packet_rec = ctx.get_packet_receipt(&(
@@ -127,6 +134,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
                     channel_id: packet.destination_channel,
                     sequence: packet.sequence,
                     receipt: Receipt::Ok,
+                    client_state: client_state.map(|client_state| (client_id, client_state)),
                 })
             }
             Err(_) => return Err(Error::implementation_specific()),
@@ -1,3 +1,4 @@
+use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::core::ics04_channel::events::TimeoutPacket;
@@ -7,7 +8,7 @@ use crate::core::ics04_channel::handler::verify::{
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::core::ics04_channel::packet::{PacketResult, Sequence};
 use crate::core::ics04_channel::{context::ChannelReader, error::Error};
-use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
@@ -19,6 +20,9 @@ pub struct TimeoutPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub channel: Option<ChannelEnd>,
+    /// The client state updated by the verification of the proofs of the message, if any, with
+    /// the identifier of the client.
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
 pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<PacketResult, Error> {
@@ -88,6 +92,8 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
         return Err(Error::incorrect_packet_commitment(packet.sequence));
     }
 
+    let client_id = connection_end.client_id().clone();
+    let client_state = ctx.client_state(&client_id)?;
     let result = if source_channel_end.order_matches(&Order::Ordered) {
         if packet.sequence < msg.next_sequence_recv {
             return Err(Error::invalid_packet_sequence(
@@ -95,10 +101,11 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
                 msg.next_sequence_recv,
             ));
         }
-        verify_next_sequence_recv(
+        let client_state = verify_next_sequence_recv(
             ctx,
             msg.proofs.height(),
             &connection_end,
+            &client_state,
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs,
@@ -110,12 +117,14 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
             channel_id: packet.source_channel,
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     } else {
-        verify_packet_receipt_absence(
+        let client_state = verify_packet_receipt_absence(
             ctx,
             msg.proofs.height(),
             &connection_end,
+            &client_state,
             packet.clone(),
             &msg.proofs,
         )?;
@@ -125,6 +134,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
             channel_id: packet.source_channel,
             seq: packet.sequence,
             channel: None,
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     };
 
@@ -73,11 +73,14 @@ pub fn process(
         source_channel_end.version().clone(),
     );
 
-    verify_channel_proofs(
+    let client_id = connection_end.client_id().clone();
+    let client_state = ctx.client_state(&client_id)?;
+    let channel_client_state = verify_channel_proofs(
         ctx,
         msg.proofs.height(),
         &source_channel_end,
         &connection_end,
+        &client_state,
         &expected_channel_end,
         &msg.proofs,
     )?;
@@ -89,35 +92,41 @@ pub fn process(
                 msg.next_sequence_recv,
             ));
         }
-        verify_next_sequence_recv(
+        let client_state = verify_next_sequence_recv(
             ctx,
             msg.proofs.height(),
             &connection_end,
+            channel_client_state.as_ref().unwrap_or(&client_state),
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs,
-        )?;
+        )?
+        .or(channel_client_state);
 
         PacketResult::Timeout(TimeoutPacketResult {
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel,
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     } else {
-        verify_packet_receipt_absence(
+        let client_state = verify_packet_receipt_absence(
             ctx,
             msg.proofs.height(),
             &connection_end,
+            channel_client_state.as_ref().unwrap_or(&client_state),
             packet.clone(),
             &msg.proofs,
-        )?;
+        )?
+        .or(channel_client_state);
 
         PacketResult::Timeout(TimeoutPacketResult {
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel,
             seq: packet.sequence,
             channel: None,
+            client_state: client_state.map(|client_state| (client_id, client_state)),
         })
     };
 
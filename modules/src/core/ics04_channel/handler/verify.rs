@@ -1,5 +1,5 @@
 use crate::core::ics02_client::client_consensus::ConsensusState;
-use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::core::ics02_client::{client_def::AnyClient, client_def::ClientDef};
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
@@ -12,19 +12,22 @@ use crate::proofs::Proofs;
 use crate::Height;
 
 /// Entry point for verifying all proofs bundled in any ICS4 message for channel protocols.
+///
+/// The proofs are verified against `client_state`, the state of the client of `connection_end`.
+/// Like the other verification functions of this module, it returns the client state to store
+/// if the verification updated it (e.g. by advancing the sequence of a solo machine).
 pub fn verify_channel_proofs(
     ctx: &dyn ChannelReader,
     height: Height,
     channel_end: &ChannelEnd,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     expected_chan: &ChannelEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<Option<AnyClientState>, Error> {
     // This is the client which will perform proof verification.
     let client_id = connection_end.client_id().clone();
 
-    let client_state = ctx.client_state(&client_id)?;
-
     // The client must not be frozen.
     if client_state.is_frozen() {
         return Err(Error::frozen_client(client_id));
@@ -38,7 +41,8 @@ pub fn verify_channel_proofs(
     // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
     client_def
         .verify_channel_state(
-            &client_state,
+            ctx,
+            client_state,
             height,
             connection_end.counterparty().prefix(),
             proofs.object_proof(),
@@ -56,10 +60,10 @@ pub fn verify_packet_recv_proofs(
     height: Height,
     packet: &Packet,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<Option<AnyClientState>, Error> {
     let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
 
     // The client must not be frozen.
     if client_state.is_frozen() {
@@ -80,7 +84,7 @@ pub fn verify_packet_recv_proofs(
     client_def
         .verify_packet_data(
             ctx,
-            &client_state,
+            client_state,
             height,
             connection_end,
             proofs.object_proof(),
@@ -90,9 +94,7 @@ pub fn verify_packet_recv_proofs(
             packet.sequence,
             commitment,
         )
-        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
-
-    Ok(())
+        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))
 }
 
 /// Entry point for verifying all proofs bundled in an ICS4 packet ack message.
@@ -102,10 +104,10 @@ pub fn verify_packet_acknowledgement_proofs(
     packet: &Packet,
     acknowledgement: Acknowledgement,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<Option<AnyClientState>, Error> {
     let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
 
     // The client must not be frozen.
     if client_state.is_frozen() {
@@ -122,7 +124,7 @@ pub fn verify_packet_acknowledgement_proofs(
     client_def
         .verify_packet_acknowledgement(
             ctx,
-            &client_state,
+            client_state,
             height,
             connection_end,
             proofs.object_proof(),
@@ -132,9 +134,7 @@ pub fn verify_packet_acknowledgement_proofs(
             packet.sequence,
             ack_commitment,
         )
-        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
-
-    Ok(())
+        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))
 }
 
 /// Entry point for verifying all timeout proofs.
@@ -142,12 +142,12 @@ pub fn verify_next_sequence_recv(
     ctx: &dyn ChannelReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     packet: Packet,
     seq: Sequence,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<Option<AnyClientState>, Error> {
     let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
 
     // The client must not be frozen.
     if client_state.is_frozen() {
@@ -162,7 +162,7 @@ pub fn verify_next_sequence_recv(
     client_def
         .verify_next_sequence_recv(
             ctx,
-            &client_state,
+            client_state,
             height,
             connection_end,
             proofs.object_proof(),
@@ -171,20 +171,18 @@ pub fn verify_next_sequence_recv(
             &packet.destination_channel,
             packet.sequence,
         )
-        .map_err(|e| Error::packet_verification_failed(seq, e))?;
-
-    Ok(())
+        .map_err(|e| Error::packet_verification_failed(seq, e))
 }
 
 pub fn verify_packet_receipt_absence(
     ctx: &dyn ChannelReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    client_state: &AnyClientState,
     packet: Packet,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<Option<AnyClientState>, Error> {
     let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
 
     // The client must not be frozen.
     if client_state.is_frozen() {
@@ -199,7 +197,7 @@ pub fn verify_packet_receipt_absence(
     client_def
         .verify_packet_receipt_absence(
             ctx,
-            &client_state,
+            client_state,
             height,
             connection_end,
             proofs.object_proof(),
@@ -208,7 +206,5 @@ pub fn verify_packet_receipt_absence(
             &packet.destination_channel,
             packet.sequence,
         )
-        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
-
-    Ok(())
+        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))
 }
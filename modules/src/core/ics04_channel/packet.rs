@@ -4,10 +4,11 @@ use core::str::FromStr;
 
 use serde_derive::{Deserialize, Serialize};
 
-use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
+use ibc_proto::ibc::core::channel::v1::{Packet as RawPacket, PacketId as RawPacketId};
 
+use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics04_channel::error::Error;
-use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::events::{extract_attribute, Error as EventError, RawObject};
 use crate::timestamp::{Expiry::Expired, Timestamp};
 use crate::Height;
@@ -36,6 +37,24 @@ pub enum PacketResult {
     Timeout(TimeoutPacketResult),
 }
 
+impl PacketResult {
+    /// Returns the client state updated by the verification of the proofs of the message, if
+    /// any, with the identifier of the client.
+    pub fn client_state(&self) -> Option<&(ClientId, AnyClientState)> {
+        match self {
+            PacketResult::Recv(RecvPacketResult::Ordered { client_state, .. })
+            | PacketResult::Recv(RecvPacketResult::Unordered { client_state, .. })
+            | PacketResult::Ack(AckPacketResult { client_state, .. })
+            | PacketResult::Timeout(TimeoutPacketResult { client_state, .. }) => {
+                client_state.as_ref()
+            }
+            PacketResult::Send(_)
+            | PacketResult::Recv(RecvPacketResult::NoOp)
+            | PacketResult::WriteAck(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Receipt {
     Ok,
@@ -104,7 +123,10 @@ pub struct Packet {
     pub source_channel: ChannelId,
     pub destination_port: PortId,
     pub destination_channel: ChannelId,
-    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    #[serde(
+        serialize_with = "crate::serializers::ser_hex_upper",
+        deserialize_with = "crate::serializers::deser_hex_upper"
+    )]
     pub data: Vec<u8>,
     pub timeout_height: Height,
     pub timeout_timestamp: Timestamp,
@@ -188,6 +210,67 @@ impl core::fmt::Display for Packet {
     }
 }
 
+/// Identifies a packet by the port and channel on one of its ends and its sequence number.
+/// The source chain refers to a packet by its source port and channel, the destination chain
+/// by its destination port and channel.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct PacketId {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+impl PacketId {
+    pub fn new(port_id: PortId, channel_id: ChannelId, sequence: Sequence) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            sequence,
+        }
+    }
+
+    /// The identifier of `packet` on its source chain.
+    pub fn source(packet: &Packet) -> Self {
+        Self::new(
+            packet.source_port.clone(),
+            packet.source_channel,
+            packet.sequence,
+        )
+    }
+}
+
+impl core::fmt::Display for PacketId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{}/{}/{}", self.port_id, self.channel_id, self.sequence)
+    }
+}
+
+impl TryFrom<RawPacketId> for PacketId {
+    type Error = Error;
+
+    fn try_from(raw: RawPacketId) -> Result<Self, Self::Error> {
+        if Sequence::from(raw.sequence).is_zero() {
+            return Err(Error::zero_packet_sequence());
+        }
+
+        Ok(PacketId {
+            port_id: raw.port_id.parse().map_err(Error::identifier)?,
+            channel_id: raw.channel_id.parse().map_err(Error::identifier)?,
+            sequence: raw.sequence.into(),
+        })
+    }
+}
+
+impl From<PacketId> for RawPacketId {
+    fn from(packet_id: PacketId) -> Self {
+        RawPacketId {
+            port_id: packet_id.port_id.to_string(),
+            channel_id: packet_id.channel_id.to_string(),
+            sequence: packet_id.sequence.into(),
+        }
+    }
+}
+
 impl TryFrom<RawPacket> for Packet {
     type Error = Error;
 
@@ -24,6 +24,10 @@ impl fmt::Debug for CommitmentRoot {
 }
 
 impl CommitmentRoot {
+    pub const fn empty() -> Self {
+        Self { bytes: Vec::new() }
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Self {
         Self {
             bytes: Vec::from(bytes),
@@ -148,6 +148,25 @@ pub enum ClientUpgradePath {
     UpgradedClientConsensusState(u64),
 }
 
+impl ClientUpgradePath {
+    /// The key of the upgraded state under `upgrade_key`, the key identifying the upgraded
+    /// IBC state within the upgrade sub-store, which is `upgradedIBCState` unless the chain
+    /// configures another one.
+    pub fn key(&self, upgrade_key: &str) -> String {
+        match self {
+            Self::UpgradedClientState(height) => {
+                format!("{}/{}/{}", upgrade_key, height, UPGRADED_CLIENT_STATE)
+            }
+            Self::UpgradedClientConsensusState(height) => {
+                format!(
+                    "{}/{}/{}",
+                    upgrade_key, height, UPGRADED_CLIENT_CONSENSUS_STATE
+                )
+            }
+        }
+    }
+}
+
 /// Sub-paths which are not part of the specification, but are still
 /// useful to represent for parsing purposes.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -161,7 +161,7 @@ pub trait Module: Send + Sync + AsAnyMut {
     }
 
     fn on_recv_packet(
-        &self,
+        &mut self,
         _output: &mut ModuleOutputBuilder,
         _packet: &Packet,
         _relayer: &Signer,
@@ -75,6 +75,11 @@ where
             let handler_output = ics3_msg_dispatcher(ctx, msg).map_err(Error::ics03_connection)?;
 
             // Apply any results to the host chain store.
+            if let Some(client_state) = handler_output.result.client_state.clone() {
+                let client_id = handler_output.result.connection_end.client_id().clone();
+                ctx.store_client_state(client_id, client_state)
+                    .map_err(Error::ics02_client)?;
+            }
             ctx.store_connection_result(handler_output.result)
                 .map_err(Error::ics03_connection)?;
 
@@ -96,6 +101,10 @@ where
             let channel_result = cb_result.map_err(Error::ics04_channel)?;
 
             // Apply any results to the host chain store.
+            if let Some((client_id, client_state)) = channel_result.client_state.clone() {
+                ctx.store_client_state(client_id, client_state)
+                    .map_err(Error::ics02_client)?;
+            }
             ctx.store_channel_result(channel_result)
                 .map_err(Error::ics04_channel)?;
 
@@ -117,6 +126,10 @@ where
             cb_result.map_err(Error::ics04_channel)?;
 
             // Apply any results to the host chain store.
+            if let Some((client_id, client_state)) = packet_result.client_state().cloned() {
+                ctx.store_client_state(client_id, client_state)
+                    .map_err(Error::ics02_client)?;
+            }
             ctx.store_packet_result(packet_result)
                 .map_err(Error::ics04_channel)?;
 
@@ -309,6 +322,7 @@ mod tests {
                 },
                 sender: msg_transfer_two.sender.clone(),
                 receiver: msg_transfer_two.receiver.clone(),
+                memo: String::new(),
             };
             serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
         };
@@ -0,0 +1,373 @@
+//! Middleware stacking for the modules registered in a [`Router`](super::context::Router).
+//!
+//! A [`Middleware`] wraps a module and intercepts all the channel handshake and packet callbacks
+//! on the way to it. Middlewares are assembled into a stack over an application module with a
+//! [`ModuleStackBuilder`], and the resulting stack is registered in the router as a single
+//! [`Module`]. Since a router route is bound to ports through the `PortReader`, each port gets
+//! its own stack by binding it to a dedicated `ModuleId`.
+//!
+//! The middleware pushed last onto a stack is the outermost one: it is the first to see each
+//! callback and the last to see its result. A middleware may also return early without calling
+//! the module below it, in which case none of the modules further down see the callback.
+use core::any::Any;
+
+use crate::core::ics04_channel::channel::{Counterparty, Order};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck, WriteFn};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// A middleware intercepts the callbacks of the module `next` it is stacked over. All callbacks
+/// pass through to `next` unchanged by default.
+///
+/// The write functions of the acknowledgement returned by [`Middleware::on_recv_packet`] are
+/// called with the [`MiddlewareStack`] the middleware is part of, so that they may update the
+/// state of the middleware as well as the one of the modules below it. The write functions
+/// returned by `next` must be adapted with [`MiddlewareStack::forward_ack`] or
+/// [`MiddlewareStack::forward_write_fn`] before being passed on.
+pub trait Middleware: Send + Sync + Sized + 'static {
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(), Error> {
+        next.on_chan_open_init(
+            output,
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        next.on_chan_open_try(
+            output,
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+            counterparty_version,
+        )
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        next.on_chan_open_ack(output, port_id, channel_id, counterparty_version)
+    }
+
+    fn on_chan_open_confirm(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        next.on_chan_open_confirm(output, port_id, channel_id)
+    }
+
+    fn on_chan_close_init(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        next.on_chan_close_init(output, port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        next.on_chan_close_confirm(output, port_id, channel_id)
+    }
+
+    fn on_recv_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        MiddlewareStack::<Self>::forward_ack(next.on_recv_packet(output, packet, relayer))
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        next.on_acknowledgement_packet(output, packet, acknowledgement, relayer)
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        next: &mut dyn Module,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        next.on_timeout_packet(output, packet, relayer)
+    }
+}
+
+/// A module made of the middleware `Mw` stacked over the module `next`, which may itself be a
+/// stack.
+pub struct MiddlewareStack<Mw> {
+    middleware: Mw,
+    next: Box<dyn Module>,
+}
+
+impl<Mw: Middleware> MiddlewareStack<Mw> {
+    pub fn new(middleware: Mw, next: impl Module) -> Self {
+        Self {
+            middleware,
+            next: Box::new(next),
+        }
+    }
+
+    pub fn middleware(&self) -> &Mw {
+        &self.middleware
+    }
+
+    pub fn middleware_mut(&mut self) -> &mut Mw {
+        &mut self.middleware
+    }
+
+    pub fn next(&self) -> &dyn Module {
+        self.next.as_ref()
+    }
+
+    pub fn next_mut(&mut self) -> &mut dyn Module {
+        self.next.as_mut()
+    }
+
+    /// Adapts a write function returned by the module below the middleware `Mw` to be called
+    /// with this stack.
+    pub fn forward_write_fn(write_fn: Box<WriteFn>) -> Box<WriteFn> {
+        Box::new(move |module: &mut dyn Any| {
+            let stack = module
+                .downcast_mut::<Self>()
+                .ok_or_else(|| "write function called with a foreign module".to_string())?;
+            write_fn(stack.next.as_mut().as_any_mut())
+        })
+    }
+
+    /// Adapts the write functions of an acknowledgement returned by the module below the
+    /// middleware `Mw` to be called with this stack.
+    pub fn forward_ack(ack: OnRecvPacketAck) -> OnRecvPacketAck {
+        match ack {
+            OnRecvPacketAck::Nil(write_fn) => {
+                OnRecvPacketAck::Nil(Self::forward_write_fn(write_fn))
+            }
+            OnRecvPacketAck::Successful(ack, write_fn) => {
+                OnRecvPacketAck::Successful(ack, Self::forward_write_fn(write_fn))
+            }
+            OnRecvPacketAck::Failed(ack) => OnRecvPacketAck::Failed(ack),
+        }
+    }
+}
+
+impl<Mw: Middleware> Module for MiddlewareStack<Mw> {
+    fn on_chan_open_init(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(), Error> {
+        self.middleware.on_chan_open_init(
+            self.next.as_mut(),
+            output,
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        )
+    }
+
+    fn on_chan_open_try(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        self.middleware.on_chan_open_try(
+            self.next.as_mut(),
+            output,
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+            counterparty_version,
+        )
+    }
+
+    fn on_chan_open_ack(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        self.middleware.on_chan_open_ack(
+            self.next.as_mut(),
+            output,
+            port_id,
+            channel_id,
+            counterparty_version,
+        )
+    }
+
+    fn on_chan_open_confirm(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_open_confirm(self.next.as_mut(), output, port_id, channel_id)
+    }
+
+    fn on_chan_close_init(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_close_init(self.next.as_mut(), output, port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_close_confirm(self.next.as_mut(), output, port_id, channel_id)
+    }
+
+    fn on_recv_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        self.middleware
+            .on_recv_packet(self.next.as_mut(), output, packet, relayer)
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        self.middleware.on_acknowledgement_packet(
+            self.next.as_mut(),
+            output,
+            packet,
+            acknowledgement,
+            relayer,
+        )
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_timeout_packet(self.next.as_mut(), output, packet, relayer)
+    }
+}
+
+/// Assembles a stack of middlewares over an application module, to be registered in the router
+/// under the `ModuleId` bound to a port.
+///
+/// ```ignore
+/// let stack = ModuleStackBuilder::new(transfer_module)
+///     .push(fee_middleware)
+///     .push(rate_limit_middleware)
+///     .build();
+/// ```
+///
+/// In the stack above, the rate limit middleware sees each callback first, then the fee
+/// middleware, then the transfer module.
+pub struct ModuleStackBuilder<M> {
+    module: M,
+}
+
+impl<M: Module> ModuleStackBuilder<M> {
+    pub fn new(app: M) -> Self {
+        Self { module: app }
+    }
+
+    /// Stacks `middleware` over the modules added so far.
+    pub fn push<Mw: Middleware>(self, middleware: Mw) -> ModuleStackBuilder<MiddlewareStack<Mw>> {
+        ModuleStackBuilder {
+            module: MiddlewareStack::new(middleware, self.module),
+        }
+    }
+
+    /// Consumes the builder and returns the outermost module of the stack.
+    pub fn build(self) -> M {
+        self.module
+    }
+}
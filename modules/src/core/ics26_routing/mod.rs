@@ -4,4 +4,5 @@
 pub mod context;
 pub mod error;
 pub mod handler;
+pub mod middleware;
 pub mod msgs;
@@ -6,6 +6,7 @@ use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::Error;
 use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
@@ -19,6 +20,7 @@ use crate::core::ics24_host::path::ClientConsensusStatePath;
 use crate::core::ics24_host::Path;
 use crate::mock::client_state::{MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
+use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
 use crate::prelude::*;
 use crate::Height;
 
@@ -29,6 +31,7 @@ impl ClientDef for MockClient {
     type Header = MockHeader;
     type ClientState = MockClientState;
     type ConsensusState = MockConsensusState;
+    type Misbehaviour = MockMisbehaviour;
 
     fn check_header_and_update_state(
         &self,
@@ -49,8 +52,42 @@ impl ClientDef for MockClient {
         ))
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Error> {
+        let MockMisbehaviour {
+            header1, header2, ..
+        } = misbehaviour;
+
+        // Two mock headers constitute evidence of misbehaviour only if they are conflicting
+        // headers for the same height.
+        if header1.height() != header2.height() {
+            return Err(Error::invalid_misbehaviour(format!(
+                "headers have different heights: {} and {}",
+                header1.height(),
+                header2.height()
+            )));
+        }
+
+        if header1 == header2 {
+            return Err(Error::invalid_misbehaviour(
+                "headers are identical".to_string(),
+            ));
+        }
+
+        Ok(MockClientState {
+            frozen_height: Some(header1.height()),
+            ..client_state
+        })
+    }
+
     fn verify_client_consensus_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         _height: Height,
         prefix: &CommitmentPrefix,
@@ -59,7 +96,7 @@ impl ClientDef for MockClient {
         client_id: &ClientId,
         consensus_height: Height,
         _expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<Self::ClientState>, Error> {
         let client_prefixed_path = Path::ClientConsensusState(ClientConsensusStatePath {
             client_id: client_id.clone(),
             epoch: consensus_height.revision_number,
@@ -69,11 +106,12 @@ impl ClientDef for MockClient {
 
         let _path = apply_prefix(prefix, vec![client_prefixed_path]);
 
-        Ok(())
+        Ok(None)
     }
 
     fn verify_connection_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
@@ -81,12 +119,13 @@ impl ClientDef for MockClient {
         _root: &CommitmentRoot,
         _connection_id: &ConnectionId,
         _expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_channel_state(
         &self,
+        _ctx: &dyn ChannelReader,
         _client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
@@ -95,12 +134,13 @@ impl ClientDef for MockClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_client_full_state(
         &self,
+        _ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
@@ -108,8 +148,8 @@ impl ClientDef for MockClient {
         _root: &CommitmentRoot,
         _client_id: &ClientId,
         _expected_client_state: &AnyClientState,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_packet_data(
@@ -124,8 +164,8 @@ impl ClientDef for MockClient {
         _channel_id: &ChannelId,
         _sequence: Sequence,
         _commitment: PacketCommitment,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_packet_acknowledgement(
@@ -140,8 +180,8 @@ impl ClientDef for MockClient {
         _channel_id: &ChannelId,
         _sequence: Sequence,
         _ack: AcknowledgementCommitment,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_next_sequence_recv(
@@ -155,8 +195,8 @@ impl ClientDef for MockClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _sequence: Sequence,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_packet_receipt_absence(
@@ -170,17 +210,19 @@ impl ClientDef for MockClient {
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _sequence: Sequence,
-    ) -> Result<(), Error> {
-        Ok(())
+    ) -> Result<Option<Self::ClientState>, Error> {
+        Ok(None)
     }
 
     fn verify_upgrade_and_update_state(
         &self,
-        client_state: &Self::ClientState,
-        consensus_state: &Self::ConsensusState,
+        _client_state: &Self::ClientState,
+        _consensus_state: &Self::ConsensusState,
+        upgraded_client_state: &Self::ClientState,
+        upgraded_consensus_state: &Self::ConsensusState,
         _proof_upgrade_client: MerkleProof,
         _proof_upgrade_consensus_state: MerkleProof,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Error> {
-        Ok((*client_state, consensus_state.clone()))
+        Ok((*upgraded_client_state, upgraded_consensus_state.clone()))
     }
 }
@@ -15,21 +15,30 @@ use ibc_proto::google::protobuf::Any;
 use sha2::Digest;
 use tracing::debug;
 
+use crate::clients::ics06_solomachine::client_state::test_util::SoloMachine;
 use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+use crate::clients::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use crate::clients::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
 use crate::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
 use crate::core::ics02_client::error::Error as Ics02Error;
 use crate::core::ics02_client::header::AnyHeader;
-use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
 use crate::core::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
 use crate::core::ics03_connection::error::Error as Ics03Error;
-use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics03_connection::version::get_compatible_versions;
+use crate::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+};
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error as Ics04Error;
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::Version;
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::Error as Ics05Error;
 use crate::core::ics05_port::error::Error;
@@ -45,7 +54,7 @@ use crate::mock::host::{HostBlock, HostType};
 use crate::relayer::ics18_relayer::context::Ics18Context;
 use crate::relayer::ics18_relayer::error::Error as Ics18Error;
 use crate::signer::Signer;
-use crate::timestamp::Timestamp;
+use crate::timestamp::{Timestamp, ZERO_DURATION};
 use crate::Height;
 
 pub const DEFAULT_BLOCK_TIME_SECS: u64 = 3;
@@ -213,6 +222,19 @@ impl MockContext {
                 // Return the tuple.
                 (Some(client_state), consensus_state)
             }
+            // If it's a localhost client, the consensus state records the host timestamp.
+            ClientType::Localhost => (
+                Some(AnyClientState::Localhost(
+                    LocalhostClientState::new(self.host_chain_id.clone(), client_state_height)
+                        .unwrap(),
+                )),
+                self.localhost_consensus_state(cs_height),
+            ),
+            // If it's a solo machine client, its sequence is the height of the client.
+            ClientType::SoloMachine => {
+                let (client_state, consensus_state) = solo_machine_states(client_state_height);
+                (Some(client_state), consensus_state)
+            }
         };
         let consensus_states = vec![(cs_height, consensus_state)].into_iter().collect();
 
@@ -265,6 +287,18 @@ impl MockContext {
                 // Return the tuple.
                 (Some(client_state), consensus_state)
             }
+            // If it's a localhost client, the consensus state records the host timestamp.
+            ClientType::Localhost => (
+                Some(AnyClientState::Localhost(
+                    LocalhostClientState::new(self.host_chain_id.clone(), client_state_height)
+                        .unwrap(),
+                )),
+                self.localhost_consensus_state(cs_height),
+            ),
+            ClientType::SoloMachine => {
+                let (client_state, consensus_state) = solo_machine_states(client_state_height);
+                (Some(client_state), consensus_state)
+            }
         };
 
         let prev_consensus_state = match client_type {
@@ -279,6 +313,8 @@ impl MockContext {
                 );
                 AnyConsensusState::from(light_block)
             }
+            ClientType::Localhost => self.localhost_consensus_state(prev_cs_height),
+            ClientType::SoloMachine => solo_machine_states(prev_cs_height).1,
         };
 
         let consensus_states = vec![
@@ -318,6 +354,52 @@ impl MockContext {
         self
     }
 
+    /// Associates an open connection over the client `client_id` to this context, whose
+    /// counterparty is the connection `counterparty_connection_id` over the default client of the
+    /// counterparty chain.
+    pub fn with_open_connection(
+        self,
+        client_id: &ClientId,
+        connection_id: ConnectionId,
+        counterparty_connection_id: ConnectionId,
+    ) -> Self {
+        let counterparty = ConnectionCounterparty::new(
+            ClientId::default(),
+            Some(counterparty_connection_id),
+            CommitmentPrefix::try_from(b"ibc".to_vec()).expect("the prefix is not empty"),
+        );
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            counterparty,
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+        self.with_connection(connection_id, connection_end)
+    }
+
+    /// Associates an open, unordered channel on the connection `connection_id` to this context,
+    /// whose counterparty is the channel `counterparty_channel_id` on the same port. Packets get
+    /// sent on the channel from sequence 1 on.
+    pub fn with_open_channel(
+        self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        connection_id: ConnectionId,
+        counterparty_channel_id: ChannelId,
+        version: Version,
+    ) -> Self {
+        let channel_end = ChannelEnd::new(
+            ChannelState::Open,
+            Order::Unordered,
+            ChannelCounterparty::new(port_id.clone(), Some(counterparty_channel_id)),
+            vec![connection_id],
+            version,
+        );
+        self.with_channel(port_id.clone(), chan_id, channel_end)
+            .with_send_sequence(port_id, chan_id, 1.into())
+    }
+
     /// Associates a channel (in an arbitrary state) to this context.
     pub fn with_channel(
         self,
@@ -419,6 +501,15 @@ impl MockContext {
         }
     }
 
+    /// The consensus state of a localhost client at `height`, which records the timestamp of the
+    /// host block at that height.
+    fn localhost_consensus_state(&self, height: Height) -> AnyConsensusState {
+        let timestamp = self
+            .host_block(height)
+            .map_or_else(Timestamp::now, |block| block.timestamp());
+        AnyConsensusState::Localhost(LocalhostConsensusState::new(timestamp))
+    }
+
     /// Triggers the advancing of the host chain, by extending the history of blocks (or headers).
     pub fn advance_host_chain_height(&mut self) {
         let latest_block = self.history.last().expect("history cannot be empty");
@@ -539,6 +630,18 @@ impl MockContext {
     }
 }
 
+/// The client and consensus states of a solo machine at the sequence given by the revision
+/// height of `height`.
+fn solo_machine_states(height: Height) -> (AnyClientState, AnyConsensusState) {
+    let mut solo_machine = SoloMachine::new("solomachine", "mock");
+    solo_machine.sequence = height.revision_height;
+
+    (
+        AnyClientState::SoloMachine(solo_machine.client_state()),
+        AnyConsensusState::SoloMachine(solo_machine.consensus_state()),
+    )
+}
+
 /// An object that stores all IBC related data.
 #[derive(Clone, Debug, Default)]
 pub struct MockIbcStore {
@@ -1311,17 +1414,21 @@ mod tests {
     use test_log::test;
 
     use alloc::str::FromStr;
+    use alloc::sync::Arc;
+    use std::sync::Mutex;
 
     use crate::core::ics04_channel::channel::{Counterparty, Order};
     use crate::core::ics04_channel::error::Error;
     use crate::core::ics04_channel::packet::Packet;
     use crate::core::ics04_channel::Version;
+    use crate::core::ics05_port::context::PortReader;
     use crate::core::ics24_host::identifier::ChainId;
     use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
     use crate::core::ics26_routing::context::{
         Acknowledgement, Module, ModuleId, ModuleOutputBuilder, OnRecvPacketAck, Router,
         RouterBuilder,
     };
+    use crate::core::ics26_routing::middleware::{Middleware, MiddlewareStack, ModuleStackBuilder};
     use crate::mock::context::MockContext;
     use crate::mock::context::MockRouterBuilder;
     use crate::mock::host::HostType;
@@ -1504,7 +1611,7 @@ mod tests {
             }
 
             fn on_recv_packet(
-                &self,
+                &mut self,
                 _output: &mut ModuleOutputBuilder,
                 _packet: &Packet,
                 _relayer: &Signer,
@@ -1581,4 +1688,257 @@ mod tests {
                 write_fn(ctx.router.get_route_mut(&mid).unwrap().as_any_mut()).unwrap()
             });
     }
+
+    #[test]
+    fn test_router_middleware_stack() {
+        type Log = Arc<Mutex<Vec<String>>>;
+
+        fn log(log: &Log, entry: &str) {
+            log.lock().unwrap().push(entry.to_string());
+        }
+
+        fn take(log: &Log) -> Vec<String> {
+            core::mem::take(&mut *log.lock().unwrap())
+        }
+
+        struct MockAck;
+
+        impl AsRef<[u8]> for MockAck {
+            fn as_ref(&self) -> &[u8] {
+                b"ack"
+            }
+        }
+
+        impl Acknowledgement for MockAck {}
+
+        struct App {
+            log: Log,
+        }
+
+        impl Module for App {
+            fn on_chan_open_init(
+                &mut self,
+                _output: &mut ModuleOutputBuilder,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                _version: &Version,
+            ) -> Result<(), Error> {
+                log(&self.log, "app init");
+                Ok(())
+            }
+
+            fn on_chan_open_try(
+                &mut self,
+                _output: &mut ModuleOutputBuilder,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                _version: &Version,
+                counterparty_version: &Version,
+            ) -> Result<Version, Error> {
+                Ok(counterparty_version.clone())
+            }
+
+            fn on_recv_packet(
+                &mut self,
+                _output: &mut ModuleOutputBuilder,
+                _packet: &Packet,
+                _relayer: &Signer,
+            ) -> OnRecvPacketAck {
+                log(&self.log, "app recv");
+                OnRecvPacketAck::Successful(
+                    Box::new(MockAck),
+                    Box::new(|module| {
+                        let module = module.downcast_mut::<App>().unwrap();
+                        log(&module.log, "app write");
+                        Ok(())
+                    }),
+                )
+            }
+        }
+
+        /// Records the callbacks passing through it, before and after the modules below it.
+        struct Tracer {
+            name: &'static str,
+            log: Log,
+            writes: usize,
+        }
+
+        impl Middleware for Tracer {
+            fn on_chan_open_init(
+                &mut self,
+                next: &mut dyn Module,
+                output: &mut ModuleOutputBuilder,
+                order: Order,
+                connection_hops: &[ConnectionId],
+                port_id: &PortId,
+                channel_id: &ChannelId,
+                counterparty: &Counterparty,
+                version: &Version,
+            ) -> Result<(), Error> {
+                log(&self.log, &format!("{} init", self.name));
+                next.on_chan_open_init(
+                    output,
+                    order,
+                    connection_hops,
+                    port_id,
+                    channel_id,
+                    counterparty,
+                    version,
+                )?;
+                log(&self.log, &format!("{} init done", self.name));
+                Ok(())
+            }
+
+            fn on_recv_packet(
+                &mut self,
+                next: &mut dyn Module,
+                output: &mut ModuleOutputBuilder,
+                packet: &Packet,
+                relayer: &Signer,
+            ) -> OnRecvPacketAck {
+                log(&self.log, &format!("{} recv", self.name));
+                match MiddlewareStack::<Self>::forward_ack(
+                    next.on_recv_packet(output, packet, relayer),
+                ) {
+                    OnRecvPacketAck::Successful(ack, write_fn) => OnRecvPacketAck::Successful(
+                        ack,
+                        Box::new(move |module| {
+                            let stack = module.downcast_mut::<MiddlewareStack<Self>>().unwrap();
+                            let tracer = stack.middleware_mut();
+                            tracer.writes += 1;
+                            log(&tracer.log, &format!("{} write", tracer.name));
+                            write_fn(module)
+                        }),
+                    ),
+                    ack => ack,
+                }
+            }
+        }
+
+        /// Rejects all channel openings, without calling the modules below it.
+        struct Gate {
+            log: Log,
+        }
+
+        impl Middleware for Gate {
+            fn on_chan_open_init(
+                &mut self,
+                _next: &mut dyn Module,
+                _output: &mut ModuleOutputBuilder,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                _version: &Version,
+            ) -> Result<(), Error> {
+                log(&self.log, "gate init");
+                Err(Error::app_module("gate closed".to_string()))
+            }
+        }
+
+        let trace = Log::default();
+        let tracer = |name| Tracer {
+            name,
+            log: trace.clone(),
+            writes: 0,
+        };
+
+        // Each port gets its own stack, bound to a module id of its own.
+        let traced_port = PortId::transfer();
+        let traced_module: ModuleId = "tracedstack".parse().unwrap();
+        let traced_stack = ModuleStackBuilder::new(App { log: trace.clone() })
+            .push(tracer("inner"))
+            .push(tracer("outer"))
+            .build();
+
+        let gated_port = PortId::from_str("gated").unwrap();
+        let gated_module: ModuleId = "gatedstack".parse().unwrap();
+        let gated_stack = ModuleStackBuilder::new(App { log: trace.clone() })
+            .push(tracer("inner"))
+            .push(Gate { log: trace.clone() })
+            .build();
+
+        let r = MockRouterBuilder::default()
+            .add_route(traced_module.clone(), traced_stack)
+            .unwrap()
+            .add_route(gated_module.clone(), gated_stack)
+            .unwrap()
+            .build();
+
+        let mut ctx = MockContext::default().with_router(r);
+        ctx.scope_port_to_module(traced_port.clone(), traced_module);
+        ctx.scope_port_to_module(gated_port.clone(), gated_module);
+
+        let mut on_chan_open_init = |port_id: &PortId| {
+            let module_id = ctx.lookup_module_by_port(port_id).unwrap();
+            ctx.router
+                .get_route_mut(&module_id)
+                .unwrap()
+                .on_chan_open_init(
+                    &mut ModuleOutputBuilder::new(),
+                    Order::Unordered,
+                    &[ConnectionId::default()],
+                    port_id,
+                    &ChannelId::default(),
+                    &Counterparty::default(),
+                    &Version::default(),
+                )
+        };
+
+        // The middleware pushed last sees the callback first, and its result last.
+        assert!(on_chan_open_init(&traced_port).is_ok());
+        assert_eq!(
+            take(&trace),
+            vec![
+                "outer init",
+                "inner init",
+                "app init",
+                "inner init done",
+                "outer init done"
+            ]
+        );
+
+        // A middleware returning early hides the callback from the modules below it.
+        assert!(on_chan_open_init(&gated_port).is_err());
+        assert_eq!(take(&trace), vec!["gate init"]);
+
+        // The write functions run in the same order as the callbacks, and each middleware
+        // gets access to its own state.
+        let module_id = ctx.lookup_module_by_port(&traced_port).unwrap();
+        let module = ctx.router.get_route_mut(&module_id).unwrap();
+        let write_fn = match module.on_recv_packet(
+            &mut ModuleOutputBuilder::new(),
+            &Packet::default(),
+            &get_dummy_bech32_account().parse().unwrap(),
+        ) {
+            OnRecvPacketAck::Successful(_, write_fn) => write_fn,
+            _ => panic!("expected a successful acknowledgement"),
+        };
+        assert_eq!(take(&trace), vec!["outer recv", "inner recv", "app recv"]);
+
+        write_fn(module.as_any_mut()).unwrap();
+        assert_eq!(
+            take(&trace),
+            vec!["outer write", "inner write", "app write"]
+        );
+
+        let outer = module
+            .as_any_mut()
+            .downcast_mut::<MiddlewareStack<Tracer>>()
+            .unwrap();
+        assert_eq!(outer.middleware().writes, 1);
+        let inner = outer
+            .next_mut()
+            .as_any_mut()
+            .downcast_mut::<MiddlewareStack<Tracer>>()
+            .unwrap();
+        assert_eq!(inner.middleware().writes, 1);
+    }
 }
@@ -1,6 +1,9 @@
+use serde::de::{self, Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use subtle_encoding::{Encoding, Hex};
 
+use crate::prelude::*;
+
 pub fn ser_hex_upper<S, T>(data: T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -10,6 +13,14 @@ where
     hex.serialize(serializer)
 }
 
+pub fn deser_hex_upper<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hex = String::deserialize(deserializer)?;
+    Hex::upper_case().decode(hex).map_err(de::Error::custom)
+}
+
 pub mod serde_string {
     use alloc::string::String;
     use core::fmt::Display;
@@ -1,10 +1,39 @@
+use alloc::collections::btree_map::BTreeMap;
+use core::ops::{Deref, DerefMut};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use ibc_proto::google::protobuf::Any;
 use tendermint::{block, consensus, evidence, public_key::Algorithm};
 
-use crate::applications::transfer::context::{BankKeeper, Ics20Context, Ics20Keeper, Ics20Reader};
-use crate::applications::transfer::{error::Error as Ics20Error, PrefixedCoin};
+use crate::applications::ics27_interchain_accounts::context::{
+    AccountKeeper, Ics27Context, Ics27Keeper, Ics27Reader,
+};
+use crate::applications::ics27_interchain_accounts::error::Error as Ics27Error;
+use crate::applications::ics29_fee::context::{
+    BankKeeper as FeeBankKeeper, Ics29Context, Ics29Keeper, Ics29Reader,
+};
+use crate::applications::ics29_fee::error::Error as Ics29Error;
+use crate::applications::ics29_fee::fee::PacketFee;
+use crate::applications::packet_forward::context::{
+    InFlightPacket, PacketForwardContext, PacketForwardKeeper, PacketForwardReader,
+};
+use crate::applications::packet_forward::error::Error as PacketForwardError;
+use crate::applications::rate_limit::context::{
+    RateLimitContext, RateLimitKeeper, RateLimitReader,
+};
+use crate::applications::rate_limit::error::Error as RateLimitError;
+use crate::applications::rate_limit::handler::record_send;
+use crate::applications::rate_limit::quota::RateLimit;
+use crate::applications::transfer::context::{
+    on_acknowledgement_packet as ics20_on_acknowledgement_packet,
+    on_recv_packet as ics20_on_recv_packet, on_timeout_packet as ics20_on_timeout_packet,
+    BankKeeper, Ics20Context, Ics20Keeper, Ics20Reader,
+};
+use crate::applications::transfer::{
+    error::Error as Ics20Error, Amount, BaseCoin, PrefixedCoin, PrefixedDenom,
+};
+use crate::bigint::U256;
 use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics02_client::error::Error as Ics02Error;
@@ -14,12 +43,14 @@ use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error;
-use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::{Packet, PacketId, Receipt, Sequence};
 use crate::core::ics04_channel::Version;
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::Error as PortError;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
-use crate::core::ics26_routing::context::{Module, ModuleId, ModuleOutputBuilder};
+use crate::core::ics26_routing::context::{Module, ModuleId, ModuleOutputBuilder, OnRecvPacketAck};
+use crate::events::IbcEvent;
 use crate::mock::context::MockIbcStore;
 use crate::prelude::*;
 use crate::signer::Signer;
@@ -60,38 +91,46 @@ pub fn get_dummy_bech32_account() -> String {
     "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng".to_string()
 }
 
-#[derive(Debug)]
-pub struct DummyTransferModule {
+/// A context over the IBC store of a `MockContext`, which implements the channel reader and
+/// keeper for the dummy application contexts below, and dereferences to the state `S` of the
+/// application. Clones share the IBC store and the host timestamp.
+#[derive(Clone, Debug)]
+pub struct DummyStoreContext<S> {
     ibc_store: Arc<Mutex<MockIbcStore>>,
+    host_timestamp: Arc<Mutex<Option<Timestamp>>>,
+    state: S,
 }
 
-impl DummyTransferModule {
-    pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>) -> Self {
-        Self { ibc_store }
+impl<S> DummyStoreContext<S> {
+    pub fn with_state(ibc_store: Arc<Mutex<MockIbcStore>>, state: S) -> Self {
+        Self {
+            ibc_store,
+            host_timestamp: Default::default(),
+            state,
+        }
+    }
+
+    /// Sets the timestamp of the host, which is the current time otherwise.
+    pub fn set_host_timestamp(&self, timestamp: Timestamp) {
+        *self.host_timestamp.lock().unwrap() = Some(timestamp);
     }
 }
 
-impl Module for DummyTransferModule {
-    fn on_chan_open_try(
-        &mut self,
-        _output: &mut ModuleOutputBuilder,
-        _order: Order,
-        _connection_hops: &[ConnectionId],
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _counterparty: &Counterparty,
-        _version: &Version,
-        counterparty_version: &Version,
-    ) -> Result<Version, Error> {
-        Ok(counterparty_version.clone())
+impl<S> Deref for DummyStoreContext<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.state
     }
 }
 
-impl Ics20Keeper for DummyTransferModule {
-    type AccountId = Signer;
+impl<S> DerefMut for DummyStoreContext<S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
 }
 
-impl ChannelKeeper for DummyTransferModule {
+impl<S> ChannelKeeper for DummyStoreContext<S> {
     fn store_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
@@ -122,10 +161,15 @@ impl ChannelKeeper for DummyTransferModule {
 
     fn store_packet_acknowledgement(
         &mut self,
-        _key: (PortId, ChannelId, Sequence),
-        _ack: AcknowledgementCommitment,
+        key: (PortId, ChannelId, Sequence),
+        ack: AcknowledgementCommitment,
     ) -> Result<(), Error> {
-        unimplemented!()
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .packet_acknowledgement
+            .insert(key, ack);
+        Ok(())
     }
 
     fn delete_packet_acknowledgement(
@@ -185,58 +229,7 @@ impl ChannelKeeper for DummyTransferModule {
     }
 }
 
-impl PortReader for DummyTransferModule {
-    fn lookup_module_by_port(&self, _port_id: &PortId) -> Result<ModuleId, PortError> {
-        unimplemented!()
-    }
-}
-
-impl BankKeeper for DummyTransferModule {
-    type AccountId = Signer;
-
-    fn send_coins(
-        &mut self,
-        _from: &Self::AccountId,
-        _to: &Self::AccountId,
-        _amt: &PrefixedCoin,
-    ) -> Result<(), Ics20Error> {
-        Ok(())
-    }
-
-    fn mint_coins(
-        &mut self,
-        _account: &Self::AccountId,
-        _amt: &PrefixedCoin,
-    ) -> Result<(), Ics20Error> {
-        Ok(())
-    }
-
-    fn burn_coins(
-        &mut self,
-        _account: &Self::AccountId,
-        _amt: &PrefixedCoin,
-    ) -> Result<(), Ics20Error> {
-        Ok(())
-    }
-}
-
-impl Ics20Reader for DummyTransferModule {
-    type AccountId = Signer;
-
-    fn get_port(&self) -> Result<PortId, Ics20Error> {
-        Ok(PortId::transfer())
-    }
-
-    fn is_send_enabled(&self) -> bool {
-        true
-    }
-
-    fn is_receive_enabled(&self) -> bool {
-        true
-    }
-}
-
-impl ChannelReader for DummyTransferModule {
+impl<S> ChannelReader for DummyStoreContext<S> {
     fn channel_end(&self, pcid: &(PortId, ChannelId)) -> Result<ChannelEnd, Error> {
         match self.ibc_store.lock().unwrap().channels.get(pcid) {
             Some(channel_end) => Ok(channel_end.clone()),
@@ -320,9 +313,12 @@ impl ChannelReader for DummyTransferModule {
 
     fn get_packet_commitment(
         &self,
-        _key: &(PortId, ChannelId, Sequence),
+        key: &(PortId, ChannelId, Sequence),
     ) -> Result<PacketCommitment, Error> {
-        unimplemented!()
+        match self.ibc_store.lock().unwrap().packet_commitment.get(key) {
+            Some(commitment) => Ok(commitment.clone()),
+            None => Err(Error::packet_commitment_not_found(key.2)),
+        }
     }
 
     fn get_packet_receipt(&self, _key: &(PortId, ChannelId, Sequence)) -> Result<Receipt, Error> {
@@ -331,9 +327,18 @@ impl ChannelReader for DummyTransferModule {
 
     fn get_packet_acknowledgement(
         &self,
-        _key: &(PortId, ChannelId, Sequence),
+        key: &(PortId, ChannelId, Sequence),
     ) -> Result<AcknowledgementCommitment, Error> {
-        unimplemented!()
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .packet_acknowledgement
+            .get(key)
+        {
+            Some(ack) => Ok(ack.clone()),
+            None => Err(Error::packet_acknowledgement_not_found(key.2)),
+        }
     }
 
     fn hash(&self, value: Vec<u8>) -> Vec<u8> {
@@ -346,8 +351,18 @@ impl ChannelReader for DummyTransferModule {
         Height::zero()
     }
 
-    fn host_consensus_state(&self, _height: Height) -> Result<AnyConsensusState, Error> {
-        unimplemented!()
+    fn host_timestamp(&self) -> Timestamp {
+        self.host_timestamp
+            .lock()
+            .unwrap()
+            .unwrap_or_else(Timestamp::now)
+    }
+
+    /// The dummy host keeps no history of its consensus states.
+    fn host_consensus_state(&self, height: Height) -> Result<AnyConsensusState, Error> {
+        Err(Error::ics03_connection(Ics03Error::ics02_client(
+            Ics02Error::missing_local_consensus_state(height),
+        )))
     }
 
     fn pending_host_consensus_state(&self) -> Result<AnyConsensusState, Error> {
@@ -379,6 +394,658 @@ impl ChannelReader for DummyTransferModule {
     }
 }
 
+pub type DummyTransferModule = DummyStoreContext<()>;
+
+impl DummyTransferModule {
+    pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>) -> Self {
+        Self::with_state(ibc_store, ())
+    }
+}
+
+impl Module for DummyTransferModule {
+    fn on_chan_open_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        Ok(counterparty_version.clone())
+    }
+}
+
+impl Ics20Keeper for DummyTransferModule {
+    type AccountId = Signer;
+}
+
+impl PortReader for DummyTransferModule {
+    fn lookup_module_by_port(&self, port_id: &PortId) -> Result<ModuleId, PortError> {
+        match self.ibc_store.lock().unwrap().port_to_module.get(port_id) {
+            Some(module_id) => Ok(module_id.clone()),
+            None => Err(PortError::unknown_port(port_id.clone())),
+        }
+    }
+}
+
+impl BankKeeper for DummyTransferModule {
+    type AccountId = Signer;
+
+    fn send_coins(
+        &mut self,
+        _from: &Self::AccountId,
+        _to: &Self::AccountId,
+        _amt: &PrefixedCoin,
+    ) -> Result<(), Ics20Error> {
+        Ok(())
+    }
+
+    fn mint_coins(
+        &mut self,
+        _account: &Self::AccountId,
+        _amt: &PrefixedCoin,
+    ) -> Result<(), Ics20Error> {
+        Ok(())
+    }
+
+    fn burn_coins(
+        &mut self,
+        _account: &Self::AccountId,
+        _amt: &PrefixedCoin,
+    ) -> Result<(), Ics20Error> {
+        Ok(())
+    }
+}
+
+impl Ics20Reader for DummyTransferModule {
+    type AccountId = Signer;
+
+    fn get_port(&self) -> Result<PortId, Ics20Error> {
+        Ok(PortId::transfer())
+    }
+
+    fn get_balance(&self, _account: &Self::AccountId, _denom: &PrefixedDenom) -> Amount {
+        U256::MAX.into()
+    }
+
+    fn is_send_enabled(&self) -> bool {
+        true
+    }
+
+    fn is_receive_enabled(&self) -> bool {
+        true
+    }
+}
+
 impl Ics20Context for DummyTransferModule {
     type AccountId = Signer;
 }
+
+/// The state of a [`DummyIcaContext`], which records the registered accounts and the messages
+/// they executed. Transactions containing a message of one of the `failing_messages` types fail
+/// to execute.
+#[derive(Debug, Default)]
+pub struct DummyIcaState {
+    allowed_messages: Vec<String>,
+    active_channels: BTreeMap<(ConnectionId, PortId), ChannelId>,
+    account_addresses: BTreeMap<(ConnectionId, PortId), Signer>,
+    pub accounts: Vec<Signer>,
+    pub executed_messages: Vec<(Signer, Any)>,
+    pub failing_messages: Vec<String>,
+}
+
+/// An interchain accounts context over the IBC store of a `MockContext`.
+pub type DummyIcaContext = DummyStoreContext<DummyIcaState>;
+
+impl DummyIcaContext {
+    pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>, allowed_messages: Vec<String>) -> Self {
+        Self::with_state(
+            ibc_store,
+            DummyIcaState {
+                allowed_messages,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+impl Ics27Keeper for DummyIcaContext {
+    type AccountId = Signer;
+
+    fn store_active_channel_id(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(), Ics27Error> {
+        self.active_channels
+            .insert((connection_id, port_id), channel_id);
+        Ok(())
+    }
+
+    fn store_interchain_account_address(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        address: Signer,
+    ) -> Result<(), Ics27Error> {
+        self.account_addresses
+            .insert((connection_id, port_id), address);
+        Ok(())
+    }
+}
+
+impl AccountKeeper for DummyIcaContext {
+    type AccountId = Signer;
+
+    fn new_interchain_account(&mut self, account: &Self::AccountId) -> Result<(), Ics27Error> {
+        self.accounts.push(account.clone());
+        Ok(())
+    }
+
+    fn execute_tx(
+        &mut self,
+        account: &Self::AccountId,
+        messages: Vec<Any>,
+    ) -> Result<Vec<u8>, Ics27Error> {
+        if let Some(message) = messages
+            .iter()
+            .find(|message| self.failing_messages.contains(&message.type_url))
+        {
+            return Err(Ics27Error::execution_failed(format!(
+                "{} failed",
+                message.type_url
+            )));
+        }
+
+        // The result data lists the types of the executed messages.
+        let data = messages
+            .iter()
+            .map(|message| message.type_url.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+            .into_bytes();
+        self.executed_messages
+            .extend(messages.into_iter().map(|msg| (account.clone(), msg)));
+        Ok(data)
+    }
+}
+
+impl Ics27Reader for DummyIcaContext {
+    type AccountId = Signer;
+
+    fn is_controller_enabled(&self) -> bool {
+        true
+    }
+
+    fn is_host_enabled(&self) -> bool {
+        true
+    }
+
+    fn is_message_allowed(&self, type_url: &str) -> bool {
+        self.allowed_messages
+            .iter()
+            .any(|allowed| allowed == type_url)
+    }
+
+    fn get_active_channel_id(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<ChannelId> {
+        self.active_channels
+            .get(&(connection_id.clone(), port_id.clone()))
+            .copied()
+    }
+
+    fn get_interchain_account_address(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<Signer> {
+        self.account_addresses
+            .get(&(connection_id.clone(), port_id.clone()))
+            .cloned()
+    }
+}
+
+impl Ics27Context for DummyIcaContext {
+    type AccountId = Signer;
+}
+
+/// The state of a [`DummyFeeContext`], which records the coins sent by the fee middleware.
+#[derive(Debug, Default)]
+pub struct DummyFeeState {
+    fee_enabled_channels: Vec<(PortId, ChannelId)>,
+    payees: BTreeMap<(Signer, ChannelId), Signer>,
+    counterparty_payees: BTreeMap<(Signer, ChannelId), Signer>,
+    fees_in_escrow: BTreeMap<PacketId, Vec<PacketFee>>,
+    pub transfers: Vec<(Signer, Signer, BaseCoin)>,
+}
+
+/// A fee middleware context over the IBC store of a `MockContext`.
+pub type DummyFeeContext = DummyStoreContext<DummyFeeState>;
+
+impl DummyFeeContext {
+    pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>) -> Self {
+        Self::with_state(ibc_store, DummyFeeState::default())
+    }
+}
+
+impl Ics29Keeper for DummyFeeContext {
+    type AccountId = Signer;
+
+    fn store_fee_enabled(
+        &mut self,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(), Ics29Error> {
+        self.fee_enabled_channels.push((port_id, channel_id));
+        Ok(())
+    }
+
+    fn store_payee(
+        &mut self,
+        relayer: Signer,
+        channel_id: ChannelId,
+        payee: Signer,
+    ) -> Result<(), Ics29Error> {
+        self.payees.insert((relayer, channel_id), payee);
+        Ok(())
+    }
+
+    fn store_counterparty_payee(
+        &mut self,
+        relayer: Signer,
+        channel_id: ChannelId,
+        counterparty_payee: Signer,
+    ) -> Result<(), Ics29Error> {
+        self.counterparty_payees
+            .insert((relayer, channel_id), counterparty_payee);
+        Ok(())
+    }
+
+    fn store_fees_in_escrow(
+        &mut self,
+        packet_id: PacketId,
+        packet_fees: Vec<PacketFee>,
+    ) -> Result<(), Ics29Error> {
+        self.fees_in_escrow.insert(packet_id, packet_fees);
+        Ok(())
+    }
+
+    fn delete_fees_in_escrow(&mut self, packet_id: &PacketId) -> Result<(), Ics29Error> {
+        self.fees_in_escrow.remove(packet_id);
+        Ok(())
+    }
+}
+
+impl FeeBankKeeper for DummyFeeContext {
+    type AccountId = Signer;
+
+    fn send_coins(
+        &mut self,
+        from: &Self::AccountId,
+        to: &Self::AccountId,
+        amt: &BaseCoin,
+    ) -> Result<(), Ics29Error> {
+        self.transfers.push((from.clone(), to.clone(), amt.clone()));
+        Ok(())
+    }
+}
+
+impl Ics29Reader for DummyFeeContext {
+    type AccountId = Signer;
+
+    fn is_fee_enabled(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        self.fee_enabled_channels
+            .contains(&(port_id.clone(), *channel_id))
+    }
+
+    fn get_payee(&self, relayer: &Signer, channel_id: &ChannelId) -> Option<Signer> {
+        self.payees.get(&(relayer.clone(), *channel_id)).cloned()
+    }
+
+    fn get_counterparty_payee(&self, relayer: &Signer, channel_id: &ChannelId) -> Option<Signer> {
+        self.counterparty_payees
+            .get(&(relayer.clone(), *channel_id))
+            .cloned()
+    }
+
+    fn get_fees_in_escrow(&self, packet_id: &PacketId) -> Vec<PacketFee> {
+        self.fees_in_escrow
+            .get(packet_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn get_identified_packet_fees_for_channel(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Vec<(PacketId, Vec<PacketFee>)> {
+        self.fees_in_escrow
+            .iter()
+            .filter(|(packet_id, _)| {
+                &packet_id.port_id == port_id && &packet_id.channel_id == channel_id
+            })
+            .map(|(packet_id, packet_fees)| (packet_id.clone(), packet_fees.clone()))
+            .collect()
+    }
+}
+
+impl Ics29Context for DummyFeeContext {
+    type AccountId = Signer;
+}
+
+/// An operation of the bank of a [`DummyForwardContext`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DummyBankOp {
+    Send {
+        from: Signer,
+        to: Signer,
+        coin: PrefixedCoin,
+    },
+    Mint {
+        account: Signer,
+        coin: PrefixedCoin,
+    },
+    Burn {
+        account: Signer,
+        coin: PrefixedCoin,
+    },
+}
+
+/// The state of a [`DummyForwardContext`].
+#[derive(Debug, Default)]
+pub struct DummyForwardState {
+    bank_ops: Vec<DummyBankOp>,
+    balances: BTreeMap<(Signer, PrefixedDenom), Amount>,
+    in_flight_packets: BTreeMap<PacketId, InFlightPacket>,
+    rate_limits: BTreeMap<(ChannelId, PrefixedDenom), RateLimit>,
+    send_times: BTreeMap<(ChannelId, Sequence), Timestamp>,
+    events: Vec<IbcEvent>,
+}
+
+/// A transfer context over the IBC store of a `MockContext`, which serves both as the ICS20
+/// module and as the context of the middlewares stacked over it. Clones share their state, so
+/// that the state can be inspected from both.
+pub type DummyForwardContext = DummyStoreContext<Arc<Mutex<DummyForwardState>>>;
+
+impl DummyForwardContext {
+    pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>) -> Self {
+        Self::with_state(ibc_store, Default::default())
+    }
+
+    /// Sets the balance of `account` in `denom`, which is otherwise unlimited. Bank operations
+    /// are only recorded, and don't change balances.
+    pub fn set_balance(&self, account: Signer, denom: PrefixedDenom, amount: Amount) {
+        self.state
+            .lock()
+            .unwrap()
+            .balances
+            .insert((account, denom), amount);
+    }
+
+    /// Returns and clears the bank operations performed so far.
+    pub fn take_bank_ops(&self) -> Vec<DummyBankOp> {
+        core::mem::take(&mut self.state.lock().unwrap().bank_ops)
+    }
+
+    /// Returns and clears the IBC events emitted so far.
+    pub fn take_events(&self) -> Vec<IbcEvent> {
+        core::mem::take(&mut self.state.lock().unwrap().events)
+    }
+}
+
+impl Module for DummyForwardContext {
+    fn on_chan_open_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        Ok(counterparty_version.clone())
+    }
+
+    fn on_recv_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> OnRecvPacketAck {
+        ics20_on_recv_packet(self, output, packet, relayer)
+    }
+
+    fn on_acknowledgement_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        acknowledgement: &GenericAcknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        ics20_on_acknowledgement_packet(self, output, packet, acknowledgement, relayer)
+            .map_err(|e: Ics20Error| Error::app_module(e.to_string()))
+    }
+
+    fn on_timeout_packet(
+        &mut self,
+        output: &mut ModuleOutputBuilder,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), Error> {
+        ics20_on_timeout_packet(self, output, packet, relayer)
+            .map_err(|e: Ics20Error| Error::app_module(e.to_string()))
+    }
+}
+
+impl Ics20Keeper for DummyForwardContext {
+    type AccountId = Signer;
+
+    fn on_send_transfer(
+        &mut self,
+        packet: &Packet,
+        tokens: &[PrefixedCoin],
+    ) -> Result<(), Ics20Error> {
+        record_send(self, packet, tokens).map_err(|e| Ics20Error::transfer_rejected(e.to_string()))
+    }
+}
+
+impl BankKeeper for DummyForwardContext {
+    type AccountId = Signer;
+
+    fn send_coins(
+        &mut self,
+        from: &Self::AccountId,
+        to: &Self::AccountId,
+        amt: &PrefixedCoin,
+    ) -> Result<(), Ics20Error> {
+        self.state.lock().unwrap().bank_ops.push(DummyBankOp::Send {
+            from: from.clone(),
+            to: to.clone(),
+            coin: amt.clone(),
+        });
+        Ok(())
+    }
+
+    fn mint_coins(
+        &mut self,
+        account: &Self::AccountId,
+        amt: &PrefixedCoin,
+    ) -> Result<(), Ics20Error> {
+        self.state.lock().unwrap().bank_ops.push(DummyBankOp::Mint {
+            account: account.clone(),
+            coin: amt.clone(),
+        });
+        Ok(())
+    }
+
+    fn burn_coins(
+        &mut self,
+        account: &Self::AccountId,
+        amt: &PrefixedCoin,
+    ) -> Result<(), Ics20Error> {
+        self.state.lock().unwrap().bank_ops.push(DummyBankOp::Burn {
+            account: account.clone(),
+            coin: amt.clone(),
+        });
+        Ok(())
+    }
+}
+
+impl Ics20Reader for DummyForwardContext {
+    type AccountId = Signer;
+
+    fn get_port(&self) -> Result<PortId, Ics20Error> {
+        Ok(PortId::transfer())
+    }
+
+    fn get_balance(&self, account: &Self::AccountId, denom: &PrefixedDenom) -> Amount {
+        self.state
+            .lock()
+            .unwrap()
+            .balances
+            .get(&(account.clone(), denom.clone()))
+            .copied()
+            .unwrap_or_else(|| U256::MAX.into())
+    }
+
+    fn is_send_enabled(&self) -> bool {
+        true
+    }
+
+    fn is_receive_enabled(&self) -> bool {
+        true
+    }
+}
+
+impl Ics20Context for DummyForwardContext {
+    type AccountId = Signer;
+}
+
+impl PacketForwardKeeper for DummyForwardContext {
+    fn store_in_flight_packet(
+        &mut self,
+        packet_id: PacketId,
+        in_flight: InFlightPacket,
+    ) -> Result<(), PacketForwardError> {
+        self.state
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .insert(packet_id, in_flight);
+        Ok(())
+    }
+
+    fn delete_in_flight_packet(&mut self, packet_id: &PacketId) -> Result<(), PacketForwardError> {
+        self.state
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .remove(packet_id);
+        Ok(())
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) {
+        self.state.lock().unwrap().events.push(event);
+    }
+}
+
+impl PacketForwardReader for DummyForwardContext {
+    fn get_in_flight_packet(&self, packet_id: &PacketId) -> Option<InFlightPacket> {
+        self.state
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .get(packet_id)
+            .cloned()
+    }
+}
+
+impl PacketForwardContext for DummyForwardContext {}
+
+impl RateLimitKeeper for DummyForwardContext {
+    fn store_rate_limit(
+        &mut self,
+        channel_id: ChannelId,
+        denom: PrefixedDenom,
+        rate_limit: RateLimit,
+    ) -> Result<(), RateLimitError> {
+        self.state
+            .lock()
+            .unwrap()
+            .rate_limits
+            .insert((channel_id, denom), rate_limit);
+        Ok(())
+    }
+
+    fn delete_rate_limit(
+        &mut self,
+        channel_id: &ChannelId,
+        denom: &PrefixedDenom,
+    ) -> Result<(), RateLimitError> {
+        self.state
+            .lock()
+            .unwrap()
+            .rate_limits
+            .remove(&(*channel_id, denom.clone()));
+        Ok(())
+    }
+
+    fn store_send_time(
+        &mut self,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        timestamp: Timestamp,
+    ) -> Result<(), RateLimitError> {
+        self.state
+            .lock()
+            .unwrap()
+            .send_times
+            .insert((channel_id, sequence), timestamp);
+        Ok(())
+    }
+
+    fn delete_send_time(
+        &mut self,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), RateLimitError> {
+        self.state
+            .lock()
+            .unwrap()
+            .send_times
+            .remove(&(*channel_id, sequence));
+        Ok(())
+    }
+}
+
+impl RateLimitReader for DummyForwardContext {
+    fn get_rate_limit(&self, channel_id: &ChannelId, denom: &PrefixedDenom) -> Option<RateLimit> {
+        self.state
+            .lock()
+            .unwrap()
+            .rate_limits
+            .get(&(*channel_id, denom.clone()))
+            .cloned()
+    }
+
+    fn get_send_time(&self, channel_id: &ChannelId, sequence: Sequence) -> Option<Timestamp> {
+        self.state
+            .lock()
+            .unwrap()
+            .send_times
+            .get(&(*channel_id, sequence))
+            .copied()
+    }
+}
+
+impl RateLimitContext for DummyForwardContext {}
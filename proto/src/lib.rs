@@ -25,12 +25,22 @@ macro_rules! include_proto {
     };
 }
 
+#[macro_export]
+macro_rules! include_vendored_proto {
+    ($path:literal) => {
+        include!(concat!("vendored/", $path));
+    };
+}
+
 /// The version (commit hash) of the Cosmos SDK used when generating this library.
 pub const COSMOS_SDK_COMMIT: &str = include_str!("COSMOS_SDK_COMMIT");
 
-/// The version (commit hash) of IBC Go used when generating this library.
+/// The version (commit hash or release tag) of IBC Go used when generating this library.
 pub const IBC_GO_COMMIT: &str = include_str!("IBC_GO_COMMIT");
 
+/// The version (commit hash or release tag) of IBC Go the protos of [`vendored`] are taken from.
+pub const VENDORED_IBC_GO_COMMIT: &str = include_str!("vendored/IBC_GO_COMMIT");
+
 pub mod cosmos {
     pub mod auth {
         pub mod v1beta1 {
@@ -129,6 +139,11 @@ pub mod ibc {
         pub use super::applications::*;
     }
     pub mod applications {
+        pub mod fee {
+            pub mod v1 {
+                include_proto!("ibc.applications.fee.v1.rs");
+            }
+        }
         pub mod transfer {
             pub mod v1 {
                 include_proto!("ibc.applications.transfer.v1.rs");
@@ -190,6 +205,9 @@ pub mod ibc {
             pub mod v1 {
                 include_proto!("ibc.lightclients.solomachine.v1.rs");
             }
+            pub mod v2 {
+                include_proto!("ibc.lightclients.solomachine.v2.rs");
+            }
         }
         pub mod tendermint {
             pub mod v1 {
@@ -206,6 +224,25 @@ pub mod ics23 {
     include_proto!("ics23.rs");
 }
 
+/// Protos of a newer IBC Go release than [`IBC_GO_COMMIT`], which are not generated along with
+/// the others. They are kept outside of `prost/` so that regenerating the protos leaves them in
+/// place, and are copied from the release recorded in [`VENDORED_IBC_GO_COMMIT`] with the same
+/// attributes as the generated ones.
+pub mod vendored {
+    pub mod ibc {
+        pub mod applications {
+            pub mod transfer {
+                pub mod v1 {
+                    include_vendored_proto!("ibc.applications.transfer.v1.rs");
+                }
+                pub mod v2 {
+                    include_vendored_proto!("ibc.applications.transfer.v2.rs");
+                }
+            }
+        }
+    }
+}
+
 pub(crate) mod base64 {
     use alloc::string::String;
     use alloc::vec::Vec;
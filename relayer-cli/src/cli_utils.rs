@@ -28,13 +28,19 @@ pub struct ChainHandlePair<Chain: ChainHandle = BaseChainHandle> {
 impl<Chain: ChainHandle> ChainHandlePair<Chain> {
     /// Spawn the source and destination chain runtime from the configuration and chain identifiers,
     /// and return the pair of associated handles.
+    ///
+    /// If both identifiers are the same, a single runtime is spawned and shared by both handles.
     pub fn spawn_generic(
         config: &Config,
         src_chain_id: &ChainId,
         dst_chain_id: &ChainId,
     ) -> Result<Self, Error> {
-        let src = spawn_chain_runtime_generic(config, src_chain_id)?;
-        let dst = spawn_chain_runtime_generic(config, dst_chain_id)?;
+        let src = spawn_chain_runtime_generic::<Chain>(config, src_chain_id)?;
+        let dst = if src_chain_id == dst_chain_id {
+            src.clone()
+        } else {
+            spawn_chain_runtime_generic(config, dst_chain_id)?
+        };
 
         Ok(ChainHandlePair { src, dst })
     }
@@ -38,6 +38,8 @@ static HINT: &str = "Consider using the default invocation\n\nhermes create chan
 /// to indicate that a new connection/client pair is being created as part of this new channel.
 /// This brings up an interactive yes/no prompt to ensure that the operator at least
 /// considers the fact that they're initializing a new connection with the channel.
+/// If `Chain-B-ID` is the same as `Chain-A-ID`, the new clients are ICS 09 localhost clients
+/// and the channel connects two ports of the same chain.
 ///
 /// Note that `Connection-ID`s have to be considered based off of the chain's perspective. Although
 /// chain A and chain B might refer to the connection with different names, they are actually referring
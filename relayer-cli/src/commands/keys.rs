@@ -2,10 +2,16 @@
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer::keyring::errors::Error as KeyringError;
+use ibc_relayer::keyring::{KeyRing, Store};
+
 mod add;
 mod balance;
 mod delete;
 mod list;
+mod migrate;
+mod rotate_passphrase;
 
 /// `keys` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
@@ -21,4 +27,19 @@ pub enum KeysCmd {
 
     /// Query balance for a key from a configured chain. If no key is given, the key is retrieved from the configuration file.
     Balance(balance::KeyBalanceCmd),
+
+    /// Migrate the unencrypted keys of a configured chain into its encrypted key store
+    Migrate(migrate::KeysMigrateCmd),
+
+    /// Re-encrypt the keys of a configured chain with a new passphrase
+    RotatePassphrase(rotate_passphrase::KeysRotatePassphraseCmd),
+}
+
+/// Opens the on-disk key store managed by the `keys` commands for the chain: the encrypted
+/// one if the chain is configured with it, the unencrypted test one otherwise.
+fn disk_keyring(config: &ChainConfig) -> Result<KeyRing, KeyringError> {
+    match config.key_store_type {
+        Store::Encrypted => KeyRing::from_config(config),
+        _ => KeyRing::new(Store::Test, &config.account_prefix, &config.id),
+    }
 }
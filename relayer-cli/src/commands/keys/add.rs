@@ -10,9 +10,10 @@ use abscissa_core::{Command, Runnable};
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{HDPath, KeyEntry, KeyRing, Store},
+    keyring::{HDPath, KeyEntry},
 };
 
+use super::disk_keyring;
 use crate::application::app_config;
 use crate::conclude::Output;
 
@@ -155,7 +156,7 @@ pub fn add_key(
     file: &Path,
     hd_path: &HDPath,
 ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = disk_keyring(config)?;
 
     let key_contents = fs::read_to_string(file).map_err(|_| "error reading the key file")?;
     let key = keyring.key_from_seed_file(&key_contents, hd_path)?;
@@ -173,7 +174,7 @@ pub fn restore_key(
     let mnemonic_content =
         fs::read_to_string(mnemonic).map_err(|_| "error reading the mnemonic file")?;
 
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = disk_keyring(config)?;
     let key_entry = keyring.key_from_mnemonic(&mnemonic_content, hdpath, &config.address_type)?;
 
     keyring.add_key(key_name, key_entry.clone())?;
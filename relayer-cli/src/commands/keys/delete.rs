@@ -2,11 +2,9 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc_relayer::{
-    config::{ChainConfig, Config},
-    keyring::{KeyRing, Store},
-};
+use ibc_relayer::config::{ChainConfig, Config};
 
+use super::disk_keyring;
 use crate::application::app_config;
 use crate::conclude::Output;
 
@@ -93,13 +91,13 @@ impl Runnable for KeysDeleteCmd {
 }
 
 pub fn delete_key(config: &ChainConfig, key_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = disk_keyring(config)?;
     keyring.remove_key(key_name)?;
     Ok(())
 }
 
 pub fn delete_all_keys(config: &ChainConfig) -> Result<(), Box<dyn std::error::Error>> {
-    let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let mut keyring = disk_keyring(config)?;
     let keys = keyring.keys()?;
     for key in keys {
         keyring.remove_key(&key.0)?;
@@ -6,9 +6,10 @@ use abscissa_core::{Command, Runnable};
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{KeyEntry, KeyRing, Store},
+    keyring::KeyEntry,
 };
 
+use super::disk_keyring;
 use crate::conclude::Output;
 use crate::{application::app_config, conclude::json};
 
@@ -64,7 +65,7 @@ pub struct KeysListOptions {
 pub fn list_keys(
     config: ChainConfig,
 ) -> Result<Vec<(String, KeyEntry)>, Box<dyn std::error::Error>> {
-    let keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let keyring = disk_keyring(&config)?;
     let keys = keyring.keys()?;
     Ok(keys)
 }
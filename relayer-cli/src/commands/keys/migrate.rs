@@ -0,0 +1,104 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::{
+    config::{ChainConfig, Config},
+    keyring::{errors::Error as KeyringError, KeyRing, Store},
+};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `keys migrate` CLI command.
+///
+/// `keys migrate [OPTIONS] <CHAIN_ID>`
+///
+/// Copies the keys of the chain from the unencrypted test key store into the encrypted one,
+/// sealed with the passphrase read from the `key_store_passphrase` source of the chain.
+/// The unencrypted key files are kept unless `--delete-plaintext` is given.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct KeysMigrateCmd {
+    #[clap(required = true, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[clap(
+        short = 'n',
+        long,
+        help = "name of the key to migrate (defaults to all the keys of the chain)"
+    )]
+    name: Option<String>,
+
+    #[clap(long, help = "delete the unencrypted key files once migrated")]
+    delete_plaintext: bool,
+}
+
+impl KeysMigrateCmd {
+    fn options(&self, config: &Config) -> Result<ChainConfig, String> {
+        config
+            .find_chain(&self.chain_id)
+            .cloned()
+            .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))
+    }
+}
+
+impl Runnable for KeysMigrateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match self.options(&config) {
+            Err(err) => Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        match migrate_keys(&chain_config, self.name.as_deref(), self.delete_plaintext) {
+            Ok(names) => {
+                let mut msg = format!(
+                    "Migrated {} key(s) on chain {} to the encrypted key store:",
+                    names.len(),
+                    chain_config.id
+                );
+                for name in names {
+                    msg.push_str(&format!("\n- {}", name));
+                }
+                if chain_config.key_store_type != Store::Encrypted {
+                    msg.push_str(
+                        "\nSet `key_store_type = 'Encrypted'` for the chain to relay with them.",
+                    );
+                }
+                Output::success_msg(msg).exit()
+            }
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Copies the key `name`, or all the keys if `None`, from the test key store of the chain into
+/// its encrypted key store, returning the names of the keys migrated. The unencrypted key files
+/// are only deleted once all the keys are migrated.
+pub fn migrate_keys(
+    config: &ChainConfig,
+    name: Option<&str>,
+    delete_plaintext: bool,
+) -> Result<Vec<String>, KeyringError> {
+    let mut plaintext = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+    let passphrase = config.key_store_passphrase.read()?;
+    let mut encrypted = KeyRing::new_encrypted(&config.account_prefix, &config.id, passphrase)?;
+
+    let keys = match name {
+        Some(name) => vec![(name.to_string(), plaintext.get_key(name)?)],
+        None => plaintext.keys()?,
+    };
+
+    for (name, key) in &keys {
+        encrypted.add_key(name, key.clone())?;
+    }
+
+    if delete_plaintext {
+        for (name, _) in &keys {
+            plaintext.remove_key(name)?;
+        }
+    }
+
+    Ok(keys.into_iter().map(|(name, _)| name).collect())
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::{
+    config::{ChainConfig, Config},
+    keyring::encrypted::{prompt_passphrase, Passphrase, PassphraseSource},
+    keyring::errors::Error as KeyringError,
+    keyring::KeyRing,
+};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `keys rotate-passphrase`
+/// CLI command.
+///
+/// `keys rotate-passphrase [OPTIONS] <CHAIN_ID>`
+///
+/// The current passphrase is read from the `key_store_passphrase` source of the chain. The new
+/// one is read from the given environment variable or file, or prompted for otherwise. The
+/// source of the chain must be updated to yield the new passphrase afterwards.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct KeysRotatePassphraseCmd {
+    #[clap(required = true, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[clap(
+        long,
+        value_name = "VAR",
+        help = "environment variable to read the new passphrase from",
+        group = "new-passphrase"
+    )]
+    new_passphrase_env: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "file to read the new passphrase from",
+        group = "new-passphrase"
+    )]
+    new_passphrase_file: Option<PathBuf>,
+}
+
+impl KeysRotatePassphraseCmd {
+    fn options(&self, config: &Config) -> Result<ChainConfig, String> {
+        config
+            .find_chain(&self.chain_id)
+            .cloned()
+            .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))
+    }
+
+    fn new_passphrase(&self) -> Result<Passphrase, KeyringError> {
+        match (&self.new_passphrase_env, &self.new_passphrase_file) {
+            (Some(var), _) => PassphraseSource::Env { var: var.clone() }.read(),
+            (_, Some(path)) => PassphraseSource::File { path: path.clone() }.read(),
+            (None, None) => prompt_passphrase("New key store passphrase", true),
+        }
+    }
+}
+
+impl Runnable for KeysRotatePassphraseCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match self.options(&config) {
+            Err(err) => Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        let new_passphrase = match self.new_passphrase() {
+            Err(e) => Output::error(format!("{}", e)).exit(),
+            Ok(passphrase) => passphrase,
+        };
+
+        match rotate_passphrase(&chain_config, new_passphrase) {
+            Ok(count) => Output::success_msg(format!(
+                "Re-encrypted {} key(s) on chain {} with the new passphrase",
+                count, chain_config.id
+            ))
+            .exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+pub fn rotate_passphrase(
+    config: &ChainConfig,
+    new_passphrase: Passphrase,
+) -> Result<usize, KeyringError> {
+    let passphrase = config.key_store_passphrase.read()?;
+    let mut keyring = KeyRing::new_encrypted(&config.account_prefix, &config.id, passphrase)?;
+
+    keyring.rotate_passphrase(new_passphrase)
+}
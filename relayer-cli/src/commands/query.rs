@@ -16,6 +16,7 @@ mod client;
 mod clients;
 mod connection;
 mod connections;
+mod fees_spent;
 mod packet;
 mod transfer;
 mod tx;
@@ -55,6 +56,9 @@ pub enum QueryCmd {
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
+
+    /// Query the fees spent on the transactions sent to a chain
+    FeesSpent(fees_spent::QueryFeesSpentCmd),
 }
 
 #[derive(Command, Debug, Parser, Runnable)]
@@ -0,0 +1,53 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::Runnable;
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::chain::cosmos::fee_spend::load_ledger;
+
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// The fees spent by the relayer on the transactions sent to a chain, as recorded in its fee
+/// ledger. The ledger is kept for the last 30 days.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryFeesSpentCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+
+    #[clap(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "day (UTC) to show the spend of, instead of all the recorded days"
+    )]
+    date: Option<String>,
+}
+
+// hermes query fees-spent ibc-0 --date 2022-06-01
+impl Runnable for QueryFeesSpentCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        debug!("Options: {:?}", self);
+
+        if config.find_chain(&self.chain_id).is_none() {
+            Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit()
+        }
+
+        let ledger = match load_ledger(&self.chain_id) {
+            Ok(ledger) => ledger,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        match &self.date {
+            Some(date) => {
+                let days: Vec<_> = ledger.day(date).cloned().into_iter().collect();
+                Output::success(days).exit()
+            }
+            None => Output::success(ledger.days).exit(),
+        }
+    }
+}
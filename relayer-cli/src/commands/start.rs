@@ -24,11 +24,21 @@ pub struct StartCmd {
         help = "Force a full scan of the chains for clients, connections and channels"
     )]
     full_scan: bool,
+
+    #[clap(
+        long = "dry-run",
+        help = "Simulate the transactions instead of broadcasting them, overriding the `dry_run` global option"
+    )]
+    dry_run: bool,
 }
 
 impl Runnable for StartCmd {
     fn run(&self) {
-        let config = (*app_config()).clone();
+        let mut config = (*app_config()).clone();
+
+        if self.dry_run {
+            config.global.dry_run = true;
+        }
 
         let supervisor_handle = make_supervisor::<CachingChainHandle>(config, self.full_scan)
             .unwrap_or_else(|e| {
@@ -53,13 +63,13 @@ impl Runnable for StartCmd {
 }
 
 /// Register the SIGHUP and SIGUSR1 signals, and notify the supervisor.
-/// - [DEPRECATED] SIGHUP: Trigger a reload of the configuration.
+/// - SIGHUP: Trigger a reload of the configuration.
 /// - SIGUSR1: Ask the supervisor to dump its state and print it to the console.
 fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     use signal_hook::{consts::signal::*, iterator::Signals};
 
     let sigs = vec![
-        SIGHUP,  // Reload of configuration (disabled)
+        SIGHUP,  // Reload of configuration
         SIGUSR1, // Dump state
     ];
 
@@ -68,10 +78,18 @@ fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     std::thread::spawn(move || {
         for signal in &mut signals {
             match signal {
-                SIGHUP => warn!(
-                    "configuration reloading via SIGHUP has been disabled, \
-                     the signal handler will be removed in the future"
-                ),
+                SIGHUP => {
+                    info!("reloading configuration (triggered by SIGHUP)");
+
+                    let (tx, rx) = crossbeam_channel::bounded(1);
+                    tx_cmd.try_send(SupervisorCmd::ReloadConfig(tx)).unwrap();
+
+                    std::thread::spawn(move || match rx.recv() {
+                        Ok(Ok(reload)) => info!("configuration reloaded: {}", reload),
+                        Ok(Err(e)) => error!("failed to reload configuration: {}", e),
+                        Err(_) => (),
+                    });
+                }
                 SIGUSR1 => {
                     info!("dumping state (triggered by SIGUSR1)");
 
@@ -107,7 +125,9 @@ fn spawn_rest_server(config: &Config) -> Option<rest::Receiver> {
     let rest = config.rest.clone();
 
     if rest.enabled {
-        let rest_config = ibc_relayer_rest::Config::new(rest.host, rest.port);
+        let rest_config = ibc_relayer_rest::Config::new(rest.host, rest.port)
+            .with_token(rest.token)
+            .with_max_event_streams(rest.max_event_streams);
         let (_, rest_receiver) = ibc_relayer_rest::server::spawn(rest_config);
         Some(rest_receiver)
     } else {
@@ -185,6 +205,7 @@ fn make_supervisor<Chain: ChainHandle>(
         SupervisorOptions {
             health_check: true,
             force_full_scan,
+            config_path: crate::config::config_path(),
         },
     )?)
 }
@@ -10,6 +10,7 @@ use crate::commands::tx::client::{
 mod channel;
 pub(crate) mod client;
 mod connection;
+mod fee;
 mod packet;
 mod transfer;
 mod upgrade;
@@ -78,6 +79,12 @@ pub enum TxRawCommands {
 
     /// Send an IBC upgrade plan
     UpgradeChain(upgrade::TxIbcUpgradeChainCmd),
+
+    /// Register the payee of the acknowledgement and timeout fees earned on a channel (ICS29)
+    RegisterPayee(fee::TxRegisterPayeeCmd),
+
+    /// Register the counterparty payee of the receive fees earned on a channel (ICS29)
+    RegisterCounterpartyPayee(fee::TxRegisterCounterpartyPayeeCmd),
 }
 
 impl Override<Config> for TxCmd {
@@ -0,0 +1,92 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::signer::Signer;
+use ibc_relayer::fee::{register_counterparty_payee, register_payee};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+#[derive(Clone, Command, Debug, Parser)]
+pub struct TxRegisterPayeeCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain to register the payee on"
+    )]
+    chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the port")]
+    port_id: PortId,
+
+    #[clap(required = true, help = "identifier of the channel")]
+    channel_id: ChannelId,
+
+    #[clap(
+        required = true,
+        help = "address of the account which gets paid the acknowledgement and timeout fees"
+    )]
+    payee: Signer,
+}
+
+impl Runnable for TxRegisterPayeeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let res = register_payee(&chain, &self.port_id, &self.channel_id, self.payee.clone())
+            .map_err(Error::fee);
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser)]
+pub struct TxRegisterCounterpartyPayeeCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain which receives the packets relayed over the channel"
+    )]
+    chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the port")]
+    port_id: PortId,
+
+    #[clap(required = true, help = "identifier of the channel")]
+    channel_id: ChannelId,
+
+    #[clap(
+        required = true,
+        help = "address of the account on the counterparty chain which gets paid the receive fees"
+    )]
+    counterparty_payee: Signer,
+}
+
+impl Runnable for TxRegisterCounterpartyPayeeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let res = register_counterparty_payee(
+            &chain,
+            &self.port_id,
+            &self.channel_id,
+            self.counterparty_payee.clone(),
+        )
+        .map_err(Error::fee);
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
@@ -8,6 +8,7 @@ use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::channel::ChannelError;
 use ibc_relayer::connection::ConnectionError;
 use ibc_relayer::error::Error as RelayerError;
+use ibc_relayer::fee::FeeError;
 use ibc_relayer::foreign_client::ForeignClientError;
 use ibc_relayer::link::error::LinkError;
 use ibc_relayer::spawn::SpawnError;
@@ -81,6 +82,10 @@ define_error! {
             [ TransferError ]
             |_| { "transfer error" },
 
+        Fee
+            [ FeeError ]
+            |_| { "fee error" },
+
         Channel
             [ ChannelError ]
             |_| { "channel error" },
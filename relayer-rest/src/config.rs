@@ -1,15 +1,39 @@
 use core::fmt;
 
+/// The maximum number of clients streaming the events at once, by default.
+pub const DEFAULT_MAX_EVENT_STREAMS: usize = 16;
+
 /// REST server configuration
 #[derive(Clone, Debug)]
 pub struct Config {
     pub host: String,
     pub port: u16,
+    /// The token the requests to all the endpoints must carry
+    /// as `Authorization: Bearer <token>`.
+    pub token: Option<String>,
+    /// The maximum number of clients streaming the events at once.
+    pub max_event_streams: usize,
 }
 
 impl Config {
     pub fn new(host: String, port: u16) -> Self {
-        Self { host, port }
+        Self {
+            host,
+            port,
+            token: None,
+            max_event_streams: DEFAULT_MAX_EVENT_STREAMS,
+        }
+    }
+
+    pub fn with_token(self, token: Option<String>) -> Self {
+        Self { token, ..self }
+    }
+
+    pub fn with_max_event_streams(self, max_event_streams: usize) -> Self {
+        Self {
+            max_event_streams,
+            ..self
+        }
     }
 
     pub fn address(&self) -> (&str, u16) {
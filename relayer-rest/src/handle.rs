@@ -1,17 +1,32 @@
 use core::fmt::Debug;
+use core::str::FromStr;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use tracing::error;
+use rouille::{ReadWrite, Upgrade};
+use tracing::{debug, error, warn};
 
 use crossbeam_channel as channel;
 
+use ibc::core::ics24_host::error::ValidationError;
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc_relayer::supervisor::dump_state::SupervisorState;
+use ibc::events::IbcEvent;
+use ibc_relayer::supervisor::{
+    dump_state::{SupervisorState, WorkerDesc},
+    reload::ConfigReload,
+};
 use ibc_relayer::{
     config::ChainConfig,
+    event::relayer::{self as relayer_event, RelayerEventFilter},
+    object::Object,
     rest::{
-        request::{reply_channel, ReplySender, Request, VersionInfo},
+        request::{reply_channel, ReplySender, Request, VersionInfo, WorkerSelector},
         RestApiError,
     },
+    worker::WorkerId,
 };
 
 pub const NAME: &str = env!(
@@ -64,6 +79,241 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+pub fn reload_config(sender: &channel::Sender<Request>) -> Result<ConfigReload, RestApiError> {
+    submit_request(sender, |reply_to| Request::ReloadConfig { reply_to })
+}
+
+pub fn clear_packets(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    port_id: &str,
+    channel_id: &str,
+) -> Result<Vec<WorkerId>, RestApiError> {
+    let port_id = parse_id(port_id)?;
+    let channel_id = parse_id(channel_id)?;
+
+    submit_request(sender, |reply_to| Request::ClearPackets {
+        chain_id: ChainId::from_string(chain_id),
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+pub fn pause_worker(
+    sender: &channel::Sender<Request>,
+    worker: WorkerSelector,
+) -> Result<Option<WorkerDesc>, RestApiError> {
+    submit_request(sender, |reply_to| Request::PauseWorker { worker, reply_to })
+}
+
+pub fn resume_worker(
+    sender: &channel::Sender<Request>,
+    worker: WorkerSelector,
+) -> Result<Option<WorkerDesc>, RestApiError> {
+    submit_request(sender, |reply_to| Request::ResumeWorker {
+        worker,
+        reply_to,
+    })
+}
+
+pub fn exclude_worker(
+    sender: &channel::Sender<Request>,
+    worker: WorkerSelector,
+) -> Result<Object, RestApiError> {
+    submit_request(sender, |reply_to| Request::ExcludeWorker {
+        worker,
+        reply_to,
+    })
+}
+
+pub fn update_client(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    client_id: &str,
+) -> Result<Vec<IbcEvent>, RestApiError> {
+    let client_id = parse_id(client_id)?;
+
+    submit_request(sender, |reply_to| Request::UpdateClient {
+        chain_id: ChainId::from_string(chain_id),
+        client_id,
+        reply_to,
+    })
+}
+
+pub fn scan_chain(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+) -> Result<Vec<WorkerDesc>, RestApiError> {
+    submit_request(sender, |reply_to| Request::ScanChain {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn event_filter(
+    chain_id: Option<String>,
+    channel_id: Option<String>,
+) -> Result<RelayerEventFilter, RestApiError> {
+    let channel = channel_id.as_deref().map(parse_id).transpose()?;
+
+    Ok(RelayerEventFilter {
+        chain: chain_id.as_deref().map(ChainId::from_string),
+        channel,
+    })
+}
+
+/// How long the event stream may stay idle before a Ping frame is sent over it,
+/// to find out whether the client is still connected.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The WebSocket opcodes of the frames sent over the event streams.
+const TEXT_OPCODE: u8 = 0x1;
+const PING_OPCODE: u8 = 0x9;
+
+/// The number of event streams open, bounded by the configuration of the server.
+#[derive(Clone, Debug)]
+pub struct EventStreams {
+    open: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl EventStreams {
+    pub fn new(max: usize) -> Self {
+        Self {
+            open: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Accounts for a new stream, unless the maximum number of streams are open already.
+    /// The stream is accounted for until the returned slot is dropped.
+    fn open(&self) -> Option<EventStreamSlot> {
+        self.open
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |open| {
+                (open < self.max).then(|| open + 1)
+            })
+            .ok()
+            .map(|_| EventStreamSlot(self.open.clone()))
+    }
+}
+
+struct EventStreamSlot(Arc<AtomicUsize>);
+
+impl Drop for EventStreamSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Hands the socket of the upgraded connection over to the thread streaming the events,
+/// which writes the WebSocket frames itself, since rouille's `Websocket` can't send Ping frames.
+struct EventSocket(mpsc::Sender<Box<dyn ReadWrite + Send>>);
+
+impl Upgrade for EventSocket {
+    fn build(&mut self, socket: Box<dyn ReadWrite + Send>) {
+        let _ = self.0.send(socket);
+    }
+}
+
+/// Upgrades the request to a WebSocket connection, over which the relayer events matching
+/// `filter` are then forwarded as JSON text messages, until the client disconnects or lags
+/// behind by [`relayer_event::SUBSCRIBER_CAPACITY`] events. The connection is refused if
+/// the maximum number of event streams are open already.
+///
+/// The events are subscribed to right away, so that none published
+/// while the connection is being upgraded is missed.
+pub fn stream_events(
+    request: &rouille::Request,
+    filter: RelayerEventFilter,
+    streams: &EventStreams,
+) -> Result<rouille::Response, RestApiError> {
+    let slot = streams
+        .open()
+        .ok_or(RestApiError::TooManyEventStreams(streams.max))?;
+
+    let (mut response, _) = rouille::websocket::start(request, None::<&str>)
+        .map_err(|e| RestApiError::WebSocket(e.to_string()))?;
+
+    let (socket_tx, socket_rx) = mpsc::channel();
+    response.upgrade = Some(Box::new(EventSocket(socket_tx)));
+
+    let events = relayer_event::subscribe();
+
+    thread::spawn(move || {
+        let _slot = slot;
+
+        let mut socket = match socket_rx.recv() {
+            Ok(socket) => socket,
+            Err(_) => return,
+        };
+
+        let mut last_sent = Instant::now();
+
+        loop {
+            let timeout = KEEP_ALIVE_INTERVAL.saturating_sub(last_sent.elapsed());
+
+            let sent = match events.recv_timeout(timeout) {
+                Ok(event) if !filter.matches(&event) => continue,
+                Ok(event) => match serde_json::to_string(&event) {
+                    Ok(json) => send_frame(&mut socket, TEXT_OPCODE, json.as_bytes()),
+                    Err(e) => {
+                        error!("[rest-server] failed to serialize relayer event: {}", e);
+                        continue;
+                    }
+                },
+                // Sending is the only way to notice that the client went away
+                // while no event is being streamed.
+                Err(channel::RecvTimeoutError::Timeout) => {
+                    send_frame(&mut socket, PING_OPCODE, &[])
+                }
+                Err(channel::RecvTimeoutError::Disconnected) => {
+                    warn!("[rest-server] event stream closed for lagging behind");
+                    break;
+                }
+            };
+
+            if sent.is_err() {
+                debug!("[rest-server] event stream closed by the client");
+                break;
+            }
+
+            last_sent = Instant::now();
+        }
+    });
+
+    Ok(response)
+}
+
+/// Writes an unmasked and unfragmented WebSocket frame, as sent by servers.
+fn send_frame(socket: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= usize::from(u16::MAX) => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+
+    frame.extend_from_slice(payload);
+    socket.write_all(&frame)?;
+    socket.flush()
+}
+
+fn parse_id<T>(id: &str) -> Result<T, RestApiError>
+where
+    T: FromStr<Err = ValidationError>,
+{
+    id.parse()
+        .map_err(|e: ValidationError| RestApiError::InvalidIdentifier(id.to_string(), e.0))
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
@@ -2,12 +2,23 @@ use std::thread;
 
 use crossbeam_channel as channel;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 use tracing::{info, trace};
 
-use ibc_relayer::rest::request::Request;
+use ibc_relayer::object::Object;
+use ibc_relayer::rest::{
+    request::{Request, WorkerSelector},
+    RestApiError,
+};
+use ibc_relayer::worker::WorkerId;
 
 use crate::{
-    handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state},
+    handle::{
+        all_chain_ids, assemble_version_info, chain_config, clear_packets, event_filter,
+        exclude_worker, pause_worker, reload_config, resume_worker, scan_chain, stream_events,
+        supervisor_state, update_client, EventStreams,
+    },
     Config,
 };
 
@@ -52,9 +63,47 @@ impl<R, E> From<Result<R, E>> for JsonResult<R, E> {
     }
 }
 
+/// The SHA-256 digest of a token.
+type TokenDigest = [u8; 32];
+
+fn token_digest(token: &str) -> TokenDigest {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+/// Returns `true` if the request carries the configured token, if any.
+/// The requests are rejected otherwise.
+fn authorized(token: Option<&TokenDigest>, request: &rouille::Request) -> bool {
+    match token {
+        None => true,
+        Some(token) => request
+            .header("Authorization")
+            .and_then(|header| header.strip_prefix("Bearer "))
+            // The digests are compared in constant time, and have the same length whatever
+            // the length of the bearer, so that the token can't be guessed from the timings.
+            .map_or(false, |bearer| {
+                bool::from(token_digest(bearer).ct_eq(token))
+            }),
+    }
+}
+
+fn object_input(request: &rouille::Request) -> Result<WorkerSelector, RestApiError> {
+    rouille::input::json_input::<Object>(request)
+        .map(WorkerSelector::Object)
+        .map_err(|e| RestApiError::InvalidBody(e.to_string()))
+}
+
 #[allow(clippy::manual_strip)]
 fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
+    let token = config.token.as_deref().map(token_digest);
+    let event_streams = EventStreams::new(config.max_event_streams);
+
     let server = rouille::Server::new(config.address(), move |request| {
+        if !authorized(token.as_ref(), request) {
+            trace!("[rest] rejecting unauthorized {} {}", request.method(), request.url());
+            let result: JsonResult<(), _> = JsonResult::Error(RestApiError::Unauthorized);
+            return rouille::Response::json(&result).with_status_code(401);
+        }
+
         router!(request,
             (GET) (/version) => {
                 trace!("[rest/server] GET /version");
@@ -82,6 +131,83 @@ fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (GET) (/events) => {
+                trace!("[rest] GET /events");
+                let filter = event_filter(request.get_param("chain"), request.get_param("channel"));
+                let result = filter.and_then(|filter| stream_events(request, filter, &event_streams));
+                match result {
+                    Ok(response) => response,
+                    Err(e) => {
+                        let status = match e {
+                            RestApiError::TooManyEventStreams(_) => 503,
+                            _ => 400,
+                        };
+                        let result: JsonResult<(), _> = JsonResult::Error(e);
+                        rouille::Response::json(&result).with_status_code(status)
+                    }
+                }
+            },
+
+            (POST) (/reload) => {
+                trace!("[rest] POST /reload");
+                let result = reload_config(&sender);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/chain/{chain_id: String}/clear_packets/{port_id: String}/{channel_id: String}) => {
+                trace!("[rest] POST /chain/{}/clear_packets/{}/{}", chain_id, port_id, channel_id);
+                let result = clear_packets(&sender, &chain_id, &port_id, &channel_id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/chain/{chain_id: String}/update_client/{client_id: String}) => {
+                trace!("[rest] POST /chain/{}/update_client/{}", chain_id, client_id);
+                let result = update_client(&sender, &chain_id, &client_id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/chain/{chain_id: String}/scan) => {
+                trace!("[rest] POST /chain/{}/scan", chain_id);
+                let result = scan_chain(&sender, &chain_id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/worker/{id: u64}/pause) => {
+                trace!("[rest] POST /worker/{}/pause", id);
+                let result = pause_worker(&sender, WorkerSelector::Id(WorkerId::new(id)));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/worker/{id: u64}/resume) => {
+                trace!("[rest] POST /worker/{}/resume", id);
+                let result = resume_worker(&sender, WorkerSelector::Id(WorkerId::new(id)));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/worker/{id: u64}/exclude) => {
+                trace!("[rest] POST /worker/{}/exclude", id);
+                let result = exclude_worker(&sender, WorkerSelector::Id(WorkerId::new(id)));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/object/pause) => {
+                trace!("[rest] POST /object/pause");
+                let result = object_input(request).and_then(|worker| pause_worker(&sender, worker));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/object/resume) => {
+                trace!("[rest] POST /object/resume");
+                let result = object_input(request).and_then(|worker| resume_worker(&sender, worker));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/object/exclude) => {
+                trace!("[rest] POST /object/exclude");
+                let result = object_input(request).and_then(|worker| exclude_worker(&sender, worker));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
             _ => rouille::Response::empty_404(),
         )
     })
@@ -1,12 +1,21 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use ibc::core::ics24_host::identifier::ChainId;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId};
 use ibc_relayer::{
+    chain::tracking::TrackingId,
     config::ChainConfig,
-    rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    event::relayer::{self as relayer_event, RelayerEvent, RelayerEventKind},
+    object::{Object, Packet},
+    rest::request::{Request, VersionInfo, WorkerSelector},
+    supervisor::{
+        dump_state::{SupervisorState, WorkerDesc},
+        reload::ConfigReload,
+    },
+    worker::WorkerId,
 };
 
 use ibc_relayer_rest::{server::spawn, Config};
@@ -25,6 +34,14 @@ enum JsonResult<R, E> {
 }
 
 fn run_test<R, F>(port: u16, path: &str, expected: R, handler: F)
+where
+    R: Serialize,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
+{
+    run_test_with_method("GET", port, path, expected, handler)
+}
+
+fn run_test_with_method<R, F>(method: &str, port: u16, path: &str, expected: R, handler: F)
 where
     R: Serialize,
     F: FnOnce(Request) -> TestResult + Send + 'static,
@@ -41,7 +58,7 @@ where
         Err(e) => panic!("got an error: {}", e),
     });
 
-    let response = ureq::get(&format!("http://127.0.0.1:{}{}", port, path))
+    let response = ureq::request(method, &format!("http://127.0.0.1:{}{}", port, path))
         .call()
         .unwrap()
         .into_string()
@@ -137,3 +154,192 @@ fn state() {
         req => TestResult::WrongRequest(req),
     });
 }
+
+#[test]
+fn reload() {
+    let reload = ConfigReload {
+        added: vec!["mock-1".parse().unwrap()],
+        removed: vec!["mock-0".parse().unwrap()],
+        ..Default::default()
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(reload.clone());
+
+    run_test_with_method("POST", 19105, "/reload", result, |req| match req {
+        Request::ReloadConfig { reply_to } => {
+            reply_to.send(Ok(reload)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    });
+}
+
+#[test]
+fn pause_worker() {
+    let object = Object::Packet(Packet {
+        dst_chain_id: "mock-1".parse().unwrap(),
+        src_chain_id: "mock-0".parse().unwrap(),
+        src_channel_id: "channel-0".parse().unwrap(),
+        src_port_id: "transfer".parse().unwrap(),
+    });
+    let desc = Some(WorkerDesc::new(WorkerId::new(3), object, None));
+    let result: JsonResult<_, ()> = JsonResult::Success(desc.clone());
+
+    run_test_with_method("POST", 19106, "/worker/3/pause", result, |req| match req {
+        Request::PauseWorker {
+            worker: WorkerSelector::Id(id),
+            reply_to,
+        } if id == WorkerId::new(3) => {
+            reply_to.send(Ok(desc)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    });
+}
+
+#[test]
+fn requests_require_the_token() {
+    let config = Config::new("127.0.0.1".to_string(), 19107).with_token(Some("secret".into()));
+
+    let (handle, rx) = spawn(config);
+
+    let response = ureq::post("http://127.0.0.1:19107/reload").call();
+    assert!(matches!(response, Err(ureq::Error::Status(401, _))));
+
+    let response = ureq::post("http://127.0.0.1:19107/reload")
+        .set("Authorization", "Bearer wrong")
+        .call();
+    assert!(matches!(response, Err(ureq::Error::Status(401, _))));
+
+    // The requests which do not perform changes, including the event stream, are
+    // restricted too.
+    for path in ["/chains", "/version", "/state", "/events"] {
+        let response = ureq::get(&format!("http://127.0.0.1:19107{}", path)).call();
+        assert!(matches!(response, Err(ureq::Error::Status(401, _))));
+
+        let response = ureq::get(&format!("http://127.0.0.1:19107{}", path))
+            .set("Authorization", "Bearer secre")
+            .call();
+        assert!(matches!(response, Err(ureq::Error::Status(401, _))));
+    }
+
+    std::thread::spawn(move || {
+        if let Ok(Request::GetChains { reply_to }) = rx.recv() {
+            reply_to.send(Ok(vec![])).unwrap();
+        }
+    });
+
+    let response = ureq::get("http://127.0.0.1:19107/chains")
+        .set("Authorization", "Bearer secret")
+        .call();
+    assert!(response.is_ok());
+
+    handle.stop();
+    handle.join().unwrap();
+}
+
+#[test]
+fn events_stream_over_websocket() {
+    let config = Config::new("127.0.0.1".to_string(), 19108);
+
+    let (handle, _rx) = spawn(config);
+
+    // Plain requests and invalid filters are rejected.
+    let response = ureq::get("http://127.0.0.1:19108/events").call();
+    assert!(matches!(response, Err(ureq::Error::Status(400, _))));
+
+    let response = ureq::get("http://127.0.0.1:19108/events?channel=chan").call();
+    assert!(matches!(response, Err(ureq::Error::Status(400, _))));
+
+    let mut stream = TcpStream::connect("127.0.0.1:19108").unwrap();
+    write!(
+        stream,
+        "GET /events?chain=mock-0&channel=channel-1 HTTP/1.1\r\n\
+         Host: 127.0.0.1:19108\r\n\
+         Connection: Upgrade\r\n\
+         Upgrade: websocket\r\n\
+         Sec-WebSocket-Version: 13\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+    )
+    .unwrap();
+
+    let mut reader = BufReader::new(stream);
+    let mut status = String::new();
+    reader.read_line(&mut status).unwrap();
+    assert!(status.starts_with("HTTP/1.1 101"), "{}", status);
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        if header == "\r\n" {
+            break;
+        }
+    }
+
+    let event = |channel| {
+        RelayerEvent::new(
+            TrackingId::new_static("mock"),
+            ChainId::from_string("mock-0"),
+            RelayerEventKind::TxFailed {
+                error: "mock".to_string(),
+            },
+        )
+        .with_channel("transfer".parse().unwrap(), ChannelId::new(channel))
+    };
+
+    relayer_event::publish(event(0));
+    relayer_event::publish(event(1));
+
+    // Only the event on the selected channel is sent, in a single unmasked text frame.
+    let mut frame_header = [0; 2];
+    reader.read_exact(&mut frame_header).unwrap();
+    assert_eq!(frame_header[0], 0x81);
+
+    let len = match frame_header[1] {
+        126 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len).unwrap();
+            u16::from_be_bytes(len) as usize
+        }
+        len => len as usize,
+    };
+
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload).unwrap();
+
+    let received: RelayerEvent = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(received.channel_id, Some(ChannelId::new(1)));
+
+    handle.stop();
+    handle.join().unwrap();
+}
+
+#[test]
+fn event_streams_are_bounded() {
+    let config = Config::new("127.0.0.1".to_string(), 19109).with_max_event_streams(1);
+
+    let (handle, _rx) = spawn(config);
+
+    let mut stream = TcpStream::connect("127.0.0.1:19109").unwrap();
+    write!(
+        stream,
+        "GET /events HTTP/1.1\r\n\
+         Host: 127.0.0.1:19109\r\n\
+         Connection: Upgrade\r\n\
+         Upgrade: websocket\r\n\
+         Sec-WebSocket-Version: 13\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+    )
+    .unwrap();
+
+    let mut reader = BufReader::new(stream);
+    let mut status = String::new();
+    reader.read_line(&mut status).unwrap();
+    assert!(status.starts_with("HTTP/1.1 101"), "{}", status);
+
+    // The connections beyond the maximum number of streams are refused.
+    let response = ureq::get("http://127.0.0.1:19109/events").call();
+    assert!(matches!(response, Err(ureq::Error::Status(503, _))));
+
+    handle.stop();
+    handle.join().unwrap();
+}
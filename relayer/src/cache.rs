@@ -134,6 +134,20 @@ impl Cache {
         }
     }
 
+    /// Stores a copy of the [`ChannelEnd`] in the cache, if the channel is open.
+    pub fn insert_channel(&self, id: PortChannelId, chan: ChannelEnd) {
+        if chan.state().is_open() {
+            self.channels.insert(id, chan);
+        }
+    }
+
+    /// Stores a copy of the [`ConnectionEnd`] in the cache, if the connection is open.
+    pub fn insert_connection(&self, id: ConnectionId, conn: ConnectionEnd) {
+        if conn.state().is_open() {
+            self.connections.insert(id, conn);
+        }
+    }
+
     /// Return a cached [`AnyClientState`] via its [`ClientId`] if it exists in the cache.
     /// Otherwise, attempts to fetch it via the supplied fetcher function `F`. If `F`
     /// returns successfully with the client state, a copy of it is stored in the cache
@@ -1,3 +1,4 @@
+use alloc::collections::BTreeSet;
 use alloc::sync::Arc;
 use bytes::{Buf, Bytes};
 use core::{
@@ -7,6 +8,7 @@ use core::{
     time::Duration,
 };
 use num_bigint::BigInt;
+use std::collections::HashMap;
 use std::thread;
 
 use bitcoin::hashes::hex::ToHex;
@@ -24,6 +26,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 use tonic::{codegen::http::Uri, metadata::AsciiMetadataValue};
 use tracing::{error, span, warn, Level};
 
+use ibc::applications::ics29_fee::fee::PacketFee;
 use ibc::clients::ics07_tendermint::consensus_state::ConsensusState as TMConsensusState;
 use ibc::clients::ics07_tendermint::header::Header as TmHeader;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
@@ -32,12 +35,13 @@ use ibc::core::ics02_client::client_type::ClientType;
 use ibc::core::ics02_client::error::Error as ClientError;
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd};
 use ibc::core::ics04_channel::channel::{
-    ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest,
+    ChannelEnd, IdentifiedChannelEnd, Order as ChannelOrder, QueryPacketEventDataRequest,
 };
 use ibc::core::ics04_channel::events as ChannelEvents;
-use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::msgs::{acknowledgement, recv_packet, timeout, timeout_on_close};
+use ibc::core::ics04_channel::packet::{Packet, PacketId, Sequence};
 use ibc::core::ics23_commitment::commitment::CommitmentPrefix;
-use ibc::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::core::ics24_host::path::{
     AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
     ConnectionsPath, ReceiptsPath, SeqRecvsPath,
@@ -45,7 +49,7 @@ use ibc::core::ics24_host::path::{
 use ibc::core::ics24_host::{ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
 use ibc::events::IbcEvent;
 use ibc::query::QueryBlockRequest;
-use ibc::query::QueryTxRequest;
+use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::signer::Signer;
 use ibc::Height as ICSHeight;
 use ibc::{
@@ -53,23 +57,34 @@ use ibc::{
     core::ics23_commitment::merkle::MerkleProof,
 };
 use ibc_proto::cosmos::staking::v1beta1::Params as StakingParams;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement as RawMsgAcknowledgement, MsgRecvPacket as RawMsgRecvPacket,
+    MsgTimeout as RawMsgTimeout, MsgTimeoutOnClose as RawMsgTimeoutOnClose,
+};
+use prost::Message;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::{
-    send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
+    send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit, SigningAccount,
 };
+use crate::chain::cosmos::dry_run::{simulate_batched_messages, DryRunTxs};
 use crate::chain::cosmos::encode::encode_to_bech32;
+use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
 use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::balance::query_balance;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
+use crate::chain::cosmos::query::fee::query_incentivized_packet;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::query_txs;
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
-use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::account::KeyAccount;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{default_gas_from_config, max_gas_from_config};
+use crate::chain::cosmos::types::tx::TxSyncResult;
 use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
 use crate::chain::tracking::TrackedMsgs;
 use crate::config::ChainConfig;
@@ -95,9 +110,12 @@ use super::requests::{
 pub mod batch;
 pub mod client;
 pub mod compatibility;
+pub mod dry_run;
 pub mod encode;
 pub mod estimate;
+pub mod fee_spend;
 pub mod gas;
+pub mod gas_price;
 pub mod query;
 pub mod retry;
 pub mod simulate;
@@ -117,8 +135,17 @@ pub struct CosmosSdkChain {
     grpc_addr: Uri,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing,
-    /// A cached copy of the account information
-    account: Option<Account>,
+    /// The keys signing transactions, with a cached copy of their account information
+    accounts: Vec<KeyAccount>,
+    /// The index in `accounts` of the account signing the next batch of messages
+    next_account: usize,
+    /// Whether the transactions are only simulated, never broadcast
+    dry_run: bool,
+    /// The events synthesized for the transactions simulated in dry-run mode
+    dry_run_txs: DryRunTxs,
+    /// The ordering of the channels of this chain which packets were relayed over, which never
+    /// changes once a channel exists
+    channel_orderings: HashMap<(PortId, ChannelId), ChannelOrder>,
 }
 
 impl CosmosSdkChain {
@@ -175,6 +202,27 @@ impl CosmosSdkChain {
             ));
         }
 
+        // A bump factor below 1 would lower the gas price after the broadcasts rejected for
+        // insufficient fees, and caps the wrong way around would leave no valid price.
+        let oracle = &self.config.gas_price_oracle;
+        if oracle.bump_factor.is_nan() || oracle.bump_factor < 1.0 {
+            return Err(Error::config_validation_gas_price_oracle(
+                self.id().clone(),
+                format!("bump factor ({}) must be at least 1", oracle.bump_factor),
+            ));
+        }
+        if let (Some(min_price), Some(max_price)) = (oracle.min_price, oracle.max_price) {
+            if min_price > max_price {
+                return Err(Error::config_validation_gas_price_oracle(
+                    self.id().clone(),
+                    format!(
+                        "min price ({}) must be smaller than the max price ({})",
+                        min_price, max_price
+                    ),
+                ));
+            }
+        }
+
         // Get the latest height and convert to tendermint Height
         let latest_height = TmHeight::try_from(self.query_chain_latest_height()?.revision_height)
             .map_err(Error::invalid_height)?;
@@ -404,9 +452,140 @@ impl CosmosSdkChain {
         Ok(status.height)
     }
 
+    /// Returns the keys signing transactions along with their accounts, fetching those which
+    /// aren't cached yet, starting with the next one in the round-robin. Each account keeps its
+    /// own cached sequence, which is recovered separately upon mismatches.
+    async fn signing_accounts(&mut self) -> Result<Vec<SigningAccount<'_>>, Error> {
+        let start = self.next_account % self.accounts.len();
+        self.next_account = (start + 1) % self.accounts.len();
+
+        let (before, after) = self.accounts.split_at_mut(start);
+
+        let mut signers = Vec::with_capacity(before.len() + after.len());
+        for key_account in after.iter_mut().chain(before.iter_mut()) {
+            let key_entry = self
+                .keybase
+                .get_key(&key_account.key_name)
+                .map_err(|e| Error::key_not_found(key_account.key_name.clone(), e))?;
+
+            let account = get_or_fetch_account(
+                &self.grpc_addr,
+                &key_entry.account,
+                &mut key_account.account,
+            )
+            .await?;
+
+            signers.push(SigningAccount { key_entry, account });
+        }
+
+        Ok(signers)
+    }
+
+    /// Simulates the transactions carrying `proto_msgs` in dry-run mode, instead of broadcasting
+    /// them, and remembers the events synthesized for them, to be retrieved by tx hash.
+    async fn simulate_messages(
+        &mut self,
+        proto_msgs: Vec<Any>,
+    ) -> Result<Vec<TxSyncResult>, Error> {
+        let (tx_config, max_msg_num, max_tx_size, memo_prefix) = (
+            self.tx_config.clone(),
+            self.config.max_msg_num,
+            self.config.max_tx_size,
+            self.config.memo_prefix.clone(),
+        );
+
+        // The transactions would be included in the next block at the earliest.
+        let latest_height = self
+            .rpc_client
+            .abci_info()
+            .await
+            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?
+            .last_block_height;
+        let height = ICSHeight::new(self.id().version(), u64::from(latest_height) + 1);
+
+        let signers = self.signing_accounts().await?;
+
+        let results = simulate_batched_messages(
+            &tx_config,
+            max_msg_num,
+            max_tx_size,
+            &signers[0],
+            &memo_prefix,
+            proto_msgs,
+            height,
+        )
+        .await?;
+
+        self.dry_run_txs.record(&results);
+
+        Ok(results)
+    }
+
+    /// Estimates the fee of a transaction signed with the account of `key_name`.
+    async fn do_estimate_tx_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        let key_entry = self.key()?;
+
+        let account = get_or_fetch_account(
+            &self.grpc_addr,
+            &key_entry.account,
+            &mut self.accounts[0].account,
+        )
+        .await?;
+
+        estimate_tx_fees(
+            &self.tx_config,
+            &key_entry,
+            account,
+            &self.config.memo_prefix,
+            tracked_msgs.msgs,
+        )
+        .await
+    }
+
+    /// Whether `proto_msgs` deliver packets, their acknowledgments or their timeouts on ordered
+    /// channels of this chain. Those must be included in the order of their sequences, which
+    /// transactions broadcast in parallel by different accounts are not.
+    fn carries_ordered_packets(&mut self, proto_msgs: &[Any]) -> Result<bool, Error> {
+        for (port_id, channel_id) in packet_channel_ends(proto_msgs) {
+            if self.channel_ordering(port_id, channel_id)? == ChannelOrder::Ordered {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the ordering of the channel end `port_id/channel_id` of this chain, which is only
+    /// queried the first time.
+    fn channel_ordering(
+        &mut self,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<ChannelOrder, Error> {
+        let key = (port_id, channel_id);
+        if let Some(ordering) = self.channel_orderings.get(&key) {
+            return Ok(*ordering);
+        }
+
+        let (channel_end, _) = self.query_channel(
+            QueryChannelRequest {
+                port_id: key.0.clone(),
+                channel_id: key.1,
+                height: HeightQuery::Latest,
+            },
+            IncludeProof::No,
+        )?;
+
+        let ordering = *channel_end.ordering();
+        self.channel_orderings.insert(key, ordering);
+
+        Ok(ordering)
+    }
+
     async fn do_send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        single_account: bool,
     ) -> Result<Vec<IbcEvent>, Error> {
         crate::time!("send_messages_and_wait_commit");
 
@@ -415,18 +594,30 @@ impl CosmosSdkChain {
 
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_entry = self.key()?;
+        if self.dry_run {
+            let results = self.simulate_messages(proto_msgs).await?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
+            return Ok(results.into_iter().flat_map(|res| res.events).collect());
+        }
 
-        send_batched_messages_and_wait_commit(
-            &self.tx_config,
+        let (tx_config, max_msg_num, max_tx_size, memo_prefix) = (
+            self.tx_config.clone(),
             self.config.max_msg_num,
             self.config.max_tx_size,
-            &key_entry,
-            account,
-            &self.config.memo_prefix,
+            self.config.memo_prefix.clone(),
+        );
+
+        let mut signers = self.signing_accounts().await?;
+        if single_account {
+            signers.truncate(1);
+        }
+
+        send_batched_messages_and_wait_commit(
+            &tx_config,
+            max_msg_num,
+            max_tx_size,
+            &mut signers,
+            &memo_prefix,
             proto_msgs,
         )
         .await
@@ -443,18 +634,27 @@ impl CosmosSdkChain {
 
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_entry = self.key()?;
+        if self.dry_run {
+            let results = self.simulate_messages(proto_msgs).await?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
+            return Ok(results.into_iter().map(|res| res.response).collect());
+        }
 
-        send_batched_messages_and_wait_check_tx(
-            &self.tx_config,
+        let (tx_config, max_msg_num, max_tx_size, memo_prefix) = (
+            self.tx_config.clone(),
             self.config.max_msg_num,
             self.config.max_tx_size,
-            &key_entry,
-            account,
-            &self.config.memo_prefix,
+            self.config.memo_prefix.clone(),
+        );
+
+        let mut signers = self.signing_accounts().await?;
+
+        send_batched_messages_and_wait_check_tx(
+            &tx_config,
+            max_msg_num,
+            max_tx_size,
+            &mut signers[0],
+            &memo_prefix,
             proto_msgs,
         )
         .await
@@ -473,14 +673,19 @@ impl ChainEndpoint for CosmosSdkChain {
             .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
 
         // Initialize key store and load key
-        let keybase = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)
-            .map_err(Error::key_base)?;
+        let keybase = KeyRing::from_config(&config).map_err(Error::key_base)?;
 
         let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
             .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
 
         let tx_config = TxConfig::try_from(&config)?;
 
+        let accounts = config
+            .signing_key_names()
+            .into_iter()
+            .map(|key_name| KeyAccount::new(key_name.to_string()))
+            .collect();
+
         // Retrieve the version specification of this chain
 
         let chain = Self {
@@ -489,8 +694,12 @@ impl ChainEndpoint for CosmosSdkChain {
             grpc_addr,
             rt,
             keybase,
-            account: None,
+            accounts,
+            next_account: 0,
             tx_config,
+            dry_run: false,
+            dry_run_txs: DryRunTxs::default(),
+            channel_orderings: HashMap::new(),
         };
 
         Ok(chain)
@@ -533,9 +742,24 @@ impl ChainEndpoint for CosmosSdkChain {
     }
 
     fn shutdown(self) -> Result<(), Error> {
+        if self.dry_run {
+            crate::telemetry!(dry_run, &self.config.id, false);
+        }
+
         Ok(())
     }
 
+    fn enable_dry_run(&mut self) {
+        warn!(
+            "[{}] running in dry-run mode: transactions are simulated, never broadcast",
+            self.config.id
+        );
+
+        crate::telemetry!(dry_run, &self.config.id, true);
+
+        self.dry_run = true;
+    }
+
     fn id(&self) -> &ChainId {
         &self.config().id
     }
@@ -591,9 +815,12 @@ impl ChainEndpoint for CosmosSdkChain {
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEvent>, Error> {
+        let single_account =
+            self.accounts.len() > 1 && self.carries_ordered_packets(&tracked_msgs.msgs)?;
+
         let runtime = self.rt.clone();
 
-        runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs))
+        runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs, single_account))
     }
 
     fn send_messages_and_wait_check_tx(
@@ -652,6 +879,25 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(version_specs.ibc_go_version)
     }
 
+    fn fee_budget_exceeded(&self) -> bool {
+        self.tx_config.fee_spend.budget_exceeded()
+    }
+
+    fn estimate_tx_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        crate::time!("estimate_tx_fee");
+
+        let runtime = self.rt.clone();
+
+        runtime.block_on(self.do_estimate_tx_fee(tracked_msgs))
+    }
+
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error> {
+        crate::time!("query_incentivized_packet");
+        crate::telemetry!(query, self.id(), "query_incentivized_packet");
+
+        self.block_on(query_incentivized_packet(&self.grpc_addr, packet_id))
+    }
+
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
         // If a key_name is given, extract the account hash.
         // Else retrieve the account from the configuration file.
@@ -1444,12 +1690,33 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::time!("query_txs");
         crate::telemetry!(query, self.id(), "query_txs");
 
-        self.block_on(query_txs(
+        // The transactions simulated in dry-run mode are not found on chain.
+        if let QueryTxRequest::Transaction(QueryTxHash(hash)) = &request {
+            if let Some(events) = self.dry_run_txs.events(hash) {
+                return Ok(events);
+            }
+        }
+
+        let hash = match &request {
+            QueryTxRequest::Transaction(QueryTxHash(hash)) => Some(*hash),
+            _ => None,
+        };
+
+        let events = self.block_on(query_txs(
             self.id(),
             &self.rpc_client,
             &self.config.rpc_addr,
             request,
-        ))
+        ))?;
+
+        // The transactions sent without waiting for their commit are confirmed here.
+        if let Some(hash) = hash {
+            if !events.is_empty() {
+                self.tx_config.fee_spend.confirm(&hash);
+            }
+        }
+
+        Ok(events)
     }
 
     fn query_blocks(
@@ -1629,6 +1896,38 @@ fn filter_matching_event(
     }
 }
 
+/// Returns the channel ends of the receiving chain which the packets, acknowledgments and
+/// timeouts in `proto_msgs` are delivered over.
+fn packet_channel_ends(proto_msgs: &[Any]) -> BTreeSet<(PortId, ChannelId)> {
+    proto_msgs
+        .iter()
+        .filter_map(|msg| {
+            let bytes = msg.value.as_slice();
+            let (port_id, channel_id) = match msg.type_url.as_str() {
+                recv_packet::TYPE_URL => RawMsgRecvPacket::decode(bytes)
+                    .ok()?
+                    .packet
+                    .map(|packet| (packet.destination_port, packet.destination_channel)),
+                acknowledgement::TYPE_URL => RawMsgAcknowledgement::decode(bytes)
+                    .ok()?
+                    .packet
+                    .map(|packet| (packet.source_port, packet.source_channel)),
+                timeout::TYPE_URL => RawMsgTimeout::decode(bytes)
+                    .ok()?
+                    .packet
+                    .map(|packet| (packet.source_port, packet.source_channel)),
+                timeout_on_close::TYPE_URL => RawMsgTimeoutOnClose::decode(bytes)
+                    .ok()?
+                    .packet
+                    .map(|packet| (packet.source_port, packet.source_channel)),
+                _ => None,
+            }?;
+
+            Some((port_id.parse().ok()?, channel_id.parse().ok()?))
+        })
+        .collect()
+}
+
 /// Returns the suffix counter for a CosmosSDK client id.
 /// Returns `None` if the client identifier is malformed
 /// and the suffix could not be parsed.
@@ -1744,6 +2043,67 @@ mod tests {
         assert_eq!(&fee.amount, "90000000000000000000000000");
     }
 
+    #[test]
+    fn packet_channel_ends_of_packet_msgs() {
+        use ibc::core::ics04_channel::msgs::{recv_packet, timeout, timeout_on_close};
+        use ibc_proto::google::protobuf::Any;
+        use ibc_proto::ibc::core::channel::v1::{
+            MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose, Packet,
+        };
+        use prost::Message;
+
+        let packet = |source_channel: &str, destination_channel: &str| Packet {
+            source_port: "transfer".to_string(),
+            source_channel: source_channel.to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: destination_channel.to_string(),
+            ..Packet::default()
+        };
+        let msgs = vec![
+            Any {
+                type_url: recv_packet::TYPE_URL.to_string(),
+                value: MsgRecvPacket {
+                    packet: Some(packet("channel-7", "channel-0")),
+                    ..MsgRecvPacket::default()
+                }
+                .encode_to_vec(),
+            },
+            Any {
+                type_url: timeout::TYPE_URL.to_string(),
+                value: MsgTimeout {
+                    packet: Some(packet("channel-1", "channel-8")),
+                    ..MsgTimeout::default()
+                }
+                .encode_to_vec(),
+            },
+            Any {
+                type_url: timeout_on_close::TYPE_URL.to_string(),
+                value: MsgTimeoutOnClose {
+                    packet: Some(packet("channel-2", "channel-9")),
+                    ..MsgTimeoutOnClose::default()
+                }
+                .encode_to_vec(),
+            },
+            Any {
+                type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+                value: vec![],
+            },
+        ];
+
+        let channel_ends: Vec<String> = super::packet_channel_ends(&msgs)
+            .into_iter()
+            .map(|(port_id, channel_id)| format!("{}/{}", port_id, channel_id))
+            .collect();
+        assert_eq!(
+            channel_ends,
+            [
+                "transfer/channel-0",
+                "transfer/channel-1",
+                "transfer/channel-2"
+            ]
+        );
+    }
+
     #[test]
     fn sort_clients_id_suffix() {
         let mut clients: Vec<IdentifiedAnyClientState> = vec![
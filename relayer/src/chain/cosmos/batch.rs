@@ -1,3 +1,4 @@
+use futures::future::join_all;
 use ibc::events::IbcEvent;
 use ibc_proto::google::protobuf::Any;
 use prost::Message;
@@ -12,12 +13,30 @@ use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
 use crate::error::Error;
 use crate::keyring::KeyEntry;
 
+/// A key signing transactions, along with the cached information of its account.
+pub struct SigningAccount<'a> {
+    pub key_entry: KeyEntry,
+    pub account: &'a mut Account,
+}
+
+/// Sends `messages` in batches, spread over the accounts of `signers` in a round-robin fashion,
+/// and waits for all of them to be committed. The batches assigned to different accounts are
+/// broadcast in parallel, while each account broadcasts its own batches in order.
+///
+/// The transactions of different accounts may be included in any order, so when the messages
+/// are split over several accounts, the first batch, which carries the client updates the other
+/// messages are proven against, is committed before the others get broadcast.
+///
+/// If some of the accounts fail to broadcast their batches while others succeed, the events
+/// of the committed transactions are returned, along with a `ChainError` event for each of the
+/// failures. The messages of ordered channels must be sent with a single account.
+///
+/// Panics if `signers` is empty.
 pub async fn send_batched_messages_and_wait_commit(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_entry: &KeyEntry,
-    account: &mut Account,
+    signers: &mut [SigningAccount<'_>],
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEvent>, Error> {
@@ -25,29 +44,69 @@ pub async fn send_batched_messages_and_wait_commit(
         return Ok(Vec::new());
     }
 
-    let mut tx_sync_results = send_messages_as_batches(
-        config,
-        max_msg_num,
-        max_tx_size,
-        key_entry,
-        account,
-        tx_memo,
-        messages,
-    )
-    .await?;
+    let mut batches = batch_messages(max_msg_num, max_tx_size, messages)?;
+
+    let mut tx_sync_results = Vec::new();
+
+    if signers.len() > 1 && batches.len() > 1 {
+        let first_batch = batches.remove(0);
+
+        let (mut first_results, errors) =
+            send_messages_as_batches(config, &mut signers[..1], tx_memo, vec![first_batch]).await;
+
+        if let Some(e) = errors.into_iter().next() {
+            return Err(e);
+        }
+
+        wait_for_block_commits(
+            &config.chain_id,
+            &config.rpc_client,
+            &config.rpc_address,
+            &config.rpc_timeout,
+            &config.fee_spend,
+            &mut first_results,
+        )
+        .await?;
+
+        tx_sync_results.extend(first_results);
+    }
+
+    let (mut other_results, errors) =
+        send_messages_as_batches(config, signers, tx_memo, batches).await;
+
+    // Only fail if none of the accounts got its batches through, as the transactions
+    // broadcast by the other accounts get committed nonetheless.
+    if other_results.is_empty() && tx_sync_results.is_empty() {
+        if let Some(e) = errors.into_iter().next() {
+            return Err(e);
+        }
+
+        return Ok(Vec::new());
+    }
 
     wait_for_block_commits(
         &config.chain_id,
         &config.rpc_client,
         &config.rpc_address,
         &config.rpc_timeout,
-        &mut tx_sync_results,
+        &config.fee_spend,
+        &mut other_results,
     )
     .await?;
 
+    tx_sync_results.extend(other_results);
+
+    // The batches which failed to be broadcast are reported the same way as the
+    // transactions which failed to be delivered.
     let events = tx_sync_results
         .into_iter()
         .flat_map(|el| el.events)
+        .chain(errors.into_iter().map(|e| {
+            IbcEvent::ChainError(format!(
+                "failed to broadcast batch on chain {}: {}",
+                config.chain_id, e
+            ))
+        }))
         .collect();
 
     Ok(events)
@@ -57,8 +116,7 @@ pub async fn send_batched_messages_and_wait_check_tx(
     config: &TxConfig,
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
-    key_entry: &KeyEntry,
-    account: &mut Account,
+    signer: &mut SigningAccount<'_>,
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<Response>, Error> {
@@ -71,9 +129,15 @@ pub async fn send_batched_messages_and_wait_check_tx(
     let mut responses = Vec::new();
 
     for batch in batches {
-        let response =
-            send_tx_with_account_sequence_retry(config, key_entry, account, tx_memo, batch, 0)
-                .await?;
+        let response = send_tx_with_account_sequence_retry(
+            config,
+            &signer.key_entry,
+            signer.account,
+            tx_memo,
+            batch,
+            0,
+        )
+        .await?;
 
         responses.push(response);
     }
@@ -81,42 +145,96 @@ pub async fn send_batched_messages_and_wait_check_tx(
     Ok(responses)
 }
 
+/// Broadcasts `batches` over the accounts of `signers`, returning the results of the batches
+/// which were broadcast, in the order of the batches, along with the errors of the accounts
+/// which failed to broadcast theirs.
 async fn send_messages_as_batches(
     config: &TxConfig,
-    max_msg_num: MaxMsgNum,
-    max_tx_size: MaxTxSize,
-    key_entry: &KeyEntry,
-    account: &mut Account,
+    signers: &mut [SigningAccount<'_>],
     tx_memo: &Memo,
-    messages: Vec<Any>,
-) -> Result<Vec<TxSyncResult>, Error> {
-    if messages.is_empty() {
-        return Ok(Vec::new());
+    batches: Vec<Vec<Any>>,
+) -> (Vec<TxSyncResult>, Vec<Error>) {
+    if batches.is_empty() {
+        return (Vec::new(), Vec::new());
     }
 
-    let batches = batch_messages(max_msg_num, max_tx_size, messages)?;
+    let assigned = assign_batches(batches, signers.len());
 
+    let sends = signers
+        .iter_mut()
+        .zip(assigned)
+        .map(|(signer, batches)| send_account_batches(config, signer, tx_memo, batches));
+
+    // All the accounts are left to complete their broadcasts, so that the sequences
+    // of those which succeeded stay in sync with the chain.
     let mut tx_sync_results = Vec::new();
+    let mut errors = Vec::new();
+    for (results, error) in join_all(sends).await {
+        tx_sync_results.extend(results);
+        errors.extend(error);
+    }
 
-    for batch in batches {
+    tx_sync_results.sort_by_key(|(index, _)| *index);
+
+    let tx_sync_results = tx_sync_results
+        .into_iter()
+        .map(|(_, tx_sync_result)| tx_sync_result)
+        .collect();
+
+    (tx_sync_results, errors)
+}
+
+/// Broadcasts the `batches` assigned to the account of `signer` in order, along with their
+/// index among all the batches. Stops at the first batch which fails to be broadcast,
+/// returning the results of the batches broadcast before it along with the error.
+async fn send_account_batches(
+    config: &TxConfig,
+    signer: &mut SigningAccount<'_>,
+    tx_memo: &Memo,
+    batches: Vec<(usize, Vec<Any>)>,
+) -> (Vec<(usize, TxSyncResult)>, Option<Error>) {
+    let mut tx_sync_results = Vec::new();
+
+    for (index, batch) in batches {
         let events_per_tx = vec![IbcEvent::default(); batch.len()];
 
-        let response =
-            send_tx_with_account_sequence_retry(config, key_entry, account, tx_memo, batch, 0)
-                .await?;
+        let response = match send_tx_with_account_sequence_retry(
+            config,
+            &signer.key_entry,
+            signer.account,
+            tx_memo,
+            batch,
+            0,
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(e) => return (tx_sync_results, Some(e)),
+        };
 
         let tx_sync_result = TxSyncResult {
             response,
             events: events_per_tx,
         };
 
-        tx_sync_results.push(tx_sync_result);
+        tx_sync_results.push((index, tx_sync_result));
     }
 
-    Ok(tx_sync_results)
+    (tx_sync_results, None)
 }
 
-fn batch_messages(
+/// Assigns the batch at index `i` to the account at index `i % account_count`.
+fn assign_batches(batches: Vec<Vec<Any>>, account_count: usize) -> Vec<Vec<(usize, Vec<Any>)>> {
+    let mut assigned = vec![Vec::new(); account_count];
+
+    for (index, batch) in batches.into_iter().enumerate() {
+        assigned[index % account_count].push((index, batch));
+    }
+
+    assigned
+}
+
+pub fn batch_messages(
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
     messages: Vec<Any>,
@@ -149,3 +267,34 @@ fn batch_messages(
 
     Ok(batches)
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+
+    use super::assign_batches;
+
+    #[test]
+    fn batches_are_assigned_round_robin() {
+        let batches = (0..5)
+            .map(|i| {
+                vec![Any {
+                    type_url: i.to_string(),
+                    value: vec![],
+                }]
+            })
+            .collect();
+
+        let assigned = assign_batches(batches, 2);
+
+        let indices: Vec<Vec<usize>> = assigned
+            .iter()
+            .map(|batches| batches.iter().map(|(index, _)| *index).collect())
+            .collect();
+        assert_eq!(indices, vec![vec![0, 2, 4], vec![1, 3]]);
+        assert!(assigned
+            .iter()
+            .flatten()
+            .all(|(index, batch)| batch[0].type_url == index.to_string()));
+    }
+}
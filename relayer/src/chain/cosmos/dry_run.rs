@@ -0,0 +1,369 @@
+//! The dry-run mode of the relayer, in which the transactions are simulated instead of
+//! broadcast, and the events they would emit are synthesized from their messages so that
+//! the workers keep operating.
+
+use alloc::collections::VecDeque;
+
+use ibc::core::ics02_client::events::{Attributes as ClientAttributes, UpdateClient};
+use ibc::core::ics02_client::header::Header;
+use ibc::core::ics02_client::msgs::update_client::{self, MsgUpdateAnyClient};
+use ibc::core::ics04_channel::events::{
+    AcknowledgePacket, ReceivePacket, TimeoutOnClosePacket, TimeoutPacket,
+};
+use ibc::core::ics04_channel::msgs::acknowledgement::{self, MsgAcknowledgement};
+use ibc::core::ics04_channel::msgs::recv_packet::{self, MsgRecvPacket};
+use ibc::core::ics04_channel::msgs::timeout::{self, MsgTimeout};
+use ibc::core::ics04_channel::msgs::timeout_on_close::{self, MsgTimeoutOnClose};
+use ibc::events::IbcEvent;
+use ibc::Height;
+use ibc_proto::google::protobuf::Any;
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use tendermint::abci::transaction::Hash;
+use tendermint::abci::Code;
+use tendermint_proto::Protobuf;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tracing::{info, warn};
+
+use crate::chain::cosmos::batch::{batch_messages, SigningAccount};
+use crate::chain::cosmos::encode::sign_and_encode_tx;
+use crate::chain::cosmos::estimate::estimate_tx_fees;
+use crate::chain::cosmos::gas::PrettyFee;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::types::tx::TxSyncResult;
+use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::error::Error;
+use crate::telemetry;
+
+/// How many of the simulated transactions are remembered, for the workers to query their
+/// events by hash.
+const DRY_RUN_TX_CACHE_SIZE: usize = 1000;
+
+/// The response code of the simulated transactions whose simulation failed, matching the
+/// generic `ErrInternal` of the Cosmos SDK.
+const SIMULATION_FAILED_CODE: u32 = 1;
+
+/// Simulates `messages` in the batches they would be broadcast in, signed with the account of
+/// `signer`, and logs the transactions which would be broadcast, along with their estimated
+/// fees. The account sequence is left untouched, since nothing gets broadcast.
+///
+/// The events of the transactions whose simulation succeeds are synthesized from their
+/// messages, as if they were committed at `height`, while the failed ones yield a
+/// [`IbcEvent::ChainError`], as when the transaction fails on chain.
+///
+/// Since every simulation starts from the state of the chain, the client updates of the
+/// earlier batches are simulated along with each batch, for its messages to be proven against
+/// the updated clients. The fee estimated for a batch thus includes those client updates.
+pub async fn simulate_batched_messages(
+    config: &TxConfig,
+    max_msg_num: MaxMsgNum,
+    max_tx_size: MaxTxSize,
+    signer: &SigningAccount<'_>,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+    height: Height,
+) -> Result<Vec<TxSyncResult>, Error> {
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let batches = batch_messages(max_msg_num, max_tx_size, messages)?;
+    let simulated_batches = with_earlier_client_updates(&batches);
+
+    let mut results = Vec::with_capacity(batches.len());
+
+    for (batch, simulated_batch) in batches.into_iter().zip(simulated_batches) {
+        let estimated_fee = estimate_tx_fees(
+            config,
+            &signer.key_entry,
+            signer.account,
+            tx_memo,
+            simulated_batch,
+        )
+        .await;
+
+        let fee = estimated_fee.as_ref().unwrap_or(&config.gas_config.max_fee);
+
+        let tx_bytes = sign_and_encode_tx(
+            config,
+            &signer.key_entry,
+            signer.account,
+            tx_memo,
+            batch.clone(),
+            fee,
+        )?;
+
+        let hash = Hash::new(Sha256::digest(&tx_bytes).into());
+
+        let msg_types = batch.iter().map(|msg| msg.type_url.as_str()).join(", ");
+
+        let result = match estimated_fee {
+            Ok(fee) => {
+                info!(
+                    id = %config.chain_id,
+                    "[dry-run] would broadcast tx {} with {} message(s) [{}], estimated fee {}",
+                    hash,
+                    batch.len(),
+                    msg_types,
+                    PrettyFee(&fee),
+                );
+
+                telemetry!(dry_run_tx, &config.chain_id, true);
+
+                TxSyncResult {
+                    response: Response {
+                        code: Code::Ok,
+                        data: Default::default(),
+                        log: "[]".into(),
+                        hash,
+                    },
+                    events: synthesize_events(height, &batch),
+                }
+            }
+            Err(e) => {
+                warn!(
+                    id = %config.chain_id,
+                    "[dry-run] simulation of tx {} with {} message(s) [{}] failed: {}",
+                    hash,
+                    batch.len(),
+                    msg_types,
+                    e,
+                );
+
+                telemetry!(dry_run_tx, &config.chain_id, false);
+
+                let log = e.to_string();
+
+                TxSyncResult {
+                    events: vec![IbcEvent::ChainError(format!(
+                        "simulation on chain {} for Tx hash {} reports error: {}",
+                        config.chain_id, hash, log
+                    ))],
+                    response: Response {
+                        code: Code::Err(SIMULATION_FAILED_CODE),
+                        data: Default::default(),
+                        log: log.as_str().into(),
+                        hash,
+                    },
+                }
+            }
+        };
+
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Returns the messages to simulate for each of the `batches`: the client updates of the
+/// earlier batches, followed by the messages of the batch.
+fn with_earlier_client_updates(batches: &[Vec<Any>]) -> Vec<Vec<Any>> {
+    let mut client_updates = vec![];
+
+    batches
+        .iter()
+        .map(|batch| {
+            let simulated = client_updates.iter().chain(batch).cloned().collect();
+
+            client_updates.extend(
+                batch
+                    .iter()
+                    .filter(|msg| msg.type_url == update_client::TYPE_URL)
+                    .cloned(),
+            );
+
+            simulated
+        })
+        .collect()
+}
+
+/// Synthesizes the events which the messages would emit once committed at `height`.
+/// Only the client updates and the packet messages are accounted for, since the events of
+/// the other messages carry identifiers which are only known once they are executed.
+pub fn synthesize_events(height: Height, messages: &[Any]) -> Vec<IbcEvent> {
+    messages
+        .iter()
+        .filter_map(|msg| synthesize_event(height, msg))
+        .collect()
+}
+
+fn synthesize_event(height: Height, msg: &Any) -> Option<IbcEvent> {
+    let value = msg.value.as_slice();
+
+    let event = match msg.type_url.as_str() {
+        update_client::TYPE_URL => {
+            let msg = MsgUpdateAnyClient::decode_vec(value).ok()?;
+
+            IbcEvent::UpdateClient(UpdateClient {
+                common: ClientAttributes {
+                    height,
+                    client_id: msg.client_id,
+                    client_type: msg.header.client_type(),
+                    consensus_height: msg.header.height(),
+                },
+                header: Some(msg.header),
+            })
+        }
+        recv_packet::TYPE_URL => IbcEvent::ReceivePacket(ReceivePacket {
+            height,
+            packet: MsgRecvPacket::decode_vec(value).ok()?.packet,
+        }),
+        acknowledgement::TYPE_URL => IbcEvent::AcknowledgePacket(AcknowledgePacket {
+            height,
+            packet: MsgAcknowledgement::decode_vec(value).ok()?.packet,
+        }),
+        timeout::TYPE_URL => IbcEvent::TimeoutPacket(TimeoutPacket {
+            height,
+            packet: MsgTimeout::decode_vec(value).ok()?.packet,
+        }),
+        timeout_on_close::TYPE_URL => IbcEvent::TimeoutOnClosePacket(TimeoutOnClosePacket {
+            height,
+            packet: MsgTimeoutOnClose::decode_vec(value).ok()?.packet,
+        }),
+        _ => return None,
+    };
+
+    Some(event)
+}
+
+/// The events synthesized for the latest simulated transactions, by transaction hash.
+#[derive(Default)]
+pub struct DryRunTxs {
+    txs: VecDeque<(Hash, Vec<IbcEvent>)>,
+}
+
+impl DryRunTxs {
+    pub fn record(&mut self, results: &[TxSyncResult]) {
+        for result in results {
+            if self.txs.len() == DRY_RUN_TX_CACHE_SIZE {
+                self.txs.pop_front();
+            }
+
+            self.txs
+                .push_back((result.response.hash, result.events.clone()));
+        }
+    }
+
+    /// Returns the events synthesized for the simulated transaction `hash`, if any.
+    pub fn events(&self, hash: &Hash) -> Option<Vec<IbcEvent>> {
+        self.txs
+            .iter()
+            .find(|(tx_hash, _)| tx_hash == hash)
+            .map(|(_, events)| events.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::events::IbcEvent;
+    use ibc::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::{
+        MsgRecvPacket as RawMsgRecvPacket, MsgTimeout as RawMsgTimeout, Packet as RawPacket,
+    };
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+    use prost::Message;
+
+    use super::{
+        recv_packet, synthesize_events, timeout, update_client, with_earlier_client_updates,
+    };
+
+    fn raw_packet(sequence: u64) -> RawPacket {
+        RawPacket {
+            sequence,
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: "channel-1".to_string(),
+            data: vec![0],
+            timeout_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: 100,
+            }),
+            timeout_timestamp: 0,
+        }
+    }
+
+    fn proof_height() -> Option<RawHeight> {
+        Some(RawHeight {
+            revision_number: 0,
+            revision_height: 10,
+        })
+    }
+
+    #[test]
+    fn synthesize_packet_events() {
+        let height = Height::new(0, 42);
+
+        let recv = RawMsgRecvPacket {
+            packet: Some(raw_packet(1)),
+            proof_commitment: get_dummy_proof(),
+            proof_height: proof_height(),
+            signer: get_dummy_bech32_account(),
+        };
+
+        let timeout = RawMsgTimeout {
+            packet: Some(raw_packet(2)),
+            proof_unreceived: get_dummy_proof(),
+            proof_height: proof_height(),
+            next_sequence_recv: 2,
+            signer: get_dummy_bech32_account(),
+        };
+
+        let messages = vec![
+            Any {
+                type_url: recv_packet::TYPE_URL.to_string(),
+                value: recv.encode_to_vec(),
+            },
+            Any {
+                type_url: timeout::TYPE_URL.to_string(),
+                value: timeout.encode_to_vec(),
+            },
+            // The events of the handshake messages are not synthesized.
+            Any {
+                type_url: "/ibc.core.channel.v1.MsgChannelOpenInit".to_string(),
+                value: vec![],
+            },
+        ];
+
+        let events = synthesize_events(height, &messages);
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            IbcEvent::ReceivePacket(ev) if ev.height == height && ev.packet.sequence == 1.into()
+        ));
+        assert!(matches!(
+            &events[1],
+            IbcEvent::TimeoutPacket(ev) if ev.height == height && ev.packet.sequence == 2.into()
+        ));
+    }
+
+    #[test]
+    fn client_updates_are_simulated_with_the_later_batches() {
+        let msg = |type_url: &str, value: u8| Any {
+            type_url: type_url.to_string(),
+            value: vec![value],
+        };
+        let update = msg(update_client::TYPE_URL, 0);
+        let recv_1 = msg(recv_packet::TYPE_URL, 1);
+        let recv_2 = msg(recv_packet::TYPE_URL, 2);
+        let recv_3 = msg(recv_packet::TYPE_URL, 3);
+
+        let batches = vec![
+            vec![update.clone(), recv_1.clone()],
+            vec![recv_2.clone()],
+            vec![recv_3.clone()],
+        ];
+
+        assert_eq!(
+            with_earlier_client_updates(&batches),
+            vec![
+                vec![update.clone(), recv_1],
+                vec![update.clone(), recv_2],
+                vec![update, recv_3],
+            ]
+        );
+    }
+}
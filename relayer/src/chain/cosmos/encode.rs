@@ -12,7 +12,7 @@ use crate::chain::cosmos::types::tx::SignedTx;
 use crate::config::types::Memo;
 use crate::config::AddressType;
 use crate::error::Error;
-use crate::keyring::{sign_message, KeyEntry};
+use crate::keyring::{KeyEntry, KeySigner};
 
 pub fn sign_and_encode_tx(
     config: &TxConfig,
@@ -51,6 +51,7 @@ pub fn sign_tx(
 
     let signed_doc = encode_sign_doc(
         &config.chain_id,
+        &config.signer,
         key_entry,
         &config.address_type,
         account.number,
@@ -78,6 +79,7 @@ fn encode_key_bytes(key: &KeyEntry) -> Result<Vec<u8>, Error> {
 
 fn encode_sign_doc(
     chain_id: &ChainId,
+    signer: &KeySigner,
     key: &KeyEntry,
     address_type: &AddressType,
     account_number: AccountNumber,
@@ -95,7 +97,9 @@ fn encode_sign_doc(
     let mut signdoc_buf = Vec::new();
     prost::Message::encode(&sign_doc, &mut signdoc_buf).unwrap();
 
-    let signed = sign_message(key, signdoc_buf, address_type).map_err(Error::key_base)?;
+    let signed = signer
+        .sign(key, signdoc_buf, address_type)
+        .map_err(Error::key_base)?;
 
     Ok(signed)
 }
@@ -0,0 +1,548 @@
+//! The fees spent on the transactions sent to a chain, accounted per day, channel and message
+//! type, and checked against the configured daily budget. The ledger is kept on disk, so that
+//! the spend of the day carries over relayer restarts and can be queried with
+//! `hermes query fees-spent`.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use ibc::core::ics04_channel::msgs::{acknowledgement, recv_packet, timeout, timeout_on_close};
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement as RawMsgAcknowledgement, MsgRecvPacket as RawMsgRecvPacket,
+    MsgTimeout as RawMsgTimeout, MsgTimeoutOnClose as RawMsgTimeoutOnClose, Packet as RawPacket,
+};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint::abci::transaction::Hash;
+use tracing::{debug, warn};
+
+use crate::config::ChainConfig;
+use crate::error::Error;
+
+/// The folder, relative to the home directory, holding the fee ledgers of the chains.
+pub const FEE_LEDGER_DEFAULT_FOLDER: &str = ".hermes/fees/";
+
+/// The number of days the spend is kept for in the ledger.
+const LEDGER_RETENTION_DAYS: usize = 30;
+
+/// The maximum number of broadcast transactions whose fee is remembered until they are
+/// confirmed.
+const UNCONFIRMED_TX_CACHE_SIZE: usize = 1000;
+
+/// The fees paid in one denomination for the messages of one type over one channel.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SpendEntry {
+    /// The channel end on the chain the messages relayed packets over, as `port/channel`,
+    /// or `None` for the messages not related to a packet, e.g. client updates.
+    pub channel: Option<String>,
+    /// The type of the messages, e.g. `MsgRecvPacket`
+    pub msg_type: String,
+    pub denom: String,
+    pub amount: u128,
+    /// The number of messages the fees were paid for
+    pub msg_count: u64,
+}
+
+/// The fees spent on one day, in UTC.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DailySpend {
+    /// The day, formatted as `YYYY-MM-DD`
+    pub date: String,
+    pub entries: Vec<SpendEntry>,
+}
+
+impl DailySpend {
+    /// Returns the total amount of `denom` spent on the day.
+    pub fn total(&self, denom: &str) -> u128 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.denom == denom)
+            .map(|entry| entry.amount)
+            .sum()
+    }
+
+    /// Returns the denoms other than `denom` which fees were paid in on the day.
+    pub fn other_denoms<'a>(&'a self, denom: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.denom.as_str())
+            .filter(move |entry_denom| *entry_denom != denom)
+    }
+
+    fn add(&mut self, channel: Option<String>, msg_type: &str, denom: &str, amount: u128) {
+        let existing = self.entries.iter_mut().find(|entry| {
+            entry.channel == channel && entry.msg_type == msg_type && entry.denom == denom
+        });
+
+        match existing {
+            Some(entry) => {
+                entry.amount = entry.amount.saturating_add(amount);
+                entry.msg_count += 1;
+            }
+            None => self.entries.push(SpendEntry {
+                channel,
+                msg_type: msg_type.to_string(),
+                denom: denom.to_string(),
+                amount,
+                msg_count: 1,
+            }),
+        }
+    }
+}
+
+/// The fees spent on a chain over the last days, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FeeLedger {
+    pub days: Vec<DailySpend>,
+}
+
+impl FeeLedger {
+    /// Returns the spend of the given day, if anything was spent on it.
+    pub fn day(&self, date: &str) -> Option<&DailySpend> {
+        self.days.iter().find(|day| day.date == date)
+    }
+
+    /// Records the fee paid for a transaction carrying `messages` on the given day. The fee is
+    /// split evenly between the messages, the remainder going to the first one.
+    pub fn record(&mut self, date: &str, messages: &[Any], fee: &Fee) {
+        if messages.is_empty() {
+            return;
+        }
+
+        if self.days.last().map_or(true, |day| day.date != date) {
+            self.days.push(DailySpend {
+                date: date.to_string(),
+                entries: vec![],
+            });
+
+            if self.days.len() > LEDGER_RETENTION_DAYS {
+                let expired = self.days.len() - LEDGER_RETENTION_DAYS;
+                self.days.drain(..expired);
+            }
+        }
+        let day = self.days.last_mut().expect("the day was just pushed");
+
+        let msg_count = messages.len() as u128;
+        for coin in &fee.amount {
+            let amount = match coin.amount.parse::<u128>() {
+                Ok(amount) => amount,
+                Err(_) => {
+                    warn!("ignoring fee amount '{}{}'", coin.amount, coin.denom);
+                    continue;
+                }
+            };
+
+            let share = amount / msg_count;
+            let remainder = amount % msg_count;
+
+            for (i, msg) in messages.iter().enumerate() {
+                let amount = if i == 0 { share + remainder } else { share };
+                day.add(message_channel(msg), message_type(msg), &coin.denom, amount);
+            }
+        }
+    }
+}
+
+/// The hash, messages and fee of a transaction broadcast but not confirmed yet.
+type UnconfirmedTx = (Hash, Vec<Any>, Fee);
+
+/// Keeps the ledger of the fees spent on a chain and checks it against the daily budget.
+/// Its clones share the ledger.
+#[derive(Clone, Debug)]
+pub struct FeeSpendTracker {
+    daily_budget: Option<u64>,
+    denom: String,
+    ledger_path: Option<PathBuf>,
+    ledger: Arc<Mutex<FeeLedger>>,
+    unconfirmed: Arc<Mutex<VecDeque<UnconfirmedTx>>>,
+    /// Held while saving the ledger, so that the saves don't interleave.
+    save_lock: Arc<Mutex<()>>,
+}
+
+impl FeeSpendTracker {
+    /// Creates the tracker of the chain, resuming from its ledger on disk, if any.
+    pub fn new(config: &ChainConfig) -> Self {
+        let ledger_path = ledger_path(&config.id);
+
+        let ledger = match ledger_path.as_deref().map(load_ledger_at) {
+            Some(Ok(ledger)) => ledger,
+            Some(Err(e)) => {
+                warn!(chain = %config.id, "starting a new fee ledger: {}", e);
+                FeeLedger::default()
+            }
+            None => {
+                warn!(
+                    chain = %config.id,
+                    "home location is unavailable, the fee ledger will not be saved"
+                );
+                FeeLedger::default()
+            }
+        };
+
+        Self {
+            daily_budget: config.daily_fee_budget,
+            denom: config.gas_price.denom.clone(),
+            ledger_path,
+            ledger: Arc::new(Mutex::new(ledger)),
+            unconfirmed: Default::default(),
+            save_lock: Default::default(),
+        }
+    }
+
+    /// Creates a tracker which does not save its ledger.
+    pub fn in_memory(daily_budget: Option<u64>, denom: String) -> Self {
+        Self {
+            daily_budget,
+            denom,
+            ledger_path: None,
+            ledger: Arc::new(Mutex::new(FeeLedger::default())),
+            unconfirmed: Default::default(),
+            save_lock: Default::default(),
+        }
+    }
+
+    /// Remembers the fee of the transaction `hash` carrying `messages`, which passed `CheckTx`,
+    /// to record it once the transaction is confirmed.
+    pub fn broadcast(&self, hash: Hash, messages: &[Any], fee: &Fee) {
+        let mut unconfirmed = self.unconfirmed.lock().expect("poisoned fee ledger lock");
+
+        if unconfirmed.len() == UNCONFIRMED_TX_CACHE_SIZE {
+            unconfirmed.pop_front();
+        }
+
+        unconfirmed.push_back((hash, messages.to_vec(), fee.clone()));
+    }
+
+    /// Records the fee of the transaction `hash`, if it was broadcast by the relayer and not
+    /// confirmed before, and saves the ledger. The fee is charged once a transaction is included
+    /// in a block, even if its messages fail then.
+    pub fn confirm(&self, hash: &Hash) {
+        let confirmed = {
+            let mut unconfirmed = self.unconfirmed.lock().expect("poisoned fee ledger lock");
+
+            let index = unconfirmed
+                .iter()
+                .position(|(tx_hash, _, _)| tx_hash == hash);
+
+            index.and_then(|index| unconfirmed.remove(index))
+        };
+
+        if let Some((_, messages, fee)) = confirmed {
+            self.record(&messages, &fee);
+        }
+    }
+
+    /// Records the fee paid for a transaction carrying `messages`, and saves the ledger.
+    fn record(&self, messages: &[Any], fee: &Fee) {
+        self.ledger
+            .lock()
+            .expect("poisoned fee ledger lock")
+            .record(&today(), messages, fee);
+
+        if let Some(path) = &self.ledger_path {
+            // The ledger is only locked for as long as it takes to copy it, and the copy
+            // is taken after the previous saves are done, so that it is the latest one.
+            let _save = self.save_lock.lock().expect("poisoned fee ledger lock");
+            let ledger = self
+                .ledger
+                .lock()
+                .expect("poisoned fee ledger lock")
+                .clone();
+
+            if let Err(e) = save_ledger_at(path, &ledger) {
+                warn!("failed to save the fee ledger: {}", e);
+            }
+        }
+    }
+
+    /// Returns `true` if the fees spent today exceed the daily budget. Fees paid in a denom other
+    /// than the one of the budget, e.g. before the gas price of the chain was reconfigured, can't
+    /// be weighed against it, so that the budget is deemed exceeded for the rest of the day.
+    pub fn budget_exceeded(&self) -> bool {
+        let budget = match self.daily_budget {
+            Some(budget) => budget,
+            None => return false,
+        };
+
+        let ledger = self.ledger.lock().expect("poisoned fee ledger lock");
+        let today = match ledger.day(&today()) {
+            Some(today) => today,
+            None => return false,
+        };
+
+        if let Some(other_denom) = today.other_denoms(&self.denom).next() {
+            warn!(
+                budget_denom = %self.denom,
+                fee_denom = %other_denom,
+                "fees were paid today in a denom the daily fee budget can't account for"
+            );
+            return true;
+        }
+
+        let spent = today.total(&self.denom);
+
+        if spent > u128::from(budget) {
+            debug!(%spent, %budget, denom = %self.denom, "daily fee budget exceeded");
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Returns the path of the ledger of the chain, or `None` if the home location is unavailable.
+pub fn ledger_path(chain_id: &ChainId) -> Option<PathBuf> {
+    let home = dirs_next::home_dir()?;
+
+    Some(
+        home.join(FEE_LEDGER_DEFAULT_FOLDER)
+            .join(format!("{}.json", chain_id)),
+    )
+}
+
+/// Loads the ledger of the fees spent on the chain, which is empty if nothing was spent yet.
+pub fn load_ledger(chain_id: &ChainId) -> Result<FeeLedger, Error> {
+    match ledger_path(chain_id) {
+        Some(path) => load_ledger_at(&path),
+        None => Ok(FeeLedger::default()),
+    }
+}
+
+fn load_ledger_at(path: &Path) -> Result<FeeLedger, Error> {
+    if !path.exists() {
+        return Ok(FeeLedger::default());
+    }
+
+    let display = path.display().to_string();
+    let content = fs::read(path).map_err(|e| Error::fee_ledger_io(display.clone(), e))?;
+
+    serde_json::from_slice(&content).map_err(|e| Error::fee_ledger_decode(display, e))
+}
+
+/// Writes the ledger to a temporary file first, so that a crash can't leave it truncated.
+fn save_ledger_at(path: &Path, ledger: &FeeLedger) -> Result<(), Error> {
+    let display = path.display().to_string();
+    let io_error = |e| Error::fee_ledger_io(display.clone(), e);
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder).map_err(io_error)?;
+    }
+
+    let content = serde_json::to_vec_pretty(ledger)
+        .map_err(|e| Error::fee_ledger_decode(display.clone(), e))?;
+
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(io_error)?;
+    fs::rename(&tmp_path, path).map_err(io_error)
+}
+
+/// Returns the current day in UTC, formatted as `YYYY-MM-DD`.
+fn today() -> String {
+    let now = humantime::format_rfc3339_seconds(SystemTime::now()).to_string();
+    now[..10].to_string()
+}
+
+/// Returns the last segment of the type URL of the message, e.g. `MsgRecvPacket`.
+fn message_type(msg: &Any) -> &str {
+    msg.type_url
+        .rsplit('.')
+        .next()
+        .unwrap_or(msg.type_url.as_str())
+}
+
+/// Returns the channel end on the chain of the packet relayed by the message, if any: the
+/// destination of a received packet, or the source of an acknowledged or timed out one.
+fn message_channel(msg: &Any) -> Option<String> {
+    fn source(packet: RawPacket) -> String {
+        format!("{}/{}", packet.source_port, packet.source_channel)
+    }
+
+    let value = msg.value.as_slice();
+
+    match msg.type_url.as_str() {
+        recv_packet::TYPE_URL => {
+            let packet = RawMsgRecvPacket::decode(value).ok()?.packet?;
+            Some(format!(
+                "{}/{}",
+                packet.destination_port, packet.destination_channel
+            ))
+        }
+        acknowledgement::TYPE_URL => {
+            Some(source(RawMsgAcknowledgement::decode(value).ok()?.packet?))
+        }
+        timeout::TYPE_URL => Some(source(RawMsgTimeout::decode(value).ok()?.packet?)),
+        timeout_on_close::TYPE_URL => {
+            Some(source(RawMsgTimeoutOnClose::decode(value).ok()?.packet?))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::cosmos::tx::v1beta1::Fee;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::{MsgRecvPacket, MsgTimeout, Packet};
+    use prost::Message;
+    use tendermint::abci::transaction::Hash;
+
+    use super::{today, FeeLedger, FeeSpendTracker, SpendEntry, LEDGER_RETENTION_DAYS};
+
+    fn packet() -> Packet {
+        Packet {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: "channel-7".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn fee(amount: &str) -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: "stake".to_string(),
+                amount: amount.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fees_are_split_per_channel_and_message_type() {
+        let recv = Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+            value: MsgRecvPacket {
+                packet: Some(packet()),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+        let timeout = Any {
+            type_url: "/ibc.core.channel.v1.MsgTimeout".to_string(),
+            value: MsgTimeout {
+                packet: Some(packet()),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+        let update_client = Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![],
+        };
+
+        let mut ledger = FeeLedger::default();
+        ledger.record(
+            "2022-06-01",
+            &[update_client, recv.clone(), timeout],
+            &fee("1001"),
+        );
+        ledger.record("2022-06-01", &[recv], &fee("500"));
+
+        let day = ledger.day("2022-06-01").unwrap();
+        assert_eq!(day.total("stake"), 1501);
+        assert_eq!(
+            day.entries,
+            vec![
+                SpendEntry {
+                    channel: None,
+                    msg_type: "MsgUpdateClient".to_string(),
+                    denom: "stake".to_string(),
+                    amount: 335,
+                    msg_count: 1,
+                },
+                SpendEntry {
+                    channel: Some("transfer/channel-7".to_string()),
+                    msg_type: "MsgRecvPacket".to_string(),
+                    denom: "stake".to_string(),
+                    amount: 833,
+                    msg_count: 2,
+                },
+                SpendEntry {
+                    channel: Some("transfer/channel-0".to_string()),
+                    msg_type: "MsgTimeout".to_string(),
+                    denom: "stake".to_string(),
+                    amount: 333,
+                    msg_count: 1,
+                },
+            ]
+        );
+
+        assert!(ledger.day("2022-06-02").is_none());
+    }
+
+    #[test]
+    fn fees_in_other_denoms_exceed_the_budget() {
+        let msg = Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![],
+        };
+        let hash = Hash::new([1; 32]);
+
+        let tracker = FeeSpendTracker::in_memory(Some(100), "stake".to_string());
+
+        let mut fee = fee("10");
+        fee.amount.push(Coin {
+            denom: "uatom".to_string(),
+            amount: "5".to_string(),
+        });
+        tracker.broadcast(hash, &[msg], &fee);
+        tracker.confirm(&hash);
+
+        assert!(tracker.budget_exceeded());
+    }
+
+    #[test]
+    fn old_days_are_dropped() {
+        let msgs = [Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![],
+        }];
+
+        let mut ledger = FeeLedger::default();
+        for day in 1..=LEDGER_RETENTION_DAYS + 1 {
+            ledger.record(&format!("2022-07-{:02}", day), &msgs, &fee("10"));
+        }
+
+        assert_eq!(ledger.days.len(), LEDGER_RETENTION_DAYS);
+        assert!(ledger.day("2022-07-01").is_none());
+        assert_eq!(ledger.day("2022-07-31").unwrap().total("stake"), 10);
+    }
+
+    #[test]
+    fn fees_are_recorded_once_confirmed() {
+        let msg = Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![],
+        };
+        let hash = Hash::new([1; 32]);
+
+        let tracker = FeeSpendTracker::in_memory(Some(100), "stake".to_string());
+        let spent = |tracker: &FeeSpendTracker| {
+            let ledger = tracker.ledger.lock().unwrap();
+            ledger.day(&today()).map_or(0, |day| day.total("stake"))
+        };
+
+        tracker.broadcast(hash, &[msg], &fee("150"));
+        assert_eq!(spent(&tracker), 0);
+        assert!(!tracker.budget_exceeded());
+
+        tracker.confirm(&Hash::new([2; 32]));
+        assert_eq!(spent(&tracker), 0);
+
+        tracker.confirm(&hash);
+        assert_eq!(spent(&tracker), 150);
+        assert!(tracker.budget_exceeded());
+
+        // A transaction is only recorded once, however many times it is queried.
+        tracker.confirm(&hash);
+        assert_eq!(spent(&tracker), 150);
+    }
+}
@@ -0,0 +1,280 @@
+//! The gas price of the transactions sent to a chain. It starts from a base price, either the
+//! configured `gas_price` or one queried from the chain, which is bumped after the broadcasts
+//! rejected for insufficient fees, and kept within the configured caps.
+
+use core::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use http::uri::Uri;
+use tendermint::abci::Code;
+use tracing::{debug, warn};
+
+use crate::chain::cosmos::query::grpc_unary;
+use crate::config::{GasPrice, GasPriceOracleConfig, GasPriceSource};
+use crate::error::Error;
+
+/// The error "insufficient fee" is defined as the unique error code 13 in the codespace "sdk"
+/// of cosmos-sdk:
+/// https://github.com/cosmos/cosmos-sdk/blob/v0.44.0/types/errors/errors.go#L64-L65
+const INSUFFICIENT_FEE_ERR: u32 = 13;
+
+/// The description of the error "insufficient fee" of cosmos-sdk, which ends the log of the
+/// transactions it rejects.
+const INSUFFICIENT_FEE_LOG: &str = "insufficient fee";
+
+/// The base price queried from the chain is reused for this long before being queried again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(6);
+
+/// The number of decimal places of the `sdk.Dec` amounts, encoded as integers in protobuf.
+const DEC_PRECISION: i32 = 18;
+
+const FEE_MARKET_GAS_PRICE_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrice";
+const NODE_CONFIG_PATH: &str = "/cosmos.base.node.v1beta1.Service/Config";
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    price: Option<ibc_proto::cosmos::base::v1beta1::DecCoin>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigResponse {
+    #[prost(string, tag = "1")]
+    minimum_gas_price: String,
+}
+
+#[derive(Debug)]
+struct OracleState {
+    /// The price obtained from the source
+    base_price: f64,
+    /// The factor the base price is multiplied by after the bumps
+    multiplier: f64,
+    /// When the base price was last queried from the chain
+    refreshed_at: Option<Instant>,
+}
+
+/// Determines the gas price of the transactions sent to a chain. Its clones share their state,
+/// so that the outcome of every broadcast is taken into account.
+#[derive(Clone, Debug)]
+pub struct GasPriceOracle {
+    config: GasPriceOracleConfig,
+    denom: String,
+    state: Arc<RwLock<OracleState>>,
+}
+
+impl GasPriceOracle {
+    pub fn new(gas_price: GasPrice, config: GasPriceOracleConfig) -> Self {
+        Self {
+            config,
+            denom: gas_price.denom,
+            state: Arc::new(RwLock::new(OracleState {
+                base_price: gas_price.price,
+                multiplier: 1.0,
+                refreshed_at: None,
+            })),
+        }
+    }
+
+    /// Returns the gas price to pay for the next transaction.
+    pub fn gas_price(&self) -> GasPrice {
+        let state = self.state.read().expect("poisoned gas price oracle lock");
+
+        let mut price = state.base_price * state.multiplier;
+        if let Some(max_price) = self.config.max_price {
+            price = price.min(max_price);
+        }
+        if let Some(min_price) = self.config.min_price {
+            price = price.max(min_price);
+        }
+
+        GasPrice::new(price, self.denom.clone())
+    }
+
+    /// Queries the base price from the chain, if it is configured as the source and the last
+    /// queried price is stale. Upon failure, the last known price is kept.
+    pub async fn refresh(&self, grpc_address: &Uri) {
+        if self.config.source == GasPriceSource::Static {
+            return;
+        }
+
+        let stale = self
+            .state
+            .read()
+            .expect("poisoned gas price oracle lock")
+            .refreshed_at
+            .map_or(true, |at| at.elapsed() >= REFRESH_INTERVAL);
+        if !stale {
+            return;
+        }
+
+        let queried = match self.config.source {
+            GasPriceSource::Static => return,
+            GasPriceSource::FeeMarket => {
+                query_fee_market_gas_price(grpc_address, &self.denom).await
+            }
+            GasPriceSource::MinGasPrice => query_min_gas_price(grpc_address, &self.denom).await,
+        };
+
+        match queried {
+            Ok(base_price) => {
+                debug!(%base_price, source = ?self.config.source, "refreshed base gas price");
+
+                let mut state = self.state.write().expect("poisoned gas price oracle lock");
+                state.base_price = base_price;
+                state.refreshed_at = Some(Instant::now());
+            }
+            Err(e) => warn!(
+                source = ?self.config.source,
+                "failed to query the base gas price, keeping the last known one: {}", e
+            ),
+        }
+    }
+
+    /// Bumps the price after a broadcast rejected for insufficient fees, or relaxes it back
+    /// towards the base price after an accepted one. The price isn't bumped any further once
+    /// it reaches `max_price`.
+    ///
+    /// Modules may define their own error with the code 13 in another codespace, which the
+    /// response of a sync broadcast leaves out. The error of cosmos-sdk is therefore told apart
+    /// by its description ending the `log` of the response.
+    pub fn record_broadcast(&self, code: Code, log: &str) {
+        let mut state = self.state.write().expect("poisoned gas price oracle lock");
+
+        match code {
+            Code::Err(INSUFFICIENT_FEE_ERR) if log.ends_with(INSUFFICIENT_FEE_LOG) => {
+                let price = state.base_price * state.multiplier;
+                if self
+                    .config
+                    .max_price
+                    .map_or(true, |max_price| price < max_price)
+                {
+                    state.multiplier *= self.config.bump_factor;
+                    debug!(multiplier = %state.multiplier, "bumped gas price after insufficient fee");
+                }
+            }
+            Code::Ok => {
+                state.multiplier = (state.multiplier / self.config.bump_factor).max(1.0);
+            }
+            Code::Err(_) => {}
+        }
+    }
+}
+
+async fn query_fee_market_gas_price(grpc_address: &Uri, denom: &str) -> Result<f64, Error> {
+    let request = GasPriceRequest {
+        denom: denom.to_string(),
+    };
+    let response: GasPriceResponse =
+        grpc_unary(grpc_address, FEE_MARKET_GAS_PRICE_PATH, request).await?;
+
+    let price = response
+        .price
+        .ok_or_else(|| Error::invalid_gas_price("empty fee market gas price".to_string()))?;
+
+    parse_proto_dec(&price.amount)
+}
+
+async fn query_min_gas_price(grpc_address: &Uri, denom: &str) -> Result<f64, Error> {
+    let response: ConfigResponse =
+        grpc_unary(grpc_address, NODE_CONFIG_PATH, ConfigRequest {}).await?;
+
+    parse_min_gas_prices(&response.minimum_gas_price, denom)
+}
+
+/// Parses an `sdk.Dec` amount as it is encoded in protobuf, i.e. as an integer scaled up by
+/// `10^DEC_PRECISION`, which is how the fee market returns its gas price.
+fn parse_proto_dec(amount: &str) -> Result<f64, Error> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::invalid_gas_price(format!(
+            "invalid protobuf decimal amount: {}",
+            amount
+        )));
+    }
+
+    let value = amount.parse::<f64>().map_err(|_| {
+        Error::invalid_gas_price(format!("invalid protobuf decimal amount: {}", amount))
+    })?;
+    Ok(value / 10f64.powi(DEC_PRECISION))
+}
+
+/// Returns the price of `denom` among the comma-separated `minimum_gas_prices` of a node,
+/// e.g. `0.025000000000000000uatom,0.100000000000000000stake`.
+fn parse_min_gas_prices(minimum_gas_prices: &str, denom: &str) -> Result<f64, Error> {
+    minimum_gas_prices
+        .split(',')
+        .map(str::trim)
+        .find_map(|coin| {
+            let amount = coin.strip_suffix(denom)?;
+            let amount = amount.parse::<f64>().ok()?;
+            Some(amount)
+        })
+        .ok_or_else(|| {
+            Error::invalid_gas_price(format!(
+                "no minimum gas price for denom {} in '{}'",
+                denom, minimum_gas_prices
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::Code;
+
+    use super::{
+        parse_min_gas_prices, parse_proto_dec, GasPriceOracle, INSUFFICIENT_FEE_ERR,
+        INSUFFICIENT_FEE_LOG,
+    };
+    use crate::config::{GasPrice, GasPriceOracleConfig, GasPriceSource};
+
+    #[test]
+    fn bumped_gas_price_within_caps() {
+        let oracle = GasPriceOracle::new(
+            GasPrice::new(0.1, "stake".to_string()),
+            GasPriceOracleConfig {
+                source: GasPriceSource::Static,
+                bump_factor: 2.0,
+                min_price: Some(0.15),
+                max_price: Some(0.3),
+            },
+        );
+        assert_eq!(oracle.gas_price(), GasPrice::new(0.15, "stake".to_string()));
+
+        oracle.record_broadcast(Code::Err(INSUFFICIENT_FEE_ERR), INSUFFICIENT_FEE_LOG);
+        assert_eq!(oracle.gas_price().price, 0.2);
+        oracle.record_broadcast(Code::Err(INSUFFICIENT_FEE_ERR), INSUFFICIENT_FEE_LOG);
+        assert_eq!(oracle.gas_price().price, 0.3);
+
+        // Other failures, including those of other codespaces with the same code, leave the
+        // price as is, accepted broadcasts relax it.
+        oracle.record_broadcast(Code::Err(32), "account sequence mismatch");
+        assert_eq!(oracle.gas_price().price, 0.3);
+        oracle.record_broadcast(Code::Err(INSUFFICIENT_FEE_ERR), "invalid denom trace");
+        assert_eq!(oracle.gas_price().price, 0.3);
+        oracle.record_broadcast(Code::Ok, "");
+        assert_eq!(oracle.gas_price().price, 0.2);
+        oracle.record_broadcast(Code::Ok, "");
+        oracle.record_broadcast(Code::Ok, "");
+        assert_eq!(oracle.gas_price().price, 0.15);
+    }
+
+    #[test]
+    fn parse_queried_gas_prices() {
+        assert_eq!(parse_proto_dec("25000000000000000").unwrap(), 0.025);
+        assert!(parse_proto_dec("0.025").is_err());
+        assert!(parse_proto_dec("abc").is_err());
+
+        let prices = "0.025000000000000000uatom,0.100000000000000000stake";
+        assert_eq!(parse_min_gas_prices(prices, "stake").unwrap(), 0.1);
+        assert!(parse_min_gas_prices(prices, "uosmo").is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use http::uri::Uri;
+use http::uri::{PathAndQuery, Uri};
 use ibc::core::ics02_client::client_consensus::QueryClientEventRequest;
 use ibc::core::ics04_channel::channel::QueryPacketEventDataRequest;
 use ibc::core::ics04_channel::packet::Sequence;
@@ -11,6 +11,7 @@ use tendermint::abci::Path as TendermintABCIPath;
 use tendermint::block::Height;
 use tendermint_rpc::query::Query;
 use tendermint_rpc::{Client, HttpClient, Url};
+use tonic::codec::ProstCodec;
 
 use crate::chain::cosmos::version::Specs;
 use crate::error::Error;
@@ -18,6 +19,7 @@ use crate::error::Error;
 pub mod account;
 pub mod balance;
 pub mod denom_trace;
+pub mod fee;
 pub mod status;
 pub mod tx;
 
@@ -156,3 +158,33 @@ pub async fn fetch_version_specs(chain_id: &ChainId, grpc_address: &Uri) -> Resu
         .try_into()
         .map_err(|e| Error::fetch_version_parsing(chain_id.clone(), grpc_addr_string.clone(), e))
 }
+
+/// Calls a gRPC method for which no client is generated.
+pub async fn grpc_unary<Req, Resp>(
+    grpc_address: &Uri,
+    path: &'static str,
+    request: Req,
+) -> Result<Resp, Error>
+where
+    Req: prost::Message + Send + Sync + 'static,
+    Resp: prost::Message + Default + Send + Sync + 'static,
+{
+    let channel = tonic::transport::Endpoint::from(grpc_address.clone())
+        .connect()
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let mut client = tonic::client::Grpc::new(channel);
+    client.ready().await.map_err(Error::grpc_transport)?;
+
+    let response = client
+        .unary(
+            tonic::Request::new(request),
+            PathAndQuery::from_static(path),
+            ProstCodec::default(),
+        )
+        .await
+        .map_err(Error::grpc_status)?;
+
+    Ok(response.into_inner())
+}
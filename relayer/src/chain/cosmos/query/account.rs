@@ -11,8 +11,8 @@ use crate::error::Error;
 /// Otherwise query for the account information, update the `Option` to `Some`,
 /// and return the underlying `&mut` reference.
 pub async fn get_or_fetch_account<'a>(
-    grpc_address: &'a Uri,
-    account_address: &'a str,
+    grpc_address: &Uri,
+    account_address: &str,
     m_account: &'a mut Option<Account>,
 ) -> Result<&'a mut Account, Error> {
     match m_account {
@@ -38,6 +38,7 @@ pub async fn refresh_account<'a>(
     let account = query_account(grpc_address, account_address).await?;
 
     info!(
+        account = %account_address,
         sequence = %account.sequence,
         number = %account.account_number,
         "refresh: retrieved account",
@@ -0,0 +1,59 @@
+use http::uri::Uri;
+
+use ibc::applications::ics29_fee::fee::PacketFee;
+use ibc::core::ics04_channel::packet::PacketId;
+use ibc_proto::ibc::applications::fee::v1::IdentifiedPacketFees;
+use ibc_proto::ibc::core::channel::v1::PacketId as RawPacketId;
+
+use crate::chain::cosmos::query::grpc_unary;
+use crate::error::{Error, ErrorDetail};
+
+const INCENTIVIZED_PACKET_PATH: &str = "/ibc.applications.fee.v1.Query/IncentivizedPacket";
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryIncentivizedPacketRequest {
+    #[prost(message, optional, tag = "1")]
+    packet_id: Option<RawPacketId>,
+    #[prost(uint64, tag = "2")]
+    query_height: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryIncentivizedPacketResponse {
+    #[prost(message, optional, tag = "1")]
+    incentivized_packet: Option<IdentifiedPacketFees>,
+}
+
+/// Uses the gRPC query of the ICS29 fee module to retrieve the fees escrowed for relaying the
+/// packet, which are empty if the packet isn't incentivized.
+pub async fn query_incentivized_packet(
+    grpc_address: &Uri,
+    packet_id: PacketId,
+) -> Result<Vec<PacketFee>, Error> {
+    let request = QueryIncentivizedPacketRequest {
+        packet_id: Some(packet_id.into()),
+        query_height: 0,
+    };
+
+    let response: QueryIncentivizedPacketResponse =
+        match grpc_unary(grpc_address, INCENTIVIZED_PACKET_PATH, request).await {
+            Ok(response) => response,
+            Err(e) => match e.detail() {
+                ErrorDetail::GrpcStatus(detail)
+                    if detail.status.code() == tonic::Code::NotFound =>
+                {
+                    return Ok(vec![]);
+                }
+                _ => return Err(e),
+            },
+        };
+
+    response
+        .incentivized_packet
+        .map_or(vec![], |packet| packet.packet_fees)
+        .into_iter()
+        .map(|fee| {
+            PacketFee::try_from(fee).map_err(|e| Error::invalid_packet_fee(format!("{}", e)))
+        })
+        .collect()
+}
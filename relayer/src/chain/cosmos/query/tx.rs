@@ -1,3 +1,4 @@
+use ibc::applications::ics29_fee::events::DistributeFeeEvent;
 use ibc::core::ics02_client::client_consensus::QueryClientEventRequest;
 use ibc::core::ics02_client::events as ClientEvents;
 use ibc::core::ics04_channel::channel::QueryPacketEventDataRequest;
@@ -10,6 +11,7 @@ use ibc::Height as ICSHeight;
 use tendermint::abci::Event;
 use tendermint_rpc::endpoint::tx::Response as ResultTx;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
+use tracing::trace;
 
 use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
 use crate::error::Error;
@@ -244,7 +246,30 @@ fn all_ibc_events_from_tx_search_response(chain_id: &ChainId, response: ResultTx
     for event in deliver_tx_result.events {
         if let Some(ibc_ev) = from_tx_response_event(height, &event) {
             result.push(ibc_ev);
+        } else if let Some(fee_ev) = DistributeFeeEvent::try_from_tx(&event) {
+            record_distributed_fee(chain_id, fee_ev);
         }
     }
     result
 }
+
+/// Reports the fees paid out by the fee middleware in a transaction to telemetry.
+fn record_distributed_fee(chain_id: &ChainId, event: DistributeFeeEvent) {
+    for coin in event.fee {
+        let amount: u64 = match coin.amount.to_string().parse() {
+            Ok(amount) => amount,
+            Err(_) => continue,
+        };
+        let denom = coin.denom.to_string();
+
+        trace!(%chain_id, %amount, %denom, receiver = %event.receiver, "fee distributed");
+
+        crate::telemetry!(
+            fees_earned,
+            chain_id,
+            event.receiver.as_ref(),
+            amount,
+            &denom,
+        );
+    }
+}
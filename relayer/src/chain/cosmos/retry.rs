@@ -41,7 +41,9 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 ///     the `broadcast_tx_sync` step.
 ///
 /// We treat both cases by re-fetching the account sequence number
-/// from the full node.
+/// from the full node. Only the sequence of the account of `key_entry`
+/// is re-fetched, the accounts of the other keys of the chain are
+/// recovered separately.
 /// Upon case #1, we do not retry submitting the same tx (retry happens
 /// nonetheless at the worker `step` level). Upon case #2, we retry
 /// submitting the same transaction.
@@ -84,9 +86,10 @@ fn do_send_tx_with_account_sequence_retry<'a>(
 ) -> Pin<Box<dyn Future<Output = Result<Response, Error>> + 'a>> {
     Box::pin(async move {
         debug!(
-            "sending {} messages using account sequence {}",
+            "sending {} messages using sequence {} of account {}",
             messages.len(),
             account.sequence,
+            key_entry.account,
         );
 
         let tx_result =
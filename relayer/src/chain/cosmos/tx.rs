@@ -10,6 +10,7 @@ use crate::chain::cosmos::types::config::TxConfig;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::keyring::KeyEntry;
+use crate::telemetry;
 
 pub async fn estimate_fee_and_send_tx(
     config: &TxConfig,
@@ -18,9 +19,21 @@ pub async fn estimate_fee_and_send_tx(
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Response, Error> {
+    let gas_price_oracle = &config.gas_config.gas_price_oracle;
+    gas_price_oracle.refresh(&config.grpc_address).await;
+
+    telemetry!({
+        let gas_price = gas_price_oracle.gas_price();
+        ibc_telemetry::global().gas_price(&config.chain_id, gas_price.price, &gas_price.denom);
+    });
+
     let fee = estimate_tx_fees(config, key_entry, account, tx_memo, messages.clone()).await?;
 
-    send_tx_with_fee(config, key_entry, account, tx_memo, messages, &fee).await
+    let response = send_tx_with_fee(config, key_entry, account, tx_memo, messages, &fee).await?;
+
+    gas_price_oracle.record_broadcast(response.code, response.log.as_ref());
+
+    Ok(response)
 }
 
 async fn send_tx_with_fee(
@@ -31,10 +44,15 @@ async fn send_tx_with_fee(
     messages: Vec<Any>,
     fee: &Fee,
 ) -> Result<Response, Error> {
-    let tx_bytes = sign_and_encode_tx(config, key_entry, account, tx_memo, messages, fee)?;
+    let tx_bytes = sign_and_encode_tx(config, key_entry, account, tx_memo, messages.clone(), fee)?;
 
     let response = broadcast_tx_sync(&config.rpc_client, &config.rpc_address, tx_bytes).await?;
 
+    // The fee is recorded once the transaction is confirmed.
+    if response.code.is_ok() {
+        config.fee_spend.broadcast(response.hash, &messages, fee);
+    }
+
     Ok(response)
 }
 
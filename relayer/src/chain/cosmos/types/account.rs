@@ -21,6 +21,23 @@ impl From<BaseAccount> for Account {
     }
 }
 
+/// The name of a key signing transactions, along with a cached copy of the information of its
+/// account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyAccount {
+    pub key_name: String,
+    pub account: Option<Account>,
+}
+
+impl KeyAccount {
+    pub fn new(key_name: String) -> Self {
+        Self {
+            key_name,
+            account: None,
+        }
+    }
+}
+
 /// Newtype for account numbers
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AccountNumber(u64);
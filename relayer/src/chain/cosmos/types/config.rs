@@ -4,9 +4,11 @@ use http::Uri;
 use ibc::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::{HttpClient, Url};
 
+use crate::chain::cosmos::fee_spend::FeeSpendTracker;
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::{AddressType, ChainConfig};
 use crate::error::Error;
+use crate::keyring::KeySigner;
 
 #[derive(Debug, Clone)]
 pub struct TxConfig {
@@ -17,6 +19,8 @@ pub struct TxConfig {
     pub grpc_address: Uri,
     pub rpc_timeout: Duration,
     pub address_type: AddressType,
+    pub signer: KeySigner,
+    pub fee_spend: FeeSpendTracker,
 }
 
 impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
@@ -31,6 +35,9 @@ impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
 
         let gas_config = GasConfig::from(config);
 
+        let signer = KeySigner::new(config.key_store_type, config.remote_signer.clone())
+            .map_err(Error::key_base)?;
+
         Ok(Self {
             chain_id: config.id.clone(),
             gas_config,
@@ -39,6 +46,8 @@ impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
             grpc_address,
             rpc_timeout: config.rpc_timeout,
             address_type: config.address_type.clone(),
+            signer,
+            fee_spend: FeeSpendTracker::new(config),
         })
     }
 }
@@ -1,6 +1,7 @@
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::chain::cosmos::calculate_fee;
+use crate::chain::cosmos::gas_price::GasPriceOracle;
 use crate::config::{ChainConfig, GasPrice};
 
 /// Default gas limit when submitting a transaction.
@@ -17,6 +18,7 @@ pub struct GasConfig {
     pub max_gas: u64,
     pub gas_adjustment: f64,
     pub gas_price: GasPrice,
+    pub gas_price_oracle: GasPriceOracle,
     pub max_fee: Fee,
     pub fee_granter: String,
 }
@@ -28,6 +30,10 @@ impl<'a> From<&'a ChainConfig> for GasConfig {
             max_gas: max_gas_from_config(config),
             gas_adjustment: gas_adjustment_from_config(config),
             gas_price: config.gas_price.clone(),
+            gas_price_oracle: GasPriceOracle::new(
+                config.gas_price.clone(),
+                config.gas_price_oracle.clone(),
+            ),
             max_fee: max_fee_from_config(config),
             fee_granter: fee_granter_from_config(config),
         }
@@ -8,6 +8,7 @@ use std::time::Instant;
 use tendermint_rpc::{HttpClient, Url};
 use tracing::{info, trace};
 
+use crate::chain::cosmos::fee_spend::FeeSpendTracker;
 use crate::chain::cosmos::query::tx::query_txs;
 use crate::chain::cosmos::types::tx::TxSyncResult;
 use crate::error::Error;
@@ -22,6 +23,7 @@ pub async fn wait_for_block_commits(
     rpc_client: &HttpClient,
     rpc_address: &Url,
     rpc_timeout: &Duration,
+    fee_spend: &FeeSpendTracker,
     tx_sync_results: &mut [TxSyncResult],
 ) -> Result<(), Error> {
     let start_time = Instant::now();
@@ -56,8 +58,14 @@ pub async fn wait_for_block_commits(
 
             for tx_sync_result in tx_sync_results.iter_mut() {
                 // ignore error
-                let _ =
-                    update_tx_sync_result(chain_id, rpc_client, rpc_address, tx_sync_result).await;
+                let _ = update_tx_sync_result(
+                    chain_id,
+                    rpc_client,
+                    rpc_address,
+                    fee_spend,
+                    tx_sync_result,
+                )
+                .await;
             }
         }
     }
@@ -67,6 +75,7 @@ async fn update_tx_sync_result(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    fee_spend: &FeeSpendTracker,
     tx_sync_result: &mut TxSyncResult,
 ) -> Result<(), Error> {
     let TxSyncResult { response, events } = tx_sync_result;
@@ -96,6 +105,7 @@ async fn update_tx_sync_result(
         // with the new ones. in both cases we will check in the next iteration
         // whether or not the transaction was fully committed.
         if !events_per_tx.is_empty() {
+            fee_spend.confirm(&response.hash);
             *events = events_per_tx;
         }
     }
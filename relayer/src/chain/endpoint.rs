@@ -4,6 +4,7 @@ use ibc::core::ics23_commitment::merkle::MerkleProof;
 
 use tokio::runtime::Runtime as TokioRuntime;
 
+use ibc::applications::ics29_fee::fee::PacketFee;
 use ibc::core::ics02_client::client_consensus::{
     AnyConsensusState, AnyConsensusStateWithHeight, ConsensusState,
 };
@@ -14,7 +15,7 @@ use ibc::core::ics02_client::header::Header;
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd, State};
 use ibc::core::ics03_connection::version::{get_compatible_versions, Version};
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
-use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::packet::{PacketId, PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::events::IbcEvent;
@@ -23,6 +24,7 @@ use ibc::query::{QueryBlockRequest, QueryTxRequest};
 use ibc::signer::Signer;
 use ibc::timestamp::Timestamp;
 use ibc::Height as ICSHeight;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 
 use crate::account::Balance;
@@ -99,6 +101,10 @@ pub trait ChainEndpoint: Sized {
     /// Shutdown the chain runtime
     fn shutdown(self) -> Result<(), Error>;
 
+    /// Switches the chain to the dry-run mode, in which the transactions sent to it are
+    /// simulated instead of broadcast.
+    fn enable_dry_run(&mut self);
+
     /// Perform a health check
     fn health_check(&self) -> Result<HealthCheck, Error>;
 
@@ -133,6 +139,16 @@ pub trait ChainEndpoint: Sized {
     /// Return the version of the IBC protocol that this chain is running, if known.
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error>;
 
+    /// Return whether the fees spent on the chain today exceed its daily fee budget.
+    fn fee_budget_exceeded(&self) -> bool;
+
+    /// Estimate the fee of a transaction carrying the given messages, without sending it.
+    fn estimate_tx_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error>;
+
+    /// Query the ICS29 fees escrowed for relaying the packet, which are empty if the packet
+    /// isn't incentivized.
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error>;
+
     // Queries
 
     /// Query the balance of the given account for the denom used to pay tx fees.
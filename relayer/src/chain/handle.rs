@@ -5,6 +5,7 @@ use crossbeam_channel as channel;
 use serde::Serialize;
 
 use ibc::{
+    applications::ics29_fee::fee::PacketFee,
     core::{
         ics02_client::{
             client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight},
@@ -19,7 +20,7 @@ use ibc::{
         },
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
-            packet::{PacketMsgType, Sequence},
+            packet::{PacketId, PacketMsgType, Sequence},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -30,6 +31,7 @@ use ibc::{
     signer::Signer,
     Height,
 };
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::{
     account::Balance,
@@ -152,6 +154,20 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Option<semver::Version>>,
     },
 
+    FeeBudgetExceeded {
+        reply_to: ReplyTo<bool>,
+    },
+
+    EstimateTxFee {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Fee>,
+    },
+
+    QueryIncentivizedPacket {
+        packet_id: PacketId,
+        reply_to: ReplyTo<Vec<PacketFee>>,
+    },
+
     QueryBalance {
         key_name: Option<String>,
         reply_to: ReplyTo<Balance>,
@@ -395,6 +411,16 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
     /// Return the version of the IBC protocol that this chain is running, if known.
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error>;
 
+    /// Return whether the fees spent on the chain today exceed its daily fee budget.
+    fn fee_budget_exceeded(&self) -> Result<bool, Error>;
+
+    /// Estimate the fee of a transaction carrying the given messages, without sending it.
+    fn estimate_tx_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error>;
+
+    /// Query the ICS29 fees escrowed for relaying the packet, which are empty if the packet
+    /// isn't incentivized.
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error>;
+
     /// Query the balance of the given account for the denom used to pay tx fees.
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error>;
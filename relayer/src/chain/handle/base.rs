@@ -4,6 +4,7 @@ use crossbeam_channel as channel;
 use serde::{Serialize, Serializer};
 
 use ibc::{
+    applications::ics29_fee::fee::PacketFee,
     core::{
         ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight},
         ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState},
@@ -13,7 +14,7 @@ use ibc::{
         ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd},
         ics03_connection::version::Version,
         ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
-        ics04_channel::packet::{PacketMsgType, Sequence},
+        ics04_channel::packet::{PacketId, PacketMsgType, Sequence},
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::ChainId,
         ics24_host::identifier::ChannelId,
@@ -25,6 +26,7 @@ use ibc::{
     signer::Signer,
     Height,
 };
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::{
     account::Balance,
@@ -151,6 +153,24 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::IbcVersion { reply_to })
     }
 
+    fn fee_budget_exceeded(&self) -> Result<bool, Error> {
+        self.send(|reply_to| ChainRequest::FeeBudgetExceeded { reply_to })
+    }
+
+    fn estimate_tx_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.send(|reply_to| ChainRequest::EstimateTxFee {
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error> {
+        self.send(|reply_to| ChainRequest::QueryIncentivizedPacket {
+            packet_id,
+            reply_to,
+        })
+    }
+
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
         self.send(|reply_to| ChainRequest::QueryBalance { key_name, reply_to })
     }
@@ -1,11 +1,12 @@
 use crossbeam_channel as channel;
+use ibc::applications::ics29_fee::fee::PacketFee;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::events::UpdateClient;
 use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
-use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::packet::{PacketId, PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::query::QueryTxRequest;
 use ibc::{
@@ -23,6 +24,7 @@ use ibc::{
     signer::Signer,
     Height,
 };
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use serde::{Serialize, Serializer};
 
 use crate::account::Balance;
@@ -47,6 +49,7 @@ use crate::connection::ConnectionMsgType;
 use crate::denom::DenomTrace;
 use crate::error::Error;
 use crate::keyring::KeyEntry;
+use crate::store;
 use crate::telemetry;
 
 /// A chain handle with support for caching.
@@ -81,7 +84,13 @@ impl<Handle: Serialize> Serialize for CachingChainHandle<Handle> {
 
 impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
     fn new(chain_id: ChainId, sender: channel::Sender<ChainRequest>) -> Self {
-        Self::new(Handle::new(chain_id, sender))
+        let handle = Self::new(Handle::new(chain_id.clone(), sender));
+
+        if let Some(store) = store::global() {
+            store.restore_cache(&chain_id, &handle.cache);
+        }
+
+        handle
     }
 
     fn id(&self) -> ChainId {
@@ -134,6 +143,18 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().ibc_version()
     }
 
+    fn fee_budget_exceeded(&self) -> Result<bool, Error> {
+        self.inner().fee_budget_exceeded()
+    }
+
+    fn estimate_tx_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.inner().estimate_tx_fee(tracked_msgs)
+    }
+
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error> {
+        self.inner().query_incentivized_packet(packet_id)
+    }
+
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
         self.inner().query_balance(key_name)
     }
@@ -263,6 +284,8 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
 
                     if in_cache == CacheStatus::Hit {
                         telemetry!(query_cache_hit, &self.id(), "query_connection");
+                    } else if let Some(store) = store::global() {
+                        store.save_connection(&self.id(), &request.connection_id, &result);
                     }
 
                     Ok((result, None))
@@ -313,17 +336,18 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             IncludeProof::Yes => handle.query_channel(request, IncludeProof::Yes),
             IncludeProof::No => {
                 if matches!(request.height, HeightQuery::Latest) {
-                    let (result, in_cache) = self.cache.get_or_try_insert_channel_with(
-                        &PortChannelId::new(request.channel_id, request.port_id.clone()),
-                        || {
+                    let id = PortChannelId::new(request.channel_id, request.port_id.clone());
+                    let (result, in_cache) =
+                        self.cache.get_or_try_insert_channel_with(&id, || {
                             handle
                                 .query_channel(request, IncludeProof::No)
                                 .map(|(channel_end, _)| channel_end)
-                        },
-                    )?;
+                        })?;
 
                     if in_cache == CacheStatus::Hit {
                         telemetry!(query_cache_hit, &self.id(), "query_channel");
+                    } else if let Some(store) = store::global() {
+                        store.save_channel(&self.id(), &id, &result);
                     }
 
                     Ok((result, None))
@@ -1,11 +1,12 @@
 use crossbeam_channel as channel;
+use ibc::applications::ics29_fee::fee::PacketFee;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::events::UpdateClient;
 use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
-use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::packet::{PacketId, PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::query::QueryTxRequest;
 use ibc::{
@@ -21,6 +22,7 @@ use ibc::{
     signer::Signer,
     Height,
 };
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use serde::{Serialize, Serializer};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, RwLockReadGuard};
@@ -159,6 +161,21 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().ibc_version()
     }
 
+    fn fee_budget_exceeded(&self) -> Result<bool, Error> {
+        self.inc_metric("fee_budget_exceeded");
+        self.inner().fee_budget_exceeded()
+    }
+
+    fn estimate_tx_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.inc_metric("estimate_tx_fee");
+        self.inner().estimate_tx_fee(tracked_msgs)
+    }
+
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error> {
+        self.inc_metric("query_incentivized_packet");
+        self.inner().query_incentivized_packet(packet_id)
+    }
+
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
         self.inc_metric("query_balance");
         self.inner().query_balance(key_name)
@@ -7,6 +7,7 @@ use crossbeam_channel as channel;
 use tendermint_testgen::light_block::TmLightBlock;
 use tokio::runtime::Runtime;
 
+use ibc::applications::ics29_fee::fee::PacketFee;
 use ibc::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TendermintClientState,
 };
@@ -17,7 +18,7 @@ use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientS
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd};
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::ics04_channel::context::ChannelReader;
-use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics04_channel::packet::{PacketId, Sequence};
 use ibc::core::ics23_commitment::{commitment::CommitmentPrefix, specs::ProofSpecs};
 use ibc::core::ics24_host::identifier::{ChainId, ConnectionId};
 use ibc::events::IbcEvent;
@@ -28,6 +29,7 @@ use ibc::relayer::ics18_relayer::context::Ics18Context;
 use ibc::signer::Signer;
 use ibc::test_utils::get_dummy_account_id;
 use ibc::Height;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
@@ -122,6 +124,9 @@ impl ChainEndpoint for MockChain {
         Ok(())
     }
 
+    /// The transactions are never broadcast to the mock chain anyway.
+    fn enable_dry_run(&mut self) {}
+
     fn keybase(&self) -> &KeyRing {
         unimplemented!()
     }
@@ -167,6 +172,18 @@ impl ChainEndpoint for MockChain {
         Ok(Some(semver::Version::new(3, 0, 0)))
     }
 
+    fn fee_budget_exceeded(&self) -> bool {
+        false
+    }
+
+    fn estimate_tx_fee(&mut self, _tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        Ok(Fee::default())
+    }
+
+    fn query_incentivized_packet(&self, _packet_id: PacketId) -> Result<Vec<PacketFee>, Error> {
+        Ok(vec![])
+    }
+
     fn query_balance(&self, _key_name: Option<String>) -> Result<Balance, Error> {
         unimplemented!()
     }
@@ -467,13 +484,16 @@ pub mod test_utils {
             rpc_timeout: crate::config::default::rpc_timeout(),
             account_prefix: "".to_string(),
             key_name: "".to_string(),
+            key_names: vec![],
             store_prefix: "".to_string(),
             default_gas: None,
             key_store_type: Default::default(),
             max_gas: None,
             gas_price: GasPrice::new(0.001, "uatom".to_string()),
+            gas_price_oracle: Default::default(),
             gas_adjustment: None,
             fee_granter: None,
+            daily_fee_budget: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             clock_drift: Duration::from_secs(5),
@@ -481,7 +501,11 @@ pub mod test_utils {
             trusting_period: Some(Duration::from_secs(14 * 24 * 60 * 60)), // 14 days
             trust_threshold: Default::default(),
             packet_filter: PacketFilter::default(),
+            relay_policy: vec![],
+            packet_priority: Default::default(),
             address_type: AddressType::default(),
+            remote_signer: None,
+            key_store_passphrase: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         }
@@ -6,6 +6,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 use tracing::error;
 
 use ibc::{
+    applications::ics29_fee::fee::PacketFee,
     core::{
         ics02_client::{
             client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight, ConsensusState},
@@ -20,7 +21,7 @@ use ibc::{
         },
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
-            packet::{PacketMsgType, Sequence},
+            packet::{PacketId, PacketMsgType, Sequence},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -31,6 +32,7 @@ use ibc::{
     signer::Signer,
     Height,
 };
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::{
     account::Balance,
@@ -64,6 +66,13 @@ use super::{
     tracking::TrackedMsgs,
 };
 
+/// The options of a chain runtime which are not part of the configuration of its chain.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuntimeOptions {
+    /// Whether the transactions to the chain are only simulated, never broadcast.
+    pub dry_run: bool,
+}
+
 pub struct Threads {
     pub chain_runtime: thread::JoinHandle<()>,
     pub event_monitor: Option<thread::JoinHandle<()>>,
@@ -160,9 +169,22 @@ where
     pub fn spawn<Handle: ChainHandle>(
         config: ChainConfig,
         rt: Arc<TokioRuntime>,
+    ) -> Result<Handle, Error> {
+        Self::spawn_with_options(config, RuntimeOptions::default(), rt)
+    }
+
+    /// Spawns a new runtime for a specific Chain implementation, with the given options.
+    pub fn spawn_with_options<Handle: ChainHandle>(
+        config: ChainConfig,
+        options: RuntimeOptions,
+        rt: Arc<TokioRuntime>,
     ) -> Result<Handle, Error> {
         // Similar to `from_config`.
-        let chain = Endpoint::bootstrap(config, rt.clone())?;
+        let mut chain = Endpoint::bootstrap(config, rt.clone())?;
+
+        if options.dry_run {
+            chain.enable_dry_run();
+        }
 
         // Start the light client
         let light_client = chain.init_light_client()?;
@@ -280,6 +302,18 @@ where
                             self.ibc_version(reply_to)?
                         },
 
+                        Ok(ChainRequest::FeeBudgetExceeded { reply_to }) => {
+                            self.fee_budget_exceeded(reply_to)?
+                        },
+
+                        Ok(ChainRequest::EstimateTxFee { tracked_msgs, reply_to }) => {
+                            self.estimate_tx_fee(tracked_msgs, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryIncentivizedPacket { packet_id, reply_to }) => {
+                            self.query_incentivized_packet(packet_id, reply_to)?
+                        },
+
                         Ok(ChainRequest::BuildHeader { trusted_height, target_height, client_state, reply_to }) => {
                             self.build_header(trusted_height, target_height, client_state, reply_to)?
                         },
@@ -523,6 +557,29 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn fee_budget_exceeded(&self, reply_to: ReplyTo<bool>) -> Result<(), Error> {
+        let result = Ok(self.chain.fee_budget_exceeded());
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn estimate_tx_fee(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Fee>,
+    ) -> Result<(), Error> {
+        let result = self.chain.estimate_tx_fee(tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_incentivized_packet(
+        &self,
+        packet_id: PacketId,
+        reply_to: ReplyTo<Vec<PacketFee>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_incentivized_packet(packet_id);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn build_header(
         &mut self,
         trusted_height: Height,
@@ -2,12 +2,19 @@
 
 pub mod error;
 pub mod filter;
+pub mod packet_priority;
 pub mod proof_specs;
+pub mod relay_policy;
 pub mod types;
 
 use alloc::collections::BTreeMap;
 use core::{fmt, time::Duration};
-use std::{fs, fs::File, io::Write, path::Path};
+use std::{
+    fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use serde_derive::{Deserialize, Serialize};
 use tendermint_light_client_verifier::types::TrustThreshold;
@@ -18,11 +25,14 @@ use ibc::timestamp::ZERO_DURATION;
 
 use crate::chain::ChainType;
 use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::keyring::encrypted::PassphraseSource;
 use crate::keyring::Store;
 
 pub use error::Error;
 
 pub use filter::PacketFilter;
+pub use packet_priority::{ChannelWeight, PacketPriority};
+pub use relay_policy::{PolicyAction, RelayPolicyRule};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -42,6 +52,52 @@ impl fmt::Display for GasPrice {
     }
 }
 
+/// Where the base gas price of the transactions sent to a chain is obtained from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPriceSource {
+    /// The configured `gas_price`
+    Static,
+    /// The gas price of the denom of `gas_price` set by the fee market module of the chain
+    FeeMarket,
+    /// The minimum gas price of the denom of `gas_price` accepted by the full node
+    MinGasPrice,
+}
+
+impl Default for GasPriceSource {
+    fn default() -> Self {
+        GasPriceSource::Static
+    }
+}
+
+/// The dynamic adjustments of the gas price of the transactions sent to a chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasPriceOracleConfig {
+    #[serde(default)]
+    pub source: GasPriceSource,
+    /// The factor the gas price is multiplied by after each broadcast rejected for insufficient
+    /// fees, and divided by after each accepted one until it is back to the base price,
+    /// in the spirit of EIP-1559. A factor of 1 disables the adjustments.
+    #[serde(default = "default::gas_price_bump_factor")]
+    pub bump_factor: f64,
+    /// The lowest gas price paid, whatever the source says
+    pub min_price: Option<f64>,
+    /// The highest gas price paid, whatever the source and the adjustments say
+    pub max_price: Option<f64>,
+}
+
+impl Default for GasPriceOracleConfig {
+    fn default() -> Self {
+        Self {
+            source: GasPriceSource::default(),
+            bump_factor: default::gas_price_bump_factor(),
+            min_price: None,
+            max_price: None,
+        }
+    }
+}
+
 /// Defaults for various fields
 pub mod default {
     use super::*;
@@ -73,6 +129,30 @@ pub mod default {
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
+
+    pub fn gas_price_bump_factor() -> f64 {
+        1.0
+    }
+
+    pub fn remote_signer_timeout() -> Duration {
+        Duration::from_secs(5)
+    }
+
+    pub fn relay_incentivized() -> bool {
+        true
+    }
+
+    pub fn max_policy_deferrals() -> u32 {
+        10
+    }
+
+    pub fn max_event_streams() -> usize {
+        16
+    }
+
+    pub fn preemption_window() -> Duration {
+        Duration::from_secs(60)
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -86,6 +166,8 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub store: StoreConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -241,6 +323,8 @@ impl fmt::Display for LogLevel {
 #[serde(default, deny_unknown_fields)]
 pub struct GlobalConfig {
     pub log_level: LogLevel,
+    /// Whether the transactions are only simulated, never broadcast.
+    pub dry_run: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -261,12 +345,29 @@ impl Default for TelemetryConfig {
     }
 }
 
+/// The store of the relayer state on disk, from which a restarted relayer resumes.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StoreConfig {
+    pub enabled: bool,
+    /// The folder holding the state, `~/.hermes/state` by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RestConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+    /// The token required by all the endpoints, which are
+    /// open to any client when it is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// The maximum number of clients streaming the events at once.
+    #[serde(default = "default::max_event_streams")]
+    pub max_event_streams: usize,
 }
 
 impl Default for RestConfig {
@@ -275,6 +376,8 @@ impl Default for RestConfig {
             enabled: false,
             host: "127.0.0.1".to_string(),
             port: 3000,
+            token: None,
+            max_event_streams: default::max_event_streams(),
         }
     }
 }
@@ -323,6 +426,10 @@ pub struct ChainConfig {
     pub rpc_timeout: Duration,
     pub account_prefix: String,
     pub key_name: String,
+    /// The names of further keys to sign transactions with, along with `key_name`. The batches
+    /// of messages sent to the chain are spread over their accounts in a round-robin fashion.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_names: Vec<String>,
     #[serde(default)]
     pub key_store_type: Store,
     pub store_prefix: String,
@@ -330,6 +437,12 @@ pub struct ChainConfig {
     pub max_gas: Option<u64>,
     pub gas_adjustment: Option<f64>,
     pub fee_granter: Option<String>,
+    /// The amount of the `gas_price` denom that may be spent on fees in a day (UTC). Once it is
+    /// exceeded, new packets are not relayed to the chain until the next day, while their
+    /// acknowledgments and timeouts still are.
+    /// Fees paid in any other denom on the day count as exceeding it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_fee_budget: Option<u64>,
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
     #[serde(default)]
@@ -364,9 +477,71 @@ pub struct ChainConfig {
     pub trust_threshold: TrustThreshold,
     pub gas_price: GasPrice,
     #[serde(default)]
+    pub gas_price_oracle: GasPriceOracleConfig,
+    #[serde(default)]
     pub packet_filter: PacketFilter,
+    /// The rules deciding whether the packets sent over the channels of the chain are worth
+    /// relaying. The first rule matching the channel applies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relay_policy: Vec<RelayPolicyRule>,
+    /// The priority of the packets sent over the channels of the chain, in the queue of the
+    /// messages pending to be relayed.
+    #[serde(default)]
+    pub packet_priority: PacketPriority,
     #[serde(default)]
     pub address_type: AddressType,
+    /// The external signer holding the keys of the chain, when `key_store_type` is `remote`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_signer: Option<RemoteSignerConfig>,
+    /// Where the passphrase of the key store is read from, when `key_store_type` is `encrypted`.
+    #[serde(default)]
+    pub key_store_passphrase: PassphraseSource,
+}
+
+impl ChainConfig {
+    /// Returns the names of the keys signing the transactions sent to the chain: `key_name`,
+    /// followed by the other ones of `key_names`.
+    pub fn signing_key_names(&self) -> Vec<&str> {
+        let mut names = vec![self.key_name.as_str()];
+        for name in &self.key_names {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
+
+/// The connection to an external signer process, such as a tmkms-style service, which holds
+/// the keys of a chain and signs its transactions on behalf of the relayer.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    /// The socket the signer listens on, e.g. `tcp://127.0.0.1:26659`
+    /// or `unix:///run/hermes/signer.sock`.
+    pub address: String,
+    #[serde(default = "default::remote_signer_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+    /// The TLS settings of the connections to the signer, which are required to reach it over
+    /// TCP on another host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<RemoteSignerTlsConfig>,
+}
+
+/// The mutually authenticated TLS settings of the connections to a remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerTlsConfig {
+    /// The PEM file of the certificate authority which issued the certificate of the signer.
+    pub ca_cert: PathBuf,
+    /// The PEM file of the certificate the relayer authenticates itself with to the signer.
+    pub client_cert: PathBuf,
+    /// The PEM file of the private key of `client_cert`.
+    pub client_key: PathBuf,
+    /// The DNS name or IP address the certificate of the signer is issued for. Defaults to the
+    /// host of the address of the signer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -0,0 +1,100 @@
+//! The priority of the packets of a chain's channels, deciding which of the pending packet
+//! messages are relayed first.
+
+use core::time::Duration;
+
+use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+use serde::{Deserialize, Serialize};
+
+use super::default;
+use super::filter::{ChannelFilterMatch, PortFilterMatch};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PacketPriority {
+    /// The pending packets which time out on the counterparty chain within this window are
+    /// relayed ahead of the other messages, the most urgent first. Zero disables preemption.
+    #[serde(default = "default::preemption_window", with = "humantime_serde")]
+    pub preemption_window: Duration,
+    /// The weights of the channels matching the port and channel identifiers, which scale
+    /// their preemption window and their share of the submissions to the counterparty chain
+    /// among its channels with packets pending. The first matching entry applies, and the
+    /// other channels have a weight of 1.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channel_weights: Vec<ChannelWeight>,
+}
+
+impl PacketPriority {
+    /// Returns the weight of the channel, or zero if its configured weight is not a positive
+    /// number.
+    pub fn weight_for(&self, port_id: &PortId, channel_id: &ChannelId) -> f64 {
+        let weight = self
+            .channel_weights
+            .iter()
+            .find(|entry| entry.port_id.matches(port_id) && entry.channel_id.matches(channel_id))
+            .map_or(1.0, |entry| entry.weight);
+
+        // Guard against the weights which would make `mul_f64` panic.
+        if weight.is_finite() && weight > 0.0 {
+            weight
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the preemption window of the channel, scaled by its weight.
+    pub fn preemption_window_for(&self, port_id: &PortId, channel_id: &ChannelId) -> Duration {
+        self.preemption_window
+            .mul_f64(self.weight_for(port_id, channel_id))
+    }
+}
+
+impl Default for PacketPriority {
+    fn default() -> Self {
+        Self {
+            preemption_window: default::preemption_window(),
+            channel_weights: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelWeight {
+    pub port_id: PortFilterMatch,
+    pub channel_id: ChannelFilterMatch,
+    pub weight: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::PacketPriority;
+
+    #[test]
+    fn deserialize_packet_priority() {
+        let toml_content = r#"
+            preemption_window = '1m'
+            channel_weights = [
+                { port_id = 'transfer', channel_id = 'channel-0', weight = 2.5 },
+                { port_id = 'ica*', channel_id = '*', weight = 0.0 },
+            ]
+        "#;
+
+        let priority = toml::from_str::<PacketPriority>(toml_content)
+            .expect("could not parse packet priority");
+
+        let window = |port: &str, channel: &str| {
+            priority.preemption_window_for(&port.parse().unwrap(), &channel.parse().unwrap())
+        };
+
+        assert_eq!(window("transfer", "channel-0"), Duration::from_secs(150));
+        assert_eq!(window("transfer", "channel-1"), Duration::from_secs(60));
+        assert_eq!(window("icahost", "channel-1"), Duration::ZERO);
+        assert_eq!(
+            priority.weight_for(&"transfer".parse().unwrap(), &"channel-0".parse().unwrap()),
+            2.5
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! The rules deciding whether the packets of a channel are worth relaying, given the fees of
+//! relaying them and what they carry.
+
+use std::collections::BTreeMap;
+
+use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+use serde::{Deserialize, Serialize};
+
+use super::default;
+use super::filter::{ChannelFilterMatch, PortFilterMatch};
+
+/// What is done with the packets failing a relay policy rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// The packets are kept in the schedule and evaluated again later, e.g. once the gas price
+    /// has dropped or a fee has been added for relaying them, up to `max_deferrals` times
+    /// before they are dropped.
+    Defer,
+    /// The packets are discarded, until they are picked up again by packet clearing.
+    Drop,
+}
+
+impl Default for PolicyAction {
+    fn default() -> Self {
+        Self::Defer
+    }
+}
+
+/// A relay policy rule for the channels matching its port and channel identifiers, which may
+/// contain wildcards as in the packet filter. The rules are not applied to ordered channels,
+/// whose later packets could never be received after a packet deferred or dropped.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelayPolicyRule {
+    pub port_id: PortFilterMatch,
+    pub channel_id: ChannelFilterMatch,
+    /// The highest fees worth paying to relay a packet, per denom of the fee paid on the chain
+    /// the packet message is sent to. The fees in the other denoms are not limited.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_fees: BTreeMap<String, u64>,
+    /// The lowest amounts worth transferring, per denom, for the ICS20 packets. The packets
+    /// transferring less than these amounts of all their tokens fail the rule.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub min_amounts: BTreeMap<String, u64>,
+    /// Whether the packets with an ICS29 fee for the relayer of the message are relayed even
+    /// if they fail the rule.
+    #[serde(default = "default::relay_incentivized")]
+    pub relay_incentivized: bool,
+    #[serde(default)]
+    pub action: PolicyAction,
+    /// How many times the packets failing the rule are deferred before they are dropped.
+    #[serde(default = "default::max_policy_deferrals")]
+    pub max_deferrals: u32,
+}
+
+impl RelayPolicyRule {
+    pub fn matches(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        self.port_id.matches(port_id) && self.channel_id.matches(channel_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PolicyAction, RelayPolicyRule};
+
+    #[derive(serde::Deserialize)]
+    struct Rules {
+        relay_policy: Vec<RelayPolicyRule>,
+    }
+
+    #[test]
+    fn deserialize_relay_policy() {
+        let toml_content = r#"
+            [[relay_policy]]
+            port_id = 'transfer'
+            channel_id = 'channel-*'
+            max_fees = { stake = 5000 }
+            min_amounts = { uatom = 1000000, 'transfer/channel-0/uosmo' = 50 }
+
+            [[relay_policy]]
+            port_id = '*'
+            channel_id = '*'
+            relay_incentivized = false
+            action = 'drop'
+            max_deferrals = 3
+        "#;
+
+        let rules = toml::from_str::<Rules>(toml_content)
+            .expect("could not parse relay policy")
+            .relay_policy;
+
+        assert!(rules[0].matches(&"transfer".parse().unwrap(), &"channel-5".parse().unwrap()));
+        assert!(!rules[0].matches(&"ica".parse().unwrap(), &"channel-5".parse().unwrap()));
+        assert_eq!(rules[0].max_fees["stake"], 5000);
+        assert_eq!(rules[0].min_amounts["transfer/channel-0/uosmo"], 50);
+        assert!(rules[0].relay_incentivized);
+        assert_eq!(rules[0].action, PolicyAction::Defer);
+        assert_eq!(rules[0].max_deferrals, 10);
+
+        assert!(rules[1].matches(&"ica".parse().unwrap(), &"channel-5".parse().unwrap()));
+        assert!(!rules[1].relay_incentivized);
+        assert_eq!(rules[1].action, PolicyAction::Drop);
+        assert_eq!(rules[1].max_deferrals, 3);
+    }
+}
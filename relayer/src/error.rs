@@ -83,6 +83,34 @@ define_error! {
             { status: GrpcStatus }
             |e| { format!("gRPC call failed with status: {0}", e.status) },
 
+        InvalidGasPrice
+            { reason: String }
+            |e| { format!("invalid gas price queried from the chain: {0}", e.reason) },
+
+        InvalidPacketFee
+            { reason: String }
+            |e| { format!("invalid ICS29 packet fee: {0}", e.reason) },
+
+        FeeLedgerIo
+            { path: String }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("failed to access the fee ledger at {0}", e.path) },
+
+        FeeLedgerDecode
+            { path: String }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to decode the fee ledger at {0}", e.path) },
+
+        StateStoreIo
+            { path: String }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("failed to access the relayer state at {0}", e.path) },
+
+        StateStoreDecode
+            { path: String }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to decode the relayer state at {0}", e.path) },
+
         GrpcTransport
             [ TraceError<TransportError> ]
             |_| { "error in underlying transport when making gRPC call" },
@@ -459,6 +487,16 @@ define_error! {
                     e.chain_id, e.default_gas, e.max_gas)
             },
 
+        ConfigValidationGasPriceOracle
+            {
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("semantic config validation failed for option `gas_price_oracle` of chain '{}', reason: {}",
+                    e.chain_id, e.reason)
+            },
+
         SdkModuleVersion
             {
                 chain_id: ChainId,
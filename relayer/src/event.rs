@@ -1,3 +1,4 @@
 pub mod bus;
 pub mod monitor;
+pub mod relayer;
 pub mod rpc;
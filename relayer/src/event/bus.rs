@@ -25,23 +25,23 @@ impl<T> EventBus<T> {
         rx
     }
 
+    /// Subscribes with a channel holding at most `capacity` values,
+    /// the subscriber being dropped once it lags behind by that many.
+    pub fn subscribe_bounded(&mut self, capacity: usize) -> channel::Receiver<T> {
+        let (tx, rx) = channel::bounded(capacity);
+        self.txs.push_back(tx);
+        rx
+    }
+
     pub fn broadcast(&mut self, value: T)
     where
         T: Clone,
     {
-        let mut disconnected = Vec::new();
-
-        for (idx, tx) in self.txs.iter().enumerate() {
-            // TODO: Avoid cloning when sending to last subscriber
-            if let Err(channel::SendError(_)) = tx.send(value.clone()) {
-                disconnected.push(idx);
-            }
-        }
-
-        // Remove all disconnected subscribers
-        for idx in disconnected {
-            self.txs.remove(idx);
-        }
+        // Remove the disconnected subscribers along the way, since removing them by index
+        // afterwards would shift the indices of the following ones. The bounded subscribers
+        // whose channel is full are removed too, rather than blocking the publisher.
+        // TODO: Avoid cloning when sending to last subscriber
+        self.txs.retain(|tx| tx.try_send(value.clone()).is_ok());
     }
 }
 
@@ -117,4 +117,53 @@ mod tests {
 
         assert_eq!(counter(), 20);
     }
+
+    #[test]
+    #[serial]
+    fn disconnected_subscribers() {
+        reset_counter();
+
+        let mut bus = EventBus::new();
+
+        let rx0 = bus.subscribe();
+        drop(bus.subscribe());
+        drop(bus.subscribe());
+        let rx3 = bus.subscribe();
+
+        bus.broadcast(Value(42));
+        bus.broadcast(Value(113));
+
+        for rx in [rx0, rx3] {
+            assert_eq!(rx.recv(), Ok(Value(42)));
+            assert_eq!(rx.recv(), Ok(Value(113)));
+        }
+
+        assert_eq!(bus.txs.len(), 2);
+    }
+
+    #[test]
+    #[serial]
+    fn lagging_subscribers() {
+        reset_counter();
+
+        let mut bus = EventBus::new();
+
+        let lagging = bus.subscribe_bounded(1);
+        let bounded = bus.subscribe_bounded(1);
+        let unbounded = bus.subscribe();
+
+        bus.broadcast(Value(42));
+        assert_eq!(bounded.recv(), Ok(Value(42)));
+        bus.broadcast(Value(113));
+
+        assert_eq!(bus.txs.len(), 2);
+
+        assert_eq!(lagging.recv(), Ok(Value(42)));
+        assert!(lagging.recv().is_err());
+
+        assert_eq!(bounded.recv(), Ok(Value(113)));
+
+        assert_eq!(unbounded.recv(), Ok(Value(42)));
+        assert_eq!(unbounded.recv(), Ok(Value(113)));
+    }
 }
@@ -0,0 +1,227 @@
+//! The events about what the relayer itself does, e.g. the transactions it submits and
+//! confirms, the clients it updates or the errors its workers run into, as opposed to the
+//! events emitted by the chains. They are published on a global [`EventBus`], which the
+//! REST server streams to its clients.
+
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossbeam_channel as channel;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc::events::IbcEvent;
+use ibc::Height;
+
+use crate::chain::tracking::TrackingId;
+use crate::event::bus::EventBus;
+
+static BUS: Lazy<Mutex<EventBus<RelayerEvent>>> = Lazy::new(|| Mutex::new(EventBus::new()));
+
+/// How many events a subscriber may lag behind by before being unsubscribed,
+/// so that a stalled subscriber does not buffer events indefinitely.
+pub const SUBSCRIBER_CAPACITY: usize = 1000;
+
+/// Publishes `event` to the current subscribers.
+pub fn publish(event: RelayerEvent) {
+    BUS.lock().unwrap().broadcast(event);
+}
+
+/// Subscribes to the events published from now on. The channel is disconnected once
+/// the subscriber lags behind by [`SUBSCRIBER_CAPACITY`] events.
+pub fn subscribe() -> channel::Receiver<RelayerEvent> {
+    BUS.lock().unwrap().subscribe_bounded(SUBSCRIBER_CAPACITY)
+}
+
+/// An event about what the relayer did on a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayerEvent {
+    /// When the event occurred, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The tracking id of the events or messages the event relates to,
+    /// as it appears in the logs.
+    pub tracking_id: String,
+    /// The chain the event occurred on.
+    pub chain_id: ChainId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterparty_chain_id: Option<ChainId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_id: Option<PortId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    #[serde(flatten)]
+    pub kind: RelayerEventKind,
+}
+
+impl RelayerEvent {
+    pub fn new(tracking_id: TrackingId, chain_id: ChainId, kind: RelayerEventKind) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+        Self {
+            timestamp,
+            tracking_id: tracking_id.to_string(),
+            chain_id,
+            counterparty_chain_id: None,
+            port_id: None,
+            channel_id: None,
+            kind,
+        }
+    }
+
+    /// Sets the counterparty chain of the event.
+    pub fn with_counterparty(mut self, counterparty_chain_id: ChainId) -> Self {
+        self.counterparty_chain_id = Some(counterparty_chain_id);
+        self
+    }
+
+    /// Sets the channel end on [`Self::chain_id`] the event relates to.
+    pub fn with_channel(mut self, port_id: PortId, channel_id: ChannelId) -> Self {
+        self.port_id = Some(port_id);
+        self.channel_id = Some(channel_id);
+        self
+    }
+}
+
+/// What the relayer did.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelayerEventKind {
+    /// A transaction was submitted, with messages of the given type URLs.
+    TxSubmitted { messages: Vec<String> },
+    /// The submission of a transaction failed.
+    TxFailed { error: String },
+    /// The transactions were committed, emitting `events`.
+    TxConfirmed {
+        tx_hashes: Vec<String>,
+        events: Vec<IbcEvent>,
+    },
+    /// The transactions were not committed in time, and were given up on.
+    TxTimedOut { tx_hashes: Vec<String> },
+    /// The client was updated to `consensus_height`.
+    ClientUpdated {
+        client_id: ClientId,
+        consensus_height: Option<Height>,
+    },
+    /// A misbehaviour of the chain tracked by the client was detected,
+    /// and the evidence submitted.
+    MisbehaviourDetected {
+        client_id: ClientId,
+        misbehaviour: String,
+    },
+    /// A worker encountered an error, which it retries on unless `fatal`.
+    WorkerError {
+        worker: String,
+        error: String,
+        fatal: bool,
+    },
+}
+
+/// Selects the events of a chain, or of a channel.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayerEventFilter {
+    /// Only the events occurring on this chain,
+    /// or whose counterparty chain it is.
+    pub chain: Option<ChainId>,
+    /// Only the events on this channel end of [`Self::chain`], if set.
+    pub channel: Option<ChannelId>,
+}
+
+impl RelayerEventFilter {
+    pub fn matches(&self, event: &RelayerEvent) -> bool {
+        let chain_matches = self.chain.as_ref().map_or(true, |chain_id| {
+            &event.chain_id == chain_id || event.counterparty_chain_id.as_ref() == Some(chain_id)
+        });
+
+        // The channel identifiers are only unique per chain, so the events occurring on the
+        // counterparty chain relate to another channel end, whatever its identifier.
+        let channel_matches = self.channel.as_ref().map_or(true, |channel_id| {
+            let on_chain = self
+                .chain
+                .as_ref()
+                .map_or(true, |chain_id| &event.chain_id == chain_id);
+
+            on_chain && event.channel_id.as_ref() == Some(channel_id)
+        });
+
+        chain_matches && channel_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId};
+
+    use super::{publish, subscribe, RelayerEvent, RelayerEventFilter, RelayerEventKind};
+    use crate::chain::tracking::TrackingId;
+
+    fn tx_submitted(chain: &str, counterparty: &str, channel: u64) -> RelayerEvent {
+        RelayerEvent::new(
+            TrackingId::new_static("test"),
+            ChainId::from_string(chain),
+            RelayerEventKind::TxSubmitted {
+                messages: vec!["/ibc.core.channel.v1.MsgRecvPacket".to_string()],
+            },
+        )
+        .with_counterparty(ChainId::from_string(counterparty))
+        .with_channel("transfer".parse().unwrap(), ChannelId::new(channel))
+    }
+
+    #[test]
+    fn filter_by_chain_and_channel() {
+        let event = tx_submitted("ibc-0", "ibc-1", 0);
+
+        assert!(RelayerEventFilter::default().matches(&event));
+
+        let by_chain = |chain: &str| RelayerEventFilter {
+            chain: Some(ChainId::from_string(chain)),
+            channel: None,
+        };
+        assert!(by_chain("ibc-0").matches(&event));
+        assert!(by_chain("ibc-1").matches(&event));
+        assert!(!by_chain("ibc-2").matches(&event));
+
+        let by_channel = |chain: &str, channel: u64| RelayerEventFilter {
+            chain: Some(ChainId::from_string(chain)),
+            channel: Some(ChannelId::new(channel)),
+        };
+        assert!(by_channel("ibc-0", 0).matches(&event));
+        assert!(!by_channel("ibc-0", 1).matches(&event));
+        assert!(!by_channel("ibc-2", 0).matches(&event));
+
+        // The channel 0 of the counterparty chain is another channel than that of the event.
+        assert!(!by_channel("ibc-1", 0).matches(&event));
+        let counterparty_event = tx_submitted("ibc-1", "ibc-0", 0);
+        assert!(!by_channel("ibc-0", 0).matches(&counterparty_event));
+        assert!(by_chain("ibc-0").matches(&counterparty_event));
+    }
+
+    #[test]
+    fn serialize_event() {
+        let event = tx_submitted("ibc-0", "ibc-1", 0);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "tx_submitted");
+        assert_eq!(json["tracking_id"], "test");
+        assert_eq!(json["channel_id"], "channel-0");
+
+        let decoded: RelayerEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, event);
+    }
+
+    #[test]
+    fn subscribers_receive_the_published_events() {
+        let rx = subscribe();
+        let event = tx_submitted("bus-0", "bus-1", 0);
+
+        publish(event.clone());
+
+        // Other tests may publish on the global bus concurrently.
+        let received = rx
+            .try_iter()
+            .find(|received| received.chain_id == event.chain_id);
+        assert_eq!(received, Some(event));
+    }
+}
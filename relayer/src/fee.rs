@@ -0,0 +1,98 @@
+//! Registration of the accounts which get paid the fees earned by the relayer on channels
+//! with the ICS29 fee middleware enabled.
+
+use flex_error::define_error;
+use ibc::applications::ics29_fee::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+use ibc::applications::ics29_fee::msgs::register_payee::MsgRegisterPayee;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::events::IbcEvent;
+use ibc::signer::Signer;
+use ibc::tx_msg::Msg;
+use ibc_proto::google::protobuf::Any;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::tracking::TrackedMsgs;
+use crate::error::Error;
+
+define_error! {
+    FeeError {
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| {
+                format!("failed while submitting the payee registration message to chain {0}",
+                    e.chain_id)
+            },
+
+        TxResponse
+            { event: String }
+            |e| {
+                format!("tx response event consists of an error: {}",
+                    e.event)
+            },
+    }
+}
+
+/// Registers `payee` as the account on `chain` which gets paid the acknowledgement and timeout
+/// fees earned by the relayer on the given channel.
+pub fn register_payee<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    payee: Signer,
+) -> Result<Vec<IbcEvent>, FeeError> {
+    let relayer = chain.get_signer().map_err(FeeError::key)?;
+
+    let msg = MsgRegisterPayee {
+        port_id: port_id.clone(),
+        channel_id: *channel_id,
+        relayer,
+        payee,
+    };
+
+    send_fee_message(chain, msg.to_any(), "register-payee")
+}
+
+/// Registers `counterparty_payee` as the account on the counterparty chain of `chain` which
+/// gets paid the receive fees earned by the relayer for the packets it delivers to `chain`
+/// on the given channel.
+pub fn register_counterparty_payee<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty_payee: Signer,
+) -> Result<Vec<IbcEvent>, FeeError> {
+    let relayer = chain.get_signer().map_err(FeeError::key)?;
+
+    let msg = MsgRegisterCounterpartyPayee {
+        port_id: port_id.clone(),
+        channel_id: *channel_id,
+        relayer,
+        counterparty_payee,
+    };
+
+    send_fee_message(chain, msg.to_any(), "register-counterparty-payee")
+}
+
+fn send_fee_message<Chain: ChainHandle>(
+    chain: &Chain,
+    msg: Any,
+    tracking_id: &'static str,
+) -> Result<Vec<IbcEvent>, FeeError> {
+    let events = chain
+        .send_messages_and_wait_commit(TrackedMsgs::new_static(vec![msg], tracking_id))
+        .map_err(|e| FeeError::submit(chain.id(), e))?;
+
+    // Check if the chain rejected the transaction
+    match events.iter().find_map(|event| match event {
+        IbcEvent::ChainError(e) => Some(e),
+        _ => None,
+    }) {
+        None => Ok(events),
+        Some(err) => Err(FeeError::tx_response(err.clone())),
+    }
+}
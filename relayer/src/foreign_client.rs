@@ -13,6 +13,9 @@ use itertools::Itertools;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
 use flex_error::define_error;
+use ibc::clients::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use ibc::clients::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
+use ibc::clients::ics09_localhost::header::Header as LocalhostHeader;
 use ibc::core::ics02_client::client_consensus::{
     AnyConsensusState, AnyConsensusStateWithHeight, ConsensusState, QueryClientEventRequest,
 };
@@ -39,11 +42,12 @@ use crate::chain::client::ClientSettings;
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::{
     HeightQuery, IncludeProof, PageRequest, QueryClientStateRequest, QueryConsensusStateRequest,
-    QueryConsensusStatesRequest, QueryUpgradedClientStateRequest,
+    QueryConsensusStatesRequest, QueryHostConsensusStateRequest, QueryUpgradedClientStateRequest,
     QueryUpgradedConsensusStateRequest,
 };
-use crate::chain::tracking::TrackedMsgs;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
 use crate::error::Error as RelayerError;
+use crate::event::relayer::{self as relayer_event, RelayerEvent, RelayerEventKind};
 
 const MAX_MISBEHAVIOUR_CHECK_DURATION: Duration = Duration::from_secs(120);
 
@@ -260,14 +264,6 @@ define_error! {
                 format_args!("cannot run misbehaviour: {0}", e.reason)
             },
 
-        SameChainId
-            {
-                chain_id: ChainId
-            }
-            |e| {
-                format_args!("the chain ID ({}) at the source and destination chains must be different", e.chain_id)
-            },
-
         MissingClientIdFromEvent
             { event: IbcEvent }
             |e| {
@@ -366,15 +362,13 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
     /// Creates a new foreign client on `dst_chain`. Blocks until the client is created, or
     /// an error occurs.
     /// Post-condition: `dst_chain` hosts an IBC client for `src_chain`.
+    ///
+    /// If both handles refer to the same chain, the created client is an
+    /// ICS 09 localhost client.
     pub fn new(
         dst_chain: DstChain,
         src_chain: SrcChain,
     ) -> Result<ForeignClient<DstChain, SrcChain>, ForeignClientError> {
-        // Sanity check
-        if src_chain.id().eq(&dst_chain.id()) {
-            return Err(ForeignClientError::same_chain_id(src_chain.id()));
-        }
-
         let mut client = ForeignClient {
             id: ClientId::default(),
             dst_chain,
@@ -548,6 +542,26 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         &self.id
     }
 
+    /// Returns `true` if the source and destination chains are the same chain,
+    /// in which case the client is a localhost client.
+    pub fn is_localhost(&self) -> bool {
+        self.src_chain.id() == self.dst_chain.id()
+    }
+
+    /// Queries the timestamp of the source chain's own block at `height`.
+    fn src_host_timestamp(&self, height: Height) -> Result<Timestamp, ForeignClientError> {
+        let consensus_state = self
+            .src_chain
+            .query_host_consensus_state(QueryHostConsensusStateRequest {
+                height: HeightQuery::Specific(height),
+            })
+            .map_err(|e| {
+                ForeignClientError::client_query(self.id.clone(), self.src_chain.id(), e)
+            })?;
+
+        Ok(consensus_state.timestamp())
+    }
+
     /// Lower-level interface for preparing a message to create a client.
     pub fn build_create_client(
         &self,
@@ -574,6 +588,17 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             )
         })?;
 
+        if self.is_localhost() {
+            let client_state = LocalhostClientState::new(self.src_chain.id(), latest_height)
+                .map_err(|e| ForeignClientError::client(e.into()))?
+                .wrap_any();
+            let consensus_state =
+                LocalhostConsensusState::new(self.src_host_timestamp(latest_height)?).wrap_any();
+
+            return MsgCreateAnyClient::new(client_state, consensus_state, signer)
+                .map_err(ForeignClientError::client);
+        }
+
         // Calculate client state settings from the chain configurations and
         // optional user overrides.
         let src_config = self.src_chain.config().map_err(|e| {
@@ -1031,6 +1056,10 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             return Ok(vec![]);
         }
 
+        if let AnyClientState::Localhost(_) = client_state {
+            return self.build_localhost_update(target_height);
+        }
+
         let (header, support) = self
             .src_chain()
             .build_header(trusted_height, target_height, client_state.clone())
@@ -1090,6 +1119,37 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         Ok(msgs)
     }
 
+    /// Builds the update message for a localhost client. The header carries the
+    /// height and timestamp of the chain's own block at `target_height`, which the
+    /// client checks against the host, so no light client verification is needed.
+    fn build_localhost_update(
+        &self,
+        target_height: Height,
+    ) -> Result<Vec<Any>, ForeignClientError> {
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ForeignClientError::client_update(
+                self.dst_chain.id(),
+                "failed getting signer for dst chain".to_string(),
+                e,
+            )
+        })?;
+
+        let header =
+            LocalhostHeader::new(target_height, self.src_host_timestamp(target_height)?).wrap_any();
+
+        debug!(
+            "[{}] MsgUpdateAnyClient for localhost height {}",
+            self, target_height
+        );
+
+        Ok(vec![MsgUpdateAnyClient {
+            header,
+            signer,
+            client_id: self.id.clone(),
+        }
+        .to_any()])
+    }
+
     pub fn build_latest_update_client_and_send(&self) -> Result<Vec<IbcEvent>, ForeignClientError> {
         self.build_update_client_and_send(Height::zero(), Height::zero())
     }
@@ -1121,6 +1181,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
 
         let tm = TrackedMsgs::new_static(new_msgs, "update client");
+        let tracking_id = tm.tracking_id;
 
         let events = self
             .dst_chain()
@@ -1133,6 +1194,19 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 )
             })?;
 
+        let consensus_height = events.iter().rev().find_map(|event| match event {
+            IbcEvent::UpdateClient(update) => Some(update.consensus_height()),
+            _ => None,
+        });
+
+        self.publish_event(
+            tracking_id,
+            RelayerEventKind::ClientUpdated {
+                client_id: self.id.clone(),
+                consensus_height,
+            },
+        );
+
         Ok(events)
     }
 
@@ -1330,6 +1404,12 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 })?
         };
 
+        // A localhost client tracks the chain hosting it, so there is
+        // no counterparty that could misbehave.
+        if let AnyClientState::Localhost(_) = client_state {
+            return Ok(None);
+        }
+
         let consensus_state_heights = if let Some(ref event) = update {
             vec![event.consensus_height()]
         } else {
@@ -1519,6 +1599,14 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                     self, detected.misbehaviour
                 );
 
+                self.publish_event(
+                    TrackingId::new_static("evidence"),
+                    RelayerEventKind::MisbehaviourDetected {
+                        client_id: self.id.clone(),
+                        misbehaviour: detected.misbehaviour.to_string(),
+                    },
+                );
+
                 self.submit_evidence(detected)
             }
         };
@@ -1572,6 +1660,14 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
+    /// Publishes an event occurring on the destination chain, which hosts the client.
+    fn publish_event(&self, tracking_id: TrackingId, kind: RelayerEventKind) {
+        let event = RelayerEvent::new(tracking_id, self.dst_chain.id(), kind)
+            .with_counterparty(self.src_chain.id());
+
+        relayer_event::publish(event);
+    }
+
     pub fn map_chain<DstChain2: ChainHandle, SrcChain2: ChainHandle>(
         self,
         map_dst: impl Fn(DstChain) -> DstChain2,
@@ -8,22 +8,30 @@ use bech32::{ToBase32, Variant};
 use bip39::{Language, Mnemonic, Seed};
 use bitcoin::{
     network::constants::Network,
-    secp256k1::{Message, Secp256k1, SecretKey},
+    secp256k1::{ecdsa::Signature as EcdsaSignature, Message, Secp256k1, SecretKey},
     util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
 };
 use hdpath::StandardHDPath;
 use ibc::core::ics24_host::identifier::ChainId;
-use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use k256::ecdsa::{
+    signature::{Signer, Verifier},
+    Signature, SigningKey, VerifyingKey,
+};
 use ripemd160::Ripemd160;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tiny_keccak::{Hasher, Keccak};
 
+use crate::config::{ChainConfig, RemoteSignerConfig};
+use encrypted::{Encrypted, Passphrase, PassphraseSource, KEYSTORE_ENCRYPTED_BACKEND};
 use errors::Error;
 pub use pub_key::EncodedPubKey;
+use remote::{Remote, RemoteSigner};
 
+pub mod encrypted;
 pub mod errors;
 mod pub_key;
+pub mod remote;
 
 pub type HDPath = StandardHDPath;
 
@@ -41,8 +49,8 @@ pub struct KeyEntry {
     /// Public key
     pub public_key: ExtendedPubKey,
 
-    /// Private key
-    pub private_key: ExtendedPrivKey,
+    /// Private key, absent from the keys held by a remote signer
+    pub private_key: Option<ExtendedPrivKey>,
 
     /// Account Bech32 format - TODO allow hrp
     pub account: String,
@@ -91,7 +99,7 @@ impl KeyEntry {
         } else {
             Ok(Self {
                 public_key: derived_pubkey,
-                private_key,
+                private_key: Some(private_key),
                 account: key_file.address,
                 address: keyfile_address_bytes,
             })
@@ -245,6 +253,11 @@ impl KeyStore for Test {
 pub enum Store {
     Memory,
     Test,
+    /// The key files are encrypted with the passphrase read from the `key_store_passphrase`
+    /// source of the chain.
+    Encrypted,
+    /// The keys are held by the remote signer configured for the chain.
+    Remote,
 }
 
 impl Default for Store {
@@ -257,6 +270,8 @@ impl Default for Store {
 pub enum KeyRing {
     Memory(Memory),
     Test(Test),
+    Encrypted(Encrypted),
+    Remote(Remote),
 }
 
 impl KeyRing {
@@ -265,29 +280,67 @@ impl KeyRing {
             Store::Memory => Ok(Self::Memory(Memory::new(account_prefix.to_string()))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_id.as_str())?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Error::key_file_io(
-                        keys_folder.display().to_string(),
-                        "failed to create keys folder".to_string(),
-                        e,
-                    )
-                })?;
+                let keys_folder = create_disk_store(chain_id.as_str(), KEYSTORE_DISK_BACKEND)?;
 
                 Ok(Self::Test(Test::new(
                     account_prefix.to_string(),
                     keys_folder,
                 )))
             }
+
+            Store::Encrypted => {
+                let passphrase = PassphraseSource::default().read()?;
+                Self::new_encrypted(account_prefix, chain_id, passphrase)
+            }
+
+            Store::Remote => Err(Error::remote_signer_unconfigured()),
+        }
+    }
+
+    /// Creates the key ring of the chain configured with `config`, unlocking its encrypted key
+    /// store or connecting to its remote signer if it is configured with one.
+    pub fn from_config(config: &ChainConfig) -> Result<Self, Error> {
+        match (config.key_store_type, &config.remote_signer) {
+            (Store::Remote, Some(remote)) => {
+                Ok(Self::new_remote(&config.account_prefix, remote.clone()))
+            }
+            (Store::Encrypted, _) => {
+                let passphrase = config.key_store_passphrase.read()?;
+                Self::new_encrypted(&config.account_prefix, &config.id, passphrase)
+            }
+            (store, _) => Self::new(store, &config.account_prefix, &config.id),
         }
     }
 
+    /// Creates a key ring whose key files are encrypted with `passphrase`.
+    pub fn new_encrypted(
+        account_prefix: &str,
+        chain_id: &ChainId,
+        passphrase: Passphrase,
+    ) -> Result<Self, Error> {
+        let keys_folder = create_disk_store(chain_id.as_str(), KEYSTORE_ENCRYPTED_BACKEND)?;
+
+        Ok(Self::Encrypted(Encrypted::new(
+            account_prefix.to_string(),
+            keys_folder,
+            passphrase,
+        )))
+    }
+
+    /// Creates a key ring whose keys are held by the remote signer configured with `config`.
+    pub fn new_remote(account_prefix: &str, config: RemoteSignerConfig) -> Self {
+        Self::Remote(Remote::new(
+            account_prefix.to_string(),
+            RemoteSigner::new(config),
+        ))
+    }
+
     pub fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
         match self {
             KeyRing::Memory(m) => m.get_key(key_name),
             KeyRing::Test(d) => d.get_key(key_name),
+            KeyRing::Encrypted(e) => e.get_key(key_name),
+            KeyRing::Remote(r) => r.get_key(key_name),
         }
     }
 
@@ -295,6 +348,8 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.add_key(key_name, key_entry),
             KeyRing::Test(d) => d.add_key(key_name, key_entry),
+            KeyRing::Encrypted(e) => e.add_key(key_name, key_entry),
+            KeyRing::Remote(r) => r.add_key(key_name, key_entry),
         }
     }
 
@@ -302,6 +357,8 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.remove_key(key_name),
             KeyRing::Test(d) => d.remove_key(key_name),
+            KeyRing::Encrypted(e) => e.remove_key(key_name),
+            KeyRing::Remote(r) => r.remove_key(key_name),
         }
     }
 
@@ -309,6 +366,8 @@ impl KeyRing {
         match self {
             KeyRing::Memory(m) => m.keys(),
             KeyRing::Test(d) => d.keys(),
+            KeyRing::Encrypted(e) => e.keys(),
+            KeyRing::Remote(r) => r.keys(),
         }
     }
 
@@ -345,7 +404,7 @@ impl KeyRing {
 
         Ok(KeyEntry {
             public_key,
-            private_key,
+            private_key: Some(private_key),
             account,
             address,
         })
@@ -360,13 +419,64 @@ impl KeyRing {
     ) -> Result<Vec<u8>, Error> {
         let key = self.get_key(key_name)?;
 
-        sign_message(&key, msg, address_type)
+        self.signer().sign(&key, msg, address_type)
+    }
+
+    /// Re-encrypts the keys of an encrypted key ring with `new_passphrase`, returning the
+    /// number of keys re-encrypted.
+    pub fn rotate_passphrase(&mut self, new_passphrase: Passphrase) -> Result<usize, Error> {
+        match self {
+            KeyRing::Encrypted(e) => e.rotate_passphrase(new_passphrase),
+            _ => Err(Error::unencrypted_key_store()),
+        }
+    }
+
+    /// Returns the signer of the messages signed with the keys of this key ring.
+    pub fn signer(&self) -> KeySigner {
+        match self {
+            KeyRing::Memory(_) | KeyRing::Test(_) | KeyRing::Encrypted(_) => KeySigner::Local,
+            KeyRing::Remote(r) => KeySigner::Remote(r.signer().clone()),
+        }
     }
 
     pub fn account_prefix(&self) -> &str {
         match self {
             KeyRing::Memory(m) => &m.account_prefix,
             KeyRing::Test(d) => &d.account_prefix,
+            KeyRing::Encrypted(e) => &e.account_prefix,
+            KeyRing::Remote(r) => &r.account_prefix,
+        }
+    }
+}
+
+/// Signs messages with key entries, either locally with their private key, or by delegating
+/// to the remote signer holding them.
+#[derive(Clone, Debug)]
+pub enum KeySigner {
+    Local,
+    Remote(RemoteSigner),
+}
+
+impl KeySigner {
+    /// Returns the signer of the keys of a key ring of type `store`, which needs the remote
+    /// signer `remote` to be configured if they are held by one.
+    pub fn new(store: Store, remote: Option<RemoteSignerConfig>) -> Result<Self, Error> {
+        match (store, remote) {
+            (Store::Remote, Some(config)) => Ok(KeySigner::Remote(RemoteSigner::new(config))),
+            (Store::Remote, None) => Err(Error::remote_signer_unconfigured()),
+            _ => Ok(KeySigner::Local),
+        }
+    }
+
+    pub fn sign(
+        &self,
+        key: &KeyEntry,
+        msg: Vec<u8>,
+        address_type: &AddressType,
+    ) -> Result<Vec<u8>, Error> {
+        match self {
+            KeySigner::Local => sign_message(key, msg, address_type),
+            KeySigner::Remote(signer) => signer.sign(key, msg, address_type),
         }
     }
 }
@@ -377,7 +487,12 @@ pub fn sign_message(
     msg: Vec<u8>,
     address_type: &AddressType,
 ) -> Result<Vec<u8>, Error> {
-    let private_key_bytes = key.private_key.to_priv().to_bytes();
+    let private_key_bytes = key
+        .private_key
+        .as_ref()
+        .ok_or_else(Error::missing_private_key)?
+        .to_priv()
+        .to_bytes();
     match address_type {
         AddressType::Ethermint { ref pk_type } if pk_type.ends_with(".ethsecp256k1.PubKey") => {
             let hash = keccak256_hash(msg.as_slice());
@@ -400,6 +515,40 @@ pub fn sign_message(
     }
 }
 
+/// Returns whether `signature` is a signature of `msg` by the key `key`, as made by
+/// [`sign_message`].
+pub fn verify_signature(
+    key: &KeyEntry,
+    msg: &[u8],
+    signature: &[u8],
+    address_type: &AddressType,
+) -> bool {
+    let public_key = key.public_key.public_key;
+    match address_type {
+        AddressType::Ethermint { ref pk_type } if pk_type.ends_with(".ethsecp256k1.PubKey") => {
+            let hash = keccak256_hash(msg);
+            // SAFETY: hash is 32 bytes, as expected in `Message::from_slice` -- see `keccak256_hash`, hence `unwrap`
+            let msg = Message::from_slice(hash.as_slice()).unwrap();
+            EcdsaSignature::from_compact(signature)
+                .map(|signature| {
+                    Secp256k1::verification_only()
+                        .verify_ecdsa(&msg, &signature, &public_key)
+                        .is_ok()
+                })
+                .unwrap_or(false)
+        }
+        AddressType::Cosmos | AddressType::Ethermint { .. } => {
+            match (
+                VerifyingKey::from_sec1_bytes(&public_key.serialize()),
+                Signature::try_from(signature),
+            ) {
+                (Ok(verifying_key), Ok(signature)) => verifying_key.verify(msg, &signature).is_ok(),
+                _ => false,
+            }
+        }
+    }
+}
+
 /// Decode an extended private key from a mnemonic
 fn private_key_from_mnemonic(
     mnemonic_words: &str,
@@ -464,17 +613,33 @@ fn decode_bech32(input: &str) -> Result<Vec<u8>, Error> {
     Ok(bytes)
 }
 
-fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+fn disk_store_path(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
     let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
 
     let folder = Path::new(home.as_path())
         .join(KEYSTORE_DEFAULT_FOLDER)
         .join(folder_name)
-        .join(KEYSTORE_DISK_BACKEND);
+        .join(backend);
 
     Ok(folder)
 }
 
+/// Returns the folder of the `backend` store of the keys of `folder_name`, creating it if it
+/// does not exist.
+fn create_disk_store(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
+    let keys_folder = disk_store_path(folder_name, backend)?;
+
+    fs::create_dir_all(&keys_folder).map_err(|e| {
+        Error::key_file_io(
+            keys_folder.display().to_string(),
+            "failed to create keys folder".to_string(),
+            e,
+        )
+    })?;
+
+    Ok(keys_folder)
+}
+
 fn keccak256_hash(bytes: &[u8]) -> Vec<u8> {
     let mut hasher = Keccak::v256();
     hasher.update(bytes);
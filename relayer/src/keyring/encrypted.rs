@@ -0,0 +1,463 @@
+//! An on-disk key store which encrypts the key entries with a key derived from a passphrase.
+//!
+//! Each key is stored in its own file, holding the JSON-encoded [`KeyEntry`] sealed with
+//! XChaCha20-Poly1305 under a key derived from the passphrase with scrypt. The salt and the
+//! nonce are drawn anew every time a key file is written. Key files are only readable by their
+//! owner, and the passphrase, the derived key and the plaintext key entries are wiped from
+//! memory once used.
+
+use core::fmt;
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use once_cell::sync::Lazy;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::errors::Error;
+use super::{KeyEntry, KeyStore};
+
+pub const KEYSTORE_ENCRYPTED_BACKEND: &str = "keyring-encrypted";
+pub const KEYSTORE_ENCRYPTED_FILE_EXTENSION: &str = "enc";
+
+/// The environment variable the passphrase is read from, unless configured otherwise.
+pub const KEYSTORE_PASSPHRASE_ENV_VAR: &str = "HERMES_KEYSTORE_PASSPHRASE";
+
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+
+/// The passphrase the key files of an encrypted store are sealed with, which is wiped from
+/// memory when dropped.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Passphrase(String);
+
+impl Drop for Passphrase {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Passphrase {
+    pub fn new(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
+
+/// Where the passphrase of the encrypted key store of a chain is read from when it gets
+/// unlocked, as configured with `key_store_passphrase`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum PassphraseSource {
+    /// The environment variable `var`.
+    Env {
+        #[serde(default = "default_passphrase_var")]
+        var: String,
+    },
+    /// The file at `path`, without its trailing newline.
+    File { path: PathBuf },
+    /// A prompt on the terminal, which is shown once per process.
+    Prompt,
+}
+
+fn default_passphrase_var() -> String {
+    KEYSTORE_PASSPHRASE_ENV_VAR.to_string()
+}
+
+impl Default for PassphraseSource {
+    fn default() -> Self {
+        PassphraseSource::Env {
+            var: default_passphrase_var(),
+        }
+    }
+}
+
+/// The passphrase entered at the prompt, shared by all the key stores unlocked with one.
+static PROMPTED_PASSPHRASE: Lazy<Mutex<Option<Passphrase>>> = Lazy::new(|| Mutex::new(None));
+
+impl PassphraseSource {
+    /// Reads the passphrase from this source.
+    pub fn read(&self) -> Result<Passphrase, Error> {
+        match self {
+            PassphraseSource::Env { var } => {
+                std::env::var(var).map(Passphrase::new).map_err(|_| {
+                    Error::passphrase_unavailable(format!("environment variable {}", var))
+                })
+            }
+
+            PassphraseSource::File { path } => {
+                let mut passphrase = fs::read_to_string(path).map_err(|e| {
+                    Error::key_file_io(
+                        path.display().to_string(),
+                        "failed to read passphrase file".to_string(),
+                        e,
+                    )
+                })?;
+                // Truncated in place, so that no copy of the passphrase is left behind.
+                let len = passphrase.trim_end_matches(&['\r', '\n'][..]).len();
+                passphrase.truncate(len);
+                Ok(Passphrase::new(passphrase))
+            }
+
+            PassphraseSource::Prompt => {
+                let mut prompted = PROMPTED_PASSPHRASE
+                    .lock()
+                    .expect("poisoned passphrase prompt lock");
+                if let Some(passphrase) = prompted.as_ref() {
+                    return Ok(passphrase.clone());
+                }
+
+                let passphrase = prompt_passphrase("Key store passphrase", false)?;
+                *prompted = Some(passphrase.clone());
+                Ok(passphrase)
+            }
+        }
+    }
+}
+
+/// Prompts for a passphrase on the terminal, without echoing it. A new passphrase is entered
+/// twice to guard against typos.
+pub fn prompt_passphrase(prompt: &str, confirm: bool) -> Result<Passphrase, Error> {
+    let mut input = dialoguer::Password::new();
+    input.with_prompt(prompt);
+    if confirm {
+        input.with_confirmation("Repeat the passphrase", "The passphrases don't match");
+    }
+
+    input
+        .interact()
+        .map(Passphrase::new)
+        .map_err(|e| Error::passphrase_unavailable(format!("prompt ({})", e)))
+}
+
+/// The scrypt parameters of the derivation of the key a key file is sealed with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+    /// The hex-encoded salt
+    salt: String,
+}
+
+/// The contents of an encrypted key file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct EncryptedKeyFile {
+    kdf: KdfParams,
+    /// The hex-encoded XChaCha20 nonce
+    nonce: String,
+    /// The hex-encoded sealed `KeyEntry`
+    ciphertext: String,
+}
+
+fn derive_key(passphrase: &Passphrase, kdf: &KdfParams) -> Result<Zeroizing<[u8; KEY_LEN]>, Error> {
+    let salt = hex::decode(&kdf.salt).map_err(|_| Error::key_file_corrupted("salt".into()))?;
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p)
+        .map_err(|_| Error::key_file_corrupted("scrypt parameters".into()))?;
+
+    let mut key = Zeroizing::new([0; KEY_LEN]);
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut())
+        .expect("the key length is valid for scrypt");
+
+    Ok(key)
+}
+
+fn seal(key_entry: &KeyEntry, passphrase: &Passphrase) -> Result<EncryptedKeyFile, Error> {
+    let mut salt = [0; SALT_LEN];
+    let mut nonce = [0; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let kdf = KdfParams {
+        log_n: SCRYPT_LOG_N,
+        r: SCRYPT_R,
+        p: SCRYPT_P,
+        salt: hex::encode(salt),
+    };
+    let key = derive_key(passphrase, &kdf)?;
+
+    let plaintext = Zeroizing::new(serde_json::to_vec(key_entry).map_err(Error::encode)?);
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+        .expect("encryption with XChaCha20-Poly1305 is infallible");
+
+    Ok(EncryptedKeyFile {
+        kdf,
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn open(
+    file: &EncryptedKeyFile,
+    passphrase: &Passphrase,
+    file_path: &str,
+) -> Result<KeyEntry, Error> {
+    let key = derive_key(passphrase, &file.kdf)?;
+    let nonce = hex::decode(&file.nonce)
+        .ok()
+        .filter(|nonce| nonce.len() == NONCE_LEN)
+        .ok_or_else(|| Error::key_file_corrupted("nonce".into()))?;
+    let ciphertext = hex::decode(&file.ciphertext)
+        .map_err(|_| Error::key_file_corrupted("ciphertext".into()))?;
+
+    let plaintext = XChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map(Zeroizing::new)
+        .map_err(|_| Error::wrong_passphrase(file_path.to_string()))?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| Error::key_file_decode(file_path.to_string(), e))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Encrypted {
+    pub(super) account_prefix: String,
+    store: PathBuf,
+    #[serde(skip)]
+    passphrase: Passphrase,
+}
+
+impl Encrypted {
+    pub fn new(account_prefix: String, store: PathBuf, passphrase: Passphrase) -> Self {
+        Self {
+            account_prefix,
+            store,
+            passphrase,
+        }
+    }
+
+    fn key_file(&self, key_name: &str) -> PathBuf {
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_ENCRYPTED_FILE_EXTENSION);
+        key_file
+    }
+
+    /// Seals `key_entry` into the file at `path`, which is replaced whole or not at all.
+    fn write_key_file(&self, path: &Path, key_entry: &KeyEntry) -> Result<(), Error> {
+        let tmp_path = write_tmp_key_file(path, key_entry, &self.passphrase)?;
+        replace_key_file(&tmp_path, path)
+    }
+
+    /// Re-encrypts all the keys of the store with the passphrase `new_passphrase`. All the keys
+    /// are decrypted, then sealed into temporary files, before any key file gets replaced, so
+    /// that a wrong current passphrase or a failed write leaves the store untouched.
+    pub fn rotate_passphrase(&mut self, new_passphrase: Passphrase) -> Result<usize, Error> {
+        let keys = self.keys()?;
+
+        let mut replacements = Vec::with_capacity(keys.len());
+        for (name, key_entry) in &keys {
+            let key_file = self.key_file(name);
+            match write_tmp_key_file(&key_file, key_entry, &new_passphrase) {
+                Ok(tmp_path) => replacements.push((tmp_path, key_file)),
+                Err(e) => {
+                    for (tmp_path, _) in replacements {
+                        let _ = fs::remove_file(tmp_path);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        for (tmp_path, key_file) in &replacements {
+            replace_key_file(tmp_path, key_file)?;
+        }
+        self.passphrase = new_passphrase;
+
+        Ok(keys.len())
+    }
+}
+
+/// Seals `key_entry` with `passphrase` into a new temporary file next to `path`, which only its
+/// owner may read, and returns the path of the temporary file.
+fn write_tmp_key_file(
+    path: &Path,
+    key_entry: &KeyEntry,
+    passphrase: &Passphrase,
+) -> Result<PathBuf, Error> {
+    let file_path = path.display().to_string();
+    let sealed = seal(key_entry, passphrase)?;
+    let contents = serde_json::to_vec_pretty(&sealed)
+        .map_err(|e| Error::key_file_encode(file_path.clone(), e))?;
+
+    // A file left over by an interrupted write is removed, since the mode only applies to the
+    // files created here.
+    let tmp_path = path.with_extension("tmp");
+    match fs::remove_file(&tmp_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            return Err(Error::key_file_io(
+                file_path,
+                "failed to remove stale temporary file".to_string(),
+                e,
+            ))
+        }
+        _ => {}
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(&contents)?;
+            file.sync_all()
+        })
+        .map_err(|e| Error::key_file_io(file_path, "failed to write file".to_string(), e))?;
+
+    Ok(tmp_path)
+}
+
+fn replace_key_file(tmp_path: &Path, path: &Path) -> Result<(), Error> {
+    fs::rename(tmp_path, path).map_err(|e| {
+        Error::key_file_io(
+            path.display().to_string(),
+            "failed to replace file".to_string(),
+            e,
+        )
+    })
+}
+
+impl KeyStore for Encrypted {
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        let key_file = self.key_file(key_name);
+        let file_path = key_file.display().to_string();
+
+        if !key_file.as_path().exists() {
+            return Err(Error::key_file_not_found(file_path));
+        }
+
+        let contents = fs::read(&key_file).map_err(|e| {
+            Error::key_file_io(file_path.clone(), "failed to open file".to_string(), e)
+        })?;
+        let sealed = serde_json::from_slice(&contents)
+            .map_err(|e| Error::key_file_decode(file_path.clone(), e))?;
+
+        open(&sealed, &self.passphrase, &file_path)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+        if key_file.exists() {
+            return Err(Error::key_already_exist());
+        }
+
+        self.write_key_file(&key_file, &key_entry)
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+
+        fs::remove_file(&key_file)
+            .map_err(|e| Error::remove_io_fail(key_file.display().to_string(), e))
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        let dir = fs::read_dir(&self.store).map_err(|e| {
+            Error::key_file_io(
+                self.store.display().to_string(),
+                "failed to list keys".to_string(),
+                e,
+            )
+        })?;
+
+        let ext = OsStr::new(KEYSTORE_ENCRYPTED_FILE_EXTENSION);
+
+        dir.into_iter()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(ext))
+            .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+            .flat_map(|stem| stem.to_str().map(ToString::to_string))
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+    use std::env;
+
+    use test_log::test;
+
+    use super::{Encrypted, Passphrase, KEYSTORE_ENCRYPTED_FILE_EXTENSION};
+    use crate::config::AddressType;
+    use crate::keyring::errors::ErrorDetail;
+    use crate::keyring::{HDPath, KeyRing, KeyStore, Memory};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn encrypted_store_and_passphrase_rotation() {
+        let store = env::temp_dir().join(format!("hermes-encrypted-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&store).unwrap();
+
+        let hd_path = HDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let local = KeyRing::Memory(Memory::new("cosmos".to_string()));
+        let key = local
+            .key_from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos)
+            .unwrap();
+
+        let old = Passphrase::new("correct horse".to_string());
+        let mut encrypted = Encrypted::new("cosmos".to_string(), store.clone(), old.clone());
+        encrypted.add_key("relayer", key.clone()).unwrap();
+        assert_eq!(encrypted.get_key("relayer").unwrap(), key);
+
+        // The key file doesn't hold the key entry in the clear.
+        let mut key_file = store.join("relayer");
+        key_file.set_extension(KEYSTORE_ENCRYPTED_FILE_EXTENSION);
+        let contents = std::fs::read_to_string(&key_file).unwrap();
+        assert!(!contents.contains(&key.account));
+
+        // Only the owner may read it.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(&key_file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let wrong = Encrypted::new(
+            "cosmos".to_string(),
+            store.clone(),
+            Passphrase::new("battery staple".to_string()),
+        );
+        let err = wrong.get_key("relayer").unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::WrongPassphrase(_)));
+
+        // After a rotation, only the new passphrase unlocks the store.
+        let new = Passphrase::new("battery staple".to_string());
+        assert_eq!(encrypted.rotate_passphrase(new).unwrap(), 1);
+        assert_eq!(wrong.keys().unwrap(), vec![("relayer".to_string(), key)]);
+        let stale = Encrypted::new("cosmos".to_string(), store.clone(), old);
+        assert!(stale.get_key("relayer").is_err());
+        assert_eq!(std::fs::read_dir(&store).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(store).unwrap();
+    }
+}
@@ -112,5 +112,84 @@ define_error! {
             |e| {
                 format!("invalid HD path: {0}", e.path)
             },
+
+        MissingPrivateKey
+            |_| { "the key entry holds no private key, messages must be signed by the remote signer holding it" },
+
+        RemoteSignerUnconfigured
+            |_| { "the `Remote` key store requires the `remote_signer` of the chain to be configured" },
+
+        RemoteSignerUnsupported
+            { operation: String }
+            |e| {
+                format!("cannot {} with a remote signer, its keys are managed by the signer itself",
+                    e.operation)
+            },
+
+        RemoteSignerIo
+            { address: String }
+            [ TraceError<IoError> ]
+            |e| {
+                format!("I/O error communicating with the remote signer at '{}'",
+                    e.address)
+            },
+
+        RemoteSignerProtocol
+            { address: String, reason: String }
+            |e| {
+                format!("invalid message exchanged with the remote signer at '{}': {}",
+                    e.address, e.reason)
+            },
+
+        PassphraseUnavailable
+            { origin: String }
+            |e| {
+                format!("cannot read the key store passphrase from the {}",
+                    e.origin)
+            },
+
+        WrongPassphrase
+            { file_path: String }
+            |e| {
+                format!("cannot decrypt key file at '{}', the passphrase is wrong or the file was tampered with",
+                    e.file_path)
+            },
+
+        UnencryptedKeyStore
+            |_| { "the key store is not encrypted" },
+
+        KeyFileCorrupted
+            { field: String }
+            |e| {
+                format!("invalid {} in encrypted key file", e.field)
+            },
+
+        RemoteSignerFailure
+            { address: String, reason: String }
+            |e| {
+                format!("the remote signer at '{}' failed: {}",
+                    e.address, e.reason)
+            },
+
+        RemoteSignerInsecure
+            { address: String }
+            |e| {
+                format!("the remote signer at '{}' is on another host, which requires its `tls` settings to be configured",
+                    e.address)
+            },
+
+        RemoteSignerTls
+            { address: String, reason: String }
+            |e| {
+                format!("failed to set up TLS with the remote signer at '{}': {}",
+                    e.address, e.reason)
+            },
+
+        RemoteSignerBadSignature
+            { address: String, account: String }
+            |e| {
+                format!("the remote signer at '{}' returned a signature which does not match the public key of account '{}'",
+                    e.address, e.account)
+            },
     }
 }
@@ -0,0 +1,577 @@
+//! Delegation of the signing of messages to an external signer process, such as a tmkms-style
+//! service, so that the relayer never holds the private keys of the chains it relays for.
+//!
+//! The relayer and the signer exchange [`Request`]s and [`Response`]s as JSON objects, one per
+//! line, over a TCP or Unix socket. A connection is opened for each request. Signers on
+//! another host are only reached over mutually authenticated TLS, since the requests would
+//! otherwise travel in the clear to a signer anyone could impersonate. Each signature returned
+//! is checked against the public key of the account it was requested for.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+
+use bitcoin::{
+    network::constants::Network,
+    secp256k1::PublicKey,
+    util::bip32::{ChainCode, ChildNumber, ExtendedPubKey},
+};
+use rustls::{
+    Certificate, ClientConfig, ClientConnection, PrivateKey, RootCertStore, ServerName, StreamOwned,
+};
+use rustls_pemfile::Item;
+use serde::{Deserialize, Serialize};
+
+use super::errors::Error;
+use super::{sign_message, verify_signature, KeyEntry, KeyStore, Memory};
+use crate::config::{default, AddressType, RemoteSignerConfig, RemoteSignerTlsConfig};
+
+/// A request to the remote signer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Request {
+    /// Returns the key named `key_name`.
+    GetKey { key_name: String },
+    /// Returns all the keys held by the signer, along with their names.
+    ListKeys,
+    /// Signs the hex-encoded message `msg` with the key of the account `account`.
+    Sign {
+        account: String,
+        address_type: AddressType,
+        msg: String,
+    },
+}
+
+/// A response of the remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Response {
+    Key {
+        key: RemoteKey,
+    },
+    Keys {
+        keys: Vec<(String, RemoteKey)>,
+    },
+    /// The hex-encoded signature of a message.
+    Signature {
+        signature: String,
+    },
+    Error {
+        reason: String,
+    },
+}
+
+/// The public part of a key held by the remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteKey {
+    /// The hex-encoded compressed secp256k1 public key
+    pub public_key: String,
+    /// The Bech32-encoded account
+    pub account: String,
+    /// The hex-encoded address
+    pub address: String,
+}
+
+impl From<&KeyEntry> for RemoteKey {
+    fn from(key: &KeyEntry) -> Self {
+        Self {
+            public_key: hex::encode(key.public_key.to_pub().to_bytes()),
+            account: key.account.clone(),
+            address: hex::encode(&key.address),
+        }
+    }
+}
+
+impl RemoteKey {
+    /// Converts the key into a `KeyEntry` without a private key. The signer only exposes the
+    /// public key, so the extended public key is built with an empty derivation state.
+    fn into_key_entry(self, signer_address: &str) -> Result<KeyEntry, Error> {
+        let invalid = |reason: String| Error::remote_signer_protocol(signer_address.into(), reason);
+
+        let public_key = hex::decode(&self.public_key)
+            .map_err(|e| invalid(format!("invalid public key: {}", e)))?;
+        let public_key = PublicKey::from_slice(&public_key).map_err(Error::invalid_key_raw)?;
+        let address =
+            hex::decode(&self.address).map_err(|e| invalid(format!("invalid address: {}", e)))?;
+
+        Ok(KeyEntry {
+            public_key: ExtendedPubKey {
+                network: Network::Bitcoin,
+                depth: 0,
+                parent_fingerprint: Default::default(),
+                child_number: ChildNumber::Normal { index: 0 },
+                public_key,
+                chain_code: ChainCode::from(&[0; 32][..]),
+            },
+            private_key: None,
+            account: self.account,
+            address,
+        })
+    }
+}
+
+/// The client of a remote signer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteSigner {
+    config: RemoteSignerConfig,
+}
+
+impl RemoteSigner {
+    pub fn new(config: RemoteSignerConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        let request = Request::GetKey {
+            key_name: key_name.to_string(),
+        };
+        match self.request(&request)? {
+            Response::Key { key } => key.into_key_entry(&self.config.address),
+            response => Err(self.unexpected(response)),
+        }
+    }
+
+    pub fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        match self.request(&Request::ListKeys)? {
+            Response::Keys { keys } => keys
+                .into_iter()
+                .map(|(name, key)| Ok((name, key.into_key_entry(&self.config.address)?)))
+                .collect(),
+            response => Err(self.unexpected(response)),
+        }
+    }
+
+    /// Signs `msg` with the key `key`, and checks the signature against its public key.
+    pub fn sign(
+        &self,
+        key: &KeyEntry,
+        msg: Vec<u8>,
+        address_type: &AddressType,
+    ) -> Result<Vec<u8>, Error> {
+        let request = Request::Sign {
+            account: key.account.clone(),
+            address_type: address_type.clone(),
+            msg: hex::encode(&msg),
+        };
+        let signature = match self.request(&request)? {
+            Response::Signature { signature } => hex::decode(&signature).map_err(|e| {
+                Error::remote_signer_protocol(
+                    self.config.address.clone(),
+                    format!("invalid signature: {}", e),
+                )
+            })?,
+            response => return Err(self.unexpected(response)),
+        };
+
+        if !verify_signature(key, &msg, &signature, address_type) {
+            return Err(Error::remote_signer_bad_signature(
+                self.config.address.clone(),
+                key.account.clone(),
+            ));
+        }
+        Ok(signature)
+    }
+
+    fn request(&self, request: &Request) -> Result<Response, Error> {
+        let address = &self.config.address;
+        let io_error = |e| Error::remote_signer_io(address.clone(), e);
+
+        let response = match address.strip_prefix("unix://") {
+            Some(path) => exchange_unix(path, &self.config, request).map_err(io_error)?,
+            None => self.exchange_tcp(request)?,
+        };
+
+        match serde_json::from_str::<Response>(&response) {
+            Ok(Response::Error { reason }) => {
+                Err(Error::remote_signer_failure(address.clone(), reason))
+            }
+            Ok(response) => Ok(response),
+            Err(e) => Err(Error::remote_signer_protocol(
+                address.clone(),
+                e.to_string(),
+            )),
+        }
+    }
+
+    /// Sends `request` to the signer over TCP, with TLS if it is configured. Without TLS, only
+    /// signers on this host are reached.
+    fn exchange_tcp(&self, request: &Request) -> Result<String, Error> {
+        let address = &self.config.address;
+        let io_error = |e| Error::remote_signer_io(address.clone(), e);
+
+        let host_port = address.strip_prefix("tcp://").unwrap_or(address);
+        let socket_address = host_port
+            .to_socket_addrs()
+            .map_err(io_error)?
+            .next()
+            .ok_or_else(|| {
+                io_error(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "the address resolves to no socket address",
+                ))
+            })?;
+
+        match &self.config.tls {
+            Some(tls) => {
+                let tls_error = |reason| Error::remote_signer_tls(address.clone(), reason);
+                let server_name = match &tls.server_name {
+                    Some(server_name) => server_name.as_str(),
+                    None => host_port
+                        .rsplit_once(':')
+                        .map_or(host_port, |(host, _)| host)
+                        .trim_start_matches('[')
+                        .trim_end_matches(']'),
+                };
+                let server_name = ServerName::try_from(server_name)
+                    .map_err(|_| tls_error(format!("invalid server name '{}'", server_name)))?;
+                let tls_config = tls_client_config(tls).map_err(tls_error)?;
+                let connection = ClientConnection::new(Arc::new(tls_config), server_name)
+                    .map_err(|e| tls_error(e.to_string()))?;
+
+                let stream = connect_tcp(&socket_address, &self.config).map_err(io_error)?;
+                exchange(StreamOwned::new(connection, stream), request).map_err(io_error)
+            }
+            None if socket_address.ip().is_loopback() => {
+                let stream = connect_tcp(&socket_address, &self.config).map_err(io_error)?;
+                exchange(stream, request).map_err(io_error)
+            }
+            None => Err(Error::remote_signer_insecure(address.clone())),
+        }
+    }
+
+    fn unexpected(&self, response: Response) -> Error {
+        Error::remote_signer_protocol(
+            self.config.address.clone(),
+            format!("unexpected response: {:?}", response),
+        )
+    }
+}
+
+fn connect_tcp(address: &SocketAddr, config: &RemoteSignerConfig) -> io::Result<TcpStream> {
+    let stream = TcpStream::connect_timeout(address, config.timeout)?;
+    stream.set_read_timeout(Some(config.timeout))?;
+    Ok(stream)
+}
+
+/// Returns the configuration of the TLS client authenticating the signer with the certificate
+/// authority of `tls`, and authenticating the relayer to it with the client certificate of `tls`.
+fn tls_client_config(tls: &RemoteSignerTlsConfig) -> Result<ClientConfig, String> {
+    let mut roots = RootCertStore::empty();
+    for certificate in read_certificates(&tls.ca_cert)? {
+        roots
+            .add(&certificate)
+            .map_err(|e| format!("invalid certificate in '{}': {}", tls.ca_cert.display(), e))?;
+    }
+
+    let certificates = read_certificates(&tls.client_cert)?;
+    let private_key = read_pem(&tls.client_key)?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| format!("no private key in '{}'", tls.client_key.display()))?;
+
+    ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_single_cert(certificates, private_key)
+        .map_err(|e| e.to_string())
+}
+
+fn read_pem(path: &Path) -> Result<Vec<Item>, String> {
+    File::open(path)
+        .and_then(|file| rustls_pemfile::read_all(&mut BufReader::new(file)))
+        .map_err(|e| format!("failed to read '{}': {}", path.display(), e))
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<Certificate>, String> {
+    let certificates: Vec<_> = read_pem(path)?
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::X509Certificate(certificate) => Some(Certificate(certificate)),
+            _ => None,
+        })
+        .collect();
+    if certificates.is_empty() {
+        return Err(format!("no certificate in '{}'", path.display()));
+    }
+    Ok(certificates)
+}
+
+#[cfg(unix)]
+fn exchange_unix(path: &str, config: &RemoteSignerConfig, request: &Request) -> io::Result<String> {
+    let stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(config.timeout))?;
+    exchange(stream, request)
+}
+
+#[cfg(not(unix))]
+fn exchange_unix(
+    _path: &str,
+    _config: &RemoteSignerConfig,
+    _request: &Request,
+) -> io::Result<String> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Unix sockets are not supported on this platform",
+    ))
+}
+
+/// Sends `request` over `stream` and returns the line of the response.
+fn exchange<S: Read + Write>(mut stream: S, request: &Request) -> io::Result<String> {
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    Ok(response)
+}
+
+/// The key store of a chain whose keys are held by a remote signer. Keys can't be added or
+/// removed through the relayer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Remote {
+    pub(super) account_prefix: String,
+    pub(super) signer: RemoteSigner,
+}
+
+impl Remote {
+    pub fn new(account_prefix: String, signer: RemoteSigner) -> Self {
+        Self {
+            account_prefix,
+            signer,
+        }
+    }
+
+    pub fn signer(&self) -> &RemoteSigner {
+        &self.signer
+    }
+}
+
+impl KeyStore for Remote {
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        self.signer.get_key(key_name)
+    }
+
+    fn add_key(&mut self, _key_name: &str, _key_entry: KeyEntry) -> Result<(), Error> {
+        Err(Error::remote_signer_unsupported("add a key".to_string()))
+    }
+
+    fn remove_key(&mut self, _key_name: &str) -> Result<(), Error> {
+        Err(Error::remote_signer_unsupported("remove a key".to_string()))
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        self.signer.keys()
+    }
+}
+
+/// A stand-in for a remote signer, which serves the keys of an in-memory key store over a
+/// local TCP socket. It holds the private keys like any local key store, so it is only meant
+/// for testing.
+pub struct LocalSigner {
+    address: SocketAddr,
+}
+
+impl LocalSigner {
+    /// Starts serving the keys of `keys` on a background thread, which lives as long as the
+    /// process.
+    pub fn spawn(keys: Memory) -> Result<Self, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| Error::remote_signer_io("127.0.0.1:0".to_string(), e))?;
+        let address = listener
+            .local_addr()
+            .map_err(|e| Error::remote_signer_io("127.0.0.1:0".to_string(), e))?;
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // A failed connection only affects the request it carried.
+                let _ = serve(&keys, stream);
+            }
+        });
+
+        Ok(Self { address })
+    }
+
+    /// Returns the configuration of a client of this signer.
+    pub fn config(&self) -> RemoteSignerConfig {
+        RemoteSignerConfig {
+            address: format!("tcp://{}", self.address),
+            timeout: default::remote_signer_timeout(),
+            tls: None,
+        }
+    }
+}
+
+fn serve(keys: &Memory, stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => handle(keys, request),
+            Err(e) => Response::Error {
+                reason: e.to_string(),
+            },
+        };
+        let mut response = serde_json::to_string(&response)?;
+        response.push('\n');
+        writer.write_all(response.as_bytes())?;
+        line.clear();
+    }
+
+    Ok(())
+}
+
+fn handle(keys: &Memory, request: Request) -> Response {
+    let response = match request {
+        Request::GetKey { key_name } => keys.get_key(&key_name).map(|key| Response::Key {
+            key: RemoteKey::from(&key),
+        }),
+        Request::ListKeys => keys.keys().map(|keys| Response::Keys {
+            keys: keys
+                .iter()
+                .map(|(name, key)| (name.clone(), RemoteKey::from(key)))
+                .collect(),
+        }),
+        Request::Sign {
+            account,
+            address_type,
+            msg,
+        } => {
+            let msg = match hex::decode(&msg) {
+                Ok(msg) => msg,
+                Err(e) => {
+                    return Response::Error {
+                        reason: format!("invalid message: {}", e),
+                    }
+                }
+            };
+            keys.keys()
+                .and_then(|keys| {
+                    keys.into_iter()
+                        .map(|(_, key)| key)
+                        .find(|key| key.account == account)
+                        .ok_or_else(Error::key_not_found)
+                })
+                .and_then(|key| sign_message(&key, msg, &address_type))
+                .map(|signature| Response::Signature {
+                    signature: hex::encode(signature),
+                })
+        }
+    };
+
+    response.unwrap_or_else(|e| Response::Error {
+        reason: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use test_log::test;
+
+    use super::*;
+    use crate::keyring::errors::ErrorDetail;
+    use crate::keyring::{HDPath, KeyRing};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn sign_with_remote_signer() {
+        let hd_path = HDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let local = KeyRing::Memory(Memory::new("cosmos".to_string()));
+        let key = local
+            .key_from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos)
+            .unwrap();
+
+        let mut keys = Memory::new("cosmos".to_string());
+        keys.add_key("relayer", key.clone()).unwrap();
+        let signer = LocalSigner::spawn(keys).unwrap();
+        let mut remote = KeyRing::new_remote("cosmos", signer.config());
+
+        // Only the public part of the key is exposed by the signer.
+        let remote_key = remote.get_key("relayer").unwrap();
+        assert_eq!(remote_key.account, key.account);
+        assert_eq!(remote_key.address, key.address);
+        assert_eq!(remote_key.public_key.to_pub(), key.public_key.to_pub());
+        assert_eq!(remote_key.private_key, None);
+        assert_eq!(remote.keys().unwrap().len(), 1);
+
+        let msg = b"sign doc".to_vec();
+        assert_eq!(
+            remote
+                .sign_msg("relayer", msg.clone(), &AddressType::Cosmos)
+                .unwrap(),
+            sign_message(&key, msg.clone(), &AddressType::Cosmos).unwrap()
+        );
+        assert!(sign_message(&remote_key, msg, &AddressType::Cosmos).is_err());
+
+        assert!(remote.get_key("unknown").is_err());
+        assert!(remote.add_key("other", key).is_err());
+    }
+
+    #[test]
+    fn reject_forged_signatures() {
+        let hd_path = HDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let other_hd_path = HDPath::from_str("m/44'/118'/0'/0/1").unwrap();
+        let local = KeyRing::Memory(Memory::new("cosmos".to_string()));
+        let key = local
+            .key_from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos)
+            .unwrap();
+
+        let mut keys = Memory::new("cosmos".to_string());
+        keys.add_key("relayer", key.clone()).unwrap();
+        let signer = RemoteSigner::new(LocalSigner::spawn(keys).unwrap().config());
+
+        // The signer signs with the key of the account, which is not the key expected here.
+        let mut other_key = local
+            .key_from_mnemonic(MNEMONIC, &other_hd_path, &AddressType::Cosmos)
+            .unwrap();
+        other_key.account = key.account.clone();
+
+        let msg = b"sign doc".to_vec();
+        assert!(signer.sign(&key, msg.clone(), &AddressType::Cosmos).is_ok());
+        let err = signer
+            .sign(&other_key, msg, &AddressType::Cosmos)
+            .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::RemoteSignerBadSignature(_)
+        ));
+    }
+
+    #[test]
+    fn reject_plain_tcp_to_other_hosts() {
+        let signer = RemoteSigner::new(RemoteSignerConfig {
+            address: "tcp://192.0.2.1:26659".to_string(),
+            timeout: default::remote_signer_timeout(),
+            tls: None,
+        });
+        let err = signer.get_key("relayer").unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::RemoteSignerInsecure(_)));
+
+        // With TLS configured, its settings are loaded before connecting.
+        let signer = RemoteSigner::new(RemoteSignerConfig {
+            tls: Some(RemoteSignerTlsConfig {
+                ca_cert: "/nonexistent/ca.pem".into(),
+                client_cert: "/nonexistent/client.pem".into(),
+                client_key: "/nonexistent/client.key".into(),
+                server_name: None,
+            }),
+            ..signer.config
+        });
+        let err = signer.get_key("relayer").unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::RemoteSignerTls(_)));
+    }
+}
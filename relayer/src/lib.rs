@@ -29,6 +29,7 @@ pub mod connection;
 pub mod denom;
 pub mod error;
 pub mod event;
+pub mod fee;
 pub mod foreign_client;
 pub mod keyring;
 pub mod light_client;
@@ -40,6 +41,7 @@ pub mod registry;
 pub mod rest;
 pub mod sdk_error;
 pub mod spawn;
+pub mod store;
 pub mod supervisor;
 pub mod telemetry;
 pub mod transfer;
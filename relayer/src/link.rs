@@ -16,7 +16,9 @@ pub mod operational_data;
 
 mod packet_events;
 mod pending;
+mod priority;
 mod relay_path;
+mod relay_policy;
 mod relay_sender;
 mod relay_summary;
 mod tx_hashes;
@@ -1,13 +1,17 @@
+use alloc::collections::BTreeMap;
 use core::fmt;
 use core::iter;
 use std::time::{Duration, Instant};
 
 use ibc_proto::google::protobuf::Any;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
+use uuid::Uuid;
 
 use ibc::core::ics02_client::client_state::ClientState;
 use ibc::core::ics04_channel::context::calculate_block_delay;
 use ibc::events::IbcEvent;
+use ibc::timestamp::Timestamp;
 use ibc::Height;
 
 use crate::chain::handle::ChainHandle;
@@ -20,7 +24,7 @@ use crate::link::error::LinkError;
 use crate::link::RelayPath;
 
 /// The chain that the events associated with a piece of [`OperationalData`] are bound for.
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OperationalDataTarget {
     /// The chain which generated the events associated with the `OperationalData`.
     Source,
@@ -99,6 +103,13 @@ pub struct OperationalData {
     pub tracking_id: TrackingId,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
+    /// When the messages were last deferred by the relay policy, if ever
+    deferred_at: Option<Instant>,
+    /// How many times the messages were deferred by the relay policy
+    deferrals: u32,
+    /// The timestamps of the blocks of the source chain holding the events of the messages,
+    /// by height, which the latency of the packets is measured from
+    event_timestamps: BTreeMap<Height, Timestamp>,
 }
 
 impl OperationalData {
@@ -120,9 +131,56 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            deferred_at: None,
+            deferrals: 0,
+            event_timestamps: BTreeMap::new(),
         }
     }
 
+    /// Returns a copy of this operational data carrying the given messages instead.
+    pub fn with_batch(&self, batch: Vec<TransitMessage>) -> Self {
+        OperationalData {
+            proofs_height: self.proofs_height,
+            batch,
+            target: self.target,
+            tracking_id: self.tracking_id,
+            connection_delay: self.connection_delay.clone(),
+            deferred_at: self.deferred_at,
+            deferrals: self.deferrals,
+            event_timestamps: self.event_timestamps.clone(),
+        }
+    }
+
+    /// Records that the messages were deferred by the relay policy just now.
+    pub fn set_deferred(&mut self) {
+        self.deferred_at = Some(Instant::now());
+        self.deferrals = self.deferrals.saturating_add(1);
+    }
+
+    /// Returns how many times the messages were deferred by the relay policy.
+    pub fn deferrals(&self) -> u32 {
+        self.deferrals
+    }
+
+    /// Returns true if the messages were deferred by the relay policy less than `interval` ago.
+    pub fn is_deferred(&self, interval: Duration) -> bool {
+        self.deferred_at
+            .map_or(false, |deferred_at| deferred_at.elapsed() < interval)
+    }
+
+    /// Records the timestamp of the block of the source chain at `height`.
+    pub fn set_event_timestamp(&mut self, height: Height, timestamp: Timestamp) {
+        self.event_timestamps.insert(height, timestamp);
+    }
+
+    /// Returns the time elapsed until `now` since the block of the source chain holding the
+    /// event of `msg`, if its timestamp was recorded. A block timestamp ahead of `now`, as the
+    /// clocks of the chain and of the relayer drift apart, counts as no time elapsed.
+    pub fn event_latency(&self, msg: &TransitMessage, now: Timestamp) -> Option<Duration> {
+        let timestamp = self.event_timestamps.get(&msg.event.height())?;
+        Some(now.duration_since(timestamp).unwrap_or_default())
+    }
+
     pub fn push(&mut self, msg: TransitMessage) {
         self.batch.push(msg)
     }
@@ -400,3 +458,148 @@ impl fmt::Display for OperationalInfo {
         )
     }
 }
+
+/// The [`OperationalData`] of a pending transaction, as journaled in the state store,
+/// for the transaction to be rebuilt if it was not committed before a restart.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationalDataRecord {
+    pub proofs_height: Height,
+    pub target: OperationalDataTarget,
+    /// The UUID of the tracking ID, unless it is a static one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracking_id: Option<String>,
+    pub batch: Vec<TransitMessageRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitMessageRecord {
+    pub event: IbcEvent,
+    pub type_url: String,
+    /// The hex encoding of the proto-encoded message
+    pub value: String,
+}
+
+impl From<&OperationalData> for OperationalDataRecord {
+    fn from(od: &OperationalData) -> Self {
+        let tracking_id = match od.tracking_id {
+            TrackingId::Uuid(uuid) => Some(uuid.to_string()),
+            TrackingId::Static(_) => None,
+        };
+
+        Self {
+            proofs_height: od.proofs_height,
+            target: od.target,
+            tracking_id,
+            batch: od
+                .batch
+                .iter()
+                .map(|msg| TransitMessageRecord {
+                    event: msg.event.clone(),
+                    type_url: msg.msg.type_url.clone(),
+                    value: hex::encode(&msg.msg.value),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<OperationalDataRecord> for OperationalData {
+    type Error = hex::FromHexError;
+
+    /// Rebuilds the operational data of a journaled transaction. The connection delay had
+    /// elapsed already when the transaction was sent, so it is not accounted for anymore.
+    fn try_from(record: OperationalDataRecord) -> Result<Self, Self::Error> {
+        let tracking_id = record
+            .tracking_id
+            .and_then(|uuid| Uuid::parse_str(&uuid).ok())
+            .map_or_else(|| TrackingId::new_static("journal"), TrackingId::Uuid);
+
+        let mut od = OperationalData::new(
+            record.proofs_height,
+            record.target,
+            tracking_id,
+            Duration::ZERO,
+        );
+
+        for msg in record.batch {
+            od.push(TransitMessage {
+                event: msg.event,
+                msg: Any {
+                    type_url: msg.type_url,
+                    value: hex::decode(msg.value)?,
+                },
+            });
+        }
+
+        Ok(od)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use ibc::core::ics04_channel::events::SendPacket;
+    use ibc::core::ics04_channel::msgs::recv_packet;
+    use ibc::core::ics04_channel::packet::Packet;
+    use ibc::events::IbcEvent;
+    use ibc::timestamp::Timestamp;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+
+    use crate::chain::tracking::TrackingId;
+
+    use super::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    fn recv(height: u64) -> TransitMessage {
+        TransitMessage {
+            event: IbcEvent::SendPacket(SendPacket {
+                height: Height::new(0, height),
+                packet: Packet::default(),
+            }),
+            msg: Any {
+                type_url: recv_packet::TYPE_URL.to_string(),
+                value: vec![],
+            },
+        }
+    }
+
+    fn timestamp(secs: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(secs * 1_000_000_000).unwrap()
+    }
+
+    #[test]
+    fn latency_is_measured_from_the_event_block() {
+        let mut od = OperationalData::new(
+            Height::new(0, 2),
+            OperationalDataTarget::Destination,
+            TrackingId::new_uuid(),
+            Duration::ZERO,
+        );
+        od.push(recv(1));
+        od.push(recv(2));
+        od.set_event_timestamp(Height::new(0, 1), timestamp(1_000));
+
+        let now = timestamp(1_007);
+        assert_eq!(
+            od.event_latency(&od.batch[0], now),
+            Some(Duration::from_secs(7))
+        );
+
+        // The timestamp of the block of the second event could not be queried.
+        assert_eq!(od.event_latency(&od.batch[1], now), None);
+
+        // The timestamps are kept along with the messages they relate to.
+        let od = od.with_batch(vec![recv(1)]);
+        assert_eq!(
+            od.event_latency(&od.batch[0], now),
+            Some(Duration::from_secs(7))
+        );
+
+        // The clock of the relayer lagging behind the one of the chain yields no latency.
+        assert_eq!(
+            od.event_latency(&od.batch[0], timestamp(999)),
+            Some(Duration::ZERO)
+        );
+    }
+}
@@ -2,7 +2,7 @@ use core::iter::Iterator;
 use core::time::Duration;
 use std::time::Instant;
 
-use tracing::{debug, error, trace, trace_span};
+use tracing::{debug, error, info, trace, trace_span};
 
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
@@ -10,7 +10,9 @@ use ibc::query::{QueryTxHash, QueryTxRequest};
 
 use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
+use crate::event::relayer::{self as relayer_event, RelayerEvent, RelayerEventKind};
 use crate::link::{error::LinkError, RelayPath};
+use crate::store;
 use crate::telemetry;
 use crate::util::queue::Queue;
 use crate::{
@@ -116,6 +118,16 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             }
         }
 
+        if let Some(store) = store::global() {
+            store.add_pending_tx(
+                &self.chain_id(),
+                &self.port_id,
+                &self.channel_id,
+                &tx_hashes,
+                &od,
+            );
+        }
+
         let u = PendingData {
             original_od: od,
             tx_hashes: TxHashes(tx_hashes),
@@ -126,6 +138,114 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.pending_queue.push_back(u);
     }
 
+    /// Recovers the transactions sent along this path before the relayer restarted, as
+    /// journaled in the state store. The events of the confirmed ones are returned. The others
+    /// are put back in the pending queue, to be waited for until `TIMEOUT` has elapsed since
+    /// they were sent, after which their operational data is returned for their messages to
+    /// be regenerated.
+    pub fn recover_journaled_txs(&self) -> (RelaySummary, Vec<OperationalData>) {
+        let mut summary = RelaySummary::empty();
+        let mut timed_out = Vec::new();
+
+        let store = match store::global() {
+            Some(store) => store,
+            None => return (summary, timed_out),
+        };
+
+        let journaled = store.pending_txs(&self.chain_id(), &self.port_id, &self.channel_id);
+        if journaled.is_empty() {
+            return (summary, timed_out);
+        }
+
+        let span = trace_span!(
+            "recovering journaled txs",
+            chain = %self.chain_id(),
+            counterparty_chain = %self.counterparty_chain_id,
+            port = %self.port_id,
+            channel = %self.channel_id,
+        );
+
+        let _guard = span.enter();
+
+        info!(
+            "recovering {} transaction(s) sent before the restart",
+            journaled.len()
+        );
+
+        for tx in journaled {
+            let tx_hashes = TxHashes(tx.hashes());
+
+            match self.check_tx_events(&tx_hashes) {
+                Ok(Some(events)) => {
+                    debug!(tx_hashes = %tx_hashes, "transactions confirmed");
+                    summary.extend(RelaySummary::from_events(events));
+                    self.forget_txs(&tx_hashes);
+                    continue;
+                }
+                Ok(None) => trace!("transactions are not yet committed: {}", tx_hashes),
+                Err(e) => error!("error querying for tx hashes {}: {}", tx_hashes, e),
+            }
+
+            let age = tx.age();
+            let od = match OperationalData::try_from(tx.operational_data) {
+                Ok(od) => od,
+                Err(e) => {
+                    error!("failed to decode the journaled operational data: {}", e);
+                    self.forget_txs(&tx_hashes);
+                    continue;
+                }
+            };
+
+            match Instant::now().checked_sub(age) {
+                Some(submit_time) if age < TIMEOUT => {
+                    debug!(
+                        "waiting for the transactions sent {:?} ago to be committed: {}",
+                        age, tx_hashes
+                    );
+
+                    // The transactions stay journaled, until they are confirmed or time out.
+                    self.pending_queue.push_back(PendingData {
+                        original_od: od,
+                        tx_hashes,
+                        submit_time,
+                        error_events: Vec::new(),
+                    });
+                }
+                _ => {
+                    info!(
+                        "transactions were not committed in time, regenerating: {}",
+                        tx_hashes
+                    );
+
+                    self.forget_txs(&tx_hashes);
+                    timed_out.push(od);
+                }
+            }
+        }
+
+        (summary, timed_out)
+    }
+
+    /// Removes the transactions from the state store, once they are confirmed or given up on.
+    fn forget_txs(&self, tx_hashes: &TxHashes) {
+        if let Some(store) = store::global() {
+            store.remove_pending_txs(
+                &self.chain_id(),
+                &self.port_id,
+                &self.channel_id,
+                &tx_hashes.0,
+            );
+        }
+    }
+
+    fn publish_event(&self, pending: &PendingData, kind: RelayerEventKind) {
+        let event = RelayerEvent::new(pending.tracking_id(), self.chain_id(), kind)
+            .with_counterparty(self.counterparty_chain_id.clone())
+            .with_channel(self.port_id.clone(), self.channel_id);
+
+        relayer_event::publish(event);
+    }
+
     fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
         let mut all_events = Vec::new();
         for hash in &tx_hashes.0 {
@@ -192,6 +312,15 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         // relayer to resubmit the transaction to the chain again.
                         error!("timed out while confirming {}", tx_hashes);
 
+                        self.forget_txs(tx_hashes);
+
+                        self.publish_event(
+                            &pending,
+                            RelayerEventKind::TxTimedOut {
+                                tx_hashes: tx_hashes.0.iter().map(ToString::to_string).collect(),
+                            },
+                        );
+
                         match resubmit {
                             Some(f) => {
                                 // The pending tx needs to be resubmitted. This involves replacing the tx's
@@ -242,6 +371,8 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         "transactions confirmed",
                     );
 
+                    self.forget_txs(tx_hashes);
+
                     telemetry!(
                         tx_confirmed,
                         pending.tracking_id(),
@@ -251,6 +382,20 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         &self.counterparty_chain_id
                     );
 
+                    telemetry!(packet_latency_metrics(
+                        &pending.original_od,
+                        &self.chain_id(),
+                        &self.counterparty_chain_id
+                    ));
+
+                    self.publish_event(
+                        &pending,
+                        RelayerEventKind::TxConfirmed {
+                            tx_hashes: tx_hashes.0.iter().map(ToString::to_string).collect(),
+                            events: events.clone(),
+                        },
+                    );
+
                     // Convert the events to RelaySummary and return them.
                     let mut summary = RelaySummary::from_events(events);
                     summary.extend(RelaySummary::from_events(pending.error_events));
@@ -288,3 +433,42 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         }
     }
 }
+
+/// Records the latency of the packets received or acknowledged on `chain` by the committed
+/// messages of `od`, since the blocks holding their events on `counterparty_chain`.
+#[cfg(feature = "telemetry")]
+pub(crate) fn packet_latency_metrics(
+    od: &OperationalData,
+    chain: &ChainId,
+    counterparty_chain: &ChainId,
+) {
+    use ibc::core::ics04_channel::msgs::{acknowledgement, recv_packet};
+    use ibc::timestamp::Timestamp;
+
+    let now = Timestamp::now();
+
+    for msg in &od.batch {
+        let (packet, latency) = match (msg.event.packet(), od.event_latency(msg, now)) {
+            (Some(packet), Some(latency)) => (packet, latency),
+            _ => continue,
+        };
+
+        match msg.msg.type_url.as_str() {
+            recv_packet::TYPE_URL => ibc_telemetry::global().receive_packet_latency(
+                latency,
+                counterparty_chain,
+                chain,
+                &packet.source_channel,
+                &packet.source_port,
+            ),
+            acknowledgement::TYPE_URL => ibc_telemetry::global().acknowledgment_packet_latency(
+                latency,
+                chain,
+                counterparty_chain,
+                &packet.source_channel,
+                &packet.source_port,
+            ),
+            _ => {}
+        }
+    }
+}
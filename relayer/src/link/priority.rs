@@ -0,0 +1,235 @@
+//! Ordering of the pending operational data, so that a flood of packets does not starve the
+//! packets which are about to time out, and could then no longer be received.
+
+use alloc::collections::VecDeque;
+use core::cmp::Ordering;
+use core::time::Duration;
+
+use ibc::core::ics04_channel::msgs::recv_packet;
+use ibc::core::ics04_channel::packet::Packet;
+
+use crate::chain::endpoint::ChainStatus;
+use crate::link::operational_data::OperationalData;
+
+/// Returns how long until the packet times out on the chain whose latest status is `status`,
+/// taking `max_block_time` for the time between the blocks up to the timeout height.
+/// Returns zero if the packet has timed out already, and `None` if it never times out.
+pub fn time_to_timeout(
+    packet: &Packet,
+    status: &ChainStatus,
+    max_block_time: Duration,
+) -> Option<Duration> {
+    let timeout_height = &packet.timeout_height;
+    let latest_height = &status.height;
+
+    let until_height = if timeout_height.is_zero() {
+        None
+    } else if timeout_height.revision_number != latest_height.revision_number {
+        // The heights of a later revision are never reached by the current one.
+        (timeout_height.revision_number < latest_height.revision_number).then(|| Duration::ZERO)
+    } else {
+        let blocks = timeout_height
+            .revision_height
+            .saturating_sub(latest_height.revision_height);
+
+        Some(
+            u32::try_from(blocks)
+                .ok()
+                .and_then(|blocks| max_block_time.checked_mul(blocks))
+                .unwrap_or(Duration::MAX),
+        )
+    };
+
+    let until_timestamp = if packet.timeout_timestamp.nanoseconds() == 0 {
+        None
+    } else {
+        Some(
+            packet
+                .timeout_timestamp
+                .duration_since(&status.timestamp)
+                .unwrap_or(Duration::ZERO),
+        )
+    };
+
+    match (until_height, until_timestamp) {
+        (Some(height), Some(timestamp)) => Some(height.min(timestamp)),
+        (height, timestamp) => height.or(timestamp),
+    }
+}
+
+/// Moves the operational data carrying a packet to receive which times out within `window`
+/// to the front of the queue, the most urgent first, as ranked by how close to the end of the
+/// window their first packet times out. The other operational data keep their order behind
+/// them, including those whose packets have timed out already, which are left to be timed out
+/// on the source chain. The channels are weighted against each other by the packet workers,
+/// see [`TurnScheduler`](crate::worker::turns::TurnScheduler).
+pub fn prioritize(
+    ods: VecDeque<OperationalData>,
+    window: Duration,
+    time_to_timeout: impl Fn(&Packet) -> Option<Duration>,
+) -> VecDeque<OperationalData> {
+    if window.is_zero() || ods.len() < 2 {
+        return ods;
+    }
+
+    let (urgent, rest): (Vec<_>, Vec<_>) = ods
+        .into_iter()
+        .map(|od| (urgency(&od, &time_to_timeout), od))
+        .partition(|(urgency, _)| urgency.map_or(false, |until| until <= window));
+
+    let score = |until: Duration| 1.0 - until.as_secs_f64() / window.as_secs_f64();
+
+    let mut urgent: Vec<_> = urgent
+        .into_iter()
+        .map(|(until, od)| (until.map_or(0.0, score), od))
+        .collect();
+
+    // The sort is stable, so the operational data as urgent keep their order.
+    urgent.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    urgent
+        .into_iter()
+        .map(|(_, od)| od)
+        .chain(rest.into_iter().map(|(_, od)| od))
+        .collect()
+}
+
+/// The time until the first of the packets to receive in the operational data times out,
+/// ignoring those which have timed out already.
+fn urgency(
+    od: &OperationalData,
+    time_to_timeout: impl Fn(&Packet) -> Option<Duration>,
+) -> Option<Duration> {
+    od.batch
+        .iter()
+        .filter(|msg| msg.msg.type_url == recv_packet::TYPE_URL)
+        .filter_map(|msg| msg.event.packet())
+        .filter_map(time_to_timeout)
+        .filter(|until| !until.is_zero())
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+    use core::time::Duration;
+
+    use ibc::core::ics02_client::height::Height;
+    use ibc::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+    use ibc::core::ics04_channel::msgs::{acknowledgement, recv_packet};
+    use ibc::core::ics04_channel::packet::Packet;
+    use ibc::events::IbcEvent;
+    use ibc::timestamp::Timestamp;
+    use ibc_proto::google::protobuf::Any;
+
+    use super::{prioritize, time_to_timeout};
+    use crate::chain::endpoint::ChainStatus;
+    use crate::chain::tracking::TrackingId;
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    fn packet(sequence: u64, timeout_height: u64) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            timeout_height: Height::new(0, timeout_height),
+            ..Default::default()
+        }
+    }
+
+    fn od(messages: Vec<(&str, IbcEvent)>) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 1),
+            OperationalDataTarget::Destination,
+            TrackingId::new_uuid(),
+            Duration::ZERO,
+        );
+
+        for (type_url, event) in messages {
+            od.push(TransitMessage {
+                event,
+                msg: Any {
+                    type_url: type_url.to_string(),
+                    value: vec![],
+                },
+            });
+        }
+
+        od
+    }
+
+    fn recv(sequence: u64, timeout_height: u64) -> (&'static str, IbcEvent) {
+        let event = IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 1),
+            packet: packet(sequence, timeout_height),
+        });
+
+        (recv_packet::TYPE_URL, event)
+    }
+
+    fn ack(sequence: u64) -> (&'static str, IbcEvent) {
+        let event = IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+            height: Height::new(0, 1),
+            packet: packet(sequence, 1),
+            ack: vec![1],
+        });
+
+        (acknowledgement::TYPE_URL, event)
+    }
+
+    fn sequences(ods: &VecDeque<OperationalData>) -> Vec<u64> {
+        ods.iter()
+            .map(|od| u64::from(od.batch[0].event.packet().unwrap().sequence))
+            .collect()
+    }
+
+    #[test]
+    fn time_to_timeout_height_and_timestamp() {
+        let status = ChainStatus {
+            height: Height::new(0, 100),
+            timestamp: Timestamp::from_nanoseconds(1_000_000_000_000).unwrap(),
+        };
+        let block_time = Duration::from_secs(5);
+
+        assert_eq!(time_to_timeout(&packet(1, 0), &status, block_time), None);
+        assert_eq!(
+            time_to_timeout(&packet(1, 110), &status, block_time),
+            Some(Duration::from_secs(50))
+        );
+        assert_eq!(
+            time_to_timeout(&packet(1, 90), &status, block_time),
+            Some(Duration::ZERO)
+        );
+
+        let mut by_timestamp = packet(1, 110);
+        by_timestamp.timeout_timestamp = Timestamp::from_nanoseconds(1_020_000_000_000).unwrap();
+        assert_eq!(
+            time_to_timeout(&by_timestamp, &status, block_time),
+            Some(Duration::from_secs(20))
+        );
+    }
+
+    #[test]
+    fn urgent_packets_are_preempted() {
+        let ods = VecDeque::from(vec![
+            od(vec![ack(1)]),
+            od(vec![recv(2, 1000)]),
+            od(vec![recv(3, 130), recv(4, 1000)]),
+            od(vec![ack(5)]),
+            od(vec![recv(6, 110)]),
+            od(vec![recv(7, 90)]),
+            od(vec![recv(8, 95), recv(9, 150)]),
+        ]);
+
+        let until = |packet: &Packet| {
+            let status = ChainStatus {
+                height: Height::new(0, 100),
+                timestamp: Timestamp::none(),
+            };
+            time_to_timeout(packet, &status, Duration::from_secs(1))
+        };
+
+        let prioritized = prioritize(ods, Duration::from_secs(60), until);
+
+        // The packets 7 and 8 have timed out already, unlike the packet 9 batched with 8.
+        assert_eq!(sequences(&prioritized), vec![6, 3, 8, 1, 2, 5, 7]);
+    }
+}
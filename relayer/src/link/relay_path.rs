@@ -1,4 +1,7 @@
+use alloc::collections::BTreeMap;
 use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "telemetry")]
+use alloc::collections::BTreeSet;
 use alloc::collections::VecDeque;
 use std::ops::Sub;
 use std::time::{Duration, Instant};
@@ -23,7 +26,9 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::config::RelayPolicyRule;
 use crate::event::monitor::EventBatch;
+use crate::event::relayer::{self as relayer_event, RelayerEvent, RelayerEventKind};
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::link::error::{self, LinkError};
 use crate::link::operational_data::{
@@ -33,6 +38,8 @@ use crate::link::packet_events::query_packet_events_with;
 use crate::link::packet_events::query_send_packet_events;
 use crate::link::packet_events::query_write_ack_events;
 use crate::link::pending::PendingTxs;
+use crate::link::priority;
+use crate::link::relay_policy::{self, PolicyDecision, PolicyInput};
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
 use crate::link::{pending, relay_sender};
@@ -50,13 +57,15 @@ use ibc::{
             channel::{ChannelEnd, Order, State as ChannelState},
             events::{SendPacket, WriteAcknowledgement},
             msgs::{
-                acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
-                recv_packet::MsgRecvPacket, timeout::MsgTimeout,
+                acknowledgement::MsgAcknowledgement,
+                chan_close_confirm::MsgChannelCloseConfirm,
+                recv_packet::{self, MsgRecvPacket},
+                timeout::MsgTimeout,
                 timeout_on_close::MsgTimeoutOnClose,
             },
-            packet::{Packet, PacketMsgType},
+            packet::{Packet, PacketId, PacketMsgType},
         },
-        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
     },
     events::{IbcEvent, PrettyEvents, WithBlockDataType},
     query::QueryTxRequest,
@@ -68,6 +77,9 @@ use ibc::{
 
 const MAX_RETRIES: usize = 5;
 
+/// How long the packet messages deferred by the relay policy wait before being evaluated again.
+const POLICY_DEFER_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Whether or not to resubmit packets when pending transactions
 /// fail to process within the given timeout duration.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -112,6 +124,12 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // The relay policy rules of the source chain configuration for this channel.
+    relay_policy: Vec<RelayPolicyRule>,
+
+    // The packets to receive which time out within this window are relayed first.
+    preemption_window: Duration,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -136,6 +154,29 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_port_id = channel.src_port_id().clone();
         let dst_port_id = channel.dst_port_id().clone();
 
+        let src_config = src_chain.config().map_err(LinkError::relayer)?;
+
+        let relay_policy: Vec<RelayPolicyRule> = src_config
+            .relay_policy
+            .into_iter()
+            .filter(|rule| rule.matches(&src_port_id, &src_channel_id))
+            .collect();
+
+        // A packet deferred or dropped on an ordered channel would block all the later ones.
+        let relay_policy = if channel.ordering == Order::Ordered && !relay_policy.is_empty() {
+            warn!(
+                "ignoring the relay policy of the ordered channel {}/{} on chain {}",
+                src_port_id, src_channel_id, src_chain_id
+            );
+            vec![]
+        } else {
+            relay_policy
+        };
+
+        let preemption_window = src_config
+            .packet_priority
+            .preemption_window_for(&src_port_id, &src_channel_id);
+
         let path = PathIdentifiers {
             port_id: dst_port_id.clone(),
             channel_id: dst_channel_id,
@@ -154,6 +195,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            relay_policy,
+            preemption_window,
         })
     }
 
@@ -488,6 +532,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let dst_latest_height = dst_latest_info.height;
 
+        // New packets are not relayed to a destination chain over its daily fee budget, they
+        // are picked up again by packet clearing once the budget is renewed. Timeouts and
+        // acknowledgments are still relayed, so that the packets in flight are completed.
+        let recv_paused = self
+            .dst_chain()
+            .fee_budget_exceeded()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
         // Operational data targeting the source chain (e.g., Timeout packets)
         let mut src_od = OperationalData::new(
             dst_latest_height,
@@ -530,6 +582,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     if self.send_packet_event_handled(send_packet_ev)? {
                         debug!("{} already handled", send_packet_ev);
                         (None, None)
+                    } else if recv_paused {
+                        let timeout = self.build_timeout_from_send_packet_event(
+                            send_packet_ev,
+                            &dst_latest_info,
+                        )?;
+                        if timeout.is_none() {
+                            debug!(
+                                "{} not relayed, the daily fee budget of {} is exceeded",
+                                send_packet_ev,
+                                self.dst_chain().id()
+                            );
+                        }
+                        (None, timeout)
                     } else {
                         self.build_recv_or_timeout_from_send_packet_event(
                             send_packet_ev,
@@ -585,12 +650,44 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let dst_od_res = if dst_od.batch.is_empty() {
             None
         } else {
+            telemetry!(self.record_event_timestamps(&mut dst_od));
             Some(dst_od)
         };
 
         Ok((src_od_res, dst_od_res))
     }
 
+    /// Records the timestamps of the blocks of the source chain holding the events of the
+    /// packet messages of `od`, which the latency of the packets is measured from. The blocks
+    /// whose timestamp cannot be queried, e.g. as the node pruned them, are left out.
+    #[cfg(feature = "telemetry")]
+    fn record_event_timestamps(&self, od: &mut OperationalData) {
+        let heights: BTreeSet<Height> = od
+            .batch
+            .iter()
+            .filter(|msg| msg.event.packet().is_some())
+            .map(|msg| msg.event.height())
+            .collect();
+
+        for height in heights {
+            let consensus_state =
+                self.src_chain()
+                    .query_host_consensus_state(QueryHostConsensusStateRequest {
+                        height: HeightQuery::Specific(height),
+                    });
+
+            match consensus_state {
+                Ok(consensus_state) => od.set_event_timestamp(height, consensus_state.timestamp()),
+                Err(e) => debug!(
+                    "no timestamp for the block of {} at height {}: {}",
+                    self.src_chain().id(),
+                    height,
+                    e
+                ),
+            }
+        }
+    }
+
     /// Relays an [`OperationalData`] using a specific
     /// sender, which implements [`relay_sender::Submit`].
     pub(crate) fn relay_from_operational_data<S: relay_sender::Submit>(
@@ -733,9 +830,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let msgs = odata.assemble_msgs(self)?;
 
-        telemetry!({
-            let (chain, counterparty, channel_id, port_id) = self.target_info(odata.target);
+        let (chain, counterparty, channel_id, port_id) = self.target_info(odata.target);
 
+        telemetry!({
             ibc_telemetry::global().tx_submitted(
                 msgs.tracking_id,
                 &chain,
@@ -745,10 +842,41 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             );
         });
 
-        match odata.target {
+        let event = RelayerEvent::new(
+            msgs.tracking_id,
+            chain.clone(),
+            RelayerEventKind::TxSubmitted {
+                messages: msgs.msgs.iter().map(|msg| msg.type_url.clone()).collect(),
+            },
+        )
+        .with_counterparty(counterparty.clone())
+        .with_channel(port_id.clone(), *channel_id);
+
+        let reply = match odata.target {
             OperationalDataTarget::Source => S::submit(self.src_chain(), msgs),
             OperationalDataTarget::Destination => S::submit(self.dst_chain(), msgs),
+        };
+
+        // The transactions of the asynchronous sender are accounted for once confirmed.
+        if S::WAITS_FOR_COMMIT && reply.is_ok() {
+            telemetry!(pending::packet_latency_metrics(
+                odata,
+                &chain,
+                &counterparty
+            ));
+        }
+
+        match &reply {
+            Ok(_) => relayer_event::publish(event),
+            Err(e) => relayer_event::publish(RelayerEvent {
+                kind: RelayerEventKind::TxFailed {
+                    error: e.to_string(),
+                },
+                ..event
+            }),
         }
+
+        reply
     }
 
     fn enqueue_pending_tx(&self, reply: AsyncReply, odata: OperationalData) {
@@ -1346,9 +1474,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
             match elapsed_result {
                 Ok(elapsed) => {
-                    if elapsed {
-                        // The current piece of operational data has elapsed; we can go ahead and
-                        // attempt to relay it.
+                    if od.is_deferred(POLICY_DEFER_INTERVAL) {
+                        // The relay policy deferred the messages recently; keep them for later.
+                        unprocessed.push_back(od);
+                    } else if elapsed {
+                        // The current piece of operational data has elapsed; apply the relay
+                        // policy and go ahead and attempt to relay the messages that pass it.
+                        let (od, deferred) = self.apply_relay_policy(od);
+
+                        if let Some(mut deferred) = deferred {
+                            deferred.set_deferred();
+                            unprocessed.push_back(deferred);
+                        }
+
+                        let od = match od {
+                            Some(od) => od,
+                            None => continue,
+                        };
+
                         match self
                             .relay_from_operational_data::<relay_sender::AsyncSender>(od.clone())
                         {
@@ -1385,6 +1528,126 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(unprocessed)
     }
 
+    /// Evaluates the receive messages of the operational data against the relay policy rule of
+    /// the channel, if any. Returns the operational data with the messages to relay, and the
+    /// one with the messages to defer, each only if it has any messages left; the messages to
+    /// drop are discarded, to be picked up again by packet clearing.
+    fn apply_relay_policy(
+        &self,
+        od: OperationalData,
+    ) -> (Option<OperationalData>, Option<OperationalData>) {
+        let rule = match self.relay_policy.first() {
+            Some(rule) => rule,
+            None => return (Some(od), None),
+        };
+
+        let has_recv_msgs = od
+            .batch
+            .iter()
+            .any(|msg| msg.msg.type_url == recv_packet::TYPE_URL);
+        if !has_recv_msgs {
+            return (Some(od), None);
+        }
+
+        let estimated_fees = if rule.max_fees.is_empty() {
+            BTreeMap::new()
+        } else {
+            self.estimated_fees_per_msg(&od)
+        };
+
+        let mut relayed = vec![];
+        let mut deferred = vec![];
+
+        for msg in od.batch.iter() {
+            // Only the packets yet to be received are up to the policy, the acknowledgments
+            // and timeouts of the packets already sent are always relayed.
+            let packet = match msg.event.packet() {
+                Some(packet) if msg.msg.type_url == recv_packet::TYPE_URL => packet,
+                _ => {
+                    relayed.push(msg.clone());
+                    continue;
+                }
+            };
+
+            let input = PolicyInput::new(packet, estimated_fees.clone(), od.deferrals());
+            let decision =
+                relay_policy::evaluate(rule, &input, || self.packet_incentivized(packet));
+
+            match decision {
+                PolicyDecision::Relay => relayed.push(msg.clone()),
+                PolicyDecision::Defer => {
+                    debug!(
+                        "deferring {} for packet {} by the relay policy",
+                        msg.msg.type_url, packet
+                    );
+                    deferred.push(msg.clone());
+                }
+                PolicyDecision::Drop => {
+                    info!(
+                        "dropping {} for packet {} by the relay policy",
+                        msg.msg.type_url, packet
+                    );
+                }
+            }
+        }
+
+        let with_msgs = |batch: Vec<TransitMessage>| {
+            if batch.is_empty() {
+                None
+            } else {
+                Some(od.with_batch(batch))
+            }
+        };
+
+        (with_msgs(relayed), with_msgs(deferred))
+    }
+
+    /// Estimates the fee of submitting the operational data, and returns the share of each of
+    /// its messages per denom. Returns no fees if the fee could not be estimated, letting the
+    /// messages pass the fee limits of the relay policy.
+    fn estimated_fees_per_msg(&self, od: &OperationalData) -> BTreeMap<String, u128> {
+        let msgs = match od.assemble_msgs(self) {
+            Ok(msgs) => msgs,
+            Err(_) => return BTreeMap::new(),
+        };
+
+        let fee = match od.target {
+            OperationalDataTarget::Source => self.src_chain().estimate_tx_fee(msgs),
+            OperationalDataTarget::Destination => self.dst_chain().estimate_tx_fee(msgs),
+        };
+
+        match fee {
+            Ok(fee) => {
+                let msg_count = od.batch.len().max(1) as u128;
+                let mut amounts = relay_policy::fee_amounts(&fee);
+                amounts.values_mut().for_each(|amount| *amount /= msg_count);
+                amounts
+            }
+            Err(e) => {
+                warn!("failed to estimate the fee for the relay policy: {}", e);
+                BTreeMap::new()
+            }
+        }
+    }
+
+    /// Returns true if the packet carries an ICS29 fee paying for relaying its receive message.
+    /// The fees are escrowed on the source chain, which sent the packet.
+    fn packet_incentivized(&self, packet: &Packet) -> bool {
+        let packet_id = PacketId {
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel,
+            sequence: packet.sequence,
+        };
+
+        match self.src_chain().query_incentivized_packet(packet_id) {
+            Ok(packet_fees) => relay_policy::pays_for_recv(&packet_fees),
+            Err(e) => {
+                warn!("failed to query the fees of packet {}: {}", packet, e);
+                false
+            }
+        }
+    }
+
     /// While there are pending operational data items, this function
     /// performs the relaying of packets corresponding to those
     /// operational data items to both the source and destination chains.
@@ -1403,7 +1666,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             }
         }
 
-        let dst_od_iter = self.dst_operational_data.take().into_iter();
+        let dst_od_iter = self
+            .prioritize_dst_operational_data(self.dst_operational_data.take())
+            .into_iter();
 
         match self
             .execute_schedule_for_target_chain(dst_od_iter, OperationalDataTarget::Destination)
@@ -1418,6 +1683,28 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
+    /// Recovers the transactions sent along this path before the relayer restarted: the
+    /// events of the confirmed ones are returned, those which may still be committed are
+    /// waited for like the other pending transactions, while the messages of those which
+    /// timed out are regenerated and scheduled to be sent again.
+    pub fn recover_journaled_txs(&self) -> RelaySummary {
+        let (mut summary, mut timed_out) = self.pending_txs_src.recover_journaled_txs();
+        let (summary_dst, timed_out_dst) = self.pending_txs_dst.recover_journaled_txs();
+
+        summary.extend(summary_dst);
+        timed_out.extend(timed_out_dst);
+
+        for od in timed_out {
+            if let Some(new_od) = self.regenerate_operational_data(od) {
+                if let Err(e) = self.schedule_operational_data(new_od) {
+                    error!("failed to schedule regenerated operational data: {}", e);
+                }
+            }
+        }
+
+        summary
+    }
+
     /// Kicks off the process of relaying pending txs to the source and destination chains.
     ///
     /// See [`Resubmit::from_clear_interval`] for more info about the `resubmit` parameter.
@@ -1475,6 +1762,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(res)
     }
 
+    /// Returns true if there are messages pending to be submitted to the destination chain.
+    pub fn has_pending_dst_operational_data(&self) -> bool {
+        !self.dst_operational_data.is_empty()
+    }
+
     /// Refreshes the scheduled batches.
     /// Verifies if any sendPacket messages timed-out. If so, moves them from destination op. data
     /// to source operational data, and adjusts the events and messages accordingly.
@@ -1672,9 +1964,46 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         self.src_operational_data.replace(unelapsed_src_ods);
         self.dst_operational_data.replace(unelapsed_dst_ods);
+
+        let elapsed_dst_ods = self.prioritize_dst_operational_data(elapsed_dst_ods);
+
         Ok((elapsed_src_ods, elapsed_dst_ods))
     }
 
+    /// Moves the operational data carrying packets which are about to time out on the
+    /// destination chain to the front of the queue, see [`priority::prioritize`].
+    /// The packets to receive could no longer be relayed once timed out, while the
+    /// acknowledgments and timeouts can be relayed at any time. The packets of ordered
+    /// channels must be received in order, so they are left as they are.
+    fn prioritize_dst_operational_data(
+        &self,
+        ods: VecDeque<OperationalData>,
+    ) -> VecDeque<OperationalData> {
+        if self.preemption_window.is_zero() || ods.len() < 2 || self.ordered_channel() {
+            return ods;
+        }
+
+        let dst_status = match self.dst_chain().query_application_status() {
+            Ok(status) => status,
+            Err(e) => {
+                warn!("failed to query the status of the destination chain, keeping the order of the pending messages: {}", e);
+                return ods;
+            }
+        };
+
+        let max_block_time = match self.dst_max_block_time() {
+            Ok(max_block_time) => max_block_time,
+            Err(e) => {
+                warn!("failed to get the max block time of the destination chain, keeping the order of the pending messages: {}", e);
+                return ods;
+            }
+        };
+
+        priority::prioritize(ods, self.preemption_window, |packet| {
+            priority::time_to_timeout(packet, &dst_status, max_block_time)
+        })
+    }
+
     fn restore_src_client(&self) -> ForeignClient<ChainA, ChainB> {
         ForeignClient::restore(
             self.src_client_id().clone(),
@@ -1691,14 +2020,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         )
     }
 
-    // we need fully qualified ChainId to avoid unneeded imports warnings
-    #[cfg(feature = "telemetry")]
     fn target_info(
         &self,
         target: OperationalDataTarget,
     ) -> (
-        ibc::core::ics24_host::identifier::ChainId, // source chain
-        ibc::core::ics24_host::identifier::ChainId, // destination chain
+        ChainId, // source chain
+        ChainId, // destination chain
         &ChannelId,
         &PortId,
     ) {
@@ -0,0 +1,255 @@
+//! Evaluation of the relay policy rules, deciding whether the receive messages of an
+//! [`OperationalData`](super::operational_data::OperationalData) are worth sending.
+
+use std::collections::BTreeMap;
+
+use ibc::applications::ics29_fee::fee::PacketFee;
+use ibc::applications::transfer::packet::PacketDataV2;
+use ibc::applications::transfer::{Amount, PrefixedCoin};
+use ibc::core::ics04_channel::packet::Packet;
+use ibc::core::ics04_channel::Version;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+
+use crate::config::{PolicyAction, RelayPolicyRule};
+
+/// What is done with a packet message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PolicyDecision {
+    Relay,
+    Defer,
+    Drop,
+}
+
+impl From<PolicyAction> for PolicyDecision {
+    fn from(action: PolicyAction) -> Self {
+        match action {
+            PolicyAction::Defer => Self::Defer,
+            PolicyAction::Drop => Self::Drop,
+        }
+    }
+}
+
+/// What a relay policy rule decides upon for a packet message.
+#[derive(Clone, Debug, Default)]
+pub struct PolicyInput {
+    /// The share of the message in the estimated fee of its transaction, per denom of the fee,
+    /// which is empty if the fee was not estimated.
+    pub estimated_fees: BTreeMap<String, u128>,
+    /// The tokens transferred by the packet, if it is an ICS20 packet.
+    pub tokens: Vec<PrefixedCoin>,
+    /// How many times the message was deferred already.
+    pub deferrals: u32,
+}
+
+impl PolicyInput {
+    pub fn new(packet: &Packet, estimated_fees: BTreeMap<String, u128>, deferrals: u32) -> Self {
+        Self {
+            estimated_fees,
+            tokens: transfer_tokens(packet),
+            deferrals,
+        }
+    }
+}
+
+/// Decides what is done with a packet message under `rule`. The packets failing the rule are
+/// relayed nonetheless if the rule allows for incentivized packets and `incentivized` tells
+/// the packet carries an ICS29 fee for the message, which is only queried then. The packets
+/// deferred `max_deferrals` times already are dropped instead.
+pub fn evaluate(
+    rule: &RelayPolicyRule,
+    input: &PolicyInput,
+    incentivized: impl FnOnce() -> bool,
+) -> PolicyDecision {
+    let too_costly = input.estimated_fees.iter().any(|(denom, fee)| {
+        rule.max_fees
+            .get(denom)
+            .map_or(false, |max_fee| *fee > u128::from(*max_fee))
+    });
+
+    let too_small = !input.tokens.is_empty()
+        && input.tokens.iter().all(|token| {
+            rule.min_amounts
+                .get(&token.denom.to_string())
+                .map_or(false, |min_amount| token.amount < Amount::from(*min_amount))
+        });
+
+    if !too_costly && !too_small {
+        return PolicyDecision::Relay;
+    }
+
+    if rule.relay_incentivized && incentivized() {
+        return PolicyDecision::Relay;
+    }
+
+    match rule.action {
+        PolicyAction::Defer if input.deferrals >= rule.max_deferrals => PolicyDecision::Drop,
+        action => action.into(),
+    }
+}
+
+/// Returns the amounts of the coins of a transaction fee, per denom.
+pub fn fee_amounts(fee: &Fee) -> BTreeMap<String, u128> {
+    let mut amounts = BTreeMap::new();
+    for coin in &fee.amount {
+        if let Ok(amount) = coin.amount.parse::<u128>() {
+            let total: &mut u128 = amounts.entry(coin.denom.clone()).or_default();
+            *total = total.saturating_add(amount);
+        }
+    }
+    amounts
+}
+
+/// Returns true if any of the ICS29 fees escrowed for a packet pays for relaying its receive
+/// message.
+pub fn pays_for_recv(packet_fees: &[PacketFee]) -> bool {
+    packet_fees
+        .iter()
+        .any(|packet_fee| !packet_fee.fee.recv_fee.is_empty())
+}
+
+/// Decodes the tokens of an ICS20 packet, of either version. The JSON encoding of `ics20-1`
+/// is tried first, as it never parses protobuf-encoded `ics20-2` data.
+fn transfer_tokens(packet: &Packet) -> Vec<PrefixedCoin> {
+    PacketDataV2::decode(&Version::ics20(), &packet.data)
+        .or_else(|_| PacketDataV2::decode(&Version::ics20_v2(), &packet.data))
+        .map(|data| data.tokens)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ibc::applications::transfer::PrefixedCoin;
+
+    use super::{evaluate, PolicyDecision, PolicyInput};
+    use crate::config::filter::{ChannelFilterMatch, PortFilterMatch};
+    use crate::config::{PolicyAction, RelayPolicyRule};
+
+    fn rule(action: PolicyAction) -> RelayPolicyRule {
+        RelayPolicyRule {
+            port_id: PortFilterMatch::Exact("transfer".parse().unwrap()),
+            channel_id: ChannelFilterMatch::Wildcard("channel-*".parse().unwrap()),
+            max_fees: BTreeMap::from([("stake".to_string(), 1000)]),
+            min_amounts: BTreeMap::from([("uatom".to_string(), 500)]),
+            relay_incentivized: true,
+            action,
+            max_deferrals: 2,
+        }
+    }
+
+    fn fees(amount: u128) -> BTreeMap<String, u128> {
+        BTreeMap::from([("stake".to_string(), amount)])
+    }
+
+    fn tokens(amount: u64) -> Vec<PrefixedCoin> {
+        vec![PrefixedCoin {
+            denom: "uatom".parse().unwrap(),
+            amount: amount.into(),
+        }]
+    }
+
+    #[test]
+    fn packets_failing_the_rule_are_deferred_or_dropped() {
+        let defer = rule(PolicyAction::Defer);
+        let drop = rule(PolicyAction::Drop);
+        let not_incentivized = || false;
+
+        let cheap_and_large = PolicyInput {
+            estimated_fees: fees(800),
+            tokens: tokens(1000),
+            ..PolicyInput::default()
+        };
+        assert_eq!(
+            evaluate(&defer, &cheap_and_large, not_incentivized),
+            PolicyDecision::Relay
+        );
+
+        let costly = PolicyInput {
+            estimated_fees: fees(1200),
+            tokens: tokens(1000),
+            ..PolicyInput::default()
+        };
+        assert_eq!(
+            evaluate(&defer, &costly, not_incentivized),
+            PolicyDecision::Defer
+        );
+
+        let small = PolicyInput {
+            estimated_fees: fees(800),
+            tokens: tokens(100),
+            ..PolicyInput::default()
+        };
+        assert_eq!(
+            evaluate(&defer, &small, not_incentivized),
+            PolicyDecision::Defer
+        );
+        assert_eq!(
+            evaluate(&drop, &small, not_incentivized),
+            PolicyDecision::Drop
+        );
+
+        // Packets of other applications, or whose fee couldn't be estimated, pass.
+        let unknown = PolicyInput::default();
+        assert_eq!(
+            evaluate(&defer, &unknown, not_incentivized),
+            PolicyDecision::Relay
+        );
+    }
+
+    #[test]
+    fn fees_are_compared_per_denom() {
+        let rule = rule(PolicyAction::Defer);
+
+        // The fees in a denom without a limit are not compared against the limit of another.
+        let other_denom = PolicyInput {
+            estimated_fees: BTreeMap::from([
+                ("stake".to_string(), 800),
+                ("uosmo".to_string(), 5000),
+            ]),
+            tokens: tokens(1000),
+            ..PolicyInput::default()
+        };
+        assert_eq!(
+            evaluate(&rule, &other_denom, || false),
+            PolicyDecision::Relay
+        );
+
+        let costly = PolicyInput {
+            estimated_fees: BTreeMap::from([("stake".to_string(), 1200), ("uosmo".to_string(), 1)]),
+            tokens: tokens(1000),
+            ..PolicyInput::default()
+        };
+        assert_eq!(evaluate(&rule, &costly, || false), PolicyDecision::Defer);
+    }
+
+    #[test]
+    fn packets_deferred_too_often_are_dropped() {
+        let rule = rule(PolicyAction::Defer);
+        let mut small = PolicyInput {
+            tokens: tokens(100),
+            ..PolicyInput::default()
+        };
+
+        small.deferrals = 1;
+        assert_eq!(evaluate(&rule, &small, || false), PolicyDecision::Defer);
+
+        small.deferrals = 2;
+        assert_eq!(evaluate(&rule, &small, || false), PolicyDecision::Drop);
+    }
+
+    #[test]
+    fn incentivized_packets_are_relayed() {
+        let mut rule = rule(PolicyAction::Drop);
+        let costly = PolicyInput {
+            estimated_fees: fees(1200),
+            tokens: tokens(1000),
+            ..PolicyInput::default()
+        };
+
+        assert_eq!(evaluate(&rule, &costly, || true), PolicyDecision::Relay);
+
+        rule.relay_incentivized = false;
+        assert_eq!(evaluate(&rule, &costly, || true), PolicyDecision::Drop);
+    }
+}
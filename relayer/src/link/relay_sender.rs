@@ -24,6 +24,9 @@ impl SubmitReply for RelaySummary {
 pub trait Submit {
     type Reply: SubmitReply;
 
+    /// Whether the messages are committed once submitted, rather than only checked.
+    const WAITS_FOR_COMMIT: bool;
+
     fn submit(target: &impl ChainHandle, msgs: TrackedMsgs) -> Result<Self::Reply, LinkError>;
 }
 
@@ -33,6 +36,8 @@ pub struct SyncSender;
 impl Submit for SyncSender {
     type Reply = RelaySummary;
 
+    const WAITS_FOR_COMMIT: bool = true;
+
     // TODO: Switch from the `Chain::send_msgs` interface in this method
     //  to use `Chain::submit_msgs` instead; implement waiting for block
     //  commits directly here (instead of blocking in the chain runtime).
@@ -76,6 +81,8 @@ pub struct AsyncSender;
 impl Submit for AsyncSender {
     type Reply = AsyncReply;
 
+    const WAITS_FOR_COMMIT: bool = false;
+
     fn submit(target: &impl ChainHandle, msgs: TrackedMsgs) -> Result<Self::Reply, LinkError> {
         let a = target
             .send_messages_and_wait_check_tx(msgs)
@@ -1,6 +1,7 @@
 //! Registry for keeping track of [`ChainHandle`]s indexed by a `ChainId`.
 
 use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::collections::BTreeSet;
 use alloc::sync::Arc;
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
@@ -24,6 +25,8 @@ pub struct Registry<Chain: ChainHandle> {
     config: Config,
     handles: HashMap<ChainId, Chain>,
     rt: Arc<TokioRuntime>,
+    /// The chains whose handles were copied from the registry this one was forked from.
+    forked: BTreeSet<ChainId>,
 }
 
 #[derive(Clone)]
@@ -38,6 +41,36 @@ impl<Chain: ChainHandle> Registry<Chain> {
             config,
             handles: HashMap::new(),
             rt: Arc::new(TokioRuntime::new().unwrap()),
+            forked: BTreeSet::new(),
+        }
+    }
+
+    /// Returns a registry with the same chain handles, to be used without holding the lock
+    /// on this one, and then merged back into it with [`Registry::merge`].
+    pub fn fork(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            handles: self.handles.clone(),
+            rt: self.rt.clone(),
+            forked: self.handles.keys().cloned().collect(),
+        }
+    }
+
+    /// Adds the handles of the chain runtimes spawned by a fork of this registry. The
+    /// runtimes of the chains which got a runtime in this registry meanwhile are shut down.
+    pub fn merge(&mut self, fork: Self) {
+        for (chain_id, handle) in fork.handles {
+            if fork.forked.contains(&chain_id) {
+                continue;
+            }
+
+            if self.handles.contains_key(&chain_id) {
+                if let Err(e) = handle.shutdown() {
+                    warn!(chain = %chain_id, "chain runtime might have failed to shutdown properly: {}", e);
+                }
+            } else {
+                self.handles.insert(chain_id, handle);
+            }
         }
     }
 
@@ -81,6 +114,12 @@ impl<Chain: ChainHandle> Registry<Chain> {
         }
     }
 
+    /// Replace the configuration the chain runtimes are spawned with.
+    /// The runtimes which are running already are left as they are.
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
     /// Shutdown the runtime associated with the given chain identifier.
     pub fn shutdown(&mut self, chain_id: &ChainId) {
         if let Some(handle) = self.handles.remove(chain_id) {
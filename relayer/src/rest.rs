@@ -1,11 +1,21 @@
 use crossbeam_channel::TryRecvError;
 use tracing::{error, trace};
 
+use ibc::{
+    core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+    events::IbcEvent,
+};
+
 use crate::{
     config::Config,
+    object::Object,
     rest::request::ReplySender,
-    rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    rest::request::{Request, VersionInfo, WorkerSelector},
+    supervisor::{
+        dump_state::{SupervisorState, WorkerDesc},
+        reload::ConfigReload,
+    },
+    worker::WorkerId,
 };
 
 pub mod request;
@@ -26,11 +36,26 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 
 // TODO: Unify this enum with `SupervisorCmd`
 //  We won't unify yet as it is possible we will never implement
-//  REST API `/chain` adding endpoint; instead of `/chain` we might
-//  implement `/reload` for supporting a broader range of functionality
-//  e.g., adjusting chain config, removing chains, etc.
+//  REST API `/chain` adding endpoint; the `/reload` endpoint covers
+//  adding, adjusting and removing chains through the config file.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    ReloadConfig(ReplySender<ConfigReload>),
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<Vec<WorkerId>>,
+    },
+    PauseWorker(WorkerSelector, ReplySender<Option<WorkerDesc>>),
+    ResumeWorker(WorkerSelector, ReplySender<Option<WorkerDesc>>),
+    ExcludeWorker(WorkerSelector, ReplySender<Object>),
+    UpdateClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        reply_to: ReplySender<Vec<IbcEvent>>,
+    },
+    ScanChain(ChainId, ReplySender<Vec<WorkerDesc>>),
 }
 
 /// Process incoming REST requests.
@@ -82,6 +107,66 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::ReloadConfig { reply_to } => {
+                trace!("ReloadConfig");
+
+                return Some(Command::ReloadConfig(reply_to));
+            }
+
+            Request::ClearPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("ClearPackets {}/{}/{}", chain_id, port_id, channel_id);
+
+                return Some(Command::ClearPackets {
+                    chain_id,
+                    port_id,
+                    channel_id,
+                    reply_to,
+                });
+            }
+
+            Request::PauseWorker { worker, reply_to } => {
+                trace!("PauseWorker {}", worker);
+
+                return Some(Command::PauseWorker(worker, reply_to));
+            }
+
+            Request::ResumeWorker { worker, reply_to } => {
+                trace!("ResumeWorker {}", worker);
+
+                return Some(Command::ResumeWorker(worker, reply_to));
+            }
+
+            Request::ExcludeWorker { worker, reply_to } => {
+                trace!("ExcludeWorker {}", worker);
+
+                return Some(Command::ExcludeWorker(worker, reply_to));
+            }
+
+            Request::UpdateClient {
+                chain_id,
+                client_id,
+                reply_to,
+            } => {
+                trace!("UpdateClient {} on {}", client_id, chain_id);
+
+                return Some(Command::UpdateClient {
+                    chain_id,
+                    client_id,
+                    reply_to,
+                });
+            }
+
+            Request::ScanChain { chain_id, reply_to } => {
+                trace!("ScanChain {}", chain_id);
+
+                return Some(Command::ScanChain(chain_id, reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
@@ -1,7 +1,10 @@
 use serde::ser::{Serialize, SerializeMap, Serializer};
 use thiserror::Error;
 
-use ibc::core::ics24_host::{error::ValidationErrorDetail, identifier::ChainId};
+use ibc::core::ics24_host::{
+    error::ValidationErrorDetail,
+    identifier::{ChainId, ClientId},
+};
 
 #[derive(Error, Debug)]
 pub enum RestApiError {
@@ -23,6 +26,36 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed to reload the configuration: {0}")]
+    ConfigReload(String),
+
+    #[error("failed to parse the string {0} into a valid identifier: {1}")]
+    InvalidIdentifier(String, ValidationErrorDetail),
+
+    #[error("failed while parsing the request body: {0}")]
+    InvalidBody(String),
+
+    #[error("could not find {0}")]
+    WorkerNotFound(String),
+
+    #[error("failed to spawn the worker: {0}")]
+    WorkerSpawn(String),
+
+    #[error("failed to update client {0} on chain {1}: {2}")]
+    ClientUpdate(ClientId, ChainId, String),
+
+    #[error("failed to scan chain {0}: {1}")]
+    Scan(ChainId, String),
+
+    #[error("missing or invalid authentication token")]
+    Unauthorized,
+
+    #[error("failed to open the WebSocket connection: {0}")]
+    WebSocket(String),
+
+    #[error("the maximum number of event streams ({0}) are open already")]
+    TooManyEventStreams(usize),
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,6 +69,16 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::ConfigReload(_) => "ConfigReload",
+            RestApiError::InvalidIdentifier(_, _) => "InvalidIdentifier",
+            RestApiError::InvalidBody(_) => "InvalidBody",
+            RestApiError::WorkerNotFound(_) => "WorkerNotFound",
+            RestApiError::WorkerSpawn(_) => "WorkerSpawn",
+            RestApiError::ClientUpdate(_, _, _) => "ClientUpdate",
+            RestApiError::Scan(_, _) => "Scan",
+            RestApiError::Unauthorized => "Unauthorized",
+            RestApiError::WebSocket(_) => "WebSocket",
+            RestApiError::TooManyEventStreams(_) => "TooManyEventStreams",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
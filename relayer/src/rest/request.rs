@@ -1,8 +1,22 @@
-use serde::Serialize;
+use core::fmt;
 
-use ibc::core::ics24_host::identifier::ChainId;
+use serde::{Deserialize, Serialize};
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use ibc::{
+    core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+    events::IbcEvent,
+};
+
+use crate::{
+    config::ChainConfig,
+    object::Object,
+    rest::RestApiError,
+    supervisor::{
+        dump_state::{SupervisorState, WorkerDesc},
+        reload::ConfigReload,
+    },
+    worker::WorkerId,
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -17,6 +31,23 @@ pub struct VersionInfo {
     pub version: String,
 }
 
+/// Identifies a worker, either by its id or by the object it relays for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerSelector {
+    Id(WorkerId),
+    Object(Object),
+}
+
+impl fmt::Display for WorkerSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkerSelector::Id(id) => write!(f, "worker {}", id),
+            WorkerSelector::Object(object) => write!(f, "object {}", object.short_name()),
+        }
+    }
+}
+
 /// REST API request variants
 #[derive(Clone, Debug)]
 pub enum Request {
@@ -36,4 +67,41 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    ReloadConfig {
+        reply_to: ReplySender<ConfigReload>,
+    },
+
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<Vec<WorkerId>>,
+    },
+
+    PauseWorker {
+        worker: WorkerSelector,
+        reply_to: ReplySender<Option<WorkerDesc>>,
+    },
+
+    ResumeWorker {
+        worker: WorkerSelector,
+        reply_to: ReplySender<Option<WorkerDesc>>,
+    },
+
+    ExcludeWorker {
+        worker: WorkerSelector,
+        reply_to: ReplySender<Object>,
+    },
+
+    UpdateClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        reply_to: ReplySender<Vec<IbcEvent>>,
+    },
+
+    ScanChain {
+        chain_id: ChainId,
+        reply_to: ReplySender<Vec<WorkerDesc>>,
+    },
 }
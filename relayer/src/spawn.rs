@@ -6,7 +6,12 @@ use tokio::runtime::Runtime as TokioRuntime;
 use ibc::core::ics24_host::identifier::ChainId;
 
 use crate::{
-    chain::{cosmos::CosmosSdkChain, handle::ChainHandle, runtime::ChainRuntime, ChainType},
+    chain::{
+        cosmos::CosmosSdkChain,
+        handle::ChainHandle,
+        runtime::{ChainRuntime, RuntimeOptions},
+        ChainType,
+    },
     config::Config,
     error::Error as RelayerError,
 };
@@ -55,13 +60,21 @@ pub fn spawn_chain_runtime<Handle: ChainHandle>(
         .cloned()
         .ok_or_else(|| SpawnError::missing_chain_config(chain_id.clone()))?;
 
+    let options = RuntimeOptions {
+        dry_run: config.global.dry_run,
+    };
+
     dbg!(chain_config.r#type);
 
     let handle = match chain_config.r#type {
-        ChainType::CosmosSdk => ChainRuntime::<CosmosSdkChain>::spawn::<Handle>(chain_config, rt),
+        ChainType::CosmosSdk => {
+            ChainRuntime::<CosmosSdkChain>::spawn_with_options::<Handle>(chain_config, options, rt)
+        }
 
         #[cfg(test)]
-        ChainType::Mock => ChainRuntime::<MockChain>::spawn::<Handle>(chain_config, rt),
+        ChainType::Mock => {
+            ChainRuntime::<MockChain>::spawn_with_options::<Handle>(chain_config, options, rt)
+        }
     }
     .map_err(SpawnError::relayer)?;
 
@@ -0,0 +1,841 @@
+//! An optional store of the relayer state on disk, so that a restarted relayer resumes where
+//! it left off: the objects found by the last full scan of each chain, the journal of the
+//! transactions which were not confirmed yet, the last height processed on each chain and the
+//! open channel and connection ends last queried.
+//!
+//! The state of each chain is kept in a snapshot file, along with a journal to which every
+//! change is appended. The journal is folded into the snapshot once it grows long enough.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use tendermint::abci::transaction::Hash;
+use tendermint_proto::Protobuf;
+use tracing::{info, warn};
+
+use ibc::core::ics03_connection::connection::ConnectionEnd;
+use ibc::core::ics04_channel::channel::ChannelEnd;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortChannelId, PortId};
+use ibc::Height;
+
+use crate::cache::Cache;
+use crate::config::{PacketFilter, StoreConfig};
+use crate::error::Error;
+use crate::link::operational_data::{OperationalData, OperationalDataRecord};
+
+/// The folder, relative to the home directory, holding the state of the chains by default.
+pub const STATE_STORE_DEFAULT_FOLDER: &str = ".hermes/state/";
+
+/// How long the transactions which were neither confirmed nor given up on are kept for,
+/// since they are only recovered when a worker is spawned for their path.
+const PENDING_TX_RETENTION: Duration = Duration::from_secs(3600);
+
+/// How many changes the journal of a chain holds before it is folded into its snapshot.
+const COMPACT_AFTER: usize = 1000;
+
+/// How often the last height processed on a chain is journaled, since it advances with
+/// every block.
+const HEIGHT_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+static STORE: OnceCell<StateStore> = OnceCell::new();
+
+/// Opens the global state store if it is enabled in the configuration.
+/// Only the first call has an effect.
+pub fn init(config: &StoreConfig) {
+    if !config.enabled || STORE.get().is_some() {
+        return;
+    }
+
+    let folder = match config
+        .path
+        .clone()
+        .or_else(|| dirs_next::home_dir().map(|home| home.join(STATE_STORE_DEFAULT_FOLDER)))
+    {
+        Some(folder) => folder,
+        None => {
+            warn!("home location is unavailable, the relayer state will not be saved");
+            return;
+        }
+    };
+
+    info!("saving the relayer state to {}", folder.display());
+
+    let _ = STORE.set(StateStore::new(folder));
+}
+
+/// Returns the global state store, if it is enabled.
+pub fn global() -> Option<&'static StateStore> {
+    STORE.get()
+}
+
+/// The objects found by a full scan of a chain, along with the packet filter they were found
+/// under and all the connections and channels of the chain at the time, since a change of the
+/// filter, or connections and channels opened since, call for a new scan.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    pub packet_filter: String,
+    pub channels: Vec<(PortId, ChannelId)>,
+    /// The connections found without any channel, e.g. those being opened.
+    #[serde(default)]
+    pub connections: Vec<ConnectionId>,
+    #[serde(default)]
+    pub chain_connections: BTreeSet<ConnectionId>,
+    #[serde(default)]
+    pub chain_channels: BTreeSet<(PortId, ChannelId)>,
+}
+
+impl ScanCheckpoint {
+    pub fn new(
+        packet_filter: &PacketFilter,
+        channels: Vec<(PortId, ChannelId)>,
+        connections: Vec<ConnectionId>,
+        chain_connections: BTreeSet<ConnectionId>,
+        chain_channels: BTreeSet<(PortId, ChannelId)>,
+    ) -> Self {
+        Self {
+            packet_filter: filter_fingerprint(packet_filter),
+            channels,
+            connections,
+            chain_connections,
+            chain_channels,
+        }
+    }
+
+    /// Returns true if the chain has the same connections and channels as when it was scanned.
+    pub fn is_current(
+        &self,
+        chain_connections: &BTreeSet<ConnectionId>,
+        chain_channels: &BTreeSet<(PortId, ChannelId)>,
+    ) -> bool {
+        &self.chain_connections == chain_connections && &self.chain_channels == chain_channels
+    }
+}
+
+/// A transaction which was sent but not confirmed yet, along with the operational data it
+/// was built from, to be completed or rebuilt after a restart.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournaledTx {
+    pub hashes: Vec<String>,
+    /// When the transaction was sent, in seconds since the Unix epoch.
+    pub submitted_at: u64,
+    pub operational_data: OperationalDataRecord,
+}
+
+impl JournaledTx {
+    pub fn hashes(&self) -> Vec<Hash> {
+        self.hashes
+            .iter()
+            .filter_map(|hash| hash.parse().ok())
+            .collect()
+    }
+
+    /// How long ago the transaction was sent.
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(unix_time().saturating_sub(self.submitted_at))
+    }
+}
+
+/// The state of the relayer for a chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan: Option<ScanCheckpoint>,
+    /// The transactions sent to the chain which were not confirmed yet,
+    /// keyed by the `port/channel` of the chain end of their path.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pending_txs: BTreeMap<String, Vec<JournaledTx>>,
+    /// The last height at which the events of the chain were processed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_height: Option<Height>,
+    /// The hex encoding of the proto-encoded open channel ends last queried,
+    /// keyed by their `port/channel`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channels: BTreeMap<String, String>,
+    /// The hex encoding of the proto-encoded open connection ends last queried.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub connections: BTreeMap<ConnectionId, String>,
+}
+
+impl ChainState {
+    /// Applies a change to the state. Applying a change twice has the same effect as
+    /// applying it once, since a crash while compacting the journal may replay its changes
+    /// on top of a snapshot which includes them.
+    fn apply(&mut self, change: Change) {
+        match change {
+            Change::Scan { scan } => self.scan = Some(scan),
+            Change::AddTx { path, tx } => {
+                let txs = self.pending_txs.entry(path).or_default();
+
+                txs.retain(|pending| {
+                    pending.age() < PENDING_TX_RETENTION && pending.hashes != tx.hashes
+                });
+                txs.push(tx);
+            }
+            Change::RemoveTxs { path, hashes } => {
+                if let Some(txs) = self.pending_txs.get_mut(&path) {
+                    txs.retain(|tx| !tx.hashes.iter().any(|hash| hashes.contains(hash)));
+
+                    if txs.is_empty() {
+                        self.pending_txs.remove(&path);
+                    }
+                }
+            }
+            Change::LastHeight { height } => self.last_height = Some(height),
+            Change::Channel {
+                path,
+                end: Some(end),
+            } => {
+                self.channels.insert(path, end);
+            }
+            Change::Channel { path, end: None } => {
+                self.channels.remove(&path);
+            }
+            Change::Connection {
+                connection_id,
+                end: Some(end),
+            } => {
+                self.connections.insert(connection_id, end);
+            }
+            Change::Connection {
+                connection_id,
+                end: None,
+            } => {
+                self.connections.remove(&connection_id);
+            }
+        }
+    }
+}
+
+/// A change to the state of a chain, as appended to its journal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum Change {
+    Scan {
+        scan: ScanCheckpoint,
+    },
+    AddTx {
+        path: String,
+        tx: JournaledTx,
+    },
+    RemoveTxs {
+        path: String,
+        hashes: Vec<String>,
+    },
+    LastHeight {
+        height: Height,
+    },
+    /// Saves the open end of a channel, or forgets it once it is no longer open.
+    Channel {
+        path: String,
+        end: Option<String>,
+    },
+    /// Saves the open end of a connection, or forgets it once it is no longer open.
+    Connection {
+        connection_id: ConnectionId,
+        end: Option<String>,
+    },
+}
+
+/// The snapshot and the journal of the state of a chain on disk, along with the state they
+/// hold, which is loaded the first time it is accessed.
+#[derive(Debug)]
+struct ChainJournal {
+    chain_id: ChainId,
+    snapshot_path: PathBuf,
+    journal_path: PathBuf,
+    state: Option<ChainState>,
+    /// The last height processed on the chain before the relayer was restarted.
+    restored_height: Option<Height>,
+    /// The number of changes in the journal.
+    changes: usize,
+    /// When the last height processed on the chain was last journaled.
+    height_saved_at: Option<Instant>,
+}
+
+impl ChainJournal {
+    fn new(folder: &Path, chain_id: &ChainId) -> Self {
+        Self {
+            chain_id: chain_id.clone(),
+            snapshot_path: folder.join(format!("{}.json", chain_id)),
+            journal_path: folder.join(format!("{}.journal", chain_id)),
+            state: None,
+            restored_height: None,
+            changes: 0,
+            height_saved_at: None,
+        }
+    }
+
+    fn state(&mut self) -> &mut ChainState {
+        if self.state.is_none() {
+            let state = match self.load() {
+                Ok(state) => state,
+                Err(e) => {
+                    warn!(chain = %self.chain_id, "starting from an empty relayer state: {}", e);
+                    ChainState::default()
+                }
+            };
+
+            self.restored_height = state.last_height;
+            self.state = Some(state);
+        }
+
+        self.state.get_or_insert_with(ChainState::default)
+    }
+
+    /// Reads the snapshot, and replays the changes of the journal on top of it.
+    fn load(&mut self) -> Result<ChainState, Error> {
+        let mut state = load_state_at(&self.snapshot_path)?;
+
+        if !self.journal_path.exists() {
+            return Ok(state);
+        }
+
+        let display = self.journal_path.display().to_string();
+        let journal = fs::read_to_string(&self.journal_path)
+            .map_err(|e| Error::state_store_io(display.clone(), e))?;
+
+        for line in journal.lines() {
+            match serde_json::from_str(line) {
+                Ok(change) => {
+                    state.apply(change);
+                    self.changes += 1;
+                }
+                // Only the last change may be cut short, by a crash while it was appended.
+                Err(e) => {
+                    warn!(
+                        chain = %self.chain_id,
+                        "ignoring the rest of the journal at {}: {}",
+                        self.journal_path.display(),
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Applies the change to the state, and appends it to the journal.
+    fn record(&mut self, change: Change) {
+        self.state().apply(change.clone());
+
+        if let Err(e) = self.append(&change) {
+            warn!(chain = %self.chain_id, "failed to save the relayer state: {}", e);
+            return;
+        }
+
+        self.changes += 1;
+
+        if self.changes >= COMPACT_AFTER {
+            if let Err(e) = self.compact() {
+                warn!(chain = %self.chain_id, "failed to compact the relayer state: {}", e);
+            }
+        }
+    }
+
+    fn append(&self, change: &Change) -> Result<(), Error> {
+        let display = self.journal_path.display().to_string();
+        let io_error = |e| Error::state_store_io(display.clone(), e);
+
+        if let Some(folder) = self.journal_path.parent() {
+            fs::create_dir_all(folder).map_err(io_error)?;
+        }
+
+        let mut line = serde_json::to_vec(change)
+            .map_err(|e| Error::state_store_decode(display.clone(), e))?;
+        line.push(b'\n');
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.journal_path)
+            .and_then(|mut journal| journal.write_all(&line))
+            .map_err(io_error)
+    }
+
+    /// Saves the state to the snapshot, and empties the journal.
+    fn compact(&mut self) -> Result<(), Error> {
+        let state = self.state().clone();
+        save_state_at(&self.snapshot_path, &state)?;
+
+        fs::write(&self.journal_path, b"")
+            .map_err(|e| Error::state_store_io(self.journal_path.display().to_string(), e))?;
+
+        self.changes = 0;
+        Ok(())
+    }
+}
+
+/// The state of the relayer, kept in a snapshot and a journal per chain within a folder.
+/// The files are only read the first time the state of their chain is accessed, and the
+/// state of each chain is locked separately.
+#[derive(Debug)]
+pub struct StateStore {
+    folder: PathBuf,
+    chains: Mutex<HashMap<ChainId, Arc<Mutex<ChainJournal>>>>,
+}
+
+impl StateStore {
+    pub fn new(folder: PathBuf) -> Self {
+        Self {
+            folder,
+            chains: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the last full scan of the chain, unless the packet filter changed since.
+    pub fn scan_checkpoint(
+        &self,
+        chain_id: &ChainId,
+        packet_filter: &PacketFilter,
+    ) -> Option<ScanCheckpoint> {
+        let packet_filter = filter_fingerprint(packet_filter);
+
+        self.with_journal(chain_id, |journal| journal.state().scan.clone())
+            .filter(|scan| scan.packet_filter == packet_filter)
+    }
+
+    pub fn save_scan_checkpoint(&self, chain_id: &ChainId, scan: ScanCheckpoint) {
+        self.with_journal(chain_id, |journal| journal.record(Change::Scan { scan }));
+    }
+
+    /// Returns the transactions sent along the path ending with the given channel on the
+    /// chain, which were not confirmed yet.
+    pub fn pending_txs(
+        &self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Vec<JournaledTx> {
+        self.with_journal(chain_id, |journal| {
+            journal
+                .state()
+                .pending_txs
+                .get(&path_key(port_id, channel_id))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Journals a transaction sent along the path ending with the given channel on the chain.
+    pub fn add_pending_tx(
+        &self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        hashes: &[Hash],
+        od: &OperationalData,
+    ) {
+        if hashes.is_empty() {
+            return;
+        }
+
+        let tx = JournaledTx {
+            hashes: hashes.iter().map(ToString::to_string).collect(),
+            submitted_at: unix_time(),
+            operational_data: od.into(),
+        };
+
+        self.with_journal(chain_id, |journal| {
+            journal.record(Change::AddTx {
+                path: path_key(port_id, channel_id),
+                tx,
+            })
+        });
+    }
+
+    /// Forgets about the transactions, once they are confirmed or given up on.
+    pub fn remove_pending_txs(
+        &self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        hashes: &[Hash],
+    ) {
+        if hashes.is_empty() {
+            return;
+        }
+
+        self.with_journal(chain_id, |journal| {
+            journal.record(Change::RemoveTxs {
+                path: path_key(port_id, channel_id),
+                hashes: hashes.iter().map(ToString::to_string).collect(),
+            })
+        });
+    }
+
+    /// Records the height at which the events of the chain were last processed. It is only
+    /// journaled every [`HEIGHT_SAVE_INTERVAL`], and along with the snapshots in between.
+    pub fn save_last_height(&self, chain_id: &ChainId, height: Height) {
+        self.with_journal(chain_id, |journal| {
+            let due = journal
+                .height_saved_at
+                .map_or(true, |saved_at| saved_at.elapsed() >= HEIGHT_SAVE_INTERVAL);
+
+            if due {
+                journal.record(Change::LastHeight { height });
+                journal.height_saved_at = Some(Instant::now());
+            } else {
+                journal.state().last_height = Some(height);
+            }
+        });
+    }
+
+    /// Returns the last height at which the events of the chain were processed before the
+    /// relayer was restarted, if it was saved.
+    pub fn restored_height(&self, chain_id: &ChainId) -> Option<Height> {
+        self.with_journal(chain_id, |journal| {
+            journal.state();
+            journal.restored_height
+        })
+    }
+
+    /// Saves the end of the channel of the chain, as queried while it was not cached,
+    /// or forgets it if the channel is no longer open.
+    pub fn save_channel(&self, chain_id: &ChainId, id: &PortChannelId, end: &ChannelEnd) {
+        let path = path_key(&id.port_id, &id.channel_id);
+        let end = end
+            .encode_vec()
+            .ok()
+            .filter(|_| end.state().is_open())
+            .map(hex::encode);
+
+        self.with_journal(chain_id, |journal| {
+            if journal.state().channels.get(&path) != end.as_ref() {
+                journal.record(Change::Channel { path, end });
+            }
+        });
+    }
+
+    /// Saves the end of the connection of the chain, as queried while it was not cached,
+    /// or forgets it if the connection is no longer open.
+    pub fn save_connection(
+        &self,
+        chain_id: &ChainId,
+        connection_id: &ConnectionId,
+        end: &ConnectionEnd,
+    ) {
+        let end = end
+            .encode_vec()
+            .ok()
+            .filter(|_| end.state().is_open())
+            .map(hex::encode);
+
+        self.with_journal(chain_id, |journal| {
+            if journal.state().connections.get(connection_id) != end.as_ref() {
+                journal.record(Change::Connection {
+                    connection_id: connection_id.clone(),
+                    end,
+                });
+            }
+        });
+    }
+
+    /// Fills the cache of the chain with the channel and connection ends saved for it, which
+    /// then expire as if they were just queried. The client states and the latest height are
+    /// not saved, since they are only cached for a fraction of a second.
+    pub fn restore_cache(&self, chain_id: &ChainId, cache: &Cache) {
+        self.with_journal(chain_id, |journal| {
+            let state = journal.state();
+
+            for (path, end) in &state.channels {
+                let end = hex::decode(end)
+                    .ok()
+                    .and_then(|end| ChannelEnd::decode_vec(&end).ok());
+
+                if let (Some((port_id, channel_id)), Some(end)) = (parse_path_key(path), end) {
+                    cache.insert_channel(PortChannelId::new(channel_id, port_id), end);
+                }
+            }
+
+            for (connection_id, end) in &state.connections {
+                let end = hex::decode(end)
+                    .ok()
+                    .and_then(|end| ConnectionEnd::decode_vec(&end).ok());
+
+                if let Some(end) = end {
+                    cache.insert_connection(connection_id.clone(), end);
+                }
+            }
+        });
+    }
+
+    /// Runs `f` on the journal of the chain, which is locked meanwhile. The journals of the
+    /// other chains remain accessible.
+    fn with_journal<T>(&self, chain_id: &ChainId, f: impl FnOnce(&mut ChainJournal) -> T) -> T {
+        let journal = self
+            .chains
+            .lock()
+            .expect("poisoned state store lock")
+            .entry(chain_id.clone())
+            .or_insert_with(|| Arc::new(Mutex::new(ChainJournal::new(&self.folder, chain_id))))
+            .clone();
+
+        let mut journal = journal.lock().expect("poisoned chain state lock");
+        f(&mut journal)
+    }
+}
+
+fn path_key(port_id: &PortId, channel_id: &ChannelId) -> String {
+    format!("{}/{}", port_id, channel_id)
+}
+
+fn parse_path_key(key: &str) -> Option<(PortId, ChannelId)> {
+    let (port_id, channel_id) = key.split_once('/')?;
+    Some((port_id.parse().ok()?, channel_id.parse().ok()?))
+}
+
+fn filter_fingerprint(packet_filter: &PacketFilter) -> String {
+    serde_json::to_string(packet_filter).unwrap_or_default()
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn load_state_at(path: &Path) -> Result<ChainState, Error> {
+    if !path.exists() {
+        return Ok(ChainState::default());
+    }
+
+    let display = path.display().to_string();
+    let content = fs::read(path).map_err(|e| Error::state_store_io(display.clone(), e))?;
+
+    serde_json::from_slice(&content).map_err(|e| Error::state_store_decode(display, e))
+}
+
+/// Writes the state to a temporary file first, so that a crash can't leave it truncated.
+fn save_state_at(path: &Path, state: &ChainState) -> Result<(), Error> {
+    let display = path.display().to_string();
+    let io_error = |e| Error::state_store_io(display.clone(), e);
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder).map_err(io_error)?;
+    }
+
+    let content = serde_json::to_vec_pretty(state)
+        .map_err(|e| Error::state_store_decode(display.clone(), e))?;
+
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(io_error)?;
+    fs::rename(&tmp_path, path).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::collections::BTreeSet;
+    use std::env;
+    use std::fs;
+
+    use ibc::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty, State as ConnectionState,
+    };
+    use ibc::core::ics03_connection::version::Version;
+    use ibc::core::ics04_channel::channel::{ChannelEnd, State as ChannelState};
+    use ibc::core::ics04_channel::events::SendPacket;
+    use ibc::core::ics04_channel::msgs::recv_packet;
+    use ibc::core::ics04_channel::packet::Packet;
+    use ibc::core::ics24_host::identifier::{
+        ChainId, ChannelId, ClientId, ConnectionId, PortChannelId, PortId,
+    };
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+    use tendermint::abci::transaction::Hash;
+
+    use super::{ScanCheckpoint, StateStore, COMPACT_AFTER};
+    use crate::cache::{Cache, CacheStatus};
+    use crate::chain::tracking::TrackingId;
+    use crate::config::filter::ChannelFilters;
+    use crate::config::PacketFilter;
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    fn operational_data(sequence: u64) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 5),
+            OperationalDataTarget::Destination,
+            TrackingId::new_uuid(),
+            Duration::ZERO,
+        );
+
+        od.push(TransitMessage {
+            event: IbcEvent::SendPacket(SendPacket {
+                height: Height::new(0, 4),
+                packet: Packet {
+                    sequence: sequence.into(),
+                    ..Default::default()
+                },
+            }),
+            msg: Any {
+                type_url: recv_packet::TYPE_URL.to_string(),
+                value: vec![1, 2, 3],
+            },
+        });
+
+        od
+    }
+
+    fn open_channel() -> ChannelEnd {
+        let mut end = ChannelEnd::default();
+        end.set_state(ChannelState::Open);
+        end
+    }
+
+    fn open_connection() -> ConnectionEnd {
+        ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            Counterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::new(1)),
+                b"ibc".to_vec().try_into().unwrap(),
+            ),
+            vec![Version::default()],
+            Duration::ZERO,
+        )
+    }
+
+    #[test]
+    fn state_survives_a_restart() {
+        let folder = env::temp_dir().join(format!("hermes-state-{}", std::process::id()));
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let port_id: PortId = "transfer".parse().unwrap();
+        let channel_id = "channel-0".parse().unwrap();
+        let (confirmed, pending) = (Hash::new([1; 32]), Hash::new([2; 32]));
+        let od = operational_data(2);
+
+        let connection_id = ConnectionId::new(0);
+        let chain_connections = BTreeSet::from([connection_id.clone(), ConnectionId::new(1)]);
+        let chain_channels = BTreeSet::from([(port_id.clone(), channel_id)]);
+        let checkpoint = ScanCheckpoint::new(
+            &PacketFilter::default(),
+            vec![(port_id.clone(), channel_id)],
+            vec![connection_id],
+            chain_connections.clone(),
+            chain_channels.clone(),
+        );
+
+        let store = StateStore::new(folder.clone());
+        store.save_scan_checkpoint(&chain_id, checkpoint.clone());
+        store.add_pending_tx(
+            &chain_id,
+            &port_id,
+            &channel_id,
+            &[confirmed],
+            &operational_data(1),
+        );
+        store.add_pending_tx(&chain_id, &port_id, &channel_id, &[pending], &od);
+        store.remove_pending_txs(&chain_id, &port_id, &channel_id, &[confirmed]);
+        store.save_last_height(&chain_id, Height::new(0, 10));
+        store.save_last_height(&chain_id, Height::new(0, 11));
+
+        let channel = PortChannelId::new(channel_id, port_id.clone());
+        let closed_channel = PortChannelId::new(ChannelId::new(1), port_id.clone());
+        store.save_channel(&chain_id, &channel, &open_channel());
+        store.save_channel(&chain_id, &closed_channel, &open_channel());
+        store.save_channel(&chain_id, &closed_channel, &ChannelEnd::default());
+        store.save_connection(&chain_id, &ConnectionId::new(0), &open_connection());
+
+        let restarted = StateStore::new(folder.clone());
+        let scan = restarted
+            .scan_checkpoint(&chain_id, &PacketFilter::default())
+            .unwrap();
+        assert_eq!(scan, checkpoint);
+        assert!(scan.is_current(&chain_connections, &chain_channels));
+        assert!(!scan.is_current(
+            &chain_connections,
+            &BTreeSet::from([
+                (port_id.clone(), channel_id),
+                (port_id.clone(), ChannelId::new(1))
+            ])
+        ));
+        assert_eq!(
+            restarted.scan_checkpoint(&chain_id, &PacketFilter::Deny(ChannelFilters::new(vec![]))),
+            None
+        );
+
+        let txs = restarted.pending_txs(&chain_id, &port_id, &channel_id);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].hashes(), vec![pending]);
+
+        let recovered = OperationalData::try_from(txs[0].operational_data.clone()).unwrap();
+        assert_eq!(
+            recovered.tracking_id.to_string(),
+            od.tracking_id.to_string()
+        );
+        assert_eq!(recovered.proofs_height, od.proofs_height);
+        assert_eq!(recovered.batch[0].event, od.batch[0].event);
+        assert_eq!(recovered.batch[0].msg, od.batch[0].msg);
+
+        // Only the first of the heights processed in a row is journaled.
+        assert_eq!(
+            restarted.restored_height(&chain_id),
+            Some(Height::new(0, 10))
+        );
+
+        let cache = Cache::new();
+        restarted.restore_cache(&chain_id, &cache);
+        let not_cached = || Err::<ChannelEnd, ()>(());
+        assert_eq!(
+            cache.get_or_try_insert_channel_with(&channel, not_cached),
+            Ok((open_channel(), CacheStatus::Hit))
+        );
+        assert_eq!(
+            cache.get_or_try_insert_channel_with(&closed_channel, not_cached),
+            Err(())
+        );
+        assert_eq!(
+            cache.get_or_try_insert_connection_with(&ConnectionId::new(0), || Err(())),
+            Ok((open_connection(), CacheStatus::Hit))
+        );
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn journal_is_compacted() {
+        let folder = env::temp_dir().join(format!("hermes-journal-{}", std::process::id()));
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let port_id: PortId = "transfer".parse().unwrap();
+
+        let store = StateStore::new(folder.clone());
+        for channel in 0..COMPACT_AFTER as u64 + 1 {
+            let channel = PortChannelId::new(ChannelId::new(channel), port_id.clone());
+            store.save_channel(&chain_id, &channel, &open_channel());
+        }
+
+        // The journal holds the changes made since the state was last saved.
+        let journal = fs::read_to_string(folder.join("ibc-0.journal")).unwrap();
+        assert_eq!(journal.lines().count(), 1);
+
+        // A change cut short by a crash is ignored.
+        fs::write(
+            folder.join("ibc-0.journal"),
+            format!("{}{{\"change\":\"channel\"", journal),
+        )
+        .unwrap();
+
+        let restarted = StateStore::new(folder.clone());
+        let cache = Cache::new();
+        restarted.restore_cache(&chain_id, &cache);
+        for channel in [0, COMPACT_AFTER as u64] {
+            let channel = PortChannelId::new(ChannelId::new(channel), port_id.clone());
+            assert_eq!(
+                cache.get_or_try_insert_channel_with(&channel, || Err(())),
+                Ok((open_channel(), CacheStatus::Hit))
+            );
+        }
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+}
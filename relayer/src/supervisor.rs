@@ -1,34 +1,47 @@
 use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::collections::BTreeSet;
 use alloc::sync::Arc;
 use core::convert::Infallible;
 use core::ops::Deref;
 use core::time::Duration;
-use std::sync::RwLock;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use std::thread;
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use itertools::Itertools;
 use tracing::{debug, error, error_span, info, trace, warn};
 
 use ibc::{
-    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    core::{
+        ics02_client::client_state::ClientState,
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+    },
     events::IbcEvent,
     Height,
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
+    chain::{
+        endpoint::HealthCheck,
+        handle::ChainHandle,
+        requests::{HeightQuery, IncludeProof, QueryClientStateRequest},
+        tracking::TrackingId,
+    },
     config::Config,
     event::monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
+    foreign_client::ForeignClient,
     object::Object,
     registry::{Registry, SharedRegistry},
-    rest,
+    rest::{self, request::WorkerSelector},
+    store,
     supervisor::scan::ScanMode,
     telemetry,
     util::{
         lock::LockExt,
         task::{spawn_background_task, Next, TaskError, TaskHandle},
     },
-    worker::WorkerMap,
+    worker::{WorkerId, WorkerMap},
 };
 
 pub mod client_state_filter;
@@ -38,7 +51,7 @@ pub mod error;
 pub use error::{Error, ErrorDetail};
 
 pub mod dump_state;
-use dump_state::SupervisorState;
+use dump_state::{SupervisorState, WorkerDesc};
 
 pub mod scan;
 pub mod spawn;
@@ -46,6 +59,9 @@ pub mod spawn;
 pub mod cmd;
 use cmd::SupervisorCmd;
 
+pub mod reload;
+use reload::ConfigReloader;
+
 use self::{scan::ChainScanner, spawn::SpawnContext};
 
 type ArcBatch = Arc<monitor::Result<EventBatch>>;
@@ -72,6 +88,10 @@ pub struct SupervisorOptions {
     /// even when an allow list is configured for a chain and the full scan could
     /// be omitted.
     pub force_full_scan: bool,
+
+    /// The path of the configuration file, which is read again when reloading
+    /// the configuration.
+    pub config_path: Option<PathBuf>,
 }
 
 /**
@@ -124,6 +144,9 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
     cmd_rx: Receiver<SupervisorCmd>,
     options: SupervisorOptions,
 ) -> Result<Vec<TaskHandle>, Error> {
+    // The chain handles restore their cache from the store when they are spawned.
+    store::init(&config.store);
+
     if options.health_check {
         health_check(&config, &mut registry.write());
     }
@@ -150,21 +173,39 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
-    let batch_tasks = spawn_batch_workers(
+    let config = Arc::new(RwLock::new(config));
+
+    let batch_tasks = Arc::new(Mutex::new(spawn_batch_workers(
         &config,
         registry.clone(),
-        client_state_filter,
+        client_state_filter.clone(),
         workers.clone(),
         subscriptions,
+    )));
+
+    let reloader = ConfigReloader::new(
+        options.config_path,
+        config.clone(),
+        registry.clone(),
+        client_state_filter,
+        workers.clone(),
+        batch_tasks,
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    // The tasks processing the event batches are held by the reloader,
+    // and stopped along with the tasks below.
+    let cmd_task = spawn_cmd_worker(
+        config.clone(),
+        registry.clone(),
+        workers.clone(),
+        reloader.clone(),
+        cmd_rx,
+    );
 
     let mut tasks = vec![cmd_task];
-    tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers, rest_rx);
+        let rest_task = spawn_rest_worker(config, registry, workers, reloader, rest_rx);
         tasks.push(rest_task);
     }
 
@@ -172,48 +213,62 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 }
 
 fn spawn_batch_workers<Chain: ChainHandle>(
-    config: &Config,
+    config: &Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     subscriptions: Vec<(Chain, Subscription)>,
-) -> Vec<TaskHandle> {
-    let mut handles = Vec::with_capacity(subscriptions.len());
-
-    for (chain, subscription) in subscriptions {
-        let config = config.clone();
-        let registry = registry.clone();
-        let client_state_filter = client_state_filter.clone();
-        let workers = workers.clone();
-
-        let handle = spawn_background_task(
-            tracing::Span::none(),
-            Some(Duration::from_millis(5)),
-            move || -> Result<Next, TaskError<Infallible>> {
-                if let Ok(batch) = subscription.try_recv() {
-                    handle_batch(
-                        &config,
-                        &mut registry.write(),
-                        &mut client_state_filter.acquire_write(),
-                        &mut workers.acquire_write(),
-                        chain.clone(),
-                        batch,
-                    );
-                }
+) -> HashMap<ChainId, TaskHandle> {
+    subscriptions
+        .into_iter()
+        .map(|(chain, subscription)| {
+            let handle = spawn_batch_worker(
+                config.clone(),
+                registry.clone(),
+                client_state_filter.clone(),
+                workers.clone(),
+                chain.clone(),
+                subscription,
+            );
 
-                Ok(Next::Continue)
-            },
-        );
+            (chain.id(), handle)
+        })
+        .collect()
+}
 
-        handles.push(handle);
-    }
+fn spawn_batch_worker<Chain: ChainHandle>(
+    config: Arc<RwLock<Config>>,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    chain: Chain,
+    subscription: Subscription,
+) -> TaskHandle {
+    spawn_background_task(
+        tracing::Span::none(),
+        Some(Duration::from_millis(5)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            if let Ok(batch) = subscription.try_recv() {
+                handle_batch(
+                    &config.acquire_read(),
+                    &mut registry.write(),
+                    &mut client_state_filter.acquire_write(),
+                    &mut workers.acquire_write(),
+                    chain.clone(),
+                    batch,
+                );
+            }
 
-    handles
+            Ok(Next::Continue)
+        },
+    )
 }
 
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
+    reloader: ConfigReloader<Chain>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
     spawn_background_task(
@@ -225,6 +280,26 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
                     SupervisorCmd::DumpState(reply_to) => {
                         dump_state(&registry.read(), &workers.acquire_read(), reply_to);
                     }
+                    SupervisorCmd::ReloadConfig(reply_to) => {
+                        let _ = reply_to.try_send(reloader.reload());
+                    }
+                    SupervisorCmd::PauseObject(object, reply_to) => {
+                        pause_object(&mut workers.acquire_write(), &object);
+                        let _ = reply_to.try_send(Ok(()));
+                    }
+                    SupervisorCmd::ResumeObject(object, reply_to) => {
+                        let result = resume_object(
+                            &config.acquire_read(),
+                            &mut registry.write(),
+                            &mut workers.acquire_write(),
+                            &object,
+                        );
+                        let _ = reply_to.try_send(result.map(|_| ()));
+                    }
+                    SupervisorCmd::ExcludeObject(object, reply_to) => {
+                        exclude_object(&mut workers.acquire_write(), &object);
+                        let _ = reply_to.try_send(Ok(()));
+                    }
                 }
             }
 
@@ -234,16 +309,17 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
 }
 
 pub fn spawn_rest_worker<Chain: ChainHandle>(
-    config: Config,
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
+    reloader: ConfigReloader<Chain>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&config, &registry, &workers, &reloader, &rest_rx);
 
             Ok(Next::Continue)
         },
@@ -561,35 +637,270 @@ fn dump_state<Chain: ChainHandle>(
 /// as a [`SupervisorState`].
 fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) -> SupervisorState {
     let chains = registry.chains().map(|c| c.id()).collect_vec();
-    SupervisorState::new(chains, workers.handles())
+    SupervisorState::new(chains, workers.handles()).with_excluded(workers.excluded())
 }
 
-fn handle_rest_requests<Chain: ChainHandle>(
+/// Pause the worker for the given object, see [`WorkerMap::pause`].
+fn pause_object(workers: &mut WorkerMap, object: &Object) -> Option<WorkerDesc> {
+    info!(object = %object.short_name(), "pausing worker");
+
+    workers.pause(object).map(WorkerDesc::from)
+}
+
+/// Stop the worker for the given object and exclude it, see [`WorkerMap::exclude`].
+fn exclude_object(workers: &mut WorkerMap, object: &Object) {
+    info!(object = %object.short_name(), "excluding object");
+
+    workers.exclude(object);
+}
+
+/// Lift the pause or the exclusion of the given object, see [`WorkerMap::resume`],
+/// spawning its worker again if it was excluded, and clearing its pending packets.
+fn resume_object<Chain: ChainHandle>(
     config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    registry: &mut Registry<Chain>,
+    workers: &mut WorkerMap,
+    object: &Object,
+) -> Result<Option<WorkerDesc>, Error> {
+    info!(object = %object.short_name(), "resuming worker");
+
+    let excluded = workers.is_excluded(object);
+
+    if let Some(desc) = workers.resume(object).map(WorkerDesc::from) {
+        return Ok(Some(desc));
+    }
+
+    if !excluded {
+        return Ok(None);
+    }
+
+    let src = registry
+        .get_or_spawn(object.src_chain_id())
+        .map_err(Error::spawn)?;
+
+    let dst = registry
+        .get_or_spawn(object.dst_chain_id())
+        .map_err(Error::spawn)?;
+
+    let worker = workers.get_or_spawn(object.clone(), src, dst, config);
+
+    // Relay the packets whose events were dropped while the object was excluded.
+    if let Some(worker) = worker {
+        worker.clear_pending_packets();
+    }
+
+    Ok(worker.map(WorkerDesc::from))
+}
+
+fn handle_rest_requests<Chain: ChainHandle>(
+    config: &Arc<RwLock<Config>>,
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    reloader: &ConfigReloader<Chain>,
     rest_rx: &rest::Receiver,
 ) {
-    if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+    let cmd = rest::process_incoming_requests(&config.acquire_read(), rest_rx);
+
+    if let Some(cmd) = cmd {
+        handle_rest_cmd(config, registry, workers, reloader, cmd);
     }
 }
 
 fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    config: &Arc<RwLock<Config>>,
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    reloader: &ConfigReloader<Chain>,
     m: rest::Command,
 ) {
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&registry.read(), &workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+        rest::Command::ReloadConfig(reply) => {
+            let result = reloader
+                .reload()
+                .map_err(|e| rest::RestApiError::ConfigReload(e.to_string()));
+
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ClearPackets {
+            chain_id,
+            port_id,
+            channel_id,
+            reply_to,
+        } => {
+            let result =
+                clear_packets_on_channel(&workers.acquire_read(), &chain_id, &port_id, &channel_id);
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::PauseWorker(worker, reply_to) => {
+            let mut workers = workers.acquire_write();
+
+            let result =
+                selected_object(&workers, worker).map(|object| pause_object(&mut workers, &object));
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ResumeWorker(worker, reply_to) => {
+            let config = config.acquire_read();
+            let mut registry = registry.write();
+            let mut workers = workers.acquire_write();
+
+            let result = selected_object(&workers, worker).and_then(|object| {
+                resume_object(&config, &mut registry, &mut workers, &object)
+                    .map_err(|e| rest::RestApiError::WorkerSpawn(e.to_string()))
+            });
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ExcludeWorker(worker, reply_to) => {
+            let mut workers = workers.acquire_write();
+
+            let result = selected_object(&workers, worker).map(|object| {
+                exclude_object(&mut workers, &object);
+                object
+            });
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::UpdateClient {
+            chain_id,
+            client_id,
+            reply_to,
+        } => {
+            // Updating the client waits for the transaction to be committed,
+            // so do it in the background to keep serving the other requests.
+            let registry = registry.clone();
+
+            thread::spawn(move || {
+                let result = update_client(&registry, &chain_id, &client_id)
+                    .map_err(|e| rest::RestApiError::ClientUpdate(client_id, chain_id, e));
+
+                reply_to
+                    .send(result)
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            });
+        }
+        rest::Command::ScanChain(chain_id, reply_to) => {
+            let running = workers
+                .acquire_read()
+                .handles()
+                .map(|handle| handle.id())
+                .collect::<BTreeSet<_>>();
+
+            let result = reloader
+                .rescan_chain(&chain_id)
+                .map(|()| {
+                    workers
+                        .acquire_read()
+                        .handles()
+                        .filter(|handle| !running.contains(&handle.id()))
+                        .map(WorkerDesc::from)
+                        .collect()
+                })
+                .map_err(|e| rest::RestApiError::Scan(chain_id, e.to_string()));
+
+            reply_to
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
     }
 }
 
+/// Instructs the packet workers relaying from the given channel end to clear
+/// the pending packets, and returns their ids.
+fn clear_packets_on_channel(
+    workers: &WorkerMap,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Vec<WorkerId>, rest::RestApiError> {
+    let cleared = workers
+        .handles()
+        .filter(|handle| {
+            matches!(
+                handle.object(),
+                Object::Packet(p) if &p.src_chain_id == chain_id
+                    && &p.src_port_id == port_id
+                    && &p.src_channel_id == channel_id
+            )
+        })
+        .map(|handle| {
+            info!(worker = %handle.object().short_name(), "clearing pending packets");
+            handle.clear_pending_packets();
+            handle.id()
+        })
+        .collect_vec();
+
+    if cleared.is_empty() {
+        return Err(rest::RestApiError::WorkerNotFound(format!(
+            "a packet worker for channel {}/{} on chain {}",
+            port_id, channel_id, chain_id
+        )));
+    }
+
+    Ok(cleared)
+}
+
+/// Returns the object of the selected worker. The worker selected by its id must be running,
+/// while an object may be selected whether or not a worker is running for it.
+fn selected_object(
+    workers: &WorkerMap,
+    worker: WorkerSelector,
+) -> Result<Object, rest::RestApiError> {
+    match worker {
+        WorkerSelector::Id(id) => workers
+            .handles()
+            .find(|handle| handle.id() == id)
+            .map(|handle| handle.object().clone())
+            .ok_or_else(|| rest::RestApiError::WorkerNotFound(worker.to_string())),
+        WorkerSelector::Object(object) => Ok(object),
+    }
+}
+
+/// Builds and sends an update for the client hosted on the given chain,
+/// up to the latest height of the chain it tracks.
+fn update_client<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+) -> Result<Vec<IbcEvent>, String> {
+    let host_chain = registry.get_or_spawn(chain_id).map_err(|e| e.to_string())?;
+
+    let (client_state, _) = host_chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: HeightQuery::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let src_chain = registry
+        .get_or_spawn(&client_state.chain_id())
+        .map_err(|e| e.to_string())?;
+
+    ForeignClient::restore(client_id.clone(), host_chain, src_chain)
+        .build_latest_update_client_and_send()
+        .map_err(|e| e.to_string())
+}
+
 fn clear_pending_packets(workers: &mut WorkerMap, chain_id: &ChainId) -> Result<(), Error> {
     for worker in workers.workers_for_chain(chain_id) {
         worker.clear_pending_packets();
@@ -640,6 +951,25 @@ fn process_batch<Chain: ChainHandle>(
             continue;
         }
 
+        if workers.is_excluded(&object) {
+            trace!(
+                "skipping events for '{}'. reason: the object is excluded",
+                object.short_name()
+            );
+
+            continue;
+        }
+
+        // The packets of the events dropped while paused are cleared once resumed.
+        if workers.is_paused(&object) {
+            trace!(
+                "skipping events for '{}'. reason: the object is paused",
+                object.short_name()
+            );
+
+            continue;
+        }
+
         let src = registry
             .get_or_spawn(object.src_chain_id())
             .map_err(Error::spawn)?;
@@ -648,14 +978,18 @@ fn process_batch<Chain: ChainHandle>(
             .get_or_spawn(object.dst_chain_id())
             .map_err(Error::spawn)?;
 
-        let worker = workers.get_or_spawn(object, src, dst, config);
+        if let Some(worker) = workers.get_or_spawn(object, src, dst, config) {
+            worker.send_events(
+                batch.height,
+                events,
+                batch.chain_id.clone(),
+                batch.tracking_id,
+            );
+        }
+    }
 
-        worker.send_events(
-            batch.height,
-            events,
-            batch.chain_id.clone(),
-            batch.tracking_id,
-        );
+    if let Some(store) = store::global() {
+        store.save_last_height(&batch.chain_id, batch.height);
     }
 
     Ok(())
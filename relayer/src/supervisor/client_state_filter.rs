@@ -66,7 +66,7 @@ impl FilterError {
 
 /// A cache storing filtering status (allow or deny) for
 /// arbitrary identifiers.
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct FilterPolicy {
     /// A cache associating a generic identifying key, such as
     /// client id, channel id, or connection id, with an
@@ -75,6 +75,11 @@ pub struct FilterPolicy {
 }
 
 impl FilterPolicy {
+    /// Adds the statuses cached by a copy of this policy.
+    pub fn merge(&mut self, other: Self) {
+        self.permission_cache.extend(other.permission_cache);
+    }
+
     /// Given a connection end and the underlying client for that
     /// connection, controls both the client as well as the
     /// client on the counterparty chain.
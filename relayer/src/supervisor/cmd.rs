@@ -1,8 +1,22 @@
 use crossbeam_channel::Sender;
 
+use crate::object::Object;
+
 use super::dump_state::SupervisorState;
+use super::reload::ConfigReload;
+use super::Error;
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+    /// Reload the configuration from its file, see [`super::reload::ConfigReloader`].
+    ReloadConfig(Sender<Result<ConfigReload, Error>>),
+    /// Pause the worker for the object, and the workers spawned for it later on.
+    PauseObject(Object, Sender<Result<(), Error>>),
+    /// Lift the pause or the exclusion of the object, spawning its worker again
+    /// if it was excluded.
+    ResumeObject(Object, Sender<Result<(), Error>>),
+    /// Stop the worker for the object, and do not spawn any worker for it until
+    /// it is resumed.
+    ExcludeObject(Object, Sender<Result<(), Error>>),
 }
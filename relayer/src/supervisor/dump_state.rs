@@ -16,11 +16,27 @@ pub struct WorkerDesc {
     pub id: WorkerId,
     pub object: Object,
     pub data: Option<WorkerData>,
+    #[serde(default)]
+    pub paused: bool,
 }
 
 impl WorkerDesc {
     pub fn new(id: WorkerId, object: Object, data: Option<WorkerData>) -> Self {
-        Self { id, object, data }
+        Self {
+            id,
+            object,
+            data,
+            paused: false,
+        }
+    }
+}
+
+impl From<&WorkerHandle> for WorkerDesc {
+    fn from(handle: &WorkerHandle) -> Self {
+        Self {
+            paused: handle.is_paused(),
+            ..Self::new(handle.id(), handle.object().clone(), handle.data().cloned())
+        }
     }
 }
 
@@ -28,6 +44,9 @@ impl WorkerDesc {
 pub struct SupervisorState {
     pub chains: Vec<ChainId>,
     pub workers: BTreeMap<ObjectType, Vec<WorkerDesc>>,
+    /// The objects for which no worker is spawned.
+    #[serde(default)]
+    pub excluded: Vec<Object>,
 }
 
 impl SupervisorState {
@@ -38,13 +57,22 @@ impl SupervisorState {
         chains.sort();
 
         let workers = workers
-            .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data().cloned()))
+            .map(WorkerDesc::from)
             .into_group_map_by(|desc| desc.object.object_type())
             .into_iter()
             .update(|(_, os)| os.sort_by_key(|desc| desc.object.short_name()))
             .collect::<BTreeMap<_, _>>();
 
-        Self { chains, workers }
+        Self {
+            chains,
+            workers,
+            excluded: Vec::new(),
+        }
+    }
+
+    pub fn with_excluded<'a>(mut self, excluded: impl Iterator<Item = &'a Object>) -> Self {
+        self.excluded = excluded.cloned().collect();
+        self
     }
 
     pub fn print_info(&self) {
@@ -61,7 +89,14 @@ impl fmt::Display for SupervisorState {
         for (tpe, objects) in &self.workers {
             writeln!(f, "* {tpe:?} workers:")?;
             for desc in objects {
-                writeln!(f, "  - {} (id: {})", desc.object.short_name(), desc.id)?;
+                let paused = if desc.paused { ", paused" } else { "" };
+                writeln!(
+                    f,
+                    "  - {} (id: {}{})",
+                    desc.object.short_name(),
+                    desc.id,
+                    paused
+                )?;
                 if let Some(WorkerData::Client {
                     misbehaviour,
                     refresh,
@@ -71,6 +106,12 @@ impl fmt::Display for SupervisorState {
                 }
             }
         }
+        if !self.excluded.is_empty() {
+            writeln!(f, "* Excluded:")?;
+            for object in &self.excluded {
+                writeln!(f, "  - {}", object.short_name())?;
+            }
+        }
 
         Ok(())
     }
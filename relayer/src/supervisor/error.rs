@@ -3,6 +3,7 @@ use flex_error::define_error;
 use ibc::core::ics03_connection::connection::Counterparty;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
 
+use crate::config::Error as ConfigError;
 use crate::error::Error as RelayerError;
 use crate::spawn::SpawnError;
 use crate::supervisor::scan::Error as ScanError;
@@ -69,6 +70,21 @@ define_error! {
         Scan
             [ ScanError ]
             |_| { "supervisor encountered an error when scanning chains" },
+
+        ConfigPathUnavailable
+            |_| { "the path of the configuration file is unknown, cannot reload it" },
+
+        ConfigReload
+            [ ConfigError ]
+            |_| { "failed to reload the configuration" },
+
+        DuplicateChain
+            { chain_id: ChainId }
+            |e| { format_args!("chain {} is configured more than once", e.chain_id) },
+
+        UnknownChain
+            { chain_id: ChainId }
+            |e| { format_args!("chain {} is not in the configuration", e.chain_id) },
     }
 }
 
@@ -0,0 +1,530 @@
+//! Reloading of the configuration while the supervisor is running, restarting only the
+//! chains and the workers affected by the changes.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::{
+    chain::handle::ChainHandle,
+    config::{self, ChainConfig, Config},
+    object::Object,
+    registry::SharedRegistry,
+    util::{
+        diff::{gdiff, Change},
+        lock::LockExt,
+        task::TaskHandle,
+    },
+    worker::WorkerMap,
+};
+
+use super::{
+    chain_scanner, client_state_filter::FilterPolicy, scan::ScanMode, spawn_batch_worker,
+    spawn_context, Error,
+};
+
+/// The tasks processing the event batches of the chains.
+pub type BatchTasks = Arc<Mutex<BTreeMap<ChainId, TaskHandle>>>;
+
+/// The changes applied by a configuration reload.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigReload {
+    /// The chains which were added to the configuration.
+    pub added: Vec<ChainId>,
+    /// The chains which were removed from the configuration.
+    pub removed: Vec<ChainId>,
+    /// The chains whose configuration changed, and which were restarted.
+    pub restarted: Vec<ChainId>,
+    /// The chains whose packet filter only changed, and whose workers were
+    /// started or stopped accordingly.
+    pub refiltered: Vec<ChainId>,
+    /// The sections of the configuration which changed,
+    /// but only take effect when the relayer is restarted.
+    pub ignored: Vec<String>,
+}
+
+impl ConfigReload {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.restarted.is_empty()
+            && self.refiltered.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigReload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no chain changed");
+        }
+
+        let changes = [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("restarted", &self.restarted),
+            ("refiltered", &self.refiltered),
+        ];
+
+        let changes = changes
+            .iter()
+            .filter(|(_, chains)| !chains.is_empty())
+            .map(|(change, chains)| format!("{} [{}]", change, chains.iter().join(", ")))
+            .join(", ");
+
+        write!(f, "{}", changes)
+    }
+}
+
+/// Applies the changes of the configuration to the running supervisor.
+#[derive(Clone)]
+pub struct ConfigReloader<Chain: ChainHandle> {
+    config_path: Option<PathBuf>,
+    config: Arc<RwLock<Config>>,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    batch_tasks: BatchTasks,
+}
+
+impl<Chain: ChainHandle> ConfigReloader<Chain> {
+    pub fn new(
+        config_path: Option<PathBuf>,
+        config: Arc<RwLock<Config>>,
+        registry: SharedRegistry<Chain>,
+        client_state_filter: Arc<RwLock<FilterPolicy>>,
+        workers: Arc<RwLock<WorkerMap>>,
+        batch_tasks: BatchTasks,
+    ) -> Self {
+        Self {
+            config_path,
+            config,
+            registry,
+            client_state_filter,
+            workers,
+            batch_tasks,
+        }
+    }
+
+    /// Reloads the configuration from the file the relayer was started with.
+    pub fn reload(&self) -> Result<ConfigReload, Error> {
+        let path = self
+            .config_path
+            .as_ref()
+            .ok_or_else(Error::config_path_unavailable)?;
+
+        info!("reloading the configuration from {}", path.display());
+
+        let config = config::load(path).map_err(Error::config_reload)?;
+
+        self.apply(config)
+    }
+
+    /// Applies the changes between the current configuration and `next`.
+    ///
+    /// The chains which were removed or whose configuration changed are stopped, along with
+    /// all the workers relaying to or from them, then the chains which were added or changed
+    /// are scanned and started. When only the packet filter of a chain changed, the chain
+    /// keeps running, and only the workers on the channels which are no longer allowed are
+    /// stopped, while those on the newly allowed channels are started.
+    ///
+    /// The `global`, `rest`, `telemetry` and `store` sections are only read when the relayer
+    /// starts, so their changes are ignored.
+    pub fn apply(&self, mut next: Config) -> Result<ConfigReload, Error> {
+        let prev = self.config.acquire_read().clone();
+
+        let reload = config_changes(&prev, &mut next)?;
+
+        if !reload.ignored.is_empty() {
+            warn!(
+                "the changes to the [{}] section(s) only take effect after a restart",
+                reload.ignored.join("], [")
+            );
+        }
+
+        let stopped = reload.removed.iter().chain(&reload.restarted).collect_vec();
+
+        // Stop processing the events of the stopped chains first, since the tasks doing so
+        // take the locks below.
+        for chain_id in &stopped {
+            let task = self.batch_tasks.lock().unwrap().remove(chain_id);
+
+            if let Some(task) = task {
+                task.shutdown_and_wait();
+            }
+        }
+
+        // The locks are taken in the same order as the tasks processing the events do,
+        // and held until the new configuration is in place, so that no worker gets spawned
+        // in the meantime for the stopped chains, with the previous configuration.
+        {
+            let mut config = self.config.acquire_write();
+            let mut registry = self.registry.write();
+            let mut workers = self.workers.acquire_write();
+
+            for chain_id in &stopped {
+                info!(chain = %chain_id, "stopping chain");
+
+                for object in workers.objects_for_chain(chain_id) {
+                    workers.shutdown_worker(&object);
+                }
+
+                registry.shutdown(chain_id);
+            }
+
+            for chain_id in &reload.refiltered {
+                for object in filtered_out(&next, &workers, chain_id) {
+                    info!(chain = %chain_id, "stopping worker {}", object.short_name());
+                    workers.shutdown_worker(&object);
+                }
+            }
+
+            registry.set_config(next.clone());
+            *config = next.clone();
+        }
+
+        for chain_id in reload.added.iter().chain(&reload.restarted) {
+            self.start_chain(&next, chain_id);
+        }
+
+        for chain_id in &reload.refiltered {
+            if let Err(e) = self.spawn_workers(&next, chain_id) {
+                error!(chain = %chain_id, "failed to scan chain: {}", e);
+            }
+        }
+
+        info!("configuration reloaded: {}", reload);
+
+        Ok(reload)
+    }
+
+    /// Scans the chain and spawns its workers, then subscribes to its events.
+    fn start_chain(&self, config: &Config, chain_id: &ChainId) {
+        info!(chain = %chain_id, "starting chain");
+
+        if let Err(e) = self.spawn_workers(config, chain_id) {
+            error!(chain = %chain_id, "failed to scan chain: {}", e);
+        }
+
+        let chain = match self.registry.get_or_spawn(chain_id) {
+            Ok(chain) => chain,
+            Err(e) => {
+                error!(chain = %chain_id, "failed to spawn chain runtime: {}", e);
+                return;
+            }
+        };
+
+        match chain.subscribe() {
+            Ok(subscription) => {
+                let task = spawn_batch_worker(
+                    self.config.clone(),
+                    self.registry.clone(),
+                    self.client_state_filter.clone(),
+                    self.workers.clone(),
+                    chain,
+                    subscription,
+                );
+
+                self.batch_tasks
+                    .lock()
+                    .unwrap()
+                    .insert(chain_id.clone(), task);
+            }
+            Err(e) => error!(chain = %chain_id, "failed to subscribe to events: {}", e),
+        }
+    }
+
+    /// Scans the chain again with the current configuration, and spawns the workers
+    /// for the objects found which are not running yet.
+    pub fn rescan_chain(&self, chain_id: &ChainId) -> Result<(), Error> {
+        let config = self.config.acquire_read().clone();
+
+        self.spawn_workers(&config, chain_id)
+    }
+
+    /// Scans the chain and spawns the workers which are not running yet.
+    ///
+    /// The chain is scanned with copies of the registry and of the client state filter, so
+    /// that the events of the other chains keep being processed meanwhile. The locks are
+    /// only taken to spawn the workers, in the same order as the tasks processing the events.
+    fn spawn_workers(&self, config: &Config, chain_id: &ChainId) -> Result<(), Error> {
+        let chain_config = config
+            .find_chain(chain_id)
+            .ok_or_else(|| Error::unknown_chain(chain_id.clone()))?;
+
+        let mut scan_registry = self.registry.read().fork();
+        let mut scan_filter = self.client_state_filter.acquire_read().clone();
+
+        let scan = chain_scanner(config, &mut scan_registry, &mut scan_filter, ScanMode::Auto)
+            .scan_chain(chain_config)
+            .map_err(Error::scan)?;
+
+        let config = self.config.acquire_read();
+        let mut registry = self.registry.write();
+        registry.merge(scan_registry);
+        self.client_state_filter.acquire_write().merge(scan_filter);
+
+        // The chain may have been removed by a reload while it was scanned.
+        if config.find_chain(chain_id).is_none() {
+            return Err(Error::unknown_chain(chain_id.clone()));
+        }
+
+        spawn_context(&config, &mut registry, &mut self.workers.acquire_write())
+            .spawn_workers_for_chain(scan);
+
+        Ok(())
+    }
+}
+
+/// Sorts out the chains added, removed, changed, or whose packet filter only changed,
+/// between the configurations `prev` and `next`, and the sections which are only read when
+/// the relayer starts, whose changes are reverted in `next`.
+fn config_changes(prev: &Config, next: &mut Config) -> Result<ConfigReload, Error> {
+    let mut reload = ConfigReload::default();
+
+    for (section, changed) in [
+        ("global", !same(&prev.global, &next.global)),
+        ("rest", !same(&prev.rest, &next.rest)),
+        ("telemetry", !same(&prev.telemetry, &next.telemetry)),
+        ("store", !same(&prev.store, &next.store)),
+    ] {
+        if changed {
+            reload.ignored.push(section.to_string());
+        }
+    }
+
+    next.global = prev.global.clone();
+    next.rest = prev.rest.clone();
+    next.telemetry = prev.telemetry.clone();
+    next.store = prev.store.clone();
+
+    let prev_chains = chains_by_id(prev)?;
+    let next_chains = chains_by_id(next)?;
+
+    for change in gdiff(&prev_chains, &next_chains, same) {
+        match change {
+            Change::Added(chain_id) => reload.added.push(chain_id.clone()),
+            Change::Removed(chain_id) => reload.removed.push(chain_id.clone()),
+            Change::Updated(chain_id)
+                if only_packet_filter_changed(&prev_chains[chain_id], &next_chains[chain_id]) =>
+            {
+                reload.refiltered.push(chain_id.clone())
+            }
+            Change::Updated(chain_id) => reload.restarted.push(chain_id.clone()),
+        }
+    }
+
+    Ok(reload)
+}
+
+fn chains_by_id(config: &Config) -> Result<BTreeMap<ChainId, ChainConfig>, Error> {
+    let mut chains = BTreeMap::new();
+
+    for chain in &config.chains {
+        if chains.insert(chain.id.clone(), chain.clone()).is_some() {
+            return Err(Error::duplicate_chain(chain.id.clone()));
+        }
+    }
+
+    Ok(chains)
+}
+
+/// Compares two configuration values through their serialization,
+/// since the configuration types do not implement `Eq`.
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn only_packet_filter_changed(prev: &ChainConfig, next: &ChainConfig) -> bool {
+    let mut next = next.clone();
+    next.packet_filter = prev.packet_filter.clone();
+
+    same(prev, &next)
+}
+
+/// The workers relaying from the chain on the channels which its packet filter disallows.
+fn filtered_out(config: &Config, workers: &WorkerMap, chain_id: &ChainId) -> Vec<Object> {
+    workers
+        .objects_for_chain(chain_id)
+        .into_iter()
+        .filter(|object| match object {
+            Object::Packet(p) => {
+                &p.src_chain_id == chain_id
+                    && !config.packets_on_channel_allowed(
+                        chain_id,
+                        &p.src_port_id,
+                        &p.src_channel_id,
+                    )
+            }
+            Object::Channel(c) => {
+                &c.src_chain_id == chain_id
+                    && !config.packets_on_channel_allowed(
+                        chain_id,
+                        &c.src_port_id,
+                        &c.src_channel_id,
+                    )
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    use super::{config_changes, filtered_out, ConfigReload};
+    use crate::chain::handle::BaseChainHandle;
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::chain::mock::MockChain;
+    use crate::chain::runtime::ChainRuntime;
+    use crate::config::types::MaxMsgNum;
+    use crate::config::{self, Config, PacketFilter};
+    use crate::object::{Channel, Object};
+    use crate::worker::WorkerMap;
+
+    fn config() -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        config::load(path).expect("could not parse config")
+    }
+
+    fn packet_filter(toml_content: &str) -> PacketFilter {
+        toml::from_str(toml_content).expect("could not parse packet filter")
+    }
+
+    fn chain_ids(ids: &[&str]) -> Vec<ChainId> {
+        ids.iter().map(|id| ChainId::from_string(id)).collect()
+    }
+
+    #[test]
+    fn unchanged_config() {
+        let prev = config();
+        let mut next = config();
+
+        let reload = config_changes(&prev, &mut next).unwrap();
+
+        assert!(reload.is_empty());
+        assert_eq!(reload, ConfigReload::default());
+    }
+
+    #[test]
+    fn chains_added_removed_and_changed() {
+        let prev = config();
+        let mut next = config();
+
+        let mut chain_c = next.chains[1].clone();
+        chain_c.id = ChainId::from_string("chain_C");
+        next.chains.remove(1);
+        next.chains.push(chain_c);
+        next.chains[0].max_msg_num = MaxMsgNum::new(10).unwrap();
+
+        let reload = config_changes(&prev, &mut next).unwrap();
+
+        assert_eq!(reload.added, chain_ids(&["chain_C"]));
+        assert_eq!(reload.removed, chain_ids(&["chain_B"]));
+        assert_eq!(reload.restarted, chain_ids(&["chain_A"]));
+        assert!(reload.refiltered.is_empty());
+        assert!(reload.ignored.is_empty());
+    }
+
+    #[test]
+    fn only_packet_filter_changed() {
+        let prev = config();
+        let mut next = config();
+
+        next.chains[0].packet_filter = packet_filter("policy = 'allow'\nlist = [['ica*', '*']]");
+        next.chains[1].packet_filter = packet_filter("policy = 'deny'\nlist = [['ica*', '*']]");
+        next.chains[1].key_name = "otherkey".to_string();
+
+        let reload = config_changes(&prev, &mut next).unwrap();
+
+        assert_eq!(reload.refiltered, chain_ids(&["chain_A"]));
+        assert_eq!(reload.restarted, chain_ids(&["chain_B"]));
+    }
+
+    #[test]
+    fn startup_sections_are_ignored() {
+        let prev = config();
+        let mut next = config();
+
+        next.global.dry_run = true;
+        next.telemetry.port += 1;
+
+        let reload = config_changes(&prev, &mut next).unwrap();
+
+        assert!(reload.is_empty());
+        assert_eq!(reload.ignored, vec!["global", "telemetry"]);
+        assert!(!next.global.dry_run);
+        assert_eq!(next.telemetry.port, prev.telemetry.port);
+    }
+
+    #[test]
+    fn duplicate_chains_are_rejected() {
+        let prev = config();
+        let mut next = config();
+
+        next.chains.push(next.chains[0].clone());
+
+        assert!(config_changes(&prev, &mut next).is_err());
+    }
+
+    #[test]
+    fn workers_on_disallowed_channels_are_filtered_out() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let chain = ChainRuntime::<MockChain>::spawn::<BaseChainHandle>(
+            get_basic_chain_config("chain_A"),
+            rt,
+        )
+        .unwrap();
+
+        let mut config = config();
+        config.chains[0].packet_filter =
+            packet_filter("policy = 'allow'\nlist = [['transfer', 'channel-0']]");
+
+        let channel = |src_chain_id: &str, src_channel_id: &str| {
+            let src_chain_id = ChainId::from_string(src_chain_id);
+            let dst_chain_id = if src_chain_id.as_str() == "chain_A" {
+                ChainId::from_string("chain_B")
+            } else {
+                ChainId::from_string("chain_A")
+            };
+
+            Object::Channel(Channel {
+                dst_chain_id,
+                src_chain_id,
+                src_channel_id: src_channel_id.parse().unwrap(),
+                src_port_id: "transfer".parse().unwrap(),
+            })
+        };
+
+        let allowed = channel("chain_A", "channel-0");
+        let disallowed = channel("chain_A", "channel-1");
+        let counterparty = channel("chain_B", "channel-1");
+
+        let mut workers = WorkerMap::new();
+        for object in [&allowed, &disallowed, &counterparty] {
+            workers.spawn(chain.clone(), chain.clone(), object, &config);
+        }
+
+        let chain_id = ChainId::from_string("chain_A");
+        assert_eq!(filtered_out(&config, &workers, &chain_id), vec![disallowed]);
+    }
+}
@@ -1,5 +1,5 @@
 use core::fmt;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use itertools::Itertools;
 use tracing::{debug, error, info, info_span, warn};
@@ -19,13 +19,14 @@ use crate::{
         counterparty::{channel_on_destination, connection_state_on_destination},
         handle::ChainHandle,
         requests::{
-            HeightQuery, IncludeProof, PageRequest, QueryChannelRequest,
+            HeightQuery, IncludeProof, PageRequest, QueryChannelRequest, QueryChannelsRequest,
             QueryClientConnectionsRequest, QueryClientStateRequest, QueryClientStatesRequest,
-            QueryConnectionChannelsRequest, QueryConnectionRequest,
+            QueryConnectionChannelsRequest, QueryConnectionRequest, QueryConnectionsRequest,
         },
     },
     config::{filter::ChannelFilters, ChainConfig, Config, PacketFilter},
     registry::Registry,
+    store::{self, ScanCheckpoint},
     supervisor::client_state_filter::{FilterPolicy, Permission},
 };
 
@@ -312,8 +313,45 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
                 self.query_allowed_channels(&chain, spec, &mut scan)?;
             }
             _ => {
-                info!("scanning chain for all clients, connections and channels");
-                self.scan_all_clients(&chain, &mut scan)?;
+                // The connections and channels of the chain are only queried when the state
+                // store is enabled, to check the last full scan against them and to save the
+                // next one.
+                let chain_objects = match store::global() {
+                    Some(_) => Some(query_chain_objects(&chain)?),
+                    None => None,
+                };
+
+                match chain_objects
+                    .as_ref()
+                    .and_then(|objects| self.current_checkpoint(chain_config, objects))
+                {
+                    Some(checkpoint) => {
+                        info!(
+                            "resuming from the {} channel(s) and {} connection(s) found by the \
+                             last full scan, skipping scan for fast startup",
+                            checkpoint.channels.len(),
+                            checkpoint.connections.len(),
+                        );
+
+                        self.query_channels(
+                            &chain,
+                            checkpoint
+                                .channels
+                                .iter()
+                                .map(|(port_id, channel_id)| (port_id, channel_id)),
+                            &mut scan,
+                        )?;
+                        self.query_connections(&chain, &checkpoint.connections, &mut scan);
+                    }
+                    None => {
+                        info!("scanning chain for all clients, connections and channels");
+                        self.scan_all_clients(&chain, &mut scan)?;
+
+                        if let Some(objects) = chain_objects {
+                            checkpoint_scan(chain_config, &scan, objects);
+                        }
+                    }
+                }
             }
         };
 
@@ -328,7 +366,16 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
     ) -> Result<(), Error> {
         info!("querying allowed channels...");
 
-        for (port_id, channel_id) in filters.iter_exact() {
+        self.query_channels(chain, filters.iter_exact(), scan)
+    }
+
+    fn query_channels<'b>(
+        &mut self,
+        chain: &Chain,
+        channels: impl Iterator<Item = (&'b PortId, &'b ChannelId)>,
+        scan: &mut ChainScan,
+    ) -> Result<(), Error> {
+        for (port_id, channel_id) in channels {
             let result = scan_allowed_channel(self.registry, chain, port_id, channel_id);
 
             match result {
@@ -363,6 +410,45 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
         Ok(())
     }
 
+    /// Scans the connections found without any channel by the last full scan, e.g. those
+    /// which were being opened.
+    fn query_connections(
+        &mut self,
+        chain: &Chain,
+        connection_ids: &[ConnectionId],
+        scan: &mut ChainScan,
+    ) {
+        for connection_id in connection_ids {
+            let result = query_connection(chain, connection_id).and_then(|connection| {
+                let client = query_client(chain, connection.connection_end.client_id())?;
+                Ok((client, connection))
+            });
+
+            let (client, connection) = match result {
+                Ok(found) => found,
+                Err(e) => {
+                    error!(connection = %connection_id, "failed to scan connection, reason: {}", e);
+                    continue;
+                }
+            };
+
+            match self.scan_connection(chain, &client, connection) {
+                Ok(Some(connection_scan)) => {
+                    scan.clients
+                        .entry(client.client_id.clone())
+                        .or_insert_with(|| ClientScan::new(client))
+                        .connections
+                        .entry(connection_scan.id().clone())
+                        .or_insert(connection_scan);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error!(connection = %connection_id, "failed to scan connection, reason: {}", e)
+                }
+            }
+        }
+    }
+
     pub fn scan_all_clients(&mut self, chain: &Chain, scan: &mut ChainScan) -> Result<(), Error> {
         info!("scanning all clients...");
 
@@ -520,6 +606,28 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
         Ok(counterparty_state)
     }
 
+    /// Returns the last full scan of the chain, as saved in the state store, unless a full scan
+    /// was requested, or connections or channels were opened or closed on the chain since.
+    fn current_checkpoint(
+        &self,
+        chain_config: &ChainConfig,
+        chain_objects: &ChainObjects,
+    ) -> Option<ScanCheckpoint> {
+        if self.scan_mode == ScanMode::Full {
+            return None;
+        }
+
+        let checkpoint =
+            store::global()?.scan_checkpoint(&chain_config.id, &chain_config.packet_filter)?;
+
+        if !checkpoint.is_current(&chain_objects.connections, &chain_objects.channels) {
+            info!("connections or channels were opened or closed since the last full scan");
+            return None;
+        }
+
+        Some(checkpoint)
+    }
+
     fn filtering_enabled(&self) -> bool {
         // filtering is always enabled
         true
@@ -601,6 +709,73 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
     }
 }
 
+/// All the connections and channels of a chain.
+struct ChainObjects {
+    connections: BTreeSet<ConnectionId>,
+    channels: BTreeSet<(PortId, ChannelId)>,
+}
+
+fn query_chain_objects<Chain: ChainHandle>(chain: &Chain) -> Result<ChainObjects, Error> {
+    let connections = chain
+        .query_connections(QueryConnectionsRequest {
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::query)?
+        .into_iter()
+        .map(|connection| connection.connection_id)
+        .collect();
+
+    let channels = chain
+        .query_channels(QueryChannelsRequest {
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::query)?
+        .into_iter()
+        .map(|channel| (channel.port_id, channel.channel_id))
+        .collect();
+
+    Ok(ChainObjects {
+        connections,
+        channels,
+    })
+}
+
+/// Saves the channels and the connections without channels found by a full scan of the chain
+/// to the state store, along with all the connections and channels of the chain, for the next
+/// start to only query these objects, unless others were opened since.
+fn checkpoint_scan(chain_config: &ChainConfig, scan: &ChainScan, chain_objects: ChainObjects) {
+    let store = match store::global() {
+        Some(store) => store,
+        None => return,
+    };
+
+    let connections = scan
+        .clients
+        .values()
+        .flat_map(|client| client.connections.values());
+
+    let channels = connections
+        .clone()
+        .flat_map(|connection| connection.channels.values())
+        .map(|channel| (channel.channel.port_id.clone(), channel.channel.channel_id))
+        .collect();
+
+    let connections = connections
+        .filter(|connection| connection.channels.is_empty())
+        .map(|connection| connection.id().clone())
+        .collect();
+
+    let checkpoint = ScanCheckpoint::new(
+        &chain_config.packet_filter,
+        channels,
+        connections,
+        chain_objects.connections,
+        chain_objects.channels,
+    );
+
+    store.save_scan_checkpoint(&chain_config.id, checkpoint);
+}
+
 struct ScannedChannel {
     channel: IdentifiedChannelEnd,
     counterparty_channel: Option<IdentifiedChannelEnd>,
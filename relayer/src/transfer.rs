@@ -138,6 +138,7 @@ pub fn build_transfer_message(
         receiver,
         timeout_height,
         timeout_timestamp,
+        memo: String::new(),
     };
 
     msg.to_any()
@@ -173,6 +174,7 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
         receiver,
         timeout_height: timeout.timeout_height,
         timeout_timestamp: timeout.timeout_timestamp,
+        memo: String::new(),
     };
 
     let raw_msg = msg.to_any();
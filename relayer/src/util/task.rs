@@ -8,13 +8,18 @@ use tracing::{debug, error, warn};
 
 use crate::util::lock::LockExt;
 
+/// How often a paused task checks whether it was resumed or shut down.
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /**
    A task handle holds the endpoints for stopping or waiting for a
    background task to terminate.
 
    A holder of `TaskHandle` can explicitly stop the background task by
    calling [`shutdown`](TaskHandle::shutdown) or
-   [`shutdown_and_wait`](TaskHandle::shutdown_and_wait).
+   [`shutdown_and_wait`](TaskHandle::shutdown_and_wait), or suspend it
+   with [`pause`](TaskHandle::pause) until [`resume`](TaskHandle::resume)
+   is called.
 
    Otherwise, when the `TaskHandle` is dropped, it will stop the background
    task and wait for the background task to terminate before returning.
@@ -22,6 +27,7 @@ use crate::util::lock::LockExt;
 pub struct TaskHandle {
     shutdown_sender: Sender<()>,
     stopped: Arc<RwLock<bool>>,
+    paused: Arc<RwLock<bool>>,
     join_handle: DropJoinHandle,
 }
 
@@ -95,6 +101,9 @@ pub fn spawn_background_task<E: Display>(
     let stopped = Arc::new(RwLock::new(false));
     let write_stopped = stopped.clone();
 
+    let paused = Arc::new(RwLock::new(false));
+    let read_paused = paused.clone();
+
     let (shutdown_sender, receiver) = bounded(1);
 
     let join_handle = thread::spawn(move || {
@@ -104,6 +113,10 @@ pub fn spawn_background_task<E: Display>(
                 Ok(()) => {
                     break;
                 }
+                Err(_) if *read_paused.acquire_read() => {
+                    thread::sleep(PAUSED_POLL_INTERVAL);
+                    continue;
+                }
                 _ => match step_runner() {
                     Ok(Next::Continue) => {}
                     Ok(Next::Abort) => {
@@ -132,6 +145,7 @@ pub fn spawn_background_task<E: Display>(
     TaskHandle {
         shutdown_sender,
         stopped,
+        paused,
         join_handle: DropJoinHandle(Some(join_handle)),
     }
 }
@@ -173,6 +187,29 @@ impl TaskHandle {
         let _ = self.shutdown_sender.send(());
     }
 
+    /**
+       Suspend the background task, which stops calling the step runner
+       until [`resume`](TaskHandle::resume) is called. The task can still
+       be shut down while paused.
+    */
+    pub fn pause(&self) {
+        *self.paused.acquire_write() = true;
+    }
+
+    /**
+       Resume the background task suspended by [`pause`](TaskHandle::pause).
+    */
+    pub fn resume(&self) {
+        *self.paused.acquire_write() = false;
+    }
+
+    /**
+       Check whether the background task is paused.
+    */
+    pub fn is_paused(&self) -> bool {
+        *self.paused.acquire_read()
+    }
+
     /**
        Check whether a background task has been stopped prematurely.
     */
@@ -11,6 +11,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store,
 };
 
 pub mod retry_strategy;
@@ -31,8 +32,11 @@ pub mod channel;
 pub mod client;
 pub mod connection;
 pub mod packet;
+pub mod turns;
 pub mod wallet;
 
+use turns::TurnScheduler;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct WorkerId(u64);
@@ -53,11 +57,14 @@ impl fmt::Display for WorkerId {
     }
 }
 
+/// Spawns the tasks of the worker for `object`. The packet workers take turns through
+/// `turns` with the other packet workers relaying to the same chain.
 pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     chains: ChainHandlePair<ChainA, ChainB>,
     id: WorkerId,
     object: Object,
     config: &Config,
+    turns: TurnScheduler,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
@@ -123,8 +130,14 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             match link_res {
                 Ok(link) => {
                     let channel_ordering = link.a_to_b.channel().ordering;
-                    let should_clear_on_start =
-                        packets_config.clear_on_start || channel_ordering == Order::Ordered;
+                    // The events of the blocks produced while the relayer was stopped are
+                    // missed, so their packets get cleared once the relayer is restarted.
+                    let restarted = store::global().map_or(false, |store| {
+                        store.restored_height(&path.src_chain_id).is_some()
+                    });
+                    let should_clear_on_start = packets_config.clear_on_start
+                        || channel_ordering == Order::Ordered
+                        || restarted;
 
                     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                     let link = Arc::new(Mutex::new(link));
@@ -136,10 +149,19 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                         should_clear_on_start,
                         packets_config.clear_interval,
                         path.clone(),
+                        turns.clone(),
                     );
                     task_handles.push(packet_task);
 
-                    let link_task = packet::spawn_packet_worker(path.clone(), link, resubmit);
+                    let weight = config.find_chain(&path.src_chain_id).map_or(1.0, |chain| {
+                        chain
+                            .packet_priority
+                            .weight_for(&path.src_port_id, &path.src_channel_id)
+                    });
+                    turns.register(path, weight);
+
+                    let link_task =
+                        packet::spawn_packet_worker(path.clone(), link, resubmit, turns);
                     task_handles.push(link_task);
 
                     (Some(cmd_tx), None)
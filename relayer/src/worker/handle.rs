@@ -91,6 +91,26 @@ impl WorkerHandle {
         self.try_send_command(WorkerCmd::ClearPendingPackets);
     }
 
+    /// Pause all worker tasks. The commands sent to the worker are kept,
+    /// and processed once the worker is resumed.
+    pub fn pause(&self) {
+        for task in self.task_handles.iter() {
+            task.pause()
+        }
+    }
+
+    /// Resume all worker tasks paused by [`pause`](WorkerHandle::pause).
+    pub fn resume(&self) {
+        for task in self.task_handles.iter() {
+            task.resume()
+        }
+    }
+
+    /// Returns `true` if the worker tasks are paused.
+    pub fn is_paused(&self) -> bool {
+        !self.task_handles.is_empty() && self.task_handles.iter().all(TaskHandle::is_paused)
+    }
+
     /// Shutdown all worker tasks without waiting for them to terminate.
     pub fn shutdown(&self) {
         for task in self.task_handles.iter() {
@@ -1,10 +1,11 @@
 use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::collections::btree_set::BTreeSet as HashSet;
 use core::mem;
 
 use ibc::core::ics02_client::events::NewBlock;
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc::Height;
-use tracing::{debug, trace};
+use tracing::{debug, info, trace};
 
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
@@ -13,12 +14,21 @@ use crate::{
     telemetry,
 };
 
+use super::turns::TurnScheduler;
 use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 
 /// Manage the lifecycle of [`WorkerHandle`]s associated with [`Object`]s.
+///
+/// The objects which were paused or excluded are remembered, so that the workers
+/// spawned again for them, e.g. after a configuration reload, start paused, and
+/// that no worker gets spawned for the excluded ones.
 #[derive(Debug)]
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
+    paused: HashSet<Object>,
+    excluded: HashSet<Object>,
+    /// The turns taken by the packet workers, per destination chain
+    turns: HashMap<ChainId, TurnScheduler>,
     latest_worker_id: WorkerId,
 }
 
@@ -26,6 +36,9 @@ impl Default for WorkerMap {
     fn default() -> Self {
         Self {
             workers: HashMap::new(),
+            paused: HashSet::new(),
+            excluded: HashSet::new(),
+            turns: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
         }
     }
@@ -59,6 +72,7 @@ impl WorkerMap {
                 );
 
                 let _ = handle.join();
+                self.forget_turns(&object);
 
                 trace!(
                     worker.id = %id, worker.object = %object.short_name(),
@@ -116,23 +130,27 @@ impl WorkerMap {
     /// Get a handle to the worker in charge of handling events associated
     /// with the given [`Object`].
     ///
-    /// This function will spawn a new [`WorkerHandle`] if one does not exists already.
+    /// This function will spawn a new [`WorkerHandle`] if one does not exists already,
+    /// and returns `None` if the object is excluded.
     pub fn get_or_spawn<Chain: ChainHandle>(
         &mut self,
         object: Object,
         src: Chain,
         dst: Chain,
         config: &Config,
-    ) -> &WorkerHandle {
-        if self.workers.contains_key(&object) {
-            &self.workers[&object]
+    ) -> Option<&WorkerHandle> {
+        if self.excluded.contains(&object) {
+            None
+        } else if self.workers.contains_key(&object) {
+            Some(&self.workers[&object])
         } else {
             let worker = self.spawn_worker(src, dst, &object, config);
-            self.workers.entry(object).or_insert(worker)
+            Some(self.workers.entry(object).or_insert(worker))
         }
     }
 
-    /// Spawn a new [`WorkerHandle`], only if one does not exists already.
+    /// Spawn a new [`WorkerHandle`], only if one does not exists already
+    /// and the object is not excluded.
     ///
     /// Returns whether or not the worker was actually spawned.
     pub fn spawn<Chain: ChainHandle>(
@@ -142,7 +160,10 @@ impl WorkerMap {
         object: &Object,
         config: &Config,
     ) -> bool {
-        if !self.workers.contains_key(object) {
+        if self.excluded.contains(object) {
+            trace!(worker.object = %object.short_name(), "not spawning worker for excluded object");
+            false
+        } else if !self.workers.contains_key(object) {
             let worker = self.spawn_worker(src, dst, object, config);
             self.workers.entry(object.clone()).or_insert(worker);
             true
@@ -161,12 +182,80 @@ impl WorkerMap {
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
 
-        spawn_worker_tasks(
+        let turns = self.turns.entry(dst.id()).or_default().clone();
+
+        let worker = spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
             object.clone(),
             config,
-        )
+            turns,
+        );
+
+        if self.paused.contains(object) {
+            info!(worker.object = %object.short_name(), "worker spawned paused");
+            worker.pause();
+        }
+
+        worker
+    }
+
+    /// Pause the worker for the given [`Object`], and the workers spawned for it later on,
+    /// until [`resume`](WorkerMap::resume) is called.
+    ///
+    /// Returns the handle to the paused worker, if one is running.
+    pub fn pause(&mut self, object: &Object) -> Option<&WorkerHandle> {
+        self.paused.insert(object.clone());
+
+        let worker = self.workers.get(object)?;
+        worker.pause();
+
+        Some(worker)
+    }
+
+    /// Stop the worker for the given [`Object`], if any, and do not spawn any worker
+    /// for it until [`resume`](WorkerMap::resume) is called.
+    pub fn exclude(&mut self, object: &Object) {
+        self.paused.remove(object);
+        self.excluded.insert(object.clone());
+
+        self.shutdown_worker(object);
+    }
+
+    /// Lift the pause or the exclusion of the given [`Object`].
+    ///
+    /// Returns the handle to the resumed worker, if one is running, which clears the
+    /// pending packets whose events were dropped while it was paused. The worker of an
+    /// object which was excluded has to be spawned again.
+    pub fn resume(&mut self, object: &Object) -> Option<&WorkerHandle> {
+        self.paused.remove(object);
+        self.excluded.remove(object);
+
+        let worker = self.workers.get(object)?;
+        worker.resume();
+        worker.clear_pending_packets();
+
+        Some(worker)
+    }
+
+    /// Returns `true` if no worker is to be spawned for the given [`Object`].
+    pub fn is_excluded(&self, object: &Object) -> bool {
+        self.excluded.contains(object)
+    }
+
+    /// Returns `true` if the events for the given [`Object`] are not to be processed.
+    pub fn is_paused(&self, object: &Object) -> bool {
+        self.paused.contains(object)
+    }
+
+    /// The objects which were paused, whether or not a worker is running for them.
+    pub fn paused(&self) -> impl Iterator<Item = &Object> {
+        self.paused.iter()
+    }
+
+    /// The objects which were excluded.
+    pub fn excluded(&self) -> impl Iterator<Item = &Object> {
+        self.excluded.iter()
     }
 
     /// Compute the next worker id
@@ -205,10 +294,20 @@ impl WorkerMap {
             telemetry!(worker, metric_type(object), -1);
 
             handle.shutdown_and_wait();
+            self.forget_turns(object);
         }
         // Drop handle automatically handles the waiting for tasks to terminate.
     }
 
+    /// Forgets the turns taken by the worker for the given [`Object`], if it is a packet worker.
+    fn forget_turns(&self, object: &Object) {
+        if let Object::Packet(path) = object {
+            if let Some(turns) = self.turns.get(&path.dst_chain_id) {
+                turns.unregister(path);
+            }
+        }
+    }
+
     /// Shut down all the workers, asynchronously.
     pub fn shutdown(&mut self) {
         let workers = mem::take(&mut self.workers);
@@ -239,3 +338,116 @@ fn metric_type(o: &Object) -> ibc_telemetry::state::WorkerType {
         Object::Wallet(_) => WorkerType::Wallet,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    use super::WorkerMap;
+    use crate::chain::handle::BaseChainHandle;
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::chain::mock::MockChain;
+    use crate::chain::runtime::ChainRuntime;
+    use crate::config::Config;
+    use crate::object::{Channel, Object};
+
+    fn chain() -> BaseChainHandle {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        ChainRuntime::<MockChain>::spawn::<BaseChainHandle>(get_basic_chain_config("chain_a"), rt)
+            .unwrap()
+    }
+
+    fn channel(src_channel_id: &str) -> Object {
+        Object::Channel(Channel {
+            dst_chain_id: ChainId::from_string("chain_b"),
+            src_chain_id: ChainId::from_string("chain_a"),
+            src_channel_id: src_channel_id.parse().unwrap(),
+            src_port_id: "transfer".parse().unwrap(),
+        })
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let (chain, config) = (chain(), Config::default());
+        let (object, other) = (channel("channel-0"), channel("channel-1"));
+
+        let mut workers = WorkerMap::new();
+        workers.spawn(chain.clone(), chain.clone(), &object, &config);
+        workers.spawn(chain.clone(), chain, &other, &config);
+
+        assert!(workers.pause(&object).unwrap().is_paused());
+        assert!(workers.is_paused(&object));
+        assert!(!workers.is_paused(&other));
+        assert_eq!(workers.paused().collect::<Vec<_>>(), vec![&object]);
+
+        assert!(!workers.resume(&object).unwrap().is_paused());
+        assert!(!workers.is_paused(&object));
+        assert_eq!(workers.paused().count(), 0);
+    }
+
+    #[test]
+    fn objects_without_worker_can_be_paused() {
+        let object = channel("channel-0");
+
+        let mut workers = WorkerMap::new();
+
+        assert!(workers.pause(&object).is_none());
+        assert!(workers.is_paused(&object));
+        assert!(workers.resume(&object).is_none());
+        assert!(!workers.is_paused(&object));
+    }
+
+    #[test]
+    fn exclude_and_resume() {
+        let (chain, config) = (chain(), Config::default());
+        let object = channel("channel-0");
+
+        let mut workers = WorkerMap::new();
+        workers.spawn(chain.clone(), chain.clone(), &object, &config);
+        workers.pause(&object);
+
+        workers.exclude(&object);
+        assert!(!workers.contains(&object));
+        assert!(workers.is_excluded(&object));
+        assert!(!workers.is_paused(&object));
+        assert_eq!(workers.excluded().collect::<Vec<_>>(), vec![&object]);
+
+        assert!(!workers.spawn(chain.clone(), chain.clone(), &object, &config));
+        assert!(workers
+            .get_or_spawn(object.clone(), chain.clone(), chain.clone(), &config)
+            .is_none());
+
+        // The worker of an excluded object is not running, so it is spawned again on resume.
+        assert!(workers.resume(&object).is_none());
+        assert!(!workers.is_excluded(&object));
+        assert!(workers.spawn(chain.clone(), chain, &object, &config));
+        assert!(workers.contains(&object));
+    }
+
+    #[test]
+    fn paused_objects_survive_their_workers() {
+        let (chain, config) = (chain(), Config::default());
+        let object = channel("channel-0");
+
+        let mut workers = WorkerMap::new();
+        workers.spawn(chain.clone(), chain.clone(), &object, &config);
+        workers.pause(&object);
+
+        // As when the chain is restarted by a configuration reload.
+        for object in workers.objects_for_chain(&ChainId::from_string("chain_a")) {
+            workers.shutdown_worker(&object);
+        }
+        assert!(!workers.contains(&object));
+
+        let worker = workers
+            .get_or_spawn(object.clone(), chain.clone(), chain, &config)
+            .unwrap();
+        assert!(worker.is_paused());
+        assert!(workers.is_paused(&object));
+    }
+}
@@ -7,7 +7,9 @@ use tracing::{error, error_span, trace};
 use ibc::Height;
 
 use crate::chain::handle::ChainHandle;
+use crate::chain::tracking::TrackingId;
 use crate::event::monitor::EventBatch;
+use crate::event::relayer::{self as relayer_event, RelayerEvent, RelayerEventKind};
 use crate::foreign_client::HasExpiredOrFrozenError;
 use crate::link::Resubmit;
 use crate::link::{error::LinkError, Link};
@@ -16,6 +18,7 @@ use crate::telemetry;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 
 use super::error::RunError;
+use super::turns::TurnScheduler;
 use super::WorkerCmd;
 
 fn handle_link_error_in_task(e: LinkError) -> TaskError<RunError> {
@@ -35,6 +38,7 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     resubmit: Resubmit,
+    turns: TurnScheduler,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -48,7 +52,7 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     };
 
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
-        handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
+        handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit, &turns)?;
         Ok(Next::Continue)
     })
 }
@@ -60,6 +64,7 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut should_clear_on_start: bool,
     clear_interval: u64,
     path: Packet,
+    turns: TurnScheduler,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -73,8 +78,22 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     };
 
     let mut current_command = None;
+    let mut journal_recovered = false;
 
     spawn_background_task(span, Some(Duration::from_millis(200)), move || {
+        // Recover the transactions sent before a restart, before the packets get cleared,
+        // so that the packets which were relayed already are not sent again
+        if !journal_recovered {
+            let summary = link.lock().unwrap().a_to_b.recover_journaled_txs();
+
+            if !summary.is_empty() {
+                trace!("recovered relay summary: {:?}", summary);
+            }
+
+            telemetry!(packet_metrics(&path, &summary));
+            journal_recovered = true;
+        }
+
         if current_command.is_none() {
             // Only try to receive the next command if the
             // previous command was processed successfully.
@@ -88,6 +107,7 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                 clear_interval,
                 &path,
                 cmd.clone(),
+                &turns,
             )?;
 
             // Only reset current_command if handle_packet_cmd succeeds.
@@ -117,6 +137,7 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     clear_interval: u64,
     path: &Packet,
     cmd: WorkerCmd,
+    turns: &TurnScheduler,
 ) -> Result<(), TaskError<RunError>> {
     // Handle packet clearing which is triggered from a command
     let (do_clear, maybe_height) = match &cmd {
@@ -142,7 +163,7 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     };
 
     if do_clear {
-        handle_clear_packet(link, clear_interval, path, maybe_height)?;
+        handle_clear_packet(link, clear_interval, path, maybe_height, turns)?;
 
         // Reset the `clear_on_start` flag
         if *should_clear_on_start {
@@ -152,7 +173,7 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
 
     // Handle command-specific task
     if let WorkerCmd::IbcEvents { batch } = cmd {
-        handle_update_schedule(link, clear_interval, path, batch)
+        handle_update_schedule(link, clear_interval, path, batch, turns)
     } else {
         Ok(())
     }
@@ -173,12 +194,18 @@ fn handle_update_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     clear_interval: u64,
     path: &Packet,
     batch: EventBatch,
+    turns: &TurnScheduler,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .update_schedule(batch)
         .map_err(handle_link_error_in_task)?;
 
-    handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
+    handle_execute_schedule(
+        link,
+        path,
+        Resubmit::from_clear_interval(clear_interval),
+        turns,
+    )
 }
 
 fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
@@ -186,31 +213,42 @@ fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
     clear_interval: u64,
     path: &Packet,
     height: Option<Height>,
+    turns: &TurnScheduler,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .schedule_packet_clearing(height)
         .map_err(handle_link_error_in_task)?;
 
-    handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
+    handle_execute_schedule(
+        link,
+        path,
+        Resubmit::from_clear_interval(clear_interval),
+        turns,
+    )
 }
 
+/// Executes the schedule of the link, once it is the turn of its channel among the channels
+/// relaying to the same chain, if it has messages pending for that chain.
 fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
-    _path: &Packet,
+    path: &Packet,
     resubmit: Resubmit,
+    turns: &TurnScheduler,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .refresh_schedule()
         .map_err(handle_link_error_in_task)?;
 
-    link.a_to_b.execute_schedule().map_err(|e| {
-        if e.is_expired_or_frozen_error() {
-            TaskError::Fatal(RunError::link(e))
-        } else {
-            error!("will retry: schedule execution encountered error: {}", e,);
-            TaskError::Ignore(RunError::link(e))
-        }
-    })?;
+    let turn = if link.a_to_b.has_pending_dst_operational_data() {
+        turns.try_take_turn(path)
+    } else {
+        turns.set_idle(path);
+        true
+    };
+
+    if turn {
+        execute_schedule(link, path)?;
+    }
 
     let summary = link.a_to_b.process_pending_txs(resubmit);
 
@@ -218,11 +256,45 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
         trace!("produced relay summary: {:?}", summary);
     }
 
-    telemetry!(packet_metrics(_path, &summary));
+    telemetry!(packet_metrics(path, &summary));
 
     Ok(())
 }
 
+fn execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &mut Link<ChainA, ChainB>,
+    path: &Packet,
+) -> Result<(), TaskError<RunError>> {
+    link.a_to_b.execute_schedule().map_err(|e| {
+        let fatal = e.is_expired_or_frozen_error();
+
+        publish_worker_error(path, &e, fatal);
+
+        if fatal {
+            TaskError::Fatal(RunError::link(e))
+        } else {
+            error!("will retry: schedule execution encountered error: {}", e,);
+            TaskError::Ignore(RunError::link(e))
+        }
+    })
+}
+
+fn publish_worker_error(path: &Packet, e: &LinkError, fatal: bool) {
+    let event = RelayerEvent::new(
+        TrackingId::new_static("packet worker"),
+        path.src_chain_id.clone(),
+        RelayerEventKind::WorkerError {
+            worker: path.short_name(),
+            error: e.to_string(),
+            fatal,
+        },
+    )
+    .with_counterparty(path.dst_chain_id.clone())
+    .with_channel(path.src_port_id.clone(), path.src_channel_id);
+
+    relayer_event::publish(event);
+}
+
 #[cfg(feature = "telemetry")]
 use crate::link::RelaySummary;
 
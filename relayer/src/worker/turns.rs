@@ -0,0 +1,174 @@
+//! Sharing of the submissions to a chain between the packet workers of the channels relaying to
+//! it, in proportion to the weights of the channels.
+
+use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::object::Packet;
+
+/// The lowest weight a channel is scheduled with, so that the channels with a zero weight still
+/// get their turns when no other channel is waiting.
+const MIN_WEIGHT: f64 = 0.01;
+
+/// How long a worker which stopped asking for turns, e.g. as it was paused, keeps the other
+/// workers waiting for it.
+const STALE_AFTER: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+struct Worker {
+    weight: f64,
+    /// The virtual time of the worker, which each of its turns advances by `1 / weight`
+    pass: f64,
+    /// Whether the worker has messages pending to be submitted
+    waiting: bool,
+    /// When the worker last asked for a turn
+    last_seen: Instant,
+}
+
+impl Worker {
+    fn is_waiting(&self, now: Instant) -> bool {
+        self.waiting && now.duration_since(self.last_seen) < STALE_AFTER
+    }
+}
+
+/// Takes turns between the packet workers relaying to the same chain, by stride scheduling:
+/// of the workers with messages pending, the one with the lowest pass submits next, and each
+/// turn advances its pass by the inverse of the weight of its channel. While they all have
+/// messages pending, a channel with twice the weight of another thus gets twice as many turns,
+/// and the workers without pending messages don't hold the others back.
+///
+/// The clones of a scheduler share its state.
+#[derive(Clone, Debug, Default)]
+pub struct TurnScheduler {
+    workers: Arc<Mutex<HashMap<Packet, Worker>>>,
+}
+
+impl TurnScheduler {
+    /// Registers the packet worker of `path`, whose channel has the given `weight`.
+    pub fn register(&self, path: &Packet, weight: f64) {
+        let mut workers = self.workers.lock().expect("poisoned turn scheduler lock");
+        let now = Instant::now();
+
+        // A new worker starts along with the others, rather than catching up on their turns.
+        let pass = lowest_pass(&workers, path, now).unwrap_or(0.0);
+
+        workers.insert(
+            path.clone(),
+            Worker {
+                weight: weight.max(MIN_WEIGHT),
+                pass,
+                waiting: false,
+                last_seen: now,
+            },
+        );
+    }
+
+    /// Forgets the packet worker of `path`, once it is shut down.
+    pub fn unregister(&self, path: &Packet) {
+        self.workers
+            .lock()
+            .expect("poisoned turn scheduler lock")
+            .remove(path);
+    }
+
+    /// Returns `true` if the worker of `path`, which has messages pending, may submit them now,
+    /// in which case the turn is accounted for. The unregistered workers always may.
+    pub fn try_take_turn(&self, path: &Packet) -> bool {
+        let mut workers = self.workers.lock().expect("poisoned turn scheduler lock");
+        let now = Instant::now();
+
+        let lowest_other = lowest_pass(&workers, path, now);
+
+        let worker = match workers.get_mut(path) {
+            Some(worker) => worker,
+            None => return true,
+        };
+
+        // A worker which was idle resumes along with the others, rather than taking all the
+        // turns it didn't need meanwhile.
+        if !worker.is_waiting(now) {
+            if let Some(lowest_other) = lowest_other {
+                worker.pass = worker.pass.max(lowest_other);
+            }
+        }
+
+        worker.waiting = true;
+        worker.last_seen = now;
+
+        if lowest_other.map_or(true, |lowest_other| worker.pass <= lowest_other) {
+            worker.pass += 1.0 / worker.weight;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Records that the worker of `path` has no messages pending.
+    pub fn set_idle(&self, path: &Packet) {
+        let mut workers = self.workers.lock().expect("poisoned turn scheduler lock");
+
+        if let Some(worker) = workers.get_mut(path) {
+            worker.waiting = false;
+            worker.last_seen = Instant::now();
+        }
+    }
+}
+
+/// Returns the lowest pass among the workers waiting for a turn, other than the one of `path`.
+fn lowest_pass(workers: &HashMap<Packet, Worker>, path: &Packet, now: Instant) -> Option<f64> {
+    workers
+        .iter()
+        .filter(|(other, worker)| *other != path && worker.is_waiting(now))
+        .map(|(_, worker)| worker.pass)
+        .reduce(f64::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::object::Packet;
+
+    use super::TurnScheduler;
+
+    fn path(channel: &str) -> Packet {
+        Packet {
+            dst_chain_id: "ibc-1".parse().unwrap(),
+            src_chain_id: "ibc-0".parse().unwrap(),
+            src_channel_id: channel.parse().unwrap(),
+            src_port_id: "transfer".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn turns_are_shared_by_weight() {
+        let scheduler = TurnScheduler::default();
+        let heavy = path("channel-0");
+        let light = path("channel-1");
+        scheduler.register(&heavy, 2.0);
+        scheduler.register(&light, 1.0);
+
+        // Both workers ask for a turn in every round, as long as they have messages pending.
+        let (mut heavy_turns, mut light_turns) = (0, 0);
+        while heavy_turns + light_turns < 30 {
+            if scheduler.try_take_turn(&heavy) {
+                heavy_turns += 1;
+            }
+            if scheduler.try_take_turn(&light) {
+                light_turns += 1;
+            }
+        }
+        assert_eq!((heavy_turns, light_turns), (20, 10));
+
+        // Once the heavy channel has nothing left to relay, the light one takes every turn.
+        scheduler.set_idle(&heavy);
+        assert!((0..5).all(|_| scheduler.try_take_turn(&light)));
+
+        // The heavy channel resumes along with the light one, without taking over the turns
+        // it didn't need meanwhile.
+        assert!(scheduler.try_take_turn(&heavy));
+        assert!(!scheduler.try_take_turn(&heavy));
+        assert!(scheduler.try_take_turn(&light));
+    }
+}
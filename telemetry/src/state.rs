@@ -1,4 +1,6 @@
 use core::fmt;
+use std::collections::HashSet;
+use std::sync::RwLock;
 use std::time::{Duration, Instant};
 
 use opentelemetry::{
@@ -71,6 +73,25 @@ pub struct TelemetryState {
     /// The balance in each wallet that Hermes is using, per wallet, denom and chain
     wallet_balance: ValueRecorder<u64>,
 
+    /// Fees distributed by the ICS29 fee middleware in the transactions submitted by Hermes,
+    /// per chain, receiver and denom
+    fees_earned: Counter<u64>,
+
+    /// The gas price paid for the transactions submitted by Hermes, per chain and denom
+    gas_price: ValueRecorder<f64>,
+
+    /// Whether Hermes runs in dry-run mode, simulating the transactions instead of broadcasting
+    /// them, per chain
+    dry_run: UpDownCounter<i64>,
+
+    /// The chains to which the transactions are simulated, whose packet and transaction
+    /// metrics are not recorded
+    dry_run_chains: RwLock<HashSet<ChainId>>,
+
+    /// Number of transactions simulated instead of broadcast in dry-run mode, per chain and
+    /// outcome of the simulation
+    dry_run_txs: Counter<u64>,
+
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were submitted. Milliseconds.
@@ -81,6 +102,17 @@ pub struct TelemetryState {
     /// until the corresponding transaction(s) were confirmed. Milliseconds.
     tx_latency_confirmed: ValueRecorder<u64>,
 
+    /// The latency of the receive packets relayed, per chain pair and channel,
+    /// i.e. the difference between the timestamp of the block with their `SendPacket` event
+    /// on the source chain until their `MsgRecvPacket` was committed. Milliseconds.
+    receive_packet_latency: ValueRecorder<u64>,
+
+    /// The latency of the acknowledgment packets relayed, per chain pair and channel,
+    /// i.e. the difference between the timestamp of the block with their
+    /// `WriteAcknowledgement` event on the destination chain until their
+    /// `MsgAcknowledgement` was committed. Milliseconds.
+    acknowledgment_packet_latency: ValueRecorder<u64>,
+
     /// Records the time at which we started processing an event batch.
     /// Used for computing the `tx_latency` metric.
     in_flight_events: moka::sync::Cache<String, Instant>,
@@ -100,6 +132,10 @@ impl TelemetryState {
 
     /// Update the number of client updates per client
     pub fn ibc_client_updates(&self, chain: &ChainId, client: &ClientId, count: u64) {
+        if self.is_dry_run(&[chain]) {
+            return;
+        }
+
         let labels = &[
             KeyValue::new("chain", chain.to_string()),
             KeyValue::new("client", client.to_string()),
@@ -126,6 +162,10 @@ impl TelemetryState {
         src_port: &PortId,
         count: u64,
     ) {
+        if self.is_dry_run(&[src_chain]) {
+            return;
+        }
+
         let labels = &[
             KeyValue::new("src_chain", src_chain.to_string()),
             KeyValue::new("src_channel", src_channel.to_string()),
@@ -143,6 +183,10 @@ impl TelemetryState {
         src_port: &PortId,
         count: u64,
     ) {
+        if self.is_dry_run(&[src_chain]) {
+            return;
+        }
+
         let labels = &[
             KeyValue::new("src_chain", src_chain.to_string()),
             KeyValue::new("src_channel", src_channel.to_string()),
@@ -160,6 +204,10 @@ impl TelemetryState {
         src_port: &PortId,
         count: u64,
     ) {
+        if self.is_dry_run(&[src_chain]) {
+            return;
+        }
+
         let labels = &[
             KeyValue::new("src_chain", src_chain.to_string()),
             KeyValue::new("src_channel", src_channel.to_string()),
@@ -221,6 +269,71 @@ impl TelemetryState {
         self.wallet_balance.record(amount, labels);
     }
 
+    /// Fees distributed by the ICS29 fee middleware in the transactions submitted by Hermes,
+    /// per chain, receiver and denom
+    pub fn fees_earned(&self, chain_id: &ChainId, receiver: &str, amount: u64, denom: &str) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("receiver", receiver.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.fees_earned.add(amount, labels);
+    }
+
+    /// The gas price paid for the transactions submitted by Hermes, per chain and denom
+    pub fn gas_price(&self, chain_id: &ChainId, price: f64, denom: &str) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.gas_price.record(price, labels);
+    }
+
+    /// Flags the chain as running in dry-run mode, or no longer once its runtime shuts down.
+    /// The packet and transaction metrics of the chains in dry-run mode are not recorded,
+    /// as their transactions are only simulated.
+    pub fn dry_run(&self, chain_id: &ChainId, enabled: bool) {
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        let mut chains = self
+            .dry_run_chains
+            .write()
+            .expect("poisoned dry-run chains lock");
+        let changed = if enabled {
+            chains.insert(chain_id.clone())
+        } else {
+            chains.remove(chain_id)
+        };
+
+        if changed {
+            self.dry_run.add(if enabled { 1 } else { -1 }, labels);
+        }
+    }
+
+    /// Whether any of the chains runs in dry-run mode.
+    fn is_dry_run(&self, chain_ids: &[&ChainId]) -> bool {
+        let chains = self
+            .dry_run_chains
+            .read()
+            .expect("poisoned dry-run chains lock");
+        chain_ids.iter().any(|chain_id| chains.contains(*chain_id))
+    }
+
+    /// Number of transactions simulated instead of broadcast in dry-run mode, per chain and
+    /// outcome of the simulation
+    pub fn dry_run_tx(&self, chain_id: &ChainId, simulated: bool) {
+        let outcome = if simulated { "simulated" } else { "failed" };
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("outcome", outcome),
+        ];
+
+        self.dry_run_txs.add(1, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -235,6 +348,10 @@ impl TelemetryState {
         port_id: &PortId,
         counterparty_chain_id: &ChainId,
     ) {
+        if self.is_dry_run(&[chain_id]) {
+            return;
+        }
+
         let tracking_id = tracking_id.to_string();
 
         if let Some(start) = self.in_flight_events.get(&tracking_id) {
@@ -260,6 +377,10 @@ impl TelemetryState {
         port_id: &PortId,
         counterparty_chain_id: &ChainId,
     ) {
+        if self.is_dry_run(&[chain_id]) {
+            return;
+        }
+
         let tracking_id = tracking_id.to_string();
 
         if let Some(start) = self.in_flight_events.get(&tracking_id) {
@@ -276,6 +397,59 @@ impl TelemetryState {
             self.tx_latency_confirmed.record(latency, labels);
         }
     }
+
+    /// Records the latency of a receive packet relayed from `src_chain` to `dst_chain`,
+    /// once its `MsgRecvPacket` is committed.
+    pub fn receive_packet_latency(
+        &self,
+        latency: Duration,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+    ) {
+        if self.is_dry_run(&[src_chain, dst_chain]) {
+            return;
+        }
+
+        let labels = packet_latency_labels(src_chain, dst_chain, src_channel, src_port);
+        self.receive_packet_latency
+            .record(latency.as_millis() as u64, &labels);
+    }
+
+    /// Records the latency of an acknowledgment packet relayed back to `src_chain` from
+    /// `dst_chain`, once its `MsgAcknowledgement` is committed.
+    pub fn acknowledgment_packet_latency(
+        &self,
+        latency: Duration,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+    ) {
+        if self.is_dry_run(&[src_chain, dst_chain]) {
+            return;
+        }
+
+        let labels = packet_latency_labels(src_chain, dst_chain, src_channel, src_port);
+        self.acknowledgment_packet_latency
+            .record(latency.as_millis() as u64, &labels);
+    }
+}
+
+/// The labels of the packet latency metrics.
+fn packet_latency_labels(
+    src_chain: &ChainId,
+    dst_chain: &ChainId,
+    src_channel: &ChannelId,
+    src_port: &PortId,
+) -> [KeyValue; 4] {
+    [
+        KeyValue::new("src_chain", src_chain.to_string()),
+        KeyValue::new("dst_chain", dst_chain.to_string()),
+        KeyValue::new("src_channel", src_channel.to_string()),
+        KeyValue::new("src_port", src_port.to_string()),
+    ]
 }
 
 use std::sync::Arc;
@@ -284,14 +458,24 @@ use opentelemetry::metrics::Descriptor;
 use opentelemetry::sdk::export::metrics::{Aggregator, AggregatorSelector};
 use opentelemetry::sdk::metrics::aggregators::{histogram, last_value, sum};
 
+/// The bounds of the buckets of the packet latency histograms, in milliseconds,
+/// spanning from a couple of blocks to the timeout of the pending transactions.
+const PACKET_LATENCY_BUCKETS: &[f64] = &[
+    1000.0, 2500.0, 5000.0, 10000.0, 20000.0, 30000.0, 60000.0, 120000.0, 300000.0,
+];
+
 #[derive(Debug)]
 struct CustomAggregatorSelector;
 impl AggregatorSelector for CustomAggregatorSelector {
     fn aggregator_for(&self, descriptor: &Descriptor) -> Option<Arc<dyn Aggregator + Send + Sync>> {
         match descriptor.name() {
             "wallet_balance" => Some(Arc::new(last_value())),
+            "gas_price" => Some(Arc::new(last_value())),
             "tx_latency_submitted" => Some(Arc::new(histogram(descriptor, &[0.5, 0.9, 0.99]))),
             "tx_latency_confirmed" => Some(Arc::new(histogram(descriptor, &[0.5, 0.9, 0.99]))),
+            "ibc_receive_packet_latency" | "ibc_acknowledgment_packet_latency" => {
+                Some(Arc::new(histogram(descriptor, PACKET_LATENCY_BUCKETS)))
+            }
             _ => Some(Arc::new(sum())),
         }
     }
@@ -370,6 +554,28 @@ impl Default for TelemetryState {
                 .with_description("The balance in each wallet that Hermes is using, per wallet, denom and chain")
                 .init(),
 
+            fees_earned: meter
+                .u64_counter("fees_earned")
+                .with_description("Fees distributed by the ICS29 fee middleware in the transactions submitted by Hermes, per chain, receiver and denom")
+                .init(),
+
+            gas_price: meter
+                .f64_value_recorder("gas_price")
+                .with_description("The gas price paid for the transactions submitted by Hermes, per chain and denom")
+                .init(),
+
+            dry_run: meter
+                .i64_up_down_counter("dry_run")
+                .with_description("Whether Hermes runs in dry-run mode, simulating the transactions instead of broadcasting them, per chain")
+                .init(),
+
+            dry_run_chains: RwLock::new(HashSet::new()),
+
+            dry_run_txs: meter
+                .u64_counter("dry_run_txs")
+                .with_description("Number of transactions simulated instead of broadcast in dry-run mode, per chain and outcome of the simulation")
+                .init(),
+
             tx_latency_submitted: meter
                 .u64_value_recorder("tx_latency_submitted")
                 .with_description("The latency for all transactions submitted to a specific chain, \
@@ -384,6 +590,20 @@ impl Default for TelemetryState {
                     until the corresponding transaction(s) were confirmed. Milliseconds.")
                 .init(),
 
+            receive_packet_latency: meter
+                .u64_value_recorder("ibc_receive_packet_latency")
+                .with_description("The latency of the receive packets relayed, per chain pair and channel, \
+                    i.e. the difference between the timestamp of the block with their SendPacket event \
+                    on the source chain until their MsgRecvPacket was committed. Milliseconds.")
+                .init(),
+
+            acknowledgment_packet_latency: meter
+                .u64_value_recorder("ibc_acknowledgment_packet_latency")
+                .with_description("The latency of the acknowledgment packets relayed, per chain pair and channel, \
+                    i.e. the difference between the timestamp of the block with their WriteAcknowledgement event \
+                    on the destination chain until their MsgAcknowledgement was committed. Milliseconds.")
+                .init(),
+
             in_flight_events: moka::sync::Cache::builder()
                     .time_to_live(Duration::from_secs(60 * 60)) // Remove entries after 1 hour
                     .time_to_idle(Duration::from_secs(30 * 60)) // Remove entries if they have been idle for 30 minutes
@@ -391,3 +611,64 @@ impl Default for TelemetryState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::TelemetryState;
+
+    #[test]
+    fn packet_latencies_are_recorded() {
+        let state = TelemetryState::default();
+
+        let (src_chain, dst_chain) = ("ibc-0".parse().unwrap(), "ibc-1".parse().unwrap());
+        let (channel, port) = ("channel-0".parse().unwrap(), "transfer".parse().unwrap());
+
+        state.receive_packet_latency(
+            Duration::from_millis(3_500),
+            &src_chain,
+            &dst_chain,
+            &channel,
+            &port,
+        );
+        state.receive_packet_latency(
+            Duration::from_secs(45),
+            &src_chain,
+            &dst_chain,
+            &channel,
+            &port,
+        );
+
+        let families = state.gather();
+        let family = families
+            .iter()
+            .find(|family| family.get_name() == "ibc_receive_packet_latency")
+            .expect("the receive packet latency is exported");
+
+        let metric = &family.get_metric()[0];
+        let labels: Vec<_> = metric
+            .get_label()
+            .iter()
+            .map(|label| (label.get_name(), label.get_value()))
+            .collect();
+        assert!(labels.contains(&("src_chain", "ibc-0")));
+        assert!(labels.contains(&("dst_chain", "ibc-1")));
+
+        let histogram = metric.get_histogram();
+        assert_eq!(histogram.get_sample_count(), 2);
+        assert_eq!(histogram.get_sample_sum(), 48_500.0);
+
+        // One latency falls within 5 seconds, both within 60 seconds.
+        let within = |bound: f64| {
+            histogram
+                .get_bucket()
+                .iter()
+                .find(|bucket| bucket.get_upper_bound() == bound)
+                .map(|bucket| bucket.get_cumulative_count())
+        };
+        assert_eq!(within(2_500.0), Some(0));
+        assert_eq!(within(5_000.0), Some(1));
+        assert_eq!(within(60_000.0), Some(2));
+    }
+}
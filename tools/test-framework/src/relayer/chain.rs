@@ -21,13 +21,14 @@
 */
 
 use crossbeam_channel as channel;
+use ibc::applications::ics29_fee::fee::PacketFee;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::events::UpdateClient;
 use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
-use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::packet::{PacketId, PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::query::QueryTxRequest;
 use ibc::{
@@ -45,6 +46,7 @@ use ibc::{
     signer::Signer,
     Height,
 };
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_relayer::account::Balance;
 use ibc_relayer::chain::client::ClientSettings;
 use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
@@ -133,6 +135,18 @@ where
         self.value().ibc_version()
     }
 
+    fn fee_budget_exceeded(&self) -> Result<bool, Error> {
+        self.value().fee_budget_exceeded()
+    }
+
+    fn estimate_tx_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.value().estimate_tx_fee(tracked_msgs)
+    }
+
+    fn query_incentivized_packet(&self, packet_id: PacketId) -> Result<Vec<PacketFee>, Error> {
+        self.value().query_incentivized_packet(packet_id)
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.value().query_application_status()
     }
@@ -6,7 +6,9 @@ use ibc::core::ics24_host::identifier::ChainId;
 use ibc::events::IbcEvent;
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer::chain::cosmos::fee_spend::FeeSpendTracker;
 use ibc_relayer::chain::cosmos::gas::calculate_fee;
+use ibc_relayer::chain::cosmos::gas_price::GasPriceOracle;
 use ibc_relayer::chain::cosmos::query::account::query_account;
 use ibc_relayer::chain::cosmos::tx::estimate_fee_and_send_tx;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
@@ -14,7 +16,7 @@ use ibc_relayer::chain::cosmos::types::gas::GasConfig;
 use ibc_relayer::chain::cosmos::types::tx::TxSyncResult;
 use ibc_relayer::chain::cosmos::wait::wait_for_block_commits;
 use ibc_relayer::config::GasPrice;
-use ibc_relayer::keyring::KeyEntry;
+use ibc_relayer::keyring::{KeyEntry, KeySigner};
 use tendermint_rpc::{HttpClient, Url};
 
 use crate::error::{handle_generic_error, Error};
@@ -38,6 +40,7 @@ pub fn gas_config_for_test() -> GasConfig {
         default_gas,
         max_gas,
         gas_adjustment,
+        gas_price_oracle: GasPriceOracle::new(gas_price.clone(), Default::default()),
         gas_price,
         max_fee,
         fee_granter,
@@ -61,6 +64,8 @@ pub fn new_tx_config_for_test(
 
     let address_type = Default::default();
 
+    let fee_spend = FeeSpendTracker::in_memory(None, gas_config.gas_price.denom.clone());
+
     Ok(TxConfig {
         chain_id,
         gas_config,
@@ -69,6 +74,8 @@ pub fn new_tx_config_for_test(
         grpc_address,
         rpc_timeout,
         address_type,
+        signer: KeySigner::Local,
+        fee_spend,
     })
 }
 
@@ -113,6 +120,7 @@ pub async fn simple_send_tx(
         &config.rpc_client,
         &config.rpc_address,
         &config.rpc_timeout,
+        &config.fee_spend,
         &mut tx_sync_results,
     )
     .await?;
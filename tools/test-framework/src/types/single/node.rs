@@ -128,6 +128,7 @@ impl FullNode {
             rpc_timeout: Duration::from_secs(10),
             account_prefix: self.chain_driver.account_prefix.clone(),
             key_name: self.wallets.relayer.id.0.clone(),
+            key_names: vec![],
 
             // By default we use in-memory key store to avoid polluting
             // ~/.hermes/keys. See
@@ -139,6 +140,7 @@ impl FullNode {
             max_gas: Some(3000000),
             gas_adjustment: Some(0.1),
             fee_granter: None,
+            daily_fee_budget: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             max_block_time: Duration::from_secs(30),
@@ -146,8 +148,13 @@ impl FullNode {
             trusting_period: Some(Duration::from_secs(14 * 24 * 3600)),
             trust_threshold: Default::default(),
             gas_price: config::GasPrice::new(0.001, "stake".to_string()),
+            gas_price_oracle: Default::default(),
             packet_filter: Default::default(),
+            relay_policy: vec![],
+            packet_priority: Default::default(),
             address_type: Default::default(),
+            remote_signer: None,
+            key_store_passphrase: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         })